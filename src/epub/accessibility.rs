@@ -0,0 +1,185 @@
+//! 无障碍检查模块
+//!
+//! 提供无障碍出版检查结果的数据结构定义。与结构校验（`validation`）不同，
+//! 这里的问题不影响书籍能否正常打开，而是影响读屏软件等辅助技术的使用体验。
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::epub::validation::Severity;
+
+/// 无障碍问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum A11yIssueKind {
+    /// 图片缺少替代文本
+    MissingAltText,
+    /// 元数据缺少`dc:language`
+    MissingLanguage,
+    /// EPUB3书籍缺少nav导航文档
+    MissingNavDocument,
+    /// 元数据缺少`schema:accessibilityFeature`
+    MissingAccessibilityFeature,
+    /// 标题跳级（如`<h1>`之后直接出现`<h3>`）
+    HeadingLevelSkip,
+}
+
+impl Display for A11yIssueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            A11yIssueKind::MissingAltText => write!(f, "缺少替代文本"),
+            A11yIssueKind::MissingLanguage => write!(f, "缺少语言"),
+            A11yIssueKind::MissingNavDocument => write!(f, "缺少导航文档"),
+            A11yIssueKind::MissingAccessibilityFeature => write!(f, "缺少无障碍特性声明"),
+            A11yIssueKind::HeadingLevelSkip => write!(f, "标题跳级"),
+        }
+    }
+}
+
+/// 单个无障碍问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct A11yFinding {
+    /// 问题类型
+    pub kind: A11yIssueKind,
+    /// 严重程度
+    pub severity: Severity,
+    /// 问题描述
+    pub message: String,
+    /// 相关文件路径（相对于EPUB根目录）
+    pub path: Option<String>,
+}
+
+impl A11yFinding {
+    /// 创建无障碍问题
+    pub fn new(kind: A11yIssueKind, severity: Severity, message: String, path: Option<String>) -> Self {
+        Self {
+            kind,
+            severity,
+            message,
+            path,
+        }
+    }
+}
+
+impl Display for A11yFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.path {
+            Some(path) => write!(f, "[{}] {}: {} ({})", self.severity, self.kind, self.message, path),
+            None => write!(f, "[{}] {}: {}", self.severity, self.kind, self.message),
+        }
+    }
+}
+
+/// 无障碍检查报告
+#[derive(Debug, Clone, Default)]
+pub struct A11yReport {
+    /// 发现的所有问题
+    pub findings: Vec<A11yFinding>,
+}
+
+impl A11yReport {
+    /// 创建空的检查报告
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加问题
+    pub fn push(&mut self, finding: A11yFinding) {
+        self.findings.push(finding);
+    }
+
+    /// 检查是否没有任何问题
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// 获取指定类型的所有问题
+    pub fn of_kind(&self, kind: A11yIssueKind) -> Vec<&A11yFinding> {
+        self.findings.iter().filter(|finding| finding.kind == kind).collect()
+    }
+
+    /// 获取所有错误
+    pub fn errors(&self) -> Vec<&A11yFinding> {
+        self.findings.iter().filter(|finding| finding.severity == Severity::Error).collect()
+    }
+
+    /// 获取所有警告
+    pub fn warnings(&self) -> Vec<&A11yFinding> {
+        self.findings.iter().filter(|finding| finding.severity == Severity::Warning).collect()
+    }
+}
+
+impl Display for A11yReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.findings.is_empty() {
+            return writeln!(f, "未发现无障碍问题");
+        }
+        writeln!(
+            f,
+            "无障碍检查结果: {} 个错误, {} 个警告",
+            self.errors().len(),
+            self.warnings().len()
+        )?;
+        for finding in &self.findings {
+            writeln!(f, "  {}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+/// 图片在某个章节中的一次使用
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ImageUsage {
+    /// 章节文件路径（相对于EPUB根目录）
+    pub chapter_path: String,
+    /// 引用图片的元素名（`img`或SVG中的`image`）
+    pub element: String,
+    /// 替代文本（`alt`、`aria-label`或SVG的`<title>`），缺失时为`None`，装饰性图片（`alt=""`）为空字符串
+    pub alt: Option<String>,
+}
+
+/// 单张图片的替代文本汇总
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ImageAltInfo {
+    /// 图片文件路径（相对于EPUB根目录）
+    pub path: String,
+    /// 按阅读顺序排列的所有使用位置
+    pub usages: Vec<ImageUsage>,
+    /// 是否每次使用都带有替代文本
+    pub has_alt: bool,
+}
+
+impl ImageAltInfo {
+    /// 获取缺少替代文本的使用位置
+    pub fn missing_alt_usages(&self) -> Vec<&ImageUsage> {
+        self.usages.iter().filter(|usage| usage.alt.is_none()).collect()
+    }
+}
+
+/// 查找标题跳级的位置
+///
+/// 只检查同一文档内相邻标题之间的层级变化，文档的第一个标题不受限制。
+///
+/// # 参数
+/// * `levels` - 按文档顺序排列的标题层级（1-6）
+///
+/// # 返回值
+/// * `Vec<(u8, u8)>` - 每处跳级的(前一个层级, 当前层级)
+pub fn heading_level_skips(levels: &[u8]) -> Vec<(u8, u8)> {
+    levels.windows(2)
+        .filter(|pair| pair[1] > pair[0] + 1)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_level_skips() {
+        assert_eq!(heading_level_skips(&[1, 2, 3, 2, 3]), Vec::new());
+        assert_eq!(heading_level_skips(&[1, 3, 1, 2, 4]), vec![(1, 3), (2, 4)]);
+        assert_eq!(heading_level_skips(&[3]), Vec::new());
+    }
+}
@@ -0,0 +1,105 @@
+//! 异步读取模块
+//!
+//! 提供基于tokio的[`AsyncEpub`]，将ZIP解压等阻塞操作放到`spawn_blocking`线程池中执行，
+//! 避免在异步运行时（如axum处理函数）中阻塞工作线程。
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::epub::error::{EpubError, Result};
+use crate::epub::reader::{Chapter, ChapterInfo, CoverImage, Epub};
+
+/// 异步EPUB读取器
+///
+/// 内部以`Arc`共享同一个[`Epub`]实例，克隆开销很小，可直接放入服务端的共享状态中。
+/// 各方法与同步版本同名，返回相同的`Result`类型。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use bookforge::AsyncEpub;
+///
+/// # async fn run() -> bookforge::Result<()> {
+/// let epub = AsyncEpub::open("book.epub").await?;
+/// let html = epub.read_chapter_file("OEBPS/chapter1.xhtml").await?;
+/// println!("{}", html);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncEpub {
+    inner: Arc<Epub>,
+}
+
+impl AsyncEpub {
+    /// 异步打开EPUB文件
+    ///
+    /// # 参数
+    /// * `path` - EPUB文件路径
+    ///
+    /// # 返回值
+    /// * `Result<AsyncEpub>` - 异步EPUB实例
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let epub = spawn_blocking(move || Epub::from_path(path)).await?;
+        Ok(Self::from_epub(epub))
+    }
+
+    /// 包装已打开的EPUB实例（如通过`EpubBuilder`以自定义选项打开的实例）
+    pub fn from_epub(epub: Epub) -> Self {
+        Self { inner: Arc::new(epub) }
+    }
+
+    /// 获取底层的同步EPUB实例
+    ///
+    /// 只访问已缓存的数据（如`book_info`）时可直接使用同步接口。
+    pub fn as_sync(&self) -> &Epub {
+        &self.inner
+    }
+
+    /// 读取指定文件的内容
+    ///
+    /// # 参数
+    /// * `filename` - 要读取的文件名
+    ///
+    /// # 返回值
+    /// * `Result<String>` - 文件内容
+    pub async fn read_chapter_file(&self, filename: &str) -> Result<String> {
+        let epub = Arc::clone(&self.inner);
+        let filename = filename.to_string();
+        spawn_blocking(move || epub.read_chapter_file(&filename)).await
+    }
+
+    /// 获取指定章节内容
+    ///
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    ///
+    /// # 返回值
+    /// * `Result<Chapter>` - 章节内容
+    pub async fn chapter(&self, chapter_info: &ChapterInfo) -> Result<Chapter> {
+        let epub = Arc::clone(&self.inner);
+        let chapter_info = chapter_info.clone();
+        spawn_blocking(move || epub.chapter(&chapter_info)).await
+    }
+
+    /// 获取封面图片
+    ///
+    /// # 返回值
+    /// * `Result<Option<CoverImage>>` - 封面图片（如果存在）
+    pub async fn cover(&self) -> Result<Option<CoverImage>> {
+        let epub = Arc::clone(&self.inner);
+        spawn_blocking(move || epub.cover()).await
+    }
+}
+
+/// 在阻塞线程池中执行操作，任务panic或被取消时返回`EpubError::InternalError`
+async fn spawn_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| EpubError::InternalError(format!("后台读取任务失败: {}", e)))?
+}
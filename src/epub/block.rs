@@ -0,0 +1,258 @@
+//! 结构化内容模块
+//!
+//! 提供将章节HTML转换为类型化块序列的功能，作为自定义渲染器的中间表示。
+//!
+//! 支持标题、段落、图片、列表、引用和预格式文本。表格等其他块级元素按其内容展开，
+//! 每个单元格成为独立的段落；分隔线、脚本和隐藏元素会被忽略。
+
+use scraper::{ElementRef, Html, Selector};
+
+use crate::epub::markdown::{INLINE_ELEMENTS, SKIPPED_ELEMENTS};
+use crate::epub::ncx::TocTreeNode;
+
+/// 章节中的内容块
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type", rename_all = "snake_case"))]
+pub enum Block {
+    /// 标题
+    Heading {
+        /// 标题层级（1-6）
+        level: u8,
+        /// 标题文本
+        text: String,
+    },
+    /// 段落，`<br>`换行保留为`\n`
+    Paragraph {
+        /// 段落文本
+        text: String,
+    },
+    /// 图片（包括段落中的行内图片，此时段落会在图片处拆分）
+    Image {
+        /// 原始链接（相对于章节文件）
+        src: String,
+        /// 替代文本，缺少`alt`属性时为空字符串
+        alt: String,
+    },
+    /// 列表，嵌套列表的内容以换行拼接在所属列表项中
+    List {
+        /// 是否为有序列表
+        ordered: bool,
+        /// 列表项文本
+        items: Vec<String>,
+    },
+    /// 引用，段落之间以换行分隔
+    Quote {
+        /// 引用文本
+        text: String,
+    },
+    /// 预格式文本，保留原始空白
+    CodeBlock {
+        /// 代码文本
+        text: String,
+    },
+}
+
+impl Block {
+    /// 获取块的纯文本内容
+    ///
+    /// 图片返回替代文本，列表返回以换行拼接的列表项。
+    pub fn text(&self) -> String {
+        match self {
+            Block::Heading { text, .. }
+            | Block::Paragraph { text }
+            | Block::Quote { text }
+            | Block::CodeBlock { text } => text.clone(),
+            Block::Image { alt, .. } => alt.clone(),
+            Block::List { items, .. } => items.join("\n"),
+        }
+    }
+}
+
+/// 将章节HTML转换为内容块序列
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<Block>` - 按文档顺序排列的内容块
+pub(crate) fn extract_blocks(html: &str) -> Vec<Block> {
+    let document = Html::parse_document(html);
+    let body_selector = Selector::parse("body").unwrap();
+    let root = document.select(&body_selector).next().unwrap_or_else(|| document.root_element());
+
+    let mut blocks = Vec::new();
+    collect_container(root, &mut blocks);
+    blocks
+}
+
+/// 收集包含块级内容的元素，连续的行内内容合并为一个段落
+fn collect_container(element: ElementRef, blocks: &mut Vec<Block>) {
+    let mut paragraph = String::new();
+    for node in element.children() {
+        if let Some(child) = ElementRef::wrap(node) {
+            if INLINE_ELEMENTS.contains(&child.value().name()) {
+                collect_inline(child, &mut paragraph, blocks);
+            } else {
+                flush_paragraph(&mut paragraph, blocks);
+                collect_block(child, blocks);
+            }
+        } else if let Some(text) = node.value().as_text() {
+            push_text(&mut paragraph, text);
+        }
+    }
+    flush_paragraph(&mut paragraph, blocks);
+}
+
+/// 收集块级元素
+fn collect_block(element: ElementRef, blocks: &mut Vec<Block>) {
+    let name = element.value().name();
+    if SKIPPED_ELEMENTS.contains(&name) || TocTreeNode::is_hidden_element(element) {
+        return;
+    }
+
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let text = inline_text(element);
+            if !text.is_empty() {
+                let level = name[1..].parse::<u8>().unwrap_or(1);
+                blocks.push(Block::Heading { level, text });
+            }
+        }
+        "hr" => {}
+        "pre" => {
+            let code = element.text().collect::<String>();
+            let code = code.trim_start_matches(['\r', '\n']).trim_end();
+            if !code.is_empty() {
+                blocks.push(Block::CodeBlock { text: code.to_string() });
+            }
+        }
+        "blockquote" => {
+            let text = container_text(element);
+            if !text.is_empty() {
+                blocks.push(Block::Quote { text });
+            }
+        }
+        "ul" | "ol" => {
+            let items: Vec<String> = element.children()
+                .filter_map(ElementRef::wrap)
+                .filter(|item| item.value().name() == "li" && !TocTreeNode::is_hidden_element(*item))
+                .map(container_text)
+                .filter(|text| !text.is_empty())
+                .collect();
+            if !items.is_empty() {
+                blocks.push(Block::List { ordered: name == "ol", items });
+            }
+        }
+        _ => collect_container(element, blocks),
+    }
+}
+
+/// 收集行内元素，图片会结束当前段落并作为独立的块输出
+fn collect_inline(element: ElementRef, paragraph: &mut String, blocks: &mut Vec<Block>) {
+    let name = element.value().name();
+    if SKIPPED_ELEMENTS.contains(&name) || TocTreeNode::is_hidden_element(element) {
+        return;
+    }
+
+    match name {
+        "br" => {
+            let trimmed_len = paragraph.trim_end_matches(' ').len();
+            paragraph.truncate(trimmed_len);
+            paragraph.push('\n');
+        }
+        "img" => {
+            let src = element.value().attr("src").unwrap_or("").trim();
+            if !src.is_empty() {
+                flush_paragraph(paragraph, blocks);
+                let alt = element.value().attr("alt").unwrap_or("").trim().to_string();
+                blocks.push(Block::Image { src: src.to_string(), alt });
+            }
+        }
+        _ => {
+            for node in element.children() {
+                if let Some(child) = ElementRef::wrap(node) {
+                    collect_inline(child, paragraph, blocks);
+                } else if let Some(text) = node.value().as_text() {
+                    push_text(paragraph, text);
+                }
+            }
+        }
+    }
+}
+
+/// 获取元素的行内文本（忽略图片，去掉首尾空白）
+fn inline_text(element: ElementRef) -> String {
+    let mut text = String::new();
+    let mut images = Vec::new();
+    for node in element.children() {
+        if let Some(child) = ElementRef::wrap(node) {
+            collect_inline(child, &mut text, &mut images);
+        } else if let Some(value) = node.value().as_text() {
+            push_text(&mut text, value);
+        }
+    }
+    text.trim().to_string()
+}
+
+/// 获取容器元素中所有文本块的内容（忽略图片），以换行拼接
+fn container_text(element: ElementRef) -> String {
+    let mut inner = Vec::new();
+    collect_container(element, &mut inner);
+    inner.iter()
+        .filter(|block| !matches!(block, Block::Image { .. }))
+        .map(Block::text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 将段落缓冲区作为一个块输出
+fn flush_paragraph(paragraph: &mut String, blocks: &mut Vec<Block>) {
+    let text = paragraph.trim();
+    if !text.is_empty() {
+        blocks.push(Block::Paragraph { text: text.to_string() });
+    }
+    paragraph.clear();
+}
+
+/// 追加文本，合并连续空白
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_blocks() {
+        let html = r#"<html><head><title>忽略</title></head><body>
+<h1>第一章</h1>
+<p>第一行<br/>第二行<img src="../images/a.png" alt="插图"/>图后文字</p>
+<ol><li>甲<ul><li>子项</li></ul></li><li style="display:none">隐藏</li><li>乙</li></ol>
+<blockquote><p>引文一</p><p>引文二</p></blockquote>
+<hr/>
+<pre>
+fn main() {}
+</pre>
+<div><p>嵌套段落</p></div>
+</body></html>"#;
+        assert_eq!(extract_blocks(html), vec![
+            Block::Heading { level: 1, text: "第一章".to_string() },
+            Block::Paragraph { text: "第一行\n第二行".to_string() },
+            Block::Image { src: "../images/a.png".to_string(), alt: "插图".to_string() },
+            Block::Paragraph { text: "图后文字".to_string() },
+            Block::List { ordered: true, items: vec!["甲\n子项".to_string(), "乙".to_string()] },
+            Block::Quote { text: "引文一\n引文二".to_string() },
+            Block::CodeBlock { text: "fn main() {}".to_string() },
+            Block::Paragraph { text: "嵌套段落".to_string() },
+        ]);
+    }
+}
@@ -0,0 +1,198 @@
+//! EPUB打开选项模块
+//!
+//! 提供以可配置方式打开EPUB文件的构建器。
+
+use std::path::Path;
+
+use crate::epub::error::Result;
+use crate::epub::opf::MetadataTagConfigs;
+use crate::epub::reader::{DEFAULT_MAX_ENTRY_SIZE, Epub};
+use crate::epub::warning::EpubWarning;
+
+/// EPUB打开选项构建器
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use bookforge::EpubBuilder;
+///
+/// let epub = EpubBuilder::new()
+///     .warn_incomplete_metadata(true)
+///     .open("book.epub")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct EpubBuilder {
+    /// 元数据不完整时是否输出警告
+    warn_incomplete_metadata: bool,
+    /// 单个文件解压后的最大字节数
+    max_entry_size: u64,
+    /// 是否将警告输出到标准错误
+    print_warnings: bool,
+    /// 是否去除章节标题开头的序号
+    strip_chapter_numbers: bool,
+    /// 使用的版本（rendition）索引
+    rendition: Option<usize>,
+    /// 是否以宽松模式打开
+    lenient: bool,
+    /// 解析OPF元数据时使用的标签配置
+    tag_configs: Option<MetadataTagConfigs>,
+}
+
+impl Default for EpubBuilder {
+    fn default() -> Self {
+        Self {
+            warn_incomplete_metadata: false,
+            max_entry_size: DEFAULT_MAX_ENTRY_SIZE,
+            print_warnings: true,
+            strip_chapter_numbers: false,
+            rendition: None,
+            lenient: false,
+            tag_configs: None,
+        }
+    }
+}
+
+impl EpubBuilder {
+    /// 创建使用默认选项的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置单个文件解压后的最大字节数（默认100MB）
+    ///
+    /// 读取超过该大小的文件时返回`EpubError::InvalidEpub`，
+    /// 用于处理不可信的EPUB时防止压缩炸弹耗尽内存。
+    pub fn max_entry_size(mut self, max_entry_size: u64) -> Self {
+        self.max_entry_size = max_entry_size;
+        self
+    }
+
+    /// 设置是否将警告输出到标准错误（默认输出）
+    ///
+    /// 无论是否输出，警告都会被收集，可通过`Epub::warnings()`获取。
+    pub fn print_warnings(mut self, print: bool) -> Self {
+        self.print_warnings = print;
+        self
+    }
+
+    /// 设置是否去除章节标题开头的序号（默认不去除）
+    ///
+    /// 启用后，NCX、nav文档以及从章节内容提取的标题都会去除`第一章`、`Chapter 1`、
+    /// `1.`等开头的序号，使不同目录来源得到的标题保持一致，生成的文件名也更简洁。
+    /// 具体规则见[`strip_chapter_number`](crate::epub::text::strip_chapter_number)。
+    pub fn strip_chapter_numbers(mut self, strip: bool) -> Self {
+        self.strip_chapter_numbers = strip;
+        self
+    }
+
+    /// 设置使用的版本（rendition）索引（默认使用第一个OPF）
+    ///
+    /// 用于读取多版本EPUB中的非默认版本，索引对应`Container::rendition_paths()`中的位置，
+    /// 超出范围时`open()`返回错误。
+    pub fn rendition(mut self, index: usize) -> Self {
+        self.rendition = Some(index);
+        self
+    }
+
+    /// 设置是否以宽松模式打开（默认不使用）
+    ///
+    /// 启用后，mimetype缺失或内容错误的文件只要container.xml及其指向的OPF有效即可打开，
+    /// 规则见[`Epub::from_path_lenient`]。
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// 设置解析OPF元数据时使用的标签配置（默认使用内置配置）
+    ///
+    /// 配置会用于`Epub::opf()`的缓存结果，因此`book_info()`、`chapter_list()`等所有依赖OPF的
+    /// 方法都会使用它。库不会自动读取当前目录下的配置文件，需要时请显式加载：
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use bookforge::{EpubBuilder, MetadataTagConfigs};
+    ///
+    /// let configs = MetadataTagConfigs::from_path(Path::new("config/metadata.yaml"))?;
+    /// let epub = EpubBuilder::new().metadata_tag_configs(configs).open("book.epub")?;
+    /// println!("{}", epub.book_info()?.title);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn metadata_tag_configs(mut self, configs: MetadataTagConfigs) -> Self {
+        self.tag_configs = Some(configs);
+        self
+    }
+
+    /// 设置元数据不完整时是否输出警告
+    ///
+    /// 启用后，打开文件时会立即解析OPF并检查元数据完整性，
+    /// 缺失字段会以警告形式输出到标准错误。
+    pub fn warn_incomplete_metadata(mut self, warn: bool) -> Self {
+        self.warn_incomplete_metadata = warn;
+        self
+    }
+
+    /// 按当前选项打开EPUB文件
+    ///
+    /// # 参数
+    /// * `path` - EPUB文件路径
+    ///
+    /// # 返回值
+    /// * `Result<Epub>` - EPUB实例
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Epub> {
+        let mut epub = Epub::open_path(path, self.lenient, self.max_entry_size, self.print_warnings)?;
+        epub.set_strip_chapter_numbers(self.strip_chapter_numbers);
+        if let Some(configs) = &self.tag_configs {
+            epub.set_tag_configs(configs.clone());
+        }
+        if let Some(index) = self.rendition {
+            epub.select_rendition(index)?;
+        }
+
+        if self.warn_incomplete_metadata {
+            let completeness = epub.metadata_completeness()?;
+            if !completeness.is_complete() {
+                epub.warn(EpubWarning::new(
+                    format!("元数据不完整，缺少字段: {}", completeness.missing.join(", ")),
+                    None,
+                ));
+            }
+        }
+
+        Ok(epub)
+    }
+
+    /// 按当前选项打开EPUB文件，并返回打开过程中收集的警告
+    ///
+    /// 与`open()`不同，该方法会立即解析container.xml、OPF和NCX，
+    /// 使NCX解析失败、编码声明错误等问题在打开时就能集中报告。
+    /// 之后读取章节时出现的警告仍可通过`Epub::warnings()`获取。
+    ///
+    /// # 参数
+    /// * `path` - EPUB文件路径
+    ///
+    /// # 返回值
+    /// * `Result<(Epub, Vec<EpubWarning>)>` - EPUB实例及打开过程中的警告
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use bookforge::EpubBuilder;
+    ///
+    /// let (epub, warnings) = EpubBuilder::new()
+    ///     .print_warnings(false)
+    ///     .open_with_warnings("book.epub")?;
+    /// for warning in &warnings {
+    ///     println!("{}", warning);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open_with_warnings<P: AsRef<Path>>(&self, path: P) -> Result<(Epub, Vec<EpubWarning>)> {
+        let epub = self.open(path)?;
+        epub.opf()?;
+        epub.ncx()?;
+
+        let warnings = epub.warnings();
+        Ok((epub, warnings))
+    }
+}
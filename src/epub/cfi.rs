@@ -0,0 +1,143 @@
+//! 阅读位置模块
+//!
+//! 提供简化版EPUB CFI（Canonical Fragment Identifier）的生成与解析。
+//!
+//! 只支持最常见的"脊柱项 + 元素步进"形式，如`epubcfi(/6/4[ch1]!/4/2[p1])`：
+//! * `/6`指向OPF中的`<spine>`，其后的偶数步进`/4`表示第2个脊柱项
+//! * `!`之后的每个偶数步进表示第N/2个子元素（从章节的`<html>`元素开始）
+//! * 方括号中的id断言会被生成，解析时只使用脊柱项的id断言
+//!
+//! 不支持范围、字符偏移（`:N`，解析时会被忽略）、时间/空间偏移以及
+//! 多级间接引用。
+
+use crate::epub::reader::ChapterInfo;
+
+/// 从CFI解析出的阅读位置
+#[derive(Debug, Clone)]
+pub struct ResolvedPosition {
+    /// 脊柱中的位置（从0开始）
+    pub spine_index: usize,
+    /// 对应的章节信息
+    pub chapter_info: ChapterInfo,
+    /// 从`<html>`元素开始的子元素索引路径（每级从0开始）
+    pub element_path: Vec<usize>,
+    /// 目标元素的文本（合并空白，截断到80个字符）
+    pub text: String,
+}
+
+/// 解析后的CFI各部分
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CfiParts {
+    /// 脊柱中的位置（从0开始）
+    pub spine_index: usize,
+    /// 脊柱项的id断言
+    pub idref: Option<String>,
+    /// 元素索引路径
+    pub element_path: Vec<usize>,
+}
+
+/// 生成简化版CFI字符串
+///
+/// # 参数
+/// * `spine_index` - 脊柱中的位置（从0开始）
+/// * `idref` - 脊柱项的idref
+/// * `element_path` - 元素索引路径
+/// * `element_ids` - 路径上每个元素的id（用于id断言）
+///
+/// # 返回值
+/// * `String` - 形如`epubcfi(/6/4[ch1]!/4/2)`的字符串
+pub(crate) fn format_cfi(
+    spine_index: usize,
+    idref: &str,
+    element_path: &[usize],
+    element_ids: &[Option<String>],
+) -> String {
+    let mut cfi = format!("epubcfi(/6/{}[{}]!", (spine_index + 1) * 2, idref);
+    for (depth, index) in element_path.iter().enumerate() {
+        cfi.push_str(&format!("/{}", (index + 1) * 2));
+        if let Some(Some(id)) = element_ids.get(depth) {
+            cfi.push_str(&format!("[{}]", id));
+        }
+    }
+    cfi.push(')');
+    cfi
+}
+
+/// 解析简化版CFI字符串
+///
+/// 末尾的奇数步进（指向文本节点）和字符偏移会被忽略，位置落在其父元素上。
+///
+/// # 参数
+/// * `cfi` - CFI字符串（可省略`epubcfi(...)`外壳）
+///
+/// # 返回值
+/// * `Option<CfiParts>` - 不属于支持范围的CFI返回`None`
+pub(crate) fn parse_cfi(cfi: &str) -> Option<CfiParts> {
+    let cfi = cfi.trim();
+    let cfi = cfi.strip_prefix("epubcfi(")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or(cfi);
+    let (package_part, content_part) = cfi.split_once('!')?;
+
+    let package_steps = parse_steps(package_part)?;
+    let [(6, _), (spine_step, idref)] = package_steps.as_slice() else {
+        return None;
+    };
+    if spine_step % 2 != 0 || *spine_step == 0 {
+        return None;
+    }
+
+    let content_part = content_part.split(':').next().unwrap_or("");
+    let mut steps = parse_steps(content_part)?;
+    if steps.last().is_some_and(|(step, _)| step % 2 != 0) {
+        steps.pop();
+    }
+    let element_path = steps.iter()
+        .map(|(step, _)| (step % 2 == 0 && *step > 0).then(|| step / 2 - 1))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(CfiParts {
+        spine_index: spine_step / 2 - 1,
+        idref: idref.clone(),
+        element_path,
+    })
+}
+
+/// 解析`/N[id]/M`形式的步进序列
+fn parse_steps(path: &str) -> Option<Vec<(usize, Option<String>)>> {
+    if path.is_empty() {
+        return Some(Vec::new());
+    }
+    path.strip_prefix('/')?
+        .split('/')
+        .map(|step| match step.split_once('[') {
+            Some((number, assertion)) => {
+                let id = assertion.strip_suffix(']')?;
+                Some((number.parse().ok()?, (!id.is_empty()).then(|| id.to_string())))
+            }
+            None => Some((step.parse().ok()?, None)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cfi_round_trip() {
+        let cfi = format_cfi(1, "ch2", &[1, 0, 2], &[None, Some("sec1".to_string()), None]);
+        assert_eq!(cfi, "epubcfi(/6/4[ch2]!/4/2[sec1]/6)");
+        assert_eq!(parse_cfi(&cfi), Some(CfiParts {
+            spine_index: 1,
+            idref: Some("ch2".to_string()),
+            element_path: vec![1, 0, 2],
+        }));
+
+        // 文本节点步进与字符偏移落在父元素上
+        assert_eq!(parse_cfi("epubcfi(/6/2!/4/2/1:10)").unwrap().element_path, vec![1, 0]);
+        assert_eq!(parse_cfi("epubcfi(/6/2!/4/3/2)"), None);
+        assert_eq!(parse_cfi("epubcfi(/4/2!/4)"), None);
+        assert_eq!(parse_cfi("not a cfi"), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! 内容类型分类模块
+//!
+//! 根据固定版式声明、图片与文字的体积比例以及页面构成，
+//! 粗略判断书籍是漫画（图片为主）、文字书还是图文混排。
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use scraper::{Html, Selector};
+
+/// 书籍内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    /// 漫画或画册（固定版式、以整页图片为主）
+    Comic,
+    /// 以文字为主的书籍
+    Text,
+    /// 图文混排
+    Mixed,
+}
+
+impl Display for ContentKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ContentKind::Comic => write!(f, "漫画"),
+            ContentKind::Text => write!(f, "文字"),
+            ContentKind::Mixed => write!(f, "图文混排"),
+        }
+    }
+}
+
+/// 分类所依据的统计信号
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClassificationSignals {
+    /// 元数据或所有脊柱页面声明了固定版式
+    pub fixed_layout: bool,
+    /// 图片资源的总大小（字节，未压缩）
+    pub image_bytes: u64,
+    /// 正文纯文本的总大小（字节）
+    pub text_bytes: u64,
+    /// 脊柱页面数
+    pub pages: usize,
+    /// 以图片为主的页面数（含图片且文字少于阈值）
+    pub image_pages: usize,
+}
+
+impl ClassificationSignals {
+    /// 图片在图片与文字总体积中所占的比例
+    pub fn image_byte_ratio(&self) -> f64 {
+        let total = self.image_bytes + self.text_bytes;
+        if total == 0 {
+            0.0
+        } else {
+            self.image_bytes as f64 / total as f64
+        }
+    }
+
+    /// 以图片为主的页面所占的比例
+    pub fn image_page_ratio(&self) -> f64 {
+        if self.pages == 0 {
+            0.0
+        } else {
+            self.image_pages as f64 / self.pages as f64
+        }
+    }
+}
+
+/// 内容分类结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentClassification {
+    /// 判断出的内容类型
+    pub kind: ContentKind,
+    /// 判断依据
+    pub signals: ClassificationSignals,
+}
+
+/// 页面文字少于该字符数（不含空白）且包含图片时视为图片页
+pub(crate) const IMAGE_PAGE_MAX_TEXT_CHARS: usize = 50;
+
+/// 根据统计信号判断内容类型
+///
+/// - 固定版式且至少一半页面为图片页，或至少80%的页面为图片页且图片占总体积的80%以上：漫画
+/// - 非固定版式且图片页少于20%：文字
+/// - 其他情况：图文混排
+///
+/// # 参数
+/// * `signals` - 统计信号
+///
+/// # 返回值
+/// * `ContentKind` - 内容类型
+pub fn classify_signals(signals: &ClassificationSignals) -> ContentKind {
+    let page_ratio = signals.image_page_ratio();
+    if (signals.fixed_layout && page_ratio >= 0.5)
+        || (page_ratio >= 0.8 && signals.image_byte_ratio() >= 0.8)
+    {
+        ContentKind::Comic
+    } else if !signals.fixed_layout && page_ratio < 0.2 {
+        ContentKind::Text
+    } else {
+        ContentKind::Mixed
+    }
+}
+
+/// 统计页面中的图片数量
+///
+/// # 参数
+/// * `html` - 页面HTML内容
+///
+/// # 返回值
+/// * `usize` - `<img>`和SVG `<image>`元素的数量
+pub(crate) fn count_page_images(html: &str) -> usize {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("img, image").unwrap();
+    document.select(&selector).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_signals() {
+        let comic = ClassificationSignals {
+            fixed_layout: true,
+            image_bytes: 5_000_000,
+            text_bytes: 100,
+            pages: 20,
+            image_pages: 20,
+        };
+        assert_eq!(classify_signals(&comic), ContentKind::Comic);
+
+        let novel = ClassificationSignals {
+            fixed_layout: false,
+            image_bytes: 300_000,
+            text_bytes: 400_000,
+            pages: 30,
+            image_pages: 1,
+        };
+        assert_eq!(classify_signals(&novel), ContentKind::Text);
+
+        let illustrated = ClassificationSignals {
+            pages: 10,
+            image_pages: 4,
+            ..novel
+        };
+        assert_eq!(classify_signals(&illustrated), ContentKind::Mixed);
+    }
+}
@@ -1,7 +1,11 @@
+use crate::epub::encoding::strip_bom;
 use crate::epub::error::{EpubError, Result};
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
 
+/// OPF包文件的媒体类型
+pub const OPF_MEDIA_TYPE: &str = "application/oebps-package+xml";
+
 /// Container.xml中的rootfile信息
 #[derive(Debug, Clone)]
 pub struct RootFile {
@@ -24,7 +28,7 @@ impl Container {
     /// # 返回值
     /// * `Result<Container, EpubError>` - 解析后的Container信息
     pub fn parse_xml(xml_content: &str) -> Result<Container> {
-        let mut reader = Reader::from_str(xml_content);
+        let mut reader = Reader::from_str(strip_bom(xml_content));
         reader.config_mut().trim_text(true);
         reader.config_mut().expand_empty_elements = true;
         
@@ -93,15 +97,46 @@ impl Container {
     /// # 返回值
     /// * `Option<String>` - OPF文件的完整路径
     pub fn get_opf_path(&self) -> Option<String> {
-        // 查找第一个application/oebps-package+xml类型的rootfile
-        for rootfile in &self.rootfiles {
-            if rootfile.media_type == "application/oebps-package+xml" {
-                return Some(rootfile.full_path.clone());
-            }
-        }
-        
-        // 如果没有找到标准类型，返回第一个rootfile
-        self.rootfiles.first().map(|rf| rf.full_path.clone())
+        self.opf_rootfile().map(|rf| rf.full_path.clone())
+    }
+    
+    /// 获取主要的OPF rootfile（包含路径和媒体类型）
+    /// 
+    /// 优先选择第一个`application/oebps-package+xml`类型的rootfile，
+    /// 没有时返回第一个rootfile。
+    /// 
+    /// # 返回值
+    /// * `Option<&RootFile>` - 选中的rootfile
+    pub fn opf_rootfile(&self) -> Option<&RootFile> {
+        self.rootfiles_of_type(OPF_MEDIA_TYPE)
+            .into_iter()
+            .next()
+            .or_else(|| self.rootfiles.first())
+    }
+    
+    /// 获取所有版本（rendition）的OPF rootfile
+    /// 
+    /// 多版本EPUB（如同时提供固定版式和流式版式）会在container.xml中声明多个OPF，
+    /// 返回的索引可用于`Epub::select_rendition()`。
+    /// 
+    /// # 返回值
+    /// * `Vec<&RootFile>` - 按声明顺序排列的`application/oebps-package+xml`类型rootfile
+    pub fn rendition_paths(&self) -> Vec<&RootFile> {
+        self.rootfiles_of_type(OPF_MEDIA_TYPE)
+    }
+    
+    /// 获取指定媒体类型的所有rootfile
+    /// 
+    /// # 参数
+    /// * `media_type` - 媒体类型（不区分大小写）
+    /// 
+    /// # 返回值
+    /// * `Vec<&RootFile>` - 按声明顺序排列的rootfile
+    pub fn rootfiles_of_type(&self, media_type: &str) -> Vec<&RootFile> {
+        self.rootfiles
+            .iter()
+            .filter(|rf| rf.media_type.eq_ignore_ascii_case(media_type))
+            .collect()
     }
 }
 
@@ -169,4 +204,50 @@ mod tests {
         assert_eq!(container.rootfiles.len(), 1);
         assert_eq!(container.get_opf_path(), Some("content.opf".to_string()));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_rendition_paths() {
+        let container_xml = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="fixed/content.opf" media-type="application/oebps-package+xml"/>
+        <rootfile full-path="book.pdf" media-type="application/pdf"/>
+        <rootfile full-path="reflow/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+        
+        let container = Container::parse_xml(container_xml).unwrap();
+        let renditions: Vec<&str> = container.rendition_paths().iter().map(|rf| rf.full_path.as_str()).collect();
+        assert_eq!(renditions, vec!["fixed/content.opf", "reflow/content.opf"]);
+        assert_eq!(container.get_opf_path(), Some("fixed/content.opf".to_string()));
+    }
+
+    #[test]
+    fn test_rootfiles_of_type() {
+        let container = Container {
+            rootfiles: vec![
+                RootFile {
+                    full_path: "alt/book.pdf".to_string(),
+                    media_type: "application/pdf".to_string(),
+                },
+                RootFile {
+                    full_path: "OEBPS/content.opf".to_string(),
+                    media_type: "application/oebps-package+xml".to_string(),
+                },
+                RootFile {
+                    full_path: "OEBPS/fixed.opf".to_string(),
+                    media_type: "Application/OEBPS-Package+XML".to_string(),
+                },
+            ],
+        };
+        
+        let packages = container.rootfiles_of_type(OPF_MEDIA_TYPE);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[1].full_path, "OEBPS/fixed.opf");
+        assert_eq!(container.rootfiles_of_type("application/pdf")[0].full_path, "alt/book.pdf");
+        
+        let opf = container.opf_rootfile().unwrap();
+        assert_eq!(opf.full_path, "OEBPS/content.opf");
+        assert_eq!(opf.media_type, OPF_MEDIA_TYPE);
+    }
+}
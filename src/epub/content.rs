@@ -0,0 +1,1282 @@
+//! 章节内容分析模块
+//!
+//! 提供从章节HTML中提取链接、表格等结构化信息的功能，以及EPUB内部链接的路径解析。
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::epub::encoding::strip_bom;
+use crate::epub::ncx::TocTreeNode;
+
+/// 链接类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    /// 外部链接（如http、mailto等）
+    External,
+    /// 指向书中其他章节（可能带锚点）
+    InternalChapter,
+    /// 指向当前章节内的锚点
+    InternalFragment,
+    /// 无法识别的链接（如指向不存在的文件）
+    Unknown,
+}
+
+/// 章节中的超链接
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterLink {
+    /// 原始链接地址
+    pub href: String,
+    /// 链接文本
+    pub text: String,
+    /// 链接类型
+    pub kind: LinkKind,
+}
+
+/// 书中的内部超链接及其目标是否存在
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkRef {
+    /// 链接所在章节的完整路径
+    pub source: String,
+    /// 原始链接地址
+    pub href: String,
+    /// 链接目标文件的完整路径（不含锚点）
+    pub target: String,
+    /// 链接中的锚点（`#`之后的部分）
+    pub fragment: Option<String>,
+    /// 目标文件是否在清单中
+    pub target_exists: bool,
+    /// 锚点是否存在于目标文档中（链接不带锚点时为true）
+    pub anchor_exists: bool,
+}
+
+/// 章节中的表格
+///
+/// 合并单元格会被展开：`colspan`的单元格在后续列中重复，`rowspan`的单元格在后续行中重复，
+/// 较短的行用空字符串补齐，因此所有行（包括表头）的列数相同。
+/// 展开后无法再区分原始的合并结构。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Table {
+    /// 表头（没有表头时为空）
+    pub headers: Vec<String>,
+    /// 数据行
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// 将表格转换为CSV文本
+    ///
+    /// 表头（如果有）作为第一行输出。包含逗号、双引号或换行的字段会用双引号包裹，
+    /// 字段内的双引号写为两个双引号。行之间使用`\r\n`分隔。
+    ///
+    /// # 返回值
+    /// * `String` - CSV文本
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header = (!self.headers.is_empty()).then_some(&self.headers);
+        for row in header.into_iter().chain(&self.rows) {
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+}
+
+/// 按CSV规则转义单个字段
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 章节中的引文
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Quote {
+    /// 引文正文，段落之间以换行分隔
+    pub text: String,
+    /// 出处：末尾`<cite>`/`<footer>`元素的文本，没有时使用`cite`属性
+    pub cite: Option<String>,
+}
+
+/// 单个单元格允许的最大合并数，防止异常的`colspan`/`rowspan`值导致内存占用过大
+const MAX_CELL_SPAN: usize = 1000;
+
+/// 将相对于指定目录的链接解析为EPUB内部的完整路径
+///
+/// 会去掉链接中的锚点和查询参数，并处理 `../` 和 `./` 等相对路径组件。
+/// 以`/`开头的链接视为相对于EPUB根目录，`\`分隔符和`%20`等百分号转义也会被处理，
+/// 规则见`TocTreeNode::normalize_path`。
+///
+/// # 参数
+/// * `base_dir` - 链接所在文件的目录（相对于EPUB根目录）
+/// * `href` - 相对链接
+///
+/// # 返回值
+/// * `String` - 规范化后的完整路径
+pub fn resolve_href(base_dir: &str, href: &str) -> String {
+    let href = href.split(['#', '?']).next().unwrap_or(href).replace('\\', "/");
+    // 部分制作工具会生成以`/`开头、相对于EPUB根目录的链接
+    let base_dir = if href.starts_with('/') { "" } else { base_dir };
+    let mut path = PathBuf::from(base_dir);
+    path.push(href.trim_start_matches('/'));
+    TocTreeNode::normalize_path(&path)
+}
+
+/// 获取路径所在的目录
+pub(crate) fn parent_dir(path: &str) -> &str {
+    path.rfind('/').map(|i| &path[..i]).unwrap_or("")
+}
+
+/// 检查链接是否带有URL协议（如 `http:`、`mailto:`）
+pub(crate) fn has_scheme(href: &str) -> bool {
+    match href.find(':') {
+        Some(pos) => {
+            let scheme = &href[..pos];
+            !scheme.is_empty()
+                && !scheme.contains(['/', '#', '?'])
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// 对链接进行分类
+///
+/// # 参数
+/// * `href` - 原始链接
+/// * `chapter_path` - 链接所在章节的完整路径
+/// * `documents` - 书中所有内容文档的完整路径
+pub(crate) fn classify_link(href: &str, chapter_path: &str, documents: &HashSet<String>) -> LinkKind {
+    let href = href.trim();
+    if href.is_empty() {
+        return LinkKind::Unknown;
+    }
+    if has_scheme(href) {
+        return LinkKind::External;
+    }
+    if href.starts_with('#') {
+        return LinkKind::InternalFragment;
+    }
+
+    let target = resolve_href(parent_dir(chapter_path), href);
+    if target == chapter_path {
+        if href.contains('#') {
+            LinkKind::InternalFragment
+        } else {
+            LinkKind::InternalChapter
+        }
+    } else if documents.contains(&target) {
+        LinkKind::InternalChapter
+    } else {
+        LinkKind::Unknown
+    }
+}
+
+/// 提取章节HTML中的所有超链接
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `chapter_path` - 章节的完整路径
+/// * `documents` - 书中所有内容文档的完整路径
+///
+/// # 返回值
+/// * `Vec<ChapterLink>` - 按文档顺序排列的链接
+pub(crate) fn extract_links(html: &str, chapter_path: &str, documents: &HashSet<String>) -> Vec<ChapterLink> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[href]").unwrap();
+
+    document.select(&selector)
+        .filter_map(|element| {
+            let href = element.value().attr("href")?;
+            let text = collapsed_text(element);
+            Some(ChapterLink {
+                href: href.to_string(),
+                text,
+                kind: classify_link(href, chapter_path, documents),
+            })
+        })
+        .collect()
+}
+
+/// 锚点附近文本的最大字符数
+const ANCHOR_TEXT_MAX_CHARS: usize = 80;
+
+/// 提取章节HTML中所有带`id`属性的元素
+///
+/// 附近文本取元素自身的文本；元素没有文本时（如空的`<a id="..."/>`）取其父元素的文本。
+/// 文本会合并空白，并截断到80个字符。重复的id只保留第一次出现
+/// （HTML解析器会把XHTML中自闭合的`<a id="..."/>`重新打开到后续段落中）。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<(String, String)>` - 按文档顺序排列的(id, 附近文本)
+pub(crate) fn extract_anchors(html: &str) -> Vec<(String, String)> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("body [id]").unwrap();
+    let mut seen = HashSet::new();
+
+    document.select(&selector)
+        .filter_map(|element| {
+            let id = element.value().id()?.to_string();
+            if !seen.insert(id.clone()) {
+                return None;
+            }
+            let mut text = collapsed_text(element);
+            if text.is_empty()
+                && let Some(parent) = element.parent().and_then(ElementRef::wrap)
+            {
+                text = collapsed_text(parent);
+            }
+            if let Some((index, _)) = text.char_indices().nth(ANCHOR_TEXT_MAX_CHARS) {
+                text.truncate(index);
+            }
+            Some((id, text))
+        })
+        .collect()
+}
+
+/// 收集章节HTML中所有元素的`id`
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `HashSet<String>` - 所有`id`属性值
+pub(crate) fn element_ids(html: &str) -> HashSet<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("[id]").unwrap();
+
+    document.select(&selector)
+        .filter_map(|element| element.value().id().map(str::to_string))
+        .collect()
+}
+
+/// 提取章节HTML中图片的链接、元素名与无障碍描述
+///
+/// 包含`<img>`以及SVG中的`<image>`元素（读取`href`或`xlink:href`），外部链接和
+/// `data:`URI会被忽略。描述依次取自：
+/// * `<img>`：`alt`、`aria-label`属性
+/// * `<image>`：自身的`aria-label`属性和`<title>`子元素，然后是所在`<svg>`的
+///   `aria-label`属性和`<title>`子元素
+///
+/// 没有任何描述时为`None`；`<img>`带有`alt=""`且没有`aria-label`时视为装饰性图片，
+/// 描述为空字符串。不包含`<image>`的内联SVG没有对应的图片文件，不会被返回。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<(String, &'static str, Option<String>)>` - 按文档顺序排列的(原始链接, 元素名, 描述)
+pub(crate) fn extract_image_descriptions(html: &str) -> Vec<(String, &'static str, Option<String>)> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("img, image").unwrap();
+
+    let non_empty = |text: &str| {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    };
+    let title_child = |element: ElementRef| {
+        element.children()
+            .filter_map(ElementRef::wrap)
+            .find(|child| child.value().name() == "title")
+            .and_then(|title| non_empty(&title.text().collect::<String>()))
+    };
+    let described = |element: ElementRef| {
+        element.value().attr("aria-label")
+            .and_then(non_empty)
+            .or_else(|| title_child(element))
+    };
+
+    document.select(&selector)
+        .filter_map(|element| {
+            let is_img = element.value().name() == "img";
+            let src = if is_img {
+                element.value().attr("src")
+            } else {
+                // xlink:href的命名空间不为空，需要按本地名查找
+                element.value().attrs()
+                    .find(|(name, _)| *name == "href")
+                    .map(|(_, value)| value)
+            }?.trim();
+            if src.is_empty() || has_scheme(src) {
+                return None;
+            }
+
+            let description = if is_img {
+                let alt = element.value().attr("alt");
+                alt.and_then(non_empty)
+                    .or_else(|| element.value().attr("aria-label").and_then(non_empty))
+                    .or_else(|| alt.map(|_| String::new()))
+            } else {
+                described(element).or_else(|| {
+                    element.ancestors()
+                        .filter_map(ElementRef::wrap)
+                        .find(|ancestor| ancestor.value().name() == "svg")
+                        .and_then(described)
+                })
+            };
+            Some((src.to_string(), if is_img { "img" } else { "image" }, description))
+        })
+        .collect()
+}
+
+/// 提取章节HTML中所有标题的层级
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<u8>` - 按文档顺序排列的标题层级（1-6）
+pub(crate) fn heading_levels(html: &str) -> Vec<u8> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("h1, h2, h3, h4, h5, h6").unwrap();
+
+    document.select(&selector)
+        .filter_map(|element| element.value().name()[1..].parse::<u8>().ok())
+        .collect()
+}
+
+/// 按子元素索引路径定位章节中的元素
+///
+/// 路径从`<html>`元素开始，每级为子元素（不含文本节点）的索引，从0开始。
+/// 注意HTML解析器会补全缺失的`<head>`/`<body>`，路径以补全后的结构为准。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `path` - 子元素索引路径，空路径表示`<html>`元素本身
+///
+/// # 返回值
+/// * `Option<(Vec<Option<String>>, String)>` - (路径上每个元素的id, 目标元素截断后的文本)，
+///   路径不存在时为`None`
+pub(crate) fn element_at_path(html: &str, path: &[usize]) -> Option<(Vec<Option<String>>, String)> {
+    let document = Html::parse_document(html);
+    let mut element = document.root_element();
+    let mut ids = Vec::with_capacity(path.len());
+
+    for &index in path {
+        element = element.children().filter_map(ElementRef::wrap).nth(index)?;
+        ids.push(element.value().id().map(str::to_string));
+    }
+
+    let mut text = collapsed_text(element);
+    if let Some((index, _)) = text.char_indices().nth(ANCHOR_TEXT_MAX_CHARS) {
+        text.truncate(index);
+    }
+    Some((ids, text))
+}
+
+/// 获取元素合并空白后的文本
+fn collapsed_text(element: ElementRef) -> String {
+    element.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 提取章节HTML中引用的资源（样式表、图片、音视频等）
+///
+/// 只返回EPUB内部的相对链接，外部链接和`data:`URI会被忽略。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<String>` - 按文档顺序排列、去重后的原始链接
+pub(crate) fn extract_resource_refs(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(
+        "link[href], img[src], img[srcset], image, source[src], source[srcset], video[src], audio[src], video[poster]"
+    ).unwrap();
+
+    let mut refs = Vec::new();
+    for element in document.select(&selector) {
+        let attrs = element.value();
+        if attrs.name() == "link" {
+            let is_stylesheet = attrs.attr("rel")
+                .map(|rel| rel.split_whitespace().any(|r| r.eq_ignore_ascii_case("stylesheet")))
+                .unwrap_or(false);
+            if !is_stylesheet {
+                continue;
+            }
+        }
+        for name in ["href", "src", "poster"] {
+            if let Some(value) = attr_any_namespace(attrs, name) {
+                push_internal_ref(&mut refs, value);
+            }
+        }
+        for url in attrs.attr("srcset").into_iter().flat_map(srcset_urls) {
+            push_internal_ref(&mut refs, url);
+        }
+    }
+    refs
+}
+
+/// 提取章节HTML中所有元素的`href`、`src`、`poster`和`srcset`属性中的内部链接
+///
+/// 与[`extract_resource_refs`]不同，超链接和脚本等也会被包含。外部链接、`data:`URI和
+/// 纯锚点（`#foo`）会被忽略。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<String>` - 按文档顺序排列、去重后的原始链接
+pub(crate) fn extract_link_refs(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+
+    let mut refs = Vec::new();
+    for element in document.root_element().descendants().filter_map(ElementRef::wrap) {
+        for name in ["href", "src", "poster"] {
+            if let Some(value) = attr_any_namespace(element.value(), name) {
+                push_internal_ref(&mut refs, value);
+            }
+        }
+        for url in element.value().attr("srcset").into_iter().flat_map(srcset_urls) {
+            push_internal_ref(&mut refs, url);
+        }
+    }
+    refs
+}
+
+/// 改写章节HTML中的链接
+///
+/// 只处理元素的`href`（包括`xlink:href`）、`src`、`poster`属性以及`srcset`中的各个链接，
+/// 属性值（反转义后）与`links`中的某个原始链接相同时替换为对应的新链接。
+/// 其他属性和文本内容原样保留，被改写的属性保持原有的引号风格。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `links` - 原始链接到新链接的映射
+///
+/// # 返回值
+/// * `String` - 改写后的HTML，无法解析的部分原样保留
+pub(crate) fn rewrite_link_attributes(html: &str, links: &HashMap<String, String>) -> String {
+    let mut reader = Reader::from_str(html);
+    reader.config_mut().check_end_names = false;
+    reader.config_mut().allow_unmatched_ends = true;
+
+    let mut output = String::with_capacity(html.len());
+    let mut position = 0;
+    while let Ok(event) = reader.read_event() {
+        let end = reader.buffer_position() as usize;
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                output.push_str(&rewrite_tag_links(&html[position..end], &element, links));
+            }
+            Event::Eof => break,
+            _ => output.push_str(&html[position..end]),
+        }
+        position = end;
+    }
+    output.push_str(&html[position..]);
+    output
+}
+
+/// 改写单个开始标签中的链接属性
+///
+/// 按属性顺序在原始标签文本中定位属性值，只替换需要改写的值，其余字符原样保留。
+fn rewrite_tag_links(tag: &str, element: &BytesStart, links: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(tag.len());
+    let mut copied = 0;
+    let mut scanned = 0;
+    for attr in element.html_attributes() {
+        let Ok(attr) = attr else {
+            break;
+        };
+        let key = String::from_utf8_lossy(attr.key.as_ref());
+        let raw_value = String::from_utf8_lossy(&attr.value);
+        let Some(key_start) = tag[scanned..].find(key.as_ref()).map(|pos| scanned + pos) else {
+            break;
+        };
+        let key_end = key_start + key.len();
+        let Some(value_start) = tag[key_end..].find(raw_value.as_ref()).map(|pos| key_end + pos) else {
+            break;
+        };
+        scanned = value_start + raw_value.len();
+
+        let Ok(value) = attr.unescape_value() else {
+            continue;
+        };
+        let new_value = match attr.key.local_name().as_ref() {
+            b"href" | b"src" | b"poster" => links.get(value.trim()).cloned(),
+            b"srcset" => rewrite_srcset(&value, links),
+            _ => None,
+        };
+        let Some(new_value) = new_value else {
+            continue;
+        };
+        output.push_str(&tag[copied..value_start]);
+        match tag[..value_start].chars().last() {
+            Some('"') => output.push_str(&escape_html(&new_value, true)),
+            Some('\'') => output.push_str(&escape_html(&new_value, false).replace('\'', "&#39;")),
+            _ => output.push_str(&format!("\"{}\"", escape_html(&new_value, true))),
+        }
+        copied = scanned;
+    }
+    output.push_str(&tag[copied..]);
+    output
+}
+
+/// 获取`srcset`属性中各候选项的链接
+fn srcset_urls(srcset: &str) -> impl Iterator<Item = &str> {
+    srcset.split(',').filter_map(|candidate| candidate.split_whitespace().next())
+}
+
+/// 改写`srcset`属性中的链接，没有需要改写的链接时返回`None`
+fn rewrite_srcset(srcset: &str, links: &HashMap<String, String>) -> Option<String> {
+    let mut changed = false;
+    let candidates: Vec<String> = srcset.split(',')
+        .map(str::trim)
+        .filter(|candidate| !candidate.is_empty())
+        .map(|candidate| {
+            let (url, descriptor) = candidate.split_once(char::is_whitespace).unwrap_or((candidate, ""));
+            match links.get(url) {
+                Some(new_url) => {
+                    changed = true;
+                    format!("{} {}", new_url, descriptor.trim()).trim_end().to_string()
+                }
+                None => candidate.to_string(),
+            }
+        })
+        .collect();
+    changed.then(|| candidates.join(", "))
+}
+
+/// 提取章节HTML引用的样式
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `(Vec<String>, Vec<String>)` - `<link rel="stylesheet">`的原始链接（去重），
+///   以及按文档顺序排列的`<style>`元素内容
+pub(crate) fn extract_styles(html: &str) -> (Vec<String>, Vec<String>) {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("link[href], style").unwrap();
+
+    let mut links = Vec::new();
+    let mut styles = Vec::new();
+    for element in document.select(&selector) {
+        let attrs = element.value();
+        if attrs.name() == "style" {
+            let css = element.text().collect::<String>();
+            if !css.trim().is_empty() {
+                styles.push(css);
+            }
+        } else if attrs.attr("rel")
+            .is_some_and(|rel| rel.split_whitespace().any(|r| r.eq_ignore_ascii_case("stylesheet")))
+        {
+            push_internal_ref(&mut links, attrs.attr("href").unwrap_or_default());
+        }
+    }
+    (links, styles)
+}
+
+/// 获取章节HTML中`<body>`元素的内部HTML
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `String` - `<body>`的内部HTML，没有`<body>`元素时为空字符串
+pub(crate) fn body_inner_html(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("body").unwrap();
+    document.select(&selector)
+        .next()
+        .map(|body| body.inner_html())
+        .unwrap_or_default()
+}
+
+/// 提取CSS中`url(...)`引用的资源（字体、背景图片等）
+///
+/// # 参数
+/// * `css` - CSS内容
+///
+/// # 返回值
+/// * `Vec<String>` - 去重后的原始链接
+pub(crate) fn extract_css_urls(css: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut rest = css;
+    while let Some(pos) = rest.find("url(") {
+        rest = &rest[pos + 4..];
+        let Some(end) = rest.find(')') else {
+            break;
+        };
+        let value = rest[..end].trim().trim_matches(|c| c == '"' || c == '\'');
+        push_internal_ref(&mut refs, value);
+        rest = &rest[end..];
+    }
+    refs
+}
+
+/// 提取章节HTML中的所有表格
+///
+/// 表头取自`<thead>`的第一行；没有`<thead>`时，如果第一行全部由`<th>`组成则作为表头。
+/// 嵌套表格会作为独立的表格返回，其内容不计入外层表格的行。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<Table>` - 按文档顺序排列的表格
+pub(crate) fn extract_tables(html: &str) -> Vec<Table> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("table").unwrap();
+    document.select(&selector).map(parse_table).collect()
+}
+
+/// 提取章节HTML中的所有引文
+///
+/// 嵌套在其他`<blockquote>`中的引文作为外层引文的一部分，不单独返回。
+/// `<blockquote>`的最后一个子元素为`<cite>`或`<footer>`时，其文本作为出处
+/// （去掉开头的破折号），且不计入正文。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Vec<Quote>` - 按文档顺序排列的引文（正文为空的引文会被忽略）
+pub(crate) fn extract_blockquotes(html: &str) -> Vec<Quote> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("blockquote").unwrap();
+
+    document.select(&selector)
+        .filter(|quote| nearest_ancestor(*quote, "blockquote").is_none())
+        .filter_map(parse_blockquote)
+        .collect()
+}
+
+/// 解析单个引文元素
+fn parse_blockquote(quote: ElementRef) -> Option<Quote> {
+    let attribution = quote.children()
+        .filter_map(ElementRef::wrap)
+        .last()
+        .filter(|last| matches!(last.value().name(), "cite" | "footer"));
+
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    for child in quote.children() {
+        if let Some(element) = ElementRef::wrap(child) {
+            if Some(element.id()) == attribution.map(|a| a.id()) {
+                continue;
+            }
+            if is_block_element(element.value().name()) {
+                paragraphs.push(std::mem::take(&mut current));
+                paragraphs.push(element.text().collect());
+                continue;
+            }
+            current.extend(element.text());
+        } else if let Some(text) = child.value().as_text() {
+            current.push_str(text);
+        }
+    }
+    paragraphs.push(current);
+
+    let text = paragraphs.iter()
+        .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.is_empty() {
+        return None;
+    }
+
+    let cite = attribution
+        .map(|element| collapsed_text(element).trim_start_matches(['—', '–', '-', ' ']).to_string())
+        .filter(|cite| !cite.is_empty())
+        .or_else(|| {
+            quote.value().attr("cite")
+                .map(|cite| cite.trim().to_string())
+                .filter(|cite| !cite.is_empty())
+        });
+
+    Some(Quote { text, cite })
+}
+
+/// 判断元素是否为引文中构成独立段落的块级元素
+fn is_block_element(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "div" | "li" | "pre" | "blockquote" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+    )
+}
+
+/// 解析单个表格元素
+pub(crate) fn parse_table(table: ElementRef) -> Table {
+    let row_selector = Selector::parse("tr").unwrap();
+
+    // (是否位于thead中, 是否全部为th, 展开后的单元格)
+    let mut grid: Vec<(bool, bool, Vec<String>)> = Vec::new();
+    // 每列尚未用完的rowspan: (剩余行数, 单元格文本)
+    let mut pending: Vec<(usize, String)> = Vec::new();
+
+    for row in table.select(&row_selector) {
+        if nearest_ancestor(row, "table").map(|t| t.id()) != Some(table.id()) {
+            continue;
+        }
+        let in_thead = nearest_ancestor(row, "thead").is_some_and(|thead| {
+            nearest_ancestor(thead, "table").map(|t| t.id()) == Some(table.id())
+        });
+
+        let cells: Vec<ElementRef> = row.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+            .collect();
+        let all_th = !cells.is_empty() && cells.iter().all(|cell| cell.value().name() == "th");
+
+        let mut values = Vec::new();
+        let mut column = 0;
+        for cell in cells {
+            fill_pending(&mut pending, &mut values, &mut column, false);
+
+            let text = collapsed_text(cell);
+            let colspan = span_attr(cell, "colspan");
+            let rowspan = span_attr(cell, "rowspan");
+            for _ in 0..colspan {
+                if pending.len() <= column {
+                    pending.resize(column + 1, (0, String::new()));
+                }
+                pending[column] = (rowspan - 1, text.clone());
+                values.push(text.clone());
+                column += 1;
+            }
+        }
+        fill_pending(&mut pending, &mut values, &mut column, true);
+
+        grid.push((in_thead, all_th, values));
+    }
+
+    let width = grid.iter().map(|(_, _, values)| values.len()).max().unwrap_or(0);
+    for (_, _, values) in &mut grid {
+        values.resize(width, String::new());
+    }
+
+    let header_index = grid.iter().position(|(in_thead, _, _)| *in_thead)
+        .or_else(|| grid.first().filter(|(_, all_th, _)| *all_th).map(|_| 0));
+
+    let mut table = Table::default();
+    for (index, (_, _, values)) in grid.into_iter().enumerate() {
+        if Some(index) == header_index {
+            table.headers = values;
+        } else {
+            table.rows.push(values);
+        }
+    }
+    table
+}
+
+/// 用上方行的rowspan单元格填充当前行
+///
+/// `to_end`为`false`时只填充当前列起连续的rowspan单元格，为`true`时填充到行尾。
+fn fill_pending(pending: &mut [(usize, String)], values: &mut Vec<String>, column: &mut usize, to_end: bool) {
+    while *column < pending.len() {
+        let (remaining, text) = &mut pending[*column];
+        if *remaining > 0 {
+            *remaining -= 1;
+            values.push(text.clone());
+        } else if to_end {
+            values.push(String::new());
+        } else {
+            break;
+        }
+        *column += 1;
+    }
+}
+
+/// 读取`colspan`/`rowspan`属性，缺失或无效时为1
+fn span_attr(cell: ElementRef, name: &str) -> usize {
+    cell.value().attr(name)
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|span| *span > 0)
+        .unwrap_or(1)
+        .min(MAX_CELL_SPAN)
+}
+
+/// 查找最近的指定名称的祖先元素
+fn nearest_ancestor<'a>(element: ElementRef<'a>, name: &str) -> Option<ElementRef<'a>> {
+    element.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().name() == name)
+}
+
+/// 获取章节HTML声明的语言
+///
+/// 依次检查`<html>`和`<body>`元素的`xml:lang`与`lang`属性，返回第一个非空值。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Option<String>` - 声明的语言代码
+pub(crate) fn declared_language(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("html, body").unwrap();
+
+    document.select(&selector)
+        .find_map(|element| {
+            let attrs = element.value();
+            ["xml:lang", "lang"].iter()
+                .filter_map(|name| attr_any_namespace(attrs, name))
+                .map(str::trim)
+                .find(|lang| !lang.is_empty())
+                .map(str::to_string)
+        })
+}
+
+/// 获取章节HTML根元素声明的语言
+///
+/// 只读取到第一个元素（通常为`<html>`）为止，不解析整个文档，
+/// 依次检查其`xml:lang`与`lang`属性，返回第一个非空值。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+///
+/// # 返回值
+/// * `Option<String>` - 根元素声明的语言代码
+pub(crate) fn root_language(html: &str) -> Option<String> {
+    let mut reader = Reader::from_str(strip_bom(html));
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(element) | Event::Empty(element) => {
+                let mut xml_lang = None;
+                let mut lang = None;
+                for attr in element.attributes().flatten() {
+                    let value = String::from_utf8_lossy(&attr.value).trim().to_string();
+                    match attr.key.as_ref() {
+                        b"xml:lang" => xml_lang = Some(value),
+                        b"lang" => lang = Some(value),
+                        _ => {}
+                    }
+                }
+                return [xml_lang, lang].into_iter()
+                    .flatten()
+                    .find(|value| !value.is_empty());
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+/// 按本地名称获取属性值，忽略命名空间
+///
+/// SVG中的`xlink:href`会被解析为xlink命名空间下的`href`属性，
+/// 无法通过`attr("href")`获取。
+pub(crate) fn attr_any_namespace<'a>(element: &'a scraper::node::Element, name: &str) -> Option<&'a str> {
+    element.attrs().find(|(key, _)| *key == name).map(|(_, value)| value)
+}
+
+/// 截取章节HTML中两个锚点之间的内容
+///
+/// 从`id`为`start_id`的元素开始（包含该元素），到`id`为`end_id`的元素之前结束（不包含），
+/// 两个锚点所在元素的祖先元素会保留开始和结束标签，以保持HTML结构完整；
+/// 起始锚点之前的内容（包括`<head>`）不会输出。
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `start_id` - 起始锚点，为None时从文档开头开始
+/// * `end_id` - 结束锚点，为None或在起始锚点之前时截取到文档末尾
+///
+/// # 返回值
+/// * `Option<String>` - 截取的HTML，找不到起始锚点时为`None`
+pub(crate) fn slice_between_anchors(html: &str, start_id: Option<&str>, end_id: Option<&str>) -> Option<String> {
+    let document = Html::parse_document(html);
+    let root = document.root_element();
+    if let Some(start_id) = start_id
+        && !has_id_in_subtree(root, start_id)
+    {
+        return None;
+    }
+
+    let mut state = if start_id.is_some() { SliceState::Before } else { SliceState::Inside };
+    let mut output = String::new();
+    slice_element(root, start_id, end_id, &mut state, &mut output);
+    Some(output)
+}
+
+/// 截取锚点之间内容时的遍历状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SliceState {
+    /// 尚未到达起始锚点
+    Before,
+    /// 位于截取范围内
+    Inside,
+    /// 已到达结束锚点
+    After,
+}
+
+/// 按文档顺序遍历元素，输出截取范围内的HTML
+fn slice_element(
+    element: ElementRef,
+    start_id: Option<&str>,
+    end_id: Option<&str>,
+    state: &mut SliceState,
+    output: &mut String,
+) {
+    let id = element.value().id();
+    match *state {
+        SliceState::After => return,
+        SliceState::Inside if end_id.is_some() && id == end_id => {
+            *state = SliceState::After;
+            return;
+        }
+        SliceState::Before if id == start_id => *state = SliceState::Inside,
+        _ => {}
+    }
+
+    // 子树中没有需要切分的锚点时整体输出或跳过
+    let boundary = match *state {
+        SliceState::Before => start_id,
+        _ => end_id,
+    };
+    let has_boundary = boundary.is_some_and(|boundary| {
+        element.descendants().skip(1).filter_map(ElementRef::wrap).any(|e| e.value().id() == Some(boundary))
+    });
+    if !has_boundary {
+        if *state == SliceState::Inside {
+            output.push_str(&element.html());
+        }
+        return;
+    }
+
+    let mut inner = String::new();
+    for child in element.children() {
+        match child.value() {
+            scraper::node::Node::Element(_) => {
+                if let Some(child_element) = ElementRef::wrap(child) {
+                    slice_element(child_element, start_id, end_id, state, &mut inner);
+                }
+            }
+            scraper::node::Node::Text(text) if *state == SliceState::Inside => {
+                inner.push_str(&escape_html(text, false));
+            }
+            _ => {}
+        }
+    }
+    if inner.is_empty() {
+        return;
+    }
+
+    let name = element.value().name();
+    output.push('<');
+    output.push_str(name);
+    for (attr_name, value) in element.value().attrs() {
+        output.push_str(&format!(" {}=\"{}\"", attr_name, escape_html(value, true)));
+    }
+    output.push('>');
+    output.push_str(&inner);
+    output.push_str(&format!("</{}>", name));
+}
+
+/// 检查元素及其后代中是否存在指定id
+fn has_id_in_subtree(element: ElementRef, id: &str) -> bool {
+    element.descendants().filter_map(ElementRef::wrap).any(|e| e.value().id() == Some(id))
+}
+
+/// 转义HTML文本或属性值中的特殊字符
+pub(crate) fn escape_html(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 添加EPUB内部的资源链接（忽略外部链接、数据URI和纯锚点）
+fn push_internal_ref(refs: &mut Vec<String>, value: &str) {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('#') || has_scheme(value) {
+        return;
+    }
+    if !refs.iter().any(|r| r == value) {
+        refs.push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_href() {
+        assert_eq!(resolve_href("OEBPS/text", "../images/a.png"), "OEBPS/images/a.png");
+        assert_eq!(resolve_href("", "./chapter1.xhtml#top"), "chapter1.xhtml");
+        assert_eq!(resolve_href("OEBPS/toc", "/OEBPS/text/ch1.xhtml"), "OEBPS/text/ch1.xhtml");
+        assert_eq!(resolve_href("OEBPS", "images\\cover.jpg"), "OEBPS/images/cover.jpg");
+        assert_eq!(resolve_href("OEBPS\\text", "..\\images\\cover.jpg"), "OEBPS/images/cover.jpg");
+        assert_eq!(resolve_href("OEBPS", "chapter%201.xhtml#p%201"), "OEBPS/chapter 1.xhtml");
+        assert_eq!(resolve_href("", "%E7%AC%AC%E4%B8%80%E7%AB%A0.xhtml"), "第一章.xhtml");
+        // 不完整或无效的转义保持原样
+        assert_eq!(resolve_href("", "100%.xhtml"), "100%.xhtml");
+        assert_eq!(resolve_href("", "a%zz.xhtml"), "a%zz.xhtml");
+        assert_eq!(resolve_href("", "a%+1.xhtml"), "a%+1.xhtml");
+    }
+
+    #[test]
+    fn test_slice_between_anchors() {
+        let html = r#"<html><head><title>标题</title></head><body>
+<h1>前言</h1>
+<section id="ch1"><h2>第一章</h2><p>甲 &amp; 乙</p><a id="ch2"/><p>第二章内容</p></section>
+<p id="ch3">第三章</p>
+</body></html>"#;
+
+        let first = slice_between_anchors(html, Some("ch1"), Some("ch2")).unwrap();
+        assert!(first.contains("<section id=\"ch1\">"));
+        assert!(first.contains("甲 &amp; 乙"));
+        assert!(!first.contains("第二章内容"));
+        assert!(!first.contains("前言"));
+        assert!(!first.contains("标题"));
+
+        let second = slice_between_anchors(html, Some("ch2"), Some("ch3")).unwrap();
+        assert!(second.contains("第二章内容"));
+        assert!(!second.contains("第一章"));
+        assert!(!second.contains("第三章"));
+
+        let preface = slice_between_anchors(html, None, Some("ch1")).unwrap();
+        assert!(preface.contains("前言"));
+        assert!(!preface.contains("第一章"));
+
+        let last = slice_between_anchors(html, Some("ch3"), None).unwrap();
+        assert!(last.contains("第三章"));
+        assert!(!last.contains("第二章内容"));
+
+        assert_eq!(slice_between_anchors(html, Some("missing"), None), None);
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let documents: HashSet<String> = ["OEBPS/text/ch1.xhtml", "OEBPS/text/ch2.xhtml"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let html = r##"<html><body>
+<a href="https://example.com">外部 链接</a>
+<a href="#note1">注释</a>
+<a href="ch2.xhtml#sec">下一章</a>
+<a href="ch1.xhtml#top">本章开头</a>
+<a href="missing.xhtml">失效</a>
+<a href="mailto:someone@example.com">邮件</a>
+</body></html>"##;
+
+        let links = extract_links(html, "OEBPS/text/ch1.xhtml", &documents);
+        let kinds: Vec<LinkKind> = links.iter().map(|l| l.kind).collect();
+        assert_eq!(kinds, vec![
+            LinkKind::External,
+            LinkKind::InternalFragment,
+            LinkKind::InternalChapter,
+            LinkKind::InternalFragment,
+            LinkKind::Unknown,
+            LinkKind::External,
+        ]);
+        assert_eq!(links[0].text, "外部 链接");
+        assert_eq!(links[2].href, "ch2.xhtml#sec");
+    }
+
+    #[test]
+    fn test_extract_anchors() {
+        let html = r#"<html><body id="top">
+<h1 id="ch1">第一章
+  开始</h1>
+<p><a id="p1"/>这是第一段。</p>
+<p>没有锚点</p>
+</body></html>"#;
+        let anchors = extract_anchors(html);
+        assert_eq!(anchors, vec![
+            ("ch1".to_string(), "第一章 开始".to_string()),
+            ("p1".to_string(), "这是第一段。".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_declared_language() {
+        let html = r#"<html xmlns="http://www.w3.org/1999/xhtml" xml:lang="ja" lang="ja"><body></body></html>"#;
+        assert_eq!(declared_language(html).as_deref(), Some("ja"));
+        let html = r#"<html><body lang="en-US"><p>text</p></body></html>"#;
+        assert_eq!(declared_language(html).as_deref(), Some("en-US"));
+        assert_eq!(declared_language("<html><body></body></html>"), None);
+    }
+
+    #[test]
+    fn test_extract_styles() {
+        let html = r#"<html><head>
+<link rel="stylesheet" href="../css/main.css"/>
+<link rel="alternate stylesheet" href="../css/main.css"/>
+<link rel="icon" href="icon.png"/>
+<style>p { margin: 0; }</style>
+<style>  </style>
+</head><body><p>正文<br/>第二行</p></body></html>"#;
+        let (links, styles) = extract_styles(html);
+        assert_eq!(links, vec!["../css/main.css"]);
+        assert_eq!(styles, vec!["p { margin: 0; }"]);
+        assert_eq!(body_inner_html(html), "<p>正文<br>第二行</p>");
+    }
+
+    #[test]
+    fn test_root_language() {
+        let html = "\u{feff}<?xml version=\"1.0\"?>\n<!DOCTYPE html>\n<!-- 注释 -->\n<html xmlns=\"http://www.w3.org/1999/xhtml\" lang=\"en\" xml:lang=\"fr\"><body><p>&nbsp;<br></p></body></html>";
+        assert_eq!(root_language(html).as_deref(), Some("fr"));
+        assert_eq!(root_language(r#"<html lang=" de "><body>"#).as_deref(), Some("de"));
+        // 只检查根元素
+        assert_eq!(root_language(r#"<html><body lang="en-US"></body></html>"#), None);
+        assert_eq!(root_language(""), None);
+    }
+
+    #[test]
+    fn test_extract_resource_refs() {
+        let html = r#"<html><head>
+<link rel="stylesheet" href="../styles/main.css"/>
+<link rel="next" href="ch2.xhtml"/>
+</head><body>
+<img src="../images/a.png"/>
+<img src="data:image/png;base64,AAAA"/>
+<svg><image xlink:href="../images/b.jpg"/></svg>
+<img src="../images/a.png"/>
+</body></html>"#;
+        assert_eq!(extract_resource_refs(html), vec![
+            "../styles/main.css",
+            "../images/a.png",
+            "../images/b.jpg",
+        ]);
+
+        let css = r#"@font-face { src: url("../fonts/a.otf"); } body { background: url(bg.png) } .x { background: url('https://example.com/x.png') }"#;
+        assert_eq!(extract_css_urls(css), vec!["../fonts/a.otf", "bg.png"]);
+    }
+
+    #[test]
+    fn test_rewrite_link_attributes() {
+        let html = r#"<html><body>
+<img src='../images/a.png' alt="../images/a.png"/>
+<a href="ch2.xhtml?x=1&amp;y=2" title="ch2.xhtml" data-target="ch2.xhtml">下一章</a>
+<img srcset="../images/a.png 1x, ../images/b.png 2x" src="../images/b.png"/>
+<svg><image xlink:href="../images/a.png"/></svg>
+<pre>src="../images/a.png"</pre><br>
+</body></html>"#;
+        let links = HashMap::from([
+            ("../images/a.png".to_string(), "a.png".to_string()),
+            ("../images/b.png".to_string(), "b.png".to_string()),
+            ("ch2.xhtml?x=1&y=2".to_string(), "#chapter-ch2".to_string()),
+            ("ch2.xhtml".to_string(), "#wrong".to_string()),
+        ]);
+        assert_eq!(rewrite_link_attributes(html, &links), r##"<html><body>
+<img src='a.png' alt="../images/a.png"/>
+<a href="#chapter-ch2" title="ch2.xhtml" data-target="ch2.xhtml">下一章</a>
+<img srcset="a.png 1x, b.png 2x" src="b.png"/>
+<svg><image xlink:href="a.png"/></svg>
+<pre>src="../images/a.png"</pre><br>
+</body></html>"##);
+        assert_eq!(extract_link_refs(html), vec!["../images/a.png", "ch2.xhtml?x=1&y=2", "../images/b.png"]);
+    }
+
+    #[test]
+    fn test_extract_tables() {
+        let html = r#"<html><body>
+<table>
+  <thead><tr><th>名称</th><th colspan="2">数值</th></tr></thead>
+  <tbody>
+    <tr><td rowspan="2">甲</td><td>1</td><td>2</td></tr>
+    <tr><td>3</td></tr>
+    <tr><td>乙</td><td><table><tr><td>内层</td></tr></table></td></tr>
+  </tbody>
+</table>
+<table><tr><td>a</td><td>b</td></tr></table>
+</body></html>"#;
+
+        let tables = extract_tables(html);
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].headers, vec!["名称", "数值", "数值"]);
+        assert_eq!(tables[0].rows, vec![
+            vec!["甲", "1", "2"],
+            vec!["甲", "3", ""],
+            vec!["乙", "内层", ""],
+        ]);
+        assert_eq!(tables[1].rows, vec![vec!["内层"]]);
+        assert!(tables[2].headers.is_empty());
+        assert_eq!(tables[2].rows, vec![vec!["a", "b"]]);
+    }
+
+    #[test]
+    fn test_table_to_csv() {
+        let table = Table {
+            headers: vec!["名称".to_string(), "说明".to_string()],
+            rows: vec![
+                vec!["a,b".to_string(), "say \"hi\"".to_string()],
+                vec!["多行\n文本".to_string(), "普通".to_string()],
+            ],
+        };
+        assert_eq!(
+            table.to_csv(),
+            "名称,说明\r\n\"a,b\",\"say \"\"hi\"\"\"\r\n\"多行\n文本\",普通\r\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_image_descriptions() {
+        let html = r#"<html><body>
+            <img src="a.png" alt=" 地图 "/>
+            <img src="b.png" alt="" aria-label="图表"/>
+            <img src="c.png" alt=""/>
+            <img src="../images/h.png"/>
+            <img src="data:image/png;base64,AAAA" alt="内嵌"/>
+            <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" aria-label="封面">
+                <image xlink:href="cover.jpg"/>
+            </svg>
+            <svg xmlns="http://www.w3.org/2000/svg">
+                <title>插图</title>
+                <image href="d.png"/>
+                <image href="e.png"><title>细节</title></image>
+            </svg>
+            <svg xmlns="http://www.w3.org/2000/svg"><image href="f.png"/></svg>
+            <img src="http://example.com/g.png" alt="外部"/>
+        </body></html>"#;
+        assert_eq!(extract_image_descriptions(html), vec![
+            ("a.png".to_string(), "img", Some("地图".to_string())),
+            ("b.png".to_string(), "img", Some("图表".to_string())),
+            ("c.png".to_string(), "img", Some(String::new())),
+            ("../images/h.png".to_string(), "img", None),
+            ("cover.jpg".to_string(), "image", Some("封面".to_string())),
+            ("d.png".to_string(), "image", Some("插图".to_string())),
+            ("e.png".to_string(), "image", Some("细节".to_string())),
+            ("f.png".to_string(), "image", None),
+        ]);
+    }
+
+    #[test]
+    fn test_element_at_path() {
+        let html = r#"<html><head><title>t</title></head><body><p id="p1">第一段</p><div><p>第二段</p></div></body></html>"#;
+        assert_eq!(element_at_path(html, &[1, 0]), Some((vec![None, Some("p1".to_string())], "第一段".to_string())));
+        assert_eq!(element_at_path(html, &[1, 1, 0]).unwrap().1, "第二段");
+        assert_eq!(element_at_path(html, &[1, 5]), None);
+    }
+
+    #[test]
+    fn test_extract_blockquotes() {
+        let html = r#"<html><body>
+<blockquote cite="https://example.com/source">
+    <p>第一段</p>
+    <p>第二段
+       续行</p>
+    <footer>—— 某位作者</footer>
+</blockquote>
+<blockquote cite="https://example.com/only-attr">单段<em>引文</em><blockquote><p>内层</p></blockquote></blockquote>
+<blockquote>  </blockquote>
+</body></html>"#;
+        let quotes = extract_blockquotes(html);
+        assert_eq!(quotes, vec![
+            Quote { text: "第一段\n第二段 续行".to_string(), cite: Some("某位作者".to_string()) },
+            Quote { text: "单段引文\n内层".to_string(), cite: Some("https://example.com/only-attr".to_string()) },
+        ]);
+    }
+}
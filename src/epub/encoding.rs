@@ -0,0 +1,186 @@
+//! 文本编码处理模块
+//!
+//! 提供EPUB内XML/XHTML文件的编码检测与解码功能，处理BOM、GBK/Big5等
+//! 非UTF-8编码以及XML声明中的编码与实际字节不一致等情况。
+
+use encoding_rs::Encoding;
+
+use crate::epub::error::{EpubError, Result};
+
+/// UTF-8字节顺序标记
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// 去掉字符串开头的BOM（如果存在）
+///
+/// # 参数
+/// * `content` - 文本内容
+///
+/// # 返回值
+/// * `&str` - 去掉BOM后的文本
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{FEFF}').unwrap_or(content)
+}
+
+/// 获取XML声明中的编码名称（小写）
+///
+/// # 参数
+/// * `bytes` - 文件的原始字节
+///
+/// # 返回值
+/// * `Option<String>` - 声明的编码（如果存在）
+pub fn declared_encoding(bytes: &[u8]) -> Option<String> {
+    let head = &bytes[..bytes.len().min(256)];
+    let head = String::from_utf8_lossy(head);
+    let declaration = &head[head.find("<?xml")?..];
+    let declaration = &declaration[..declaration.find("?>")?];
+
+    let pos = declaration.find("encoding")?;
+    let rest = declaration[pos + "encoding".len()..].trim_start();
+    let rest = rest.strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &rest[1..];
+    let end = value.find(quote)?;
+    Some(value[..end].trim().to_lowercase())
+}
+
+/// 获取HTML `<meta>`元素中声明的字符集名称（小写）
+///
+/// 支持`<meta charset="...">`和`<meta http-equiv="Content-Type" content="...; charset=...">`两种形式，
+/// 只检查文件开头的1024个字节。
+///
+/// # 参数
+/// * `bytes` - 文件的原始字节
+///
+/// # 返回值
+/// * `Option<String>` - 声明的字符集（如果存在）
+pub fn meta_charset(bytes: &[u8]) -> Option<String> {
+    let head = &bytes[..bytes.len().min(1024)];
+    let head = String::from_utf8_lossy(head).to_lowercase();
+
+    head.match_indices("<meta").find_map(|(start, _)| {
+        let tag = &head[start..];
+        let tag = &tag[..tag.find('>')?];
+        let rest = &tag[tag.find("charset")? + "charset".len()..];
+        let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+        let value = rest.trim_start_matches(['"', '\'']);
+        let end = value.find(|c: char| matches!(c, '"' | '\'' | ';' | '/') || c.is_whitespace())
+            .unwrap_or(value.len());
+        Some(value[..end].to_string()).filter(|charset| !charset.is_empty())
+    })
+}
+
+/// 将XML/XHTML文件的原始字节解码为字符串
+///
+/// 处理规则：
+/// 1. 带UTF-8 BOM的文件去掉BOM后按UTF-8解码
+/// 2. 带UTF-16 BOM的文件按对应字节序解码
+/// 3. 字节是合法UTF-8时按UTF-8解码（即使声明了其他编码）
+/// 4. 否则按XML声明或`<meta>`中的编码解码（如GBK、Big5、Shift_JIS），
+///    没有声明时以UTF-8有损解码
+///
+/// 解码过程中的问题（声明与实际编码不一致、有损解码等）作为警告返回，由调用方决定如何处理。
+///
+/// # 参数
+/// * `bytes` - 文件的原始字节
+/// * `filename` - 文件名（用于警告信息）
+///
+/// # 返回值
+/// * `Result<(String, Option<String>)>` - 解码后的文本（不含BOM）和警告信息，
+///   声明的编码无法识别时返回`EpubError::UnsupportedEncoding`
+pub fn decode_xml_bytes(bytes: &[u8], filename: &str) -> Result<(String, Option<String>)> {
+    if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        return Ok((String::from_utf8_lossy(rest).into_owned(), None));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return Ok((decode_utf16(rest, u16::from_le_bytes), None));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return Ok((decode_utf16(rest, u16::from_be_bytes), None));
+    }
+
+    let declared = declared_encoding(bytes).or_else(|| meta_charset(bytes));
+    if let Ok(content) = std::str::from_utf8(bytes) {
+        let warning = declared.as_ref()
+            .filter(|encoding| !is_utf8_compatible(encoding) && !content.is_ascii())
+            .map(|encoding| format!("文件 {} 声明的编码为 {}，但实际内容为UTF-8", filename, encoding));
+        return Ok((content.to_string(), warning));
+    }
+
+    let Some(label) = declared else {
+        return Ok((
+            String::from_utf8_lossy(bytes).into_owned(),
+            Some(format!("文件 {} 不是有效的UTF-8文本，将以有损方式解码", filename)),
+        ));
+    };
+    let encoding = Encoding::for_label(label.as_bytes())
+        .ok_or_else(|| EpubError::UnsupportedEncoding(format!("{}（{}）", label, filename)))?;
+
+    let (content, _, had_errors) = encoding.decode(bytes);
+    let warning = had_errors
+        .then(|| format!("文件 {} 包含无效的 {} 字节序列，已替换为占位符", filename, encoding.name()));
+    Ok((content.into_owned(), warning))
+}
+
+/// 检查编码名称是否与UTF-8兼容
+fn is_utf8_compatible(encoding: &str) -> bool {
+    matches!(encoding, "utf-8" | "utf8" | "us-ascii" | "ascii")
+}
+
+/// 解码UTF-16字节
+fn decode_utf16(bytes: &[u8], to_u16: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| to_u16([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_encoding() {
+        let xml = br#"<?xml version="1.0" encoding='ISO-8859-1'?><a/>"#;
+        assert_eq!(declared_encoding(xml).as_deref(), Some("iso-8859-1"));
+        assert_eq!(declared_encoding(b"<?xml version=\"1.0\"?><a/>"), None);
+    }
+
+    #[test]
+    fn test_decode_xml_bytes() {
+        let mut bom = UTF8_BOM.to_vec();
+        bom.extend_from_slice("<a>标题</a>".as_bytes());
+        assert_eq!(decode_xml_bytes(&bom, "a.xml").unwrap(), ("<a>标题</a>".to_string(), None));
+
+        let latin1 = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><a>caf\xE9</a>";
+        assert!(decode_xml_bytes(latin1, "b.xml").unwrap().0.ends_with("<a>caf\u{e9}</a>"));
+
+        let mut utf16 = vec![0xFF, 0xFE];
+        for unit in "<a/>".encode_utf16() {
+            utf16.extend_from_slice(&unit.to_le_bytes());
+        }
+        assert_eq!(decode_xml_bytes(&utf16, "c.xml").unwrap().0, "<a/>");
+
+        // GBK编码的"中文"
+        let gbk = b"<?xml version=\"1.0\" encoding=\"GBK\"?><a>\xD6\xD0\xCE\xC4</a>";
+        assert!(decode_xml_bytes(gbk, "d.xml").unwrap().0.ends_with("<a>中文</a>"));
+
+        // Big5编码的"中文"，编码声明在<meta>中
+        let big5 = b"<html><head><meta http-equiv=\"Content-Type\" content=\"text/html; charset=big5\"/></head><p>\xA4\xA4\xA4\xE5</p></html>";
+        assert!(decode_xml_bytes(big5, "e.xhtml").unwrap().0.contains("<p>中文</p>"));
+
+        let (content, warning) = decode_xml_bytes(b"<a>\xFF</a>", "g.xml").unwrap();
+        assert_eq!(content, "<a>\u{FFFD}</a>");
+        assert!(warning.unwrap().contains("g.xml"));
+
+        let unknown = b"<?xml version=\"1.0\" encoding=\"x-unknown\"?><a>\xFF</a>";
+        assert!(matches!(decode_xml_bytes(unknown, "f.xml"), Err(EpubError::UnsupportedEncoding(_))));
+    }
+
+    #[test]
+    fn test_meta_charset() {
+        assert_eq!(meta_charset(b"<head><meta charset=\"GB2312\"/></head>").as_deref(), Some("gb2312"));
+        assert_eq!(meta_charset(b"<meta content='text/html; charset=big5' http-equiv='Content-Type'>").as_deref(), Some("big5"));
+        assert_eq!(meta_charset(b"<meta name=\"viewport\" content=\"width=device-width\"/>"), None);
+    }
+}
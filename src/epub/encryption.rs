@@ -0,0 +1,170 @@
+//! 加密信息模块
+//!
+//! 解析`META-INF/encryption.xml`，获取被加密或混淆（如嵌入字体）的资源列表，
+//! 并提供IDPF字体混淆算法的还原。
+
+use crate::epub::encoding::strip_bom;
+use crate::epub::error::{EpubError, Result};
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+/// encryption.xml在EPUB中的路径
+pub(crate) const ENCRYPTION_PATH: &str = "META-INF/encryption.xml";
+
+/// Adobe ADEPT授权文件在EPUB中的路径
+pub(crate) const ADOBE_RIGHTS_PATH: &str = "META-INF/rights.xml";
+
+/// Readium LCP授权文件在EPUB中的路径
+pub(crate) const LCP_LICENSE_PATH: &str = "META-INF/license.lcpl";
+
+/// Apple FairPlay授权文件在EPUB中的路径
+pub(crate) const FAIRPLAY_SINF_PATH: &str = "META-INF/sinf.xml";
+
+/// IDPF字体混淆算法的标识
+pub(crate) const IDPF_OBFUSCATION_ALGORITHM: &str = "http://www.idpf.org/2008/embedding";
+
+/// IDPF算法混淆的字节数
+const IDPF_OBFUSCATED_LENGTH: usize = 1040;
+
+/// encryption.xml中声明的加密资源
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedResource {
+    /// 资源路径（相对于EPUB根目录）
+    pub uri: String,
+    /// 加密算法（`EncryptionMethod`的`Algorithm`属性）
+    pub algorithm: Option<String>,
+}
+
+/// 解析encryption.xml内容
+///
+/// # 参数
+/// * `xml_content` - encryption.xml的文件内容
+///
+/// # 返回值
+/// * `Result<Vec<EncryptedResource>>` - 按声明顺序排列的加密资源
+pub(crate) fn parse_encryption_xml(xml_content: &str) -> Result<Vec<EncryptedResource>> {
+    let mut reader = Reader::from_str(strip_bom(xml_content));
+    reader.config_mut().trim_text(true);
+    reader.config_mut().expand_empty_elements = true;
+
+    let mut resources = Vec::new();
+    let mut buf = Vec::new();
+    let mut algorithm = None;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => match e.local_name().as_ref() {
+                b"EncryptedData" => algorithm = None,
+                b"EncryptionMethod" => algorithm = attribute(e, b"Algorithm")?,
+                b"CipherReference" => {
+                    if let Some(uri) = attribute(e, b"URI")?.filter(|uri| !uri.is_empty()) {
+                        resources.push(EncryptedResource {
+                            uri: uri.trim_start_matches('/').to_string(),
+                            algorithm: algorithm.clone(),
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(resources)
+}
+
+/// 还原按IDPF算法混淆的字体数据
+///
+/// 密钥为去除空白字符（空格、制表符、回车、换行）后的唯一标识符的SHA-1摘要，
+/// 文件的前1040个字节与密钥循环异或。该操作是对称的，对原始数据调用即得到混淆后的数据。
+///
+/// # 参数
+/// * `data` - 字体数据（原地修改）
+/// * `unique_identifier` - OPF中`unique-identifier`指向的`dc:identifier`值
+pub(crate) fn deobfuscate_idpf(data: &mut [u8], unique_identifier: &str) {
+    let identifier: String = unique_identifier.chars()
+        .filter(|c| !matches!(c, ' ' | '\t' | '\r' | '\n'))
+        .collect();
+    let key = sha1_smol::Sha1::from(identifier.as_bytes()).digest().bytes();
+    for (byte, key_byte) in data.iter_mut().take(IDPF_OBFUSCATED_LENGTH).zip(key.iter().cycle()) {
+        *byte ^= key_byte;
+    }
+}
+
+/// 根据EPUB中的授权文件判断DRM方案名称
+///
+/// # 参数
+/// * `file_exists` - 判断EPUB中是否存在指定路径的文件
+/// * `algorithm` - 加密章节所用的算法，无法识别方案时用于描述
+///
+/// # 返回值
+/// * `String` - DRM方案名称
+pub(crate) fn drm_scheme_name(file_exists: impl Fn(&str) -> bool, algorithm: Option<&str>) -> String {
+    if file_exists(ADOBE_RIGHTS_PATH) {
+        "Adobe ADEPT".to_string()
+    } else if file_exists(LCP_LICENSE_PATH) {
+        "Readium LCP".to_string()
+    } else if file_exists(FAIRPLAY_SINF_PATH) {
+        "Apple FairPlay".to_string()
+    } else {
+        match algorithm {
+            Some(algorithm) => format!("未知方案，加密算法: {}", algorithm),
+            None => "未知方案".to_string(),
+        }
+    }
+}
+
+/// 读取元素的指定属性
+fn attribute(e: &quick_xml::events::BytesStart, name: &[u8]) -> Result<Option<String>> {
+    for attr_result in e.attributes() {
+        let attr = attr_result.map_err(|err| EpubError::XmlError(quick_xml::Error::InvalidAttr(err)))?;
+        if attr.key.local_name().as_ref() == name {
+            return Ok(Some(String::from_utf8_lossy(&attr.value).to_string()));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encryption_xml() {
+        let xml = r#"<?xml version="1.0"?>
+<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container" xmlns:enc="http://www.w3.org/2001/04/xmlenc#">
+    <enc:EncryptedData>
+        <enc:EncryptionMethod Algorithm="http://www.idpf.org/2008/embedding"/>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/a.otf"/></enc:CipherData>
+    </enc:EncryptedData>
+    <enc:EncryptedData>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/b.ttf"/></enc:CipherData>
+    </enc:EncryptedData>
+</encryption>"#;
+        assert_eq!(parse_encryption_xml(xml).unwrap(), vec![
+            EncryptedResource {
+                uri: "OEBPS/fonts/a.otf".to_string(),
+                algorithm: Some("http://www.idpf.org/2008/embedding".to_string()),
+            },
+            EncryptedResource {
+                uri: "OEBPS/fonts/b.ttf".to_string(),
+                algorithm: None,
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_deobfuscate_idpf() {
+        // 全零数据异或后即为循环的密钥：SHA-1("urn:uuid:1234-abcd")
+        let mut data = vec![0u8; 1100];
+        deobfuscate_idpf(&mut data, " urn:uuid:1234-abcd\n");
+        assert_eq!(&data[..4], [0x9e, 0x62, 0x5b, 0xcf]);
+        assert_eq!(&data[20..24], [0x9e, 0x62, 0x5b, 0xcf]);
+        assert!(data[1040..].iter().all(|&b| b == 0));
+
+        deobfuscate_idpf(&mut data, "urn:uuid:1234-abcd");
+        assert!(data.iter().all(|&b| b == 0));
+    }
+}
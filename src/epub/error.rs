@@ -36,6 +36,12 @@ pub enum EpubError {
     #[error("NCX文件解析错误: {0}")]
     NcxParseError(String),
     
+    #[error("不支持的文本编码: {0}")]
+    UnsupportedEncoding(String),
+    
+    #[error("EPUB受DRM保护（{0}），无法读取内容")]
+    DrmProtected(String),
+    
     #[error("配置文件错误: {0}")]
     ConfigError(String),
     
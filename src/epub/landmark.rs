@@ -0,0 +1,136 @@
+//! 结构性位置模块
+//! 
+//! 统一EPUB2 `<guide>`和EPUB3 nav文档中`epub:type="landmarks"`的`<nav>`，
+//! 提供封面、目录、正文起点等位置的数据结构与解析。
+
+use scraper::{Html, Selector};
+
+use crate::epub::content::resolve_href;
+use crate::epub::opf::GuideReference;
+
+/// 书籍中的结构性位置（封面、目录、正文起点等）
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Landmark {
+    /// 位置类型，统一使用EPUB3结构语义词汇（如`cover`、`toc`、`bodymatter`、`titlepage`）
+    pub landmark_type: String,
+    /// 标题（来源中没有标题时为空字符串）
+    pub title: String,
+    /// 目标在EPUB内的完整路径（保留锚点）
+    pub href: String,
+}
+
+impl Landmark {
+    /// 是否为正文起点（阅读器打开书籍时应跳转的位置）
+    pub fn is_bodymatter(&self) -> bool {
+        self.landmark_type == "bodymatter"
+    }
+
+    /// 从EPUB2导览引用创建结构性位置
+    /// 
+    /// 导览引用类型会映射为EPUB3的对应词汇：`text`映射为`bodymatter`，
+    /// `title-page`映射为`titlepage`，其余类型（如`cover`、`toc`）保持不变。
+    /// 
+    /// # 参数
+    /// * `reference` - 导览引用
+    /// * `opf_dir` - OPF文件所在目录（相对于EPUB根目录）
+    pub(crate) fn from_guide_reference(reference: &GuideReference, opf_dir: &str) -> Self {
+        let reference_type = reference.reference_type.trim();
+        let landmark_type = match reference_type {
+            "text" => "bodymatter",
+            "title-page" => "titlepage",
+            other => other,
+        };
+        Self {
+            landmark_type: landmark_type.to_string(),
+            title: reference.title.clone().unwrap_or_default(),
+            href: resolve_with_fragment(opf_dir, &reference.href),
+        }
+    }
+}
+
+/// 从EPUB3 nav文档解析结构性位置
+/// 
+/// 使用`epub:type="landmarks"`的`<nav>`元素，按文档顺序将其中每个带`epub:type`
+/// 的链接转换为结构性位置，链接路径解析为EPUB内的完整路径（保留锚点）。
+/// 
+/// # 参数
+/// * `nav_html` - nav文档的内容
+/// * `nav_dir` - nav文档所在目录（相对于EPUB根目录）
+/// 
+/// # 返回值
+/// * `Vec<Landmark>` - 结构性位置，nav文档中没有landmarks时为空
+pub(crate) fn parse_nav_landmarks(nav_html: &str, nav_dir: &str) -> Vec<Landmark> {
+    let document = Html::parse_document(nav_html);
+    let nav_selector = Selector::parse("nav").unwrap();
+    let Some(landmarks_nav) = document.select(&nav_selector).find(|nav| {
+        nav.value().attr("epub:type")
+            .is_some_and(|t| t.split_whitespace().any(|v| v == "landmarks"))
+    }) else {
+        return Vec::new();
+    };
+
+    let link_selector = Selector::parse("li > a[href]").unwrap();
+    landmarks_nav.select(&link_selector)
+        .filter_map(|link| {
+            let href = link.value().attr("href").unwrap_or_default().trim();
+            let landmark_type = link.value().attr("epub:type")?.split_whitespace().next()?;
+            if href.is_empty() {
+                return None;
+            }
+            Some(Landmark {
+                landmark_type: landmark_type.to_string(),
+                title: link.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "),
+                href: resolve_with_fragment(nav_dir, href),
+            })
+        })
+        .collect()
+}
+
+/// 解析相对链接并保留其锚点
+fn resolve_with_fragment(base_dir: &str, href: &str) -> String {
+    match href.split_once('#') {
+        Some((_, fragment)) => format!("{}#{}", resolve_href(base_dir, href), fragment),
+        None => resolve_href(base_dir, href),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nav_landmarks() {
+        let nav = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+            <nav epub:type="toc"><ol><li><a href="ch1.xhtml">第一章</a></li></ol></nav>
+            <nav epub:type="landmarks"><ol>
+                <li><a epub:type="cover" href="cover.xhtml">封面</a></li>
+                <li><a epub:type="bodymatter" href="../Text/ch1.xhtml#start">  正文
+                    开始 </a></li>
+                <li><a href="notes.xhtml">无类型</a></li>
+            </ol></nav>
+        </body></html>"#;
+
+        let landmarks = parse_nav_landmarks(nav, "OEBPS/Nav");
+        assert_eq!(landmarks.len(), 2);
+        assert_eq!(landmarks[0].landmark_type, "cover");
+        assert_eq!(landmarks[0].href, "OEBPS/Nav/cover.xhtml");
+        assert_eq!(landmarks[1].title, "正文 开始");
+        assert_eq!(landmarks[1].href, "OEBPS/Text/ch1.xhtml#start");
+        assert!(landmarks[1].is_bodymatter());
+
+        assert!(parse_nav_landmarks("<nav epub:type=\"toc\"></nav>", "").is_empty());
+    }
+
+    #[test]
+    fn test_landmark_from_guide_reference() {
+        let reference = GuideReference::new("text".to_string(), "Text/ch1.xhtml#top".to_string(), None);
+        let landmark = Landmark::from_guide_reference(&reference, "OEBPS");
+        assert_eq!(landmark.landmark_type, "bodymatter");
+        assert_eq!(landmark.href, "OEBPS/Text/ch1.xhtml#top");
+        assert_eq!(landmark.title, "");
+
+        let reference = GuideReference::new("title-page".to_string(), "title.xhtml".to_string(), Some("书名页".to_string()));
+        assert_eq!(Landmark::from_guide_reference(&reference, "").landmark_type, "titlepage");
+    }
+}
@@ -0,0 +1,348 @@
+//! Markdown转换模块
+//!
+//! 提供将章节HTML转换为Markdown文本的功能。
+//!
+//! 支持标题、段落、强调、行内代码、预格式文本、引用、有序/无序列表、表格、
+//! 分隔线、图片和外部链接。EPUB内部链接只保留链接文本（导出后目标位置通常不存在），
+//! 图片不导出文件，链接可以保留原样或解析为EPUB内的完整路径。
+
+use scraper::{ElementRef, Html, Selector};
+
+use crate::epub::content::{has_scheme, parse_table, resolve_href};
+use crate::epub::ncx::TocTreeNode;
+
+/// 行内元素，其内容与相邻文本处于同一段落
+pub(crate) const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "big", "br", "cite", "code", "del", "dfn", "em", "font", "i", "img",
+    "ins", "kbd", "label", "mark", "q", "rb", "rp", "rt", "ruby", "s", "samp", "small", "span", "strike",
+    "strong", "sub", "sup", "time", "tt", "u", "var",
+];
+
+/// 不输出任何内容的元素
+pub(crate) const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "script", "style", "meta", "link", "title", "base", "noscript", "rt", "rp", "svg", "video",
+    "audio", "canvas", "embed", "object", "iframe", "source", "track", "param", "area", "map",
+];
+
+/// 将章节HTML转换为Markdown
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `heading_offset` - 标题层级的偏移量，`<h1>`输出为`heading_offset + 1`个`#`（最多6个），
+///   用于将章节嵌入更高层级的文档
+///
+/// # 返回值
+/// * `String` - Markdown文本，块之间以空行分隔
+pub fn html_to_markdown(html: &str, heading_offset: u8) -> String {
+    convert(html, MarkdownConverter { heading_offset, image_base: None })
+}
+
+/// 将章节HTML转换为Markdown，图片链接解析为EPUB内的完整路径
+///
+/// # 参数
+/// * `html` - 章节HTML内容
+/// * `heading_offset` - 标题层级的偏移量
+/// * `base_dir` - 章节文件所在的目录（相对于EPUB根目录），外部链接和`data:`URI保持不变
+///
+/// # 返回值
+/// * `String` - Markdown文本，块之间以空行分隔
+pub(crate) fn html_to_markdown_with_base(html: &str, heading_offset: u8, base_dir: &str) -> String {
+    convert(html, MarkdownConverter { heading_offset, image_base: Some(base_dir) })
+}
+
+/// 使用指定的转换器转换整个文档
+fn convert(html: &str, converter: MarkdownConverter) -> String {
+    let document = Html::parse_document(html);
+    let body_selector = Selector::parse("body").unwrap();
+    let root = document.select(&body_selector).next().unwrap_or_else(|| document.root_element());
+
+    let mut blocks = Vec::new();
+    converter.render_container(root, &mut blocks);
+    blocks.join("\n\n")
+}
+
+/// HTML到Markdown的转换器
+struct MarkdownConverter<'a> {
+    /// 标题层级偏移量
+    heading_offset: u8,
+    /// 解析图片链接所用的目录，为`None`时保留原始链接
+    image_base: Option<&'a str>,
+}
+
+impl MarkdownConverter<'_> {
+    /// 渲染包含块级内容的元素，连续的行内内容合并为一个段落
+    fn render_container(&self, element: ElementRef, blocks: &mut Vec<String>) {
+        let mut paragraph = String::new();
+        for node in element.children() {
+            if let Some(child) = ElementRef::wrap(node) {
+                if is_inline(child) {
+                    self.render_inline(child, &mut paragraph);
+                } else {
+                    flush_paragraph(&mut paragraph, blocks);
+                    self.render_block(child, blocks);
+                }
+            } else if let Some(text) = node.value().as_text() {
+                push_text(&mut paragraph, text);
+            }
+        }
+        flush_paragraph(&mut paragraph, blocks);
+    }
+
+    /// 渲染块级元素
+    fn render_block(&self, element: ElementRef, blocks: &mut Vec<String>) {
+        let name = element.value().name();
+        if SKIPPED_ELEMENTS.contains(&name) || TocTreeNode::is_hidden_element(element) {
+            return;
+        }
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = self.inline_text(element);
+                if !text.is_empty() {
+                    let level = (name[1..].parse::<u8>().unwrap_or(1) + self.heading_offset).min(6);
+                    blocks.push(format!("{} {}", "#".repeat(level as usize), text));
+                }
+            }
+            "hr" => blocks.push("---".to_string()),
+            "pre" => {
+                let code = element.text().collect::<String>();
+                blocks.push(format!("```\n{}\n```", code.trim_end()));
+            }
+            "blockquote" => {
+                let mut inner = Vec::new();
+                self.render_container(element, &mut inner);
+                if !inner.is_empty() {
+                    blocks.push(prefix_lines(&inner.join("\n\n"), "> ", ">"));
+                }
+            }
+            "ul" | "ol" => {
+                let list = self.render_list(element);
+                if !list.is_empty() {
+                    blocks.push(list);
+                }
+            }
+            "table" => {
+                let table = render_table(element);
+                if !table.is_empty() {
+                    blocks.push(table);
+                }
+            }
+            _ => self.render_container(element, blocks),
+        }
+    }
+
+    /// 渲染列表，嵌套列表按项目符号宽度缩进
+    fn render_list(&self, list: ElementRef) -> String {
+        let ordered = list.value().name() == "ol";
+        let mut number = list.value().attr("start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+            .unwrap_or(1);
+
+        let mut items = Vec::new();
+        for item in list.children().filter_map(ElementRef::wrap).filter(|item| item.value().name() == "li") {
+            if TocTreeNode::is_hidden_element(item) {
+                continue;
+            }
+            let marker = if ordered {
+                if let Some(value) = item.value().attr("value").and_then(|value| value.trim().parse::<i64>().ok()) {
+                    number = value;
+                }
+                number += 1;
+                format!("{}. ", number - 1)
+            } else {
+                "- ".to_string()
+            };
+
+            let mut content = Vec::new();
+            self.render_container(item, &mut content);
+            let indent = " ".repeat(marker.len());
+            let body = prefix_lines(&content.join("\n"), &indent, "");
+            items.push(format!("{}{}", marker, body.trim_start()));
+        }
+        items.join("\n")
+    }
+
+    /// 渲染行内元素
+    fn render_inline(&self, element: ElementRef, out: &mut String) {
+        let name = element.value().name();
+        if SKIPPED_ELEMENTS.contains(&name) || TocTreeNode::is_hidden_element(element) {
+            return;
+        }
+
+        match name {
+            "br" => {
+                trim_trailing_spaces(out);
+                out.push_str("  \n");
+            }
+            "img" => {
+                let alt = element.value().attr("alt").unwrap_or("").trim();
+                let src = element.value().attr("src").unwrap_or("").trim();
+                if !src.is_empty() {
+                    let src = match self.image_base {
+                        Some(base_dir) if !has_scheme(src) => resolve_href(base_dir, src),
+                        _ => src.to_string(),
+                    };
+                    out.push_str(&format!("![{}]({})", escape_markdown(alt), src));
+                }
+            }
+            "strong" | "b" => wrap_inline(out, &self.inline_text(element), "**"),
+            "em" | "i" => wrap_inline(out, &self.inline_text(element), "*"),
+            "code" | "kbd" | "samp" | "tt" => {
+                let code = element.text().collect::<String>();
+                let code = code.trim();
+                if !code.is_empty() {
+                    out.push_str(&format!("`{}`", code.replace('`', "")));
+                }
+            }
+            "a" => {
+                let text = self.inline_text(element);
+                match element.value().attr("href").map(str::trim) {
+                    Some(href) if has_scheme(href) && !text.is_empty() => {
+                        out.push_str(&format!("[{}]({})", text, href));
+                    }
+                    _ => out.push_str(&text),
+                }
+            }
+            _ => self.render_inline_children(element, out),
+        }
+    }
+
+    /// 依次渲染元素的子节点（块级子元素按行内内容处理）
+    fn render_inline_children(&self, element: ElementRef, out: &mut String) {
+        for node in element.children() {
+            if let Some(child) = ElementRef::wrap(node) {
+                self.render_inline(child, out);
+            } else if let Some(text) = node.value().as_text() {
+                push_text(out, text);
+            }
+        }
+    }
+
+    /// 获取元素渲染后的行内文本（去掉首尾空白）
+    fn inline_text(&self, element: ElementRef) -> String {
+        let mut text = String::new();
+        self.render_inline_children(element, &mut text);
+        text.trim().to_string()
+    }
+}
+
+/// 判断元素是否为行内元素
+fn is_inline(element: ElementRef) -> bool {
+    INLINE_ELEMENTS.contains(&element.value().name())
+}
+
+/// 将段落缓冲区作为一个块输出
+fn flush_paragraph(paragraph: &mut String, blocks: &mut Vec<String>) {
+    let text = paragraph.trim();
+    if !text.is_empty() {
+        blocks.push(text.to_string());
+    }
+    paragraph.clear();
+}
+
+/// 追加文本，合并连续空白并转义Markdown特殊字符
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+        } else {
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+}
+
+/// 转义Markdown特殊字符
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+    push_text(&mut escaped, text);
+    escaped
+}
+
+/// 用强调标记包裹文本，空文本不输出
+fn wrap_inline(out: &mut String, text: &str, mark: &str) {
+    if !text.is_empty() {
+        out.push_str(mark);
+        out.push_str(text);
+        out.push_str(mark);
+    }
+}
+
+/// 去掉末尾的空格
+fn trim_trailing_spaces(out: &mut String) {
+    let trimmed_len = out.trim_end_matches(' ').len();
+    out.truncate(trimmed_len);
+}
+
+/// 为每一行添加前缀，空行使用单独的前缀
+fn prefix_lines(text: &str, prefix: &str, empty_prefix: &str) -> String {
+    text.lines()
+        .map(|line| if line.is_empty() { empty_prefix.to_string() } else { format!("{}{}", prefix, line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 渲染表格，没有表头时使用第一行作为表头
+fn render_table(element: ElementRef) -> String {
+    let table = parse_table(element);
+    let mut rows = table.rows.into_iter();
+    let headers = if table.headers.is_empty() {
+        match rows.next() {
+            Some(first) => first,
+            None => return String::new(),
+        }
+    } else {
+        table.headers
+    };
+
+    let format_row = |cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|cell| escape_markdown(cell).replace('|', "\\|")).collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![format_row(&headers), format!("|{}|", vec![" --- "; headers.len()].join("|"))];
+    lines.extend(rows.map(|row| format_row(&row)));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_markdown() {
+        let html = r#"<html><head><title>忽略</title></head><body>
+<h1>第一章</h1>
+<p>这是<strong>重要</strong>的<em>内容</em>，见<a href="https://example.com">网站</a>和<a href="ch2.xhtml">第二章</a>。</p>
+<p>第一行<br/>第二行</p>
+<blockquote><p>引文一</p><p>引文二</p></blockquote>
+<ul><li>甲<ol start="3"><li>子项</li><li>子项二</li></ol></li><li>乙</li></ul>
+<table><tr><th>名称</th><th>数量</th></tr><tr><td>a|b</td><td>1</td></tr></table>
+<pre>let x = 1;</pre>
+<hr/>
+<p><img src="images/a.png" alt="插图"/> 1*2</p>
+</body></html>"#;
+        let expected = "## 第一章\n\n\
+这是**重要**的*内容*，见[网站](https://example.com)和第二章。\n\n\
+第一行  \n第二行\n\n\
+> 引文一\n>\n> 引文二\n\n\
+- 甲\n  3. 子项\n  4. 子项二\n- 乙\n\n\
+| 名称 | 数量 |\n| --- | --- |\n| a\\|b | 1 |\n\n\
+```\nlet x = 1;\n```\n\n\
+---\n\n\
+![插图](images/a.png) 1\\*2";
+        assert_eq!(html_to_markdown(html, 1), expected);
+    }
+
+    #[test]
+    fn test_html_to_markdown_with_base() {
+        let html = r#"<p><img src="../images/a.png" alt="甲"/><img src="https://example.com/b.png"/></p>"#;
+        assert_eq!(
+            html_to_markdown_with_base(html, 0, "OEBPS/text"),
+            "![甲](OEBPS/images/a.png)![](https://example.com/b.png)"
+        );
+    }
+}
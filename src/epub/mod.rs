@@ -1,8 +1,24 @@
 pub mod error;
+pub mod accessibility;
+#[cfg(feature = "async")]
+pub mod async_reader;
+pub mod block;
+pub mod builder;
+pub mod cfi;
+pub mod classify;
 pub mod container;
+pub mod content;
+pub mod encoding;
+pub mod encryption;
+pub mod landmark;
+pub mod markdown;
 pub mod reader;
+pub mod search;
 pub mod opf;
 pub mod ncx;
+pub mod text;
+pub mod validation;
+pub mod warning;
 
 // 重新导出错误处理
 pub use error::{EpubError, Result};
@@ -10,13 +26,42 @@ pub use error::{EpubError, Result};
 // 重新导出容器相关
 pub use container::{Container, RootFile};
 
+// 重新导出章节内容分析相关
+pub use content::{ChapterLink, LinkKind, LinkRef, Quote, Table};
+
+// 重新导出结构化内容相关
+pub use block::Block;
+
+// 重新导出全文搜索相关
+pub use search::{SearchHit, SearchOptions};
+
+// 重新导出阅读位置相关
+pub use cfi::ResolvedPosition;
+
+// 重新导出结构性位置相关
+pub use landmark::Landmark;
+
+// 重新导出内容分类相关
+pub use classify::{ClassificationSignals, ContentClassification, ContentKind};
+
+// 重新导出构建器
+pub use builder::EpubBuilder;
+
+// 重新导出异步读取器
+#[cfg(feature = "async")]
+pub use async_reader::AsyncEpub;
+
 // 重新导出EPUB读取器和新的数据结构
 pub use reader::{
     Epub, 
     BookInfo, 
     ChapterInfo, 
     Chapter, 
+    ChapterSpan,
+    ContentStatistics,
+    ZipEntryInfo,
     ImageInfo, 
+    FontInfo,
     CoverImage
 };
 
@@ -24,10 +69,16 @@ pub use reader::{
 pub use opf::{
     Opf,
     Metadata, 
+    MetadataCompleteness,
     Creator, 
     Identifier, 
+    SeriesInfo,
+    GuideReference,
+    ManifestCategories,
     ManifestItem, 
+    ReadingDirection,
     SpineItem,
+    Spread,
     MetadataTagConfig, 
     MetadataTagConfigs
 };
@@ -43,7 +94,32 @@ pub use ncx::{
     TocTreeNode, 
     TocTreeStyle, 
     TocStatistics,
-    create_toc_tree_from_ncx
+    FlatTocEntry,
+    ExportFormat,
+    ExportOptions,
+    ExportStatus,
+    ExportSummary,
+    ExportedFile,
+    NavDiff,
+    NavDiffEntry,
+    NavSummary,
+    TitleMismatch,
+    OrderMismatch,
+    create_toc_tree_from_ncx,
+    create_toc_tree_from_nav,
+    create_page_list_from_nav
 };
 
+// 重新导出文本处理相关
+pub use text::{ImagePolicy, RubyMode, TextExtractor, TextNormalization, TextOptions, Tokenization};
+
+// 重新导出无障碍检查相关
+pub use accessibility::{A11yFinding, A11yIssueKind, A11yReport, ImageAltInfo, ImageUsage};
+
+// 重新导出警告相关
+pub use warning::EpubWarning;
+
+// 重新导出校验相关
+pub use validation::{Severity, ValidationIssue, ValidationReport};
+
  
\ No newline at end of file
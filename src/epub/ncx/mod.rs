@@ -4,8 +4,10 @@
 //! NCX文件主要用于定义EPUB的目录结构和导航信息。
 
 pub mod navigation;
+pub mod nav_diff;
 pub mod parser;
 pub mod toc_tree;
+pub mod writer;
 
 // 重新导出公共类型以保持API兼容性
 pub use navigation::{
@@ -19,4 +21,5 @@ pub use navigation::{
     NcxMetadata,
 };
 pub use parser::Ncx;
+pub use nav_diff::{NavDiff, NavDiffEntry, NavSummary, TitleMismatch, OrderMismatch, compare_toc_trees};
 pub use toc_tree::*; 
\ No newline at end of file
@@ -0,0 +1,226 @@
+//! 导航差异模块
+//!
+//! 比较NCX目录与EPUB3 nav文档目录之间的差异。
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::epub::content::resolve_href;
+use crate::epub::ncx::{TocTree, TocTreeNode};
+
+/// 只出现在一个目录来源中的条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavDiffEntry {
+    /// 条目标题
+    pub title: String,
+    /// 目标路径（相对于EPUB根目录，保留锚点）
+    pub src: String,
+}
+
+/// 同一目标在两个目录中标题不同
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleMismatch {
+    /// 目标路径（相对于EPUB根目录，保留锚点）
+    pub src: String,
+    /// NCX中的标题
+    pub ncx_title: String,
+    /// nav文档中的标题
+    pub nav_title: String,
+}
+
+/// 两个目录共有条目的顺序差异
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderMismatch {
+    /// 在共有条目序列中的位置（从0开始）
+    pub position: usize,
+    /// NCX中该位置的目标路径
+    pub ncx_src: String,
+    /// nav文档中该位置的目标路径
+    pub nav_src: String,
+}
+
+/// NCX与nav文档之间的目录差异
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NavDiff {
+    /// 只出现在NCX中的条目
+    pub only_in_ncx: Vec<NavDiffEntry>,
+    /// 只出现在nav文档中的条目
+    pub only_in_nav: Vec<NavDiffEntry>,
+    /// 标题不一致的条目
+    pub title_mismatches: Vec<TitleMismatch>,
+    /// 共有条目的顺序差异
+    pub order_mismatches: Vec<OrderMismatch>,
+}
+
+impl NavDiff {
+    /// 检查两个目录是否完全一致
+    pub fn is_empty(&self) -> bool {
+        self.only_in_ncx.is_empty()
+            && self.only_in_nav.is_empty()
+            && self.title_mismatches.is_empty()
+            && self.order_mismatches.is_empty()
+    }
+}
+
+impl Display for NavDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.is_empty() {
+            return writeln!(f, "NCX与nav目录一致");
+        }
+        for entry in &self.only_in_ncx {
+            writeln!(f, "仅在NCX中: {} ({})", entry.title, entry.src)?;
+        }
+        for entry in &self.only_in_nav {
+            writeln!(f, "仅在nav中: {} ({})", entry.title, entry.src)?;
+        }
+        for mismatch in &self.title_mismatches {
+            writeln!(
+                f,
+                "标题不一致: {} - NCX: '{}', nav: '{}'",
+                mismatch.src, mismatch.ncx_title, mismatch.nav_title
+            )?;
+        }
+        for mismatch in &self.order_mismatches {
+            writeln!(
+                f,
+                "顺序不一致: 第{}项 - NCX: {}, nav: {}",
+                mismatch.position + 1, mismatch.ncx_src, mismatch.nav_src
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 各导航来源的条目数量概览
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NavSummary {
+    /// 脊柱项总数
+    pub spine_total: usize,
+    /// 线性脊柱项（`linear`不为`no`）数量
+    pub spine_linear: usize,
+    /// NCX目录条目数（含所有层级），没有NCX时为None
+    pub ncx_entries: Option<usize>,
+    /// nav文档目录条目数（含所有层级），没有nav文档时为None
+    pub nav_entries: Option<usize>,
+}
+
+impl NavSummary {
+    /// 检查各来源的数量是否一致
+    ///
+    /// 存在的目录（NCX、nav）条目数都等于线性脊柱项数量时视为一致。
+    /// 目录按锚点细分章节时条目数会多于脊柱项，这种情况也会被报告为不一致。
+    pub fn agrees(&self) -> bool {
+        [self.ncx_entries, self.nav_entries]
+            .into_iter()
+            .flatten()
+            .all(|count| count == self.spine_linear)
+    }
+}
+
+impl Display for NavSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let format_count = |count: Option<usize>| count.map_or("无".to_string(), |count| count.to_string());
+        write!(
+            f,
+            "脊柱: {} (线性 {}), NCX: {}, nav: {} - {}",
+            self.spine_total,
+            self.spine_linear,
+            format_count(self.ncx_entries),
+            format_count(self.nav_entries),
+            if self.agrees() { "一致" } else { "不一致" }
+        )
+    }
+}
+
+/// 比较两棵目录树
+///
+/// 条目按解析后的目标路径（含锚点）匹配，同一目标出现多次时只使用第一次出现。
+///
+/// # 参数
+/// * `ncx_tree` - 从NCX创建的目录树
+/// * `ncx_dir` - NCX文件所在目录
+/// * `nav_tree` - 从nav文档创建的目录树
+/// * `nav_dir` - nav文档所在目录
+///
+/// # 返回值
+/// * `NavDiff` - 结构化的差异
+pub fn compare_toc_trees(ncx_tree: &TocTree, ncx_dir: &str, nav_tree: &TocTree, nav_dir: &str) -> NavDiff {
+    let ncx_entries = flatten_entries(&ncx_tree.roots, ncx_dir);
+    let nav_entries = flatten_entries(&nav_tree.roots, nav_dir);
+
+    let ncx_titles: HashMap<&str, &str> = ncx_entries.iter()
+        .map(|e| (e.src.as_str(), e.title.as_str()))
+        .collect();
+    let nav_titles: HashMap<&str, &str> = nav_entries.iter()
+        .map(|e| (e.src.as_str(), e.title.as_str()))
+        .collect();
+
+    let mut diff = NavDiff::default();
+
+    for entry in &ncx_entries {
+        match nav_titles.get(entry.src.as_str()) {
+            None => diff.only_in_ncx.push(entry.clone()),
+            Some(&nav_title) if nav_title != entry.title => {
+                diff.title_mismatches.push(TitleMismatch {
+                    src: entry.src.clone(),
+                    ncx_title: entry.title.clone(),
+                    nav_title: nav_title.to_string(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for entry in &nav_entries {
+        if !ncx_titles.contains_key(entry.src.as_str()) {
+            diff.only_in_nav.push(entry.clone());
+        }
+    }
+
+    // 比较共有条目的先后顺序
+    let ncx_common = ncx_entries.iter().filter(|e| nav_titles.contains_key(e.src.as_str()));
+    let nav_common = nav_entries.iter().filter(|e| ncx_titles.contains_key(e.src.as_str()));
+    for (position, (ncx_entry, nav_entry)) in ncx_common.zip(nav_common).enumerate() {
+        if ncx_entry.src != nav_entry.src {
+            diff.order_mismatches.push(OrderMismatch {
+                position,
+                ncx_src: ncx_entry.src.clone(),
+                nav_src: nav_entry.src.clone(),
+            });
+        }
+    }
+
+    diff
+}
+
+/// 按文档顺序平铺目录树，解析路径并去除重复目标
+fn flatten_entries(nodes: &[TocTreeNode], base_dir: &str) -> Vec<NavDiffEntry> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    collect_entries(nodes, base_dir, &mut entries, &mut seen);
+    entries
+}
+
+/// 递归收集目录条目
+fn collect_entries(
+    nodes: &[TocTreeNode],
+    base_dir: &str,
+    entries: &mut Vec<NavDiffEntry>,
+    seen: &mut HashSet<String>,
+) {
+    for node in nodes {
+        if !node.src.is_empty() {
+            let mut src = resolve_href(base_dir, &node.src);
+            if let Some((_, fragment)) = node.src.split_once('#') {
+                src.push('#');
+                src.push_str(fragment);
+            }
+            if seen.insert(src.clone()) {
+                entries.push(NavDiffEntry {
+                    title: node.title.trim().to_string(),
+                    src,
+                });
+            }
+        }
+        collect_entries(&node.children, base_dir, entries, seen);
+    }
+}
@@ -120,11 +120,10 @@ impl NavPoint {
     }
 
     /// 按playOrder排序子导航点
+    /// 
+    /// playOrder相同（例如缺失时均为0）的导航点保持原始文档顺序。
     pub fn sort_children_by_play_order(&mut self) {
-        self.children.sort_by(|a, b| a.play_order.cmp(&b.play_order));
-        for child in &mut self.children {
-            child.sort_children_by_play_order();
-        }
+        sort_nav_points(&mut self.children);
     }
 
     /// 获取所有导航点（包括子导航点）的平铺列表
@@ -146,6 +145,19 @@ impl NavPoint {
     }
 }
 
+/// 按playOrder递归排序导航点
+/// 
+/// 以原始解析顺序作为次要排序键，保证playOrder相同的导航点排序结果确定。
+fn sort_nav_points(nav_points: &mut Vec<NavPoint>) {
+    let mut indexed: Vec<(usize, NavPoint)> = nav_points.drain(..).enumerate().collect();
+    indexed.sort_by_key(|(index, nav_point)| (nav_point.play_order, *index));
+    
+    nav_points.extend(indexed.into_iter().map(|(_, nav_point)| nav_point));
+    for nav_point in nav_points.iter_mut() {
+        sort_nav_points(&mut nav_point.children);
+    }
+}
+
 /// 导航地图
 #[derive(Debug, Clone)]
 pub struct NavMap {
@@ -167,11 +179,10 @@ impl NavMap {
     }
 
     /// 按playOrder排序所有导航点（包括子导航点）
+    /// 
+    /// playOrder相同（例如缺失时均为0）的导航点保持原始文档顺序。
     pub fn sort_by_play_order(&mut self) {
-        self.nav_points.sort_by(|a, b| a.play_order.cmp(&b.play_order));
-        for nav_point in &mut self.nav_points {
-            nav_point.sort_children_by_play_order();
-        }
+        sort_nav_points(&mut self.nav_points);
     }
 
     /// 获取所有导航点的平铺列表
@@ -2,6 +2,7 @@
 //! 
 //! 提供NCX（Navigation Control file for XML）文件的XML解析功能。
 
+use crate::epub::encoding::strip_bom;
 use crate::epub::error::{EpubError, Result};
 use crate::epub::ncx::{
     NcxMetadata, DocTitle, NavMap, NavPoint, NavLabel, NavContent,
@@ -37,7 +38,7 @@ impl Ncx {
     /// # 返回值
     /// * `Result<Ncx, EpubError>` - 解析后的NCX信息
     pub fn parse_xml(xml_content: &str) -> Result<Ncx> {
-        let mut reader = Reader::from_str(xml_content);
+        let mut reader = Reader::from_str(strip_bom(xml_content));
         reader.config_mut().trim_text(true);
         reader.config_mut().expand_empty_elements = true;
 
@@ -375,6 +376,19 @@ impl Ncx {
         self.metadata.depth.unwrap_or_else(|| self.nav_map.get_depth())
     }
 
+    /// 获取声明的导航深度（`dtb:depth`）和导航点实际的嵌套深度
+    pub fn depth_info(&self) -> (Option<u32>, u32) {
+        (self.metadata.depth, self.nav_map.get_depth())
+    }
+
+    /// 检查声明的导航深度是否与实际嵌套深度一致
+    ///
+    /// 没有声明`dtb:depth`时视为一致。
+    pub fn depth_is_consistent(&self) -> bool {
+        let (declared, actual) = self.depth_info();
+        declared.is_none_or(|declared| declared == actual)
+    }
+
     /// 获取文档标题文本
     pub fn get_title(&self) -> Option<&String> {
         self.doc_title.as_ref().map(|title| &title.text)
@@ -411,4 +425,57 @@ impl Ncx {
     // 注意：创建目录树现在需要 Epub 实例，请使用 create_toc_tree_from_ncx 函数
 
 
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_play_order_keeps_document_order() {
+        let ncx_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="c" playOrder="0"><navLabel><text>C</text></navLabel><content src="c.xhtml"/></navPoint>
+        <navPoint id="a" playOrder="0"><navLabel><text>A</text></navLabel><content src="a.xhtml"/>
+            <navPoint id="a2" playOrder="0"><navLabel><text>A2</text></navLabel><content src="a2.xhtml"/></navPoint>
+            <navPoint id="a1" playOrder="0"><navLabel><text>A1</text></navLabel><content src="a1.xhtml"/></navPoint>
+        </navPoint>
+        <navPoint id="z" playOrder="5"><navLabel><text>Z</text></navLabel><content src="z.xhtml"/></navPoint>
+        <navPoint id="b"><navLabel><text>B</text></navLabel><content src="b.xhtml"/></navPoint>
+    </navMap>
+</ncx>"#;
+
+        for _ in 0..5 {
+            let ncx = Ncx::parse_xml(ncx_xml).unwrap();
+            let ids: Vec<&str> = ncx.nav_map.nav_points.iter().map(|p| p.id.as_str()).collect();
+            assert_eq!(ids, vec!["c", "a", "b", "z"]);
+            let child_ids: Vec<&str> = ncx.nav_map.nav_points[1].children.iter().map(|p| p.id.as_str()).collect();
+            assert_eq!(child_ids, vec!["a2", "a1"]);
+        }
+    }
+
+    #[test]
+    fn test_depth_consistency() {
+        let ncx_xml = |depth: &str| format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <head>{depth}</head>
+    <navMap>
+        <navPoint id="a" playOrder="1"><navLabel><text>A</text></navLabel><content src="a.xhtml"/>
+            <navPoint id="a1" playOrder="2"><navLabel><text>A1</text></navLabel><content src="a1.xhtml"/></navPoint>
+        </navPoint>
+    </navMap>
+</ncx>"#);
+
+        let ncx = Ncx::parse_xml(&ncx_xml(r#"<meta name="dtb:depth" content="3"/>"#)).unwrap();
+        assert_eq!(ncx.depth_info(), (Some(3), 2));
+        assert!(!ncx.depth_is_consistent());
+
+        let ncx = Ncx::parse_xml(&ncx_xml(r#"<meta name="dtb:depth" content="2"/>"#)).unwrap();
+        assert!(ncx.depth_is_consistent());
+
+        let ncx = Ncx::parse_xml(&ncx_xml("")).unwrap();
+        assert_eq!(ncx.depth_info(), (None, 2));
+        assert!(ncx.depth_is_consistent());
+    }
+}
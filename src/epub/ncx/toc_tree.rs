@@ -2,13 +2,21 @@
 //! 
 //! 提供NCX目录结构的树形表示和显示功能。
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
-use crate::epub::ncx::{Ncx, NavPoint};
+use crate::epub::content::{parent_dir, resolve_href, slice_between_anchors};
+use crate::epub::markdown::html_to_markdown_with_base;
+use crate::epub::ncx::{NavContent, NavLabel, Ncx, NavPoint, PageList, PageTarget};
+use crate::epub::text::{RubyMode, TextExtractor, TextOptions, count_words, render_script, strip_boilerplate};
+use crate::epub::warning::EpubWarning;
 use crate::epub::{Epub, EpubError, Result};
 use scraper::{Html, Selector};
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 /// 目录树显示样式
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -19,6 +27,260 @@ pub enum TocTreeStyle {
     Indented,
 }
 
+/// 章节导出格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// 格式化文本（保持HTML结构）
+    Formatted,
+    /// 纯文本（移除所有HTML标签）
+    Plain,
+    /// Markdown（保留标题、强调、列表、引用、表格、外部链接和图片）
+    Markdown,
+    /// 结构化JSON（包含标题、ID、源文件、播放顺序、正文和词数）
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl ExportFormat {
+    /// 获取导出文件的扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Formatted | ExportFormat::Plain => "txt",
+            ExportFormat::Markdown => "md",
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// 获取格式的显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Formatted => "格式化文本",
+            ExportFormat::Plain => "纯文本",
+            ExportFormat::Markdown => "Markdown",
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => "JSON",
+        }
+    }
+}
+
+/// 批量导出选项
+/// 
+/// # 示例
+/// 
+/// ```rust,no_run
+/// use bookforge::{Epub, ExportFormat, ExportOptions};
+/// use std::path::Path;
+/// 
+/// let epub = Epub::from_path("book.epub")?;
+/// if let Some(toc_tree) = epub.toc_tree()? {
+///     let options = ExportOptions::new(ExportFormat::Plain)
+///         .with_global_numbering(true)
+///         .with_skip_existing(true);
+///     let summary = toc_tree.generate_all_txt_files_with_options(Some(Path::new("chapters")), &options)?;
+///     println!("跳过 {} 个已存在的文件", summary.skipped().len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// 导出格式
+    pub format: ExportFormat,
+    /// 是否根据目录树结构创建子目录
+    pub create_subdirs: bool,
+    /// 是否按先序遍历的全局序号（`0001_`、`0002_`…）为文件和子目录编号，
+    /// 为false时使用节点的播放顺序
+    pub global_numbering: bool,
+    /// 是否跳过已存在且非空的文件（用于续传中断的导出）
+    pub skip_existing: bool,
+    /// 索引文件名，为None时不生成索引文件
+    pub index_filename: Option<String>,
+    /// JSON导出报告文件名，为None时不生成报告
+    #[cfg(feature = "serde")]
+    pub report_filename: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new(ExportFormat::Formatted)
+    }
+}
+
+impl ExportOptions {
+    /// 创建使用指定格式、其余选项均关闭的导出选项
+    pub fn new(format: ExportFormat) -> Self {
+        Self {
+            format,
+            create_subdirs: false,
+            global_numbering: false,
+            skip_existing: false,
+            index_filename: None,
+            #[cfg(feature = "serde")]
+            report_filename: None,
+        }
+    }
+
+    /// 设置是否根据目录树结构创建子目录
+    pub fn with_create_subdirs(mut self, create_subdirs: bool) -> Self {
+        self.create_subdirs = create_subdirs;
+        self
+    }
+
+    /// 设置是否按先序遍历的全局序号为文件和子目录编号
+    pub fn with_global_numbering(mut self, global_numbering: bool) -> Self {
+        self.global_numbering = global_numbering;
+        self
+    }
+
+    /// 设置是否跳过已存在且非空的文件
+    pub fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// 设置索引文件名，导出完成后在输出目录中生成索引文件（总是重新生成）
+    pub fn with_index(mut self, index_filename: impl Into<String>) -> Self {
+        self.index_filename = Some(index_filename.into());
+        self
+    }
+
+    /// 设置导出报告文件名，导出完成后在输出目录中生成JSON格式的报告（总是重新生成）
+    /// 
+    /// 生成报告时，单个章节导出失败不会中断整个导出，而是记录为`ExportStatus::Failed`。
+    #[cfg(feature = "serde")]
+    pub fn with_report(mut self, report_filename: impl Into<String>) -> Self {
+        self.report_filename = Some(report_filename.into());
+        self
+    }
+}
+
+/// 批量导出中单个节点的导出状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "snake_case"))]
+pub enum ExportStatus {
+    /// 已写入文件
+    Written,
+    /// 目标文件已存在且非空，未重新生成
+    Skipped,
+    /// 导出失败（只在生成导出报告时记录，否则直接返回错误）
+    Failed,
+}
+
+/// 批量导出中单个节点的导出结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedFile {
+    /// 节点ID
+    pub id: String,
+    /// 节点标题
+    pub title: String,
+    /// 导出文件路径（导出失败时为预期的路径）
+    pub path: PathBuf,
+    /// 导出状态
+    pub status: ExportStatus,
+    /// 写入内容的词数（跳过或失败时为None）
+    pub word_count: Option<usize>,
+    /// 导出失败的原因
+    pub error: Option<String>,
+}
+
+/// 一次批量导出的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportSummary {
+    /// 按先序遍历顺序排列的每个节点的导出结果
+    pub files: Vec<ExportedFile>,
+    /// 索引文件路径（未生成索引时为None）
+    pub index_path: Option<PathBuf>,
+    /// 导出报告路径（未生成报告时为None）
+    pub report_path: Option<PathBuf>,
+}
+
+impl ExportSummary {
+    /// 获取所有导出文件的路径（包括跳过的文件）
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|file| file.path.clone()).collect()
+    }
+
+    /// 获取因已存在而跳过的文件
+    pub fn skipped(&self) -> Vec<&ExportedFile> {
+        self.files.iter().filter(|file| file.status == ExportStatus::Skipped).collect()
+    }
+
+    /// 获取导出失败的节点
+    pub fn failed(&self) -> Vec<&ExportedFile> {
+        self.files.iter().filter(|file| file.status == ExportStatus::Failed).collect()
+    }
+}
+
+/// 章节的JSON表示
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChapterJson {
+    /// 标题
+    pub title: String,
+    /// 节点ID
+    pub id: String,
+    /// 源文件路径
+    pub src: String,
+    /// 播放顺序
+    pub play_order: u32,
+    /// 正文内容
+    pub text: String,
+    /// 词数
+    pub word_count: usize,
+    /// 子章节
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ChapterJson>,
+}
+
+/// 整本书的JSON表示
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize)]
+pub struct BookJson {
+    /// 书籍标题
+    pub title: Option<String>,
+    /// 章节总数
+    pub total_chapters: usize,
+    /// 章节树
+    pub chapters: Vec<ChapterJson>,
+}
+
+/// 导出报告中的单个章节记录
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReportEntry {
+    /// 节点ID
+    pub id: String,
+    /// 标题
+    pub title: String,
+    /// 源文件路径
+    pub src: String,
+    /// 导出状态
+    pub status: ExportStatus,
+    /// 导出文件路径（相对于输出目录）
+    pub output: Option<String>,
+    /// 导出文件的字节数
+    pub bytes: Option<u64>,
+    /// 写入内容的词数（跳过已存在的文件时为None）
+    pub word_count: Option<usize>,
+    /// 导出或统计过程中遇到的错误
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// 一次导出的报告
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    /// 书籍标题
+    pub title: Option<String>,
+    /// 生成时间（Unix时间戳）
+    pub generated_at: u64,
+    /// 导出文件总数（包括跳过的文件，不包括失败的节点）
+    pub total_files: usize,
+    /// 按阅读顺序排列的章节记录
+    pub entries: Vec<ExportReportEntry>,
+}
+
 /// 目录树来源类型
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TocTreeSource {
@@ -32,6 +294,7 @@ pub enum TocTreeSource {
 
 /// 目录树节点
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TocTreeNode {
     /// 播放顺序
     pub play_order: u32,
@@ -41,10 +304,18 @@ pub struct TocTreeNode {
     pub src: String,
     /// 节点ID
     pub id: String,
+    /// 样式类（NCX中navPoint的`class`属性，或nav文档中`<li>`的`class`属性）
+    pub class: Option<String>,
     /// 子节点
     pub children: Vec<TocTreeNode>,
     /// 节点深度
     pub depth: u32,
+    /// 源文件在EPUB中的完整路径（构建目录树时解析并缓存）
+    #[cfg_attr(feature = "serde", serde(skip))]
+    resolved_path: Option<String>,
+    /// 同一文件中下一个目录节点的锚点，提取内容时在此处截止（构建目录树时计算并缓存）
+    #[cfg_attr(feature = "serde", serde(skip))]
+    end_anchor: Option<String>,
 }
 
 impl TocTreeNode {
@@ -55,11 +326,27 @@ impl TocTreeNode {
             title,
             src,
             id,
+            class: None,
             children: Vec::new(),
             depth,
+            resolved_path: None,
+            end_anchor: None,
         }
     }
 
+    /// 获取构建目录树时缓存的源文件完整路径（相对于EPUB根目录）
+    /// 
+    /// 手动创建的节点没有缓存，返回None。构建后修改`src`不会更新缓存的路径。
+    pub fn resolved_path(&self) -> Option<&str> {
+        self.resolved_path.as_deref()
+    }
+
+    /// 设置样式类
+    pub fn with_class(mut self, class: Option<String>) -> Self {
+        self.class = class;
+        self
+    }
+
     /// 添加子节点
     pub fn add_child(&mut self, child: TocTreeNode) {
         self.children.push(child);
@@ -96,6 +383,90 @@ impl TocTreeNode {
         }
     }
 
+    /// 从章节标题为叶子节点生成子节点
+    /// 
+    /// 递归遍历当前节点，对每个叶子节点解析其章节内容中带`id`的标题，
+    /// 并按标题层级作为子节点追加。指向文件内部锚点的叶子节点会被跳过，
+    /// 以避免同一文件的标题被重复添加。
+    fn enrich_with_headings(&mut self, epub: &Epub, max_level: u8) {
+        if !self.children.is_empty() {
+            for child in &mut self.children {
+                child.enrich_with_headings(epub, max_level);
+            }
+            return;
+        }
+        
+        if self.src.contains('#') {
+            return;
+        }
+        
+        let html = match self.get_html_content(epub) {
+            Ok(html) => html,
+            Err(e) => {
+                epub.warn(EpubWarning::new(format!("无法读取章节 '{}' 的标题: {}", self.title, e), None));
+                return;
+            }
+        };
+        
+        // 当前插入位置上各级标题的层级
+        let mut level_stack: Vec<u8> = Vec::new();
+        for (level, anchor, text) in Self::extract_headings(&html, max_level) {
+            while level_stack.last().is_some_and(|&last| last >= level) {
+                level_stack.pop();
+            }
+            
+            let mut target = &mut self.children;
+            for _ in 0..level_stack.len() {
+                target = &mut target.last_mut().expect("标题层级栈与节点不一致").children;
+            }
+            
+            let mut heading_node = TocTreeNode::new(
+                self.play_order,
+                epub.clean_title(&text),
+                format!("{}#{}", self.src, anchor),
+                format!("{}-{}", self.id, anchor),
+                self.depth + 1 + level_stack.len() as u32,
+            );
+            heading_node.resolved_path = self.resolved_path.clone();
+            target.push(heading_node);
+            level_stack.push(level);
+        }
+    }
+
+    /// 提取HTML中带`id`的标题（`<h2>`至`<hN>`）
+    /// 
+    /// # 返回值
+    /// * `Vec<(u8, String, String)>` - 按文档顺序排列的（层级, id, 标题文本）
+    fn extract_headings(html: &str, max_level: u8) -> Vec<(u8, String, String)> {
+        let max_level = max_level.min(6);
+        if max_level < 2 {
+            return Vec::new();
+        }
+        
+        let selector_str = (2..=max_level)
+            .map(|level| format!("h{}[id]", level))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let selector = match Selector::parse(&selector_str) {
+            Ok(selector) => selector,
+            Err(_) => return Vec::new(),
+        };
+        
+        let document = Html::parse_document(html);
+        document.select(&selector)
+            .filter_map(|element| {
+                let level = element.value().name()[1..].parse::<u8>().ok()?;
+                let anchor = element.value().attr("id")?.trim();
+                let text = element.text().collect::<Vec<_>>().join(" ");
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if anchor.is_empty() || text.is_empty() {
+                    return None;
+                }
+                Some((level, anchor.to_string(), text))
+            })
+            .collect()
+    }
+
     /// 根据ID查找节点
     pub fn find_by_id(&self, id: &str) -> Option<&TocTreeNode> {
         if self.id == id {
@@ -187,6 +558,9 @@ impl TocTreeNode {
     /// 该方法会从EPUB文件中提取当前节点对应的HTML文件内容。
     /// 文件路径会根据NCX文件的位置进行解析，因为NCX中的路径是相对于NCX文件的。
     /// 
+    /// 一个文件包含多个目录节点时，只返回属于当前节点的部分：`src`带有锚点（如`part1.xhtml#ch3`）时
+    /// 从该锚点所在元素开始，到同一文件中下一个目录节点的锚点之前结束。找不到锚点时返回整个文件。
+    /// 
     /// # 参数
     /// * `epub` - EPUB阅读器的可变引用
     /// 
@@ -217,75 +591,120 @@ impl TocTreeNode {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn get_html_content(&self, epub: &Epub) -> Result<String> {
-        // 获取NCX文件的目录路径，因为NCX中的路径是相对于NCX文件的
-        let full_path = match epub.get_ncx_directory()? {
-            Some(ncx_dir) => {
-                if ncx_dir.is_empty() {
-                    // 如果NCX在根目录，直接使用src路径
-                    self.src.clone()
-                } else {
-                    // 使用PathBuf正确处理路径组合和规范化
-                    let mut path = PathBuf::from(ncx_dir);
-                    path.push(&self.src);
-                    
-                    // 规范化路径，处理 ../ 等相对路径组件
-                    Self::normalize_path(&path)
-                }
-            }
-            None => {
-                // 如果没有NCX文件，回退到使用OPF目录（兼容性处理）
-                let opf_dir = epub.get_opf_directory()?;
-                if opf_dir.is_empty() {
-                    self.src.clone()
-                } else {
-                    // 使用PathBuf正确处理路径组合和规范化
-                    let mut path = PathBuf::from(opf_dir);
-                    path.push(&self.src);
-                    
-                    // 规范化路径，处理 ../ 等相对路径组件
-                    Self::normalize_path(&path)
-                }
-            }
-        };
+        let full_path = self.full_path(epub)?;
         
         // 从EPUB文件中提取HTML内容
-        epub.read_chapter_file(&full_path).map_err(|e| {
+        let html = epub.read_chapter_file(&full_path).map_err(|e| {
             EpubError::InvalidEpub(format!(
                 "无法读取章节文件 '{}' (节点ID: {}, 标题: '{}'): {}",
                 full_path, self.id, self.title, e
             ))
-        })
+        })?;
+        
+        let fragment = self.fragment();
+        if fragment.is_none() && self.end_anchor.is_none() {
+            return Ok(html);
+        }
+        Ok(slice_between_anchors(&html, fragment, self.end_anchor.as_deref()).unwrap_or(html))
+    }
+
+    /// 获取`src`中的锚点（`#`之后的部分）
+    fn fragment(&self) -> Option<&str> {
+        self.src.split_once('#')
+            .map(|(_, fragment)| fragment)
+            .filter(|fragment| !fragment.is_empty())
+    }
+
+    /// 获取节点内容的去重键：规范化的源文件路径（不含锚点）和锚点
+    /// 
+    /// 指向同一文件不同锚点的节点各自只提取自己的片段，只有两者都相同时内容才相同。
+    pub(crate) fn content_key(&self) -> (String, Option<&str>) {
+        (resolve_href("", &self.src), self.fragment())
+    }
+
+    /// 创建指向整个源文件的节点副本（去除锚点和截止锚点），用于按文件导出
+    fn whole_file_node(&self) -> TocTreeNode {
+        let mut node = self.clone();
+        if let Some((file, _)) = self.src.split_once('#') {
+            node.src = file.to_string();
+        }
+        node.end_anchor = None;
+        node
+    }
+
+    /// 获取节点源文件在EPUB中的完整路径（优先使用缓存）
+    fn full_path(&self, epub: &Epub) -> Result<String> {
+        match &self.resolved_path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(Self::join_source_path(&Self::source_base_dir(epub)?, &self.src)),
+        }
+    }
+
+    /// 获取节点`src`所相对的目录
+    /// 
+    /// NCX中的路径相对于NCX文件；没有NCX文件时回退到OPF目录（兼容性处理）。
+    fn source_base_dir(epub: &Epub) -> Result<String> {
+        match epub.get_ncx_directory()? {
+            Some(ncx_dir) => Ok(ncx_dir),
+            None => epub.get_opf_directory(),
+        }
+    }
+
+    /// 将节点`src`与所在目录拼接为EPUB中的完整路径（不含锚点）
+    fn join_source_path(base_dir: &str, src: &str) -> String {
+        resolve_href(base_dir, src)
+    }
+
+    /// 递归缓存当前节点及其子节点的完整路径（不含锚点）
+    fn cache_resolved_paths(&mut self, base_dir: &str) {
+        self.resolved_path = Some(resolve_href(base_dir, &self.src));
+        for child in &mut self.children {
+            child.cache_resolved_paths(base_dir);
+        }
+    }
+
+    /// 按先序遍历顺序收集节点的源文件路径（不含锚点）和锚点
+    fn collect_anchor_keys(&self, keys: &mut Vec<(String, Option<String>)>) {
+        let file = self.resolved_path.clone().unwrap_or_else(|| resolve_href("", &self.src));
+        keys.push((file, self.fragment().map(str::to_string)));
+        for child in &self.children {
+            child.collect_anchor_keys(keys);
+        }
+    }
+
+    /// 按先序遍历顺序为当前节点及其子节点设置截止锚点
+    fn assign_end_anchors(&mut self, end_anchors: &mut impl Iterator<Item = Option<String>>) {
+        self.end_anchor = end_anchors.next().flatten();
+        for child in &mut self.children {
+            child.assign_end_anchors(end_anchors);
+        }
     }
 
     /// 规范化路径，处理相对路径组件如 ../ 和 ./
     /// 
     /// 该方法确保生成的路径使用Unix风格的分隔符（/），这是ZIP文件内部的标准格式。
+    /// Windows风格的`\`分隔符会被转换为`/`，开头的`/`会被去掉（视为相对于EPUB根目录）；
+    /// 由于ZIP条目名称是字面值，`%20`等百分号转义会被解码，无效的转义保持原样。
     /// 
     /// # 参数
     /// * `path` - 需要规范化的路径
     /// 
     /// # 返回值
     /// * `String` - 规范化后的路径字符串，使用Unix风格分隔符
-    fn normalize_path(path: &Path) -> String {
+    pub(crate) fn normalize_path(path: &Path) -> String {
+        let path = percent_decode(&path.to_string_lossy().replace('\\', "/"));
         let mut components = Vec::new();
         
-        for component in path.components() {
+        for component in path.split('/') {
             match component {
-                std::path::Component::ParentDir => {
+                ".." => {
                     // 遇到 ".." 时，删除最后一个组件（如果存在）
                     components.pop();
                 }
-                std::path::Component::CurDir => {
-                    // 忽略 "." 组件
-                }
-                std::path::Component::Normal(name) => {
-                    // 正常的路径组件，转换为字符串
-                    if let Some(name_str) = name.to_str() {
-                        components.push(name_str.to_string());
-                    }
+                "" | "." => {
+                    // 忽略 "." 组件以及开头的`/`和重复分隔符产生的空组件
                 }
-                // 其他组件类型（如根路径、前缀等）在EPUB中不常见，忽略处理
-                _ => {}
+                name => components.push(name),
             }
         }
         
@@ -327,10 +746,22 @@ impl TocTreeNode {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn get_text_content(&self, epub: &Epub) -> Result<String> {
+        self.get_text_content_with_options(epub, &TextOptions::default())
+    }
+
+    /// 使用指定的提取选项获取当前节点的纯文本内容
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器的引用
+    /// * `options` - 文本提取选项（如注音的处理方式）
+    /// 
+    /// # 返回值
+    /// * `Result<String, EpubError>` - 成功时返回纯文本内容，失败时返回错误
+    pub fn get_text_content_with_options(&self, epub: &Epub, options: &TextOptions) -> Result<String> {
         let html_content = self.get_html_content(epub)?;
         
         // 简单的HTML标签移除（可以后续优化为更复杂的HTML解析）
-        let text_content = Self::strip_html_tags(&html_content);
+        let text_content = Self::strip_html_tags(&html_content, options);
         
         Ok(text_content)
     }
@@ -369,24 +800,70 @@ impl TocTreeNode {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn get_formatted_text_content(&self, epub: &Epub) -> Result<String> {
+        self.get_formatted_text_content_with_options(epub, &TextOptions::default())
+    }
+
+    /// 使用指定的提取选项获取当前节点的格式化文本内容
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器的引用
+    /// * `options` - 文本提取选项（如注音的处理方式）
+    /// 
+    /// # 返回值
+    /// * `Result<String, EpubError>` - 成功时返回格式化的文本内容，失败时返回错误
+    pub fn get_formatted_text_content_with_options(&self, epub: &Epub, options: &TextOptions) -> Result<String> {
         let html_content = self.get_html_content(epub)?;
         
         // 使用智能HTML解析器转换为格式化文本
-        let formatted_text = Self::convert_html_to_formatted_text(&html_content);
+        let formatted_text = Self::convert_html_to_formatted_text(&html_content, options);
         
         Ok(formatted_text)
     }
 
-    /// 生成当前节点代表章节的txt文件
+    /// 使用自定义转换器获取当前节点的格式化文本内容
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器的引用
+    /// * `extractor` - HTML到文本的转换器，为None时使用默认的格式化文本转换
+    /// 
+    /// # 返回值
+    /// * `Result<String, EpubError>` - 成功时返回转换后的文本内容，失败时返回错误
+    pub fn get_formatted_text_content_with_extractor(
+        &self,
+        epub: &Epub,
+        extractor: Option<&dyn TextExtractor>,
+    ) -> Result<String> {
+        match extractor {
+            Some(extractor) => Ok(extractor.extract(&self.get_html_content(epub)?)),
+            None => self.get_formatted_text_content(epub),
+        }
+    }
+
+    /// 获取当前节点的Markdown内容
+    /// 
+    /// 转换规则见[`html_to_markdown`](crate::epub::markdown::html_to_markdown)，图片链接解析为EPUB内的完整路径（如`OEBPS/images/a.png`）。
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器的引用
+    /// * `heading_offset` - 章节内标题层级的偏移量（`<h1>`输出为`heading_offset + 1`个`#`）
+    /// 
+    /// # 返回值
+    /// * `Result<String, EpubError>` - 成功时返回Markdown内容，失败时返回错误
+    pub fn get_markdown_content(&self, epub: &Epub, heading_offset: u8) -> Result<String> {
+        let full_path = self.full_path(epub)?;
+        let html_content = self.get_html_content(epub)?;
+        Ok(html_to_markdown_with_base(&html_content, heading_offset, parent_dir(&full_path)))
+    }
+
+    /// 生成当前节点代表章节的导出文件
     /// 
-    /// 该方法会将当前节点对应的章节内容保存为txt文件。
+    /// 该方法会将当前节点对应的章节内容保存为txt文件（JSON格式时保存为json文件）。
     /// 文件名基于章节标题生成，并进行安全性处理以避免文件系统冲突。
-    /// 默认使用格式化文本内容，保持原有HTML结构的基本格式。
     /// 
     /// # 参数
     /// * `epub` - EPUB阅读器的引用
     /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
-    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `format` - 导出格式
     /// 
     /// # 返回值
     /// * `Result<PathBuf, EpubError>` - 成功时返回生成的文件路径，失败时返回错误
@@ -400,7 +877,7 @@ impl TocTreeNode {
     /// 
     /// ```rust
     /// use bookforge::epub::Epub;
-    /// use bookforge::epub::ncx::toc_tree::create_toc_tree_from_ncx;
+    /// use bookforge::epub::ncx::toc_tree::{create_toc_tree_from_ncx, ExportFormat};
     /// use std::path::Path;
     /// 
     /// let epub = Epub::from_path("book.epub")?;
@@ -408,7 +885,7 @@ impl TocTreeNode {
     /// let toc_tree = create_toc_tree_from_ncx(&ncx, &epub);
     /// 
     /// if let Some(first_node) = toc_tree.get_first_node() {
-    ///     match first_node.generate_txt_file(&epub, Some(Path::new("chapters")), true) {
+    ///     match first_node.generate_txt_file(&epub, Some(Path::new("chapters")), ExportFormat::Formatted) {
     ///         Ok(file_path) => println!("章节已保存到: {:?}", file_path),
     ///         Err(e) => println!("保存章节失败: {}", e),
     ///     }
@@ -419,17 +896,62 @@ impl TocTreeNode {
         &self,
         epub: &Epub,
         output_dir: Option<&Path>,
-        use_formatted_text: bool,
+        format: ExportFormat,
     ) -> Result<PathBuf> {
-        // 获取章节内容
-        let content = if use_formatted_text {
-            self.get_formatted_text_content(epub)?
-        } else {
-            self.get_text_content(epub)?
-        };
+        self.generate_txt_file_with_extractor(epub, output_dir, format, None)
+    }
+
+    /// 使用自定义转换器生成当前节点代表章节的导出文件
+    /// 
+    /// 与`generate_txt_file`相同，但导出格式为`ExportFormat::Formatted`时使用指定的转换器
+    /// 将HTML转换为文本，其他格式不受影响。
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器的引用
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式
+    /// * `extractor` - HTML到文本的转换器，为None时使用默认的格式化文本转换
+    /// 
+    /// # 返回值
+    /// * `Result<PathBuf, EpubError>` - 成功时返回生成的文件路径，失败时返回错误
+    pub fn generate_txt_file_with_extractor(
+        &self,
+        epub: &Epub,
+        output_dir: Option<&Path>,
+        format: ExportFormat,
+        extractor: Option<&dyn TextExtractor>,
+    ) -> Result<PathBuf> {
+        let prefix = format!("{:03}", self.play_order);
+        self.generate_txt_file_with_prefix(epub, output_dir, format, &prefix, extractor)
+    }
+
+    /// 获取导出文件的路径，文件名使用指定的序号前缀
+    fn prefixed_file_path(&self, dir: &Path, format: ExportFormat, prefix: &str) -> PathBuf {
+        let safe_filename = Self::generate_prefixed_filename(&self.title, &self.id, self.play_order, prefix);
+        dir.join(format!("{}.{}", safe_filename, format.extension()))
+    }
 
+    /// 生成导出文件，文件名使用指定的序号前缀
+    fn generate_txt_file_with_prefix(
+        &self,
+        epub: &Epub,
+        output_dir: Option<&Path>,
+        format: ExportFormat,
+        prefix: &str,
+        extractor: Option<&dyn TextExtractor>,
+    ) -> Result<PathBuf> {
         // 确定输出目录
         let dir = output_dir.unwrap_or_else(|| Path::new("output"));
+
+        // 获取章节内容
+        let content = self.render_content(epub, format, extractor)?;
+        self.write_prefixed_file(dir, format, prefix, &content)
+    }
+
+    /// 将已生成的内容写入导出文件，文件名使用指定的序号前缀
+    fn write_prefixed_file(&self, dir: &Path, format: ExportFormat, prefix: &str, content: &str) -> Result<PathBuf> {
+        // 生成安全的文件名
+        let file_path = self.prefixed_file_path(dir, format, prefix);
         
         // 创建输出目录（如果不存在）
         if !dir.exists() {
@@ -442,12 +964,8 @@ impl TocTreeNode {
             })?;
         }
 
-        // 生成安全的文件名
-        let safe_filename = Self::generate_safe_filename(&self.title, &self.id, self.play_order);
-        let file_path = dir.join(format!("{}.txt", safe_filename));
-
         // 创建文件内容
-        let file_content = self.create_file_content(&content);
+        let file_content = self.create_file_content(content);
 
         // 写入文件
         fs::write(&file_path, file_content).map_err(|e| {
@@ -461,25 +979,43 @@ impl TocTreeNode {
         Ok(file_path)
     }
 
+    /// 按导出格式生成当前节点的文件内容
+    fn render_content(&self, epub: &Epub, format: ExportFormat, extractor: Option<&dyn TextExtractor>) -> Result<String> {
+        match format {
+            ExportFormat::Formatted => self.get_formatted_text_content_with_extractor(epub, extractor),
+            ExportFormat::Plain => self.get_text_content(epub),
+            ExportFormat::Markdown => self.get_markdown_content(epub, 0),
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => {
+                let chapter_json = self.to_chapter_json(epub, true, false)?;
+                serde_json::to_string_pretty(&chapter_json).map_err(|e| {
+                    EpubError::InternalError(format!("JSON序列化失败: {}", e))
+                })
+            }
+        }
+    }
+
     /// 批量生成当前节点及其所有子节点的txt文件
     /// 
-    /// 该方法会递归处理当前节点及其所有子节点，为每个节点生成对应的txt文件。
+    /// 该方法会递归处理当前节点及其所有子节点，为每个节点生成对应的导出文件。
     /// 文件会按照目录树结构在输出目录中创建相应的子目录。
     /// 
     /// # 参数
     /// * `epub` - EPUB阅读器的引用
     /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
-    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `format` - 导出格式
     /// * `create_subdirs` - 是否根据目录树结构创建子目录
+    /// * `global_numbering` - 是否按先序遍历的全局序号（`0001_`、`0002_`…）为文件和子目录编号，
+    ///   为false时使用节点的播放顺序
     /// 
     /// # 返回值
-    /// * `Result<Vec<PathBuf>, EpubError>` - 成功时返回所有生成的文件路径列表，失败时返回错误
+    /// * `Result<Vec<PathBuf>, EpubError>` - 成功时返回所有文件路径列表，失败时返回错误
     /// 
     /// # 使用示例
     /// 
     /// ```rust
     /// use bookforge::epub::Epub;
-    /// use bookforge::epub::ncx::toc_tree::create_toc_tree_from_ncx;
+    /// use bookforge::epub::ncx::toc_tree::{create_toc_tree_from_ncx, ExportFormat};
     /// use std::path::Path;
     /// 
     /// let epub = Epub::from_path("book.epub")?;
@@ -487,7 +1023,7 @@ impl TocTreeNode {
     /// let toc_tree = create_toc_tree_from_ncx(&ncx, &epub);
     /// 
     /// if let Some(first_node) = toc_tree.get_first_node() {
-    ///     match first_node.generate_txt_files_recursive(&epub, Some(Path::new("chapters")), true, true) {
+    ///     match first_node.generate_txt_files_recursive(&epub, Some(Path::new("chapters")), ExportFormat::Formatted, true, false) {
     ///         Ok(file_paths) => {
     ///             println!("已生成 {} 个章节文件:", file_paths.len());
     ///             for path in file_paths {
@@ -503,44 +1039,47 @@ impl TocTreeNode {
         &self,
         epub: &Epub,
         output_dir: Option<&Path>,
-        use_formatted_text: bool,
+        format: ExportFormat,
         create_subdirs: bool,
+        global_numbering: bool,
     ) -> Result<Vec<PathBuf>> {
-        let mut file_paths = Vec::new();
-        
         // 确定输出目录
         let base_dir = output_dir.unwrap_or_else(|| Path::new("output"));
         
-        // 为当前节点生成文件
-        self.generate_txt_files_recursive_impl(
-            epub,
-            base_dir,
-            use_formatted_text,
-            create_subdirs,
-            0,
-            &mut file_paths,
-        )?;
+        let mut numbering = global_numbering.then(|| GlobalNumbering::new(self.get_total_nodes()));
+        let mut jobs = Vec::new();
+        self.collect_export_jobs(base_dir, create_subdirs, &mut numbering, &mut jobs)?;
         
-        Ok(file_paths)
+        jobs.iter()
+            .map(|job| job.run(epub, format, false, None).map(|file| file.path))
+            .collect()
     }
 
-    /// 递归生成txt文件的内部实现
-    fn generate_txt_files_recursive_impl(
-        &self,
-        epub: &Epub,
+    /// 按先序遍历顺序收集当前节点及其子节点的导出任务
+    /// 
+    /// 文件名前缀在此时确定，启用子目录时会同时创建所需的目录，
+    /// 因此任务之间互不依赖，可以按任意顺序（或并行）执行。
+    fn collect_export_jobs<'n>(
+        &'n self,
         current_dir: &Path,
-        use_formatted_text: bool,
         create_subdirs: bool,
-        depth: u32,
-        file_paths: &mut Vec<PathBuf>,
+        numbering: &mut Option<GlobalNumbering>,
+        jobs: &mut Vec<ExportJob<'n>>,
     ) -> Result<()> {
         // 为当前节点生成文件
-        let file_path = self.generate_txt_file(epub, Some(current_dir), use_formatted_text)?;
-        file_paths.push(file_path);
+        let prefix = match numbering {
+            Some(numbering) => numbering.next_prefix(),
+            None => format!("{:03}", self.play_order),
+        };
+        jobs.push(ExportJob {
+            node: self,
+            dir: current_dir.to_path_buf(),
+            prefix: prefix.clone(),
+        });
 
         // 如果需要创建子目录且有子节点，为子节点创建目录
         if create_subdirs && !self.children.is_empty() {
-            let safe_dirname = Self::generate_safe_filename(&self.title, &self.id, self.play_order);
+            let safe_dirname = Self::generate_prefixed_filename(&self.title, &self.id, self.play_order, &prefix);
             let child_dir = current_dir.join(&safe_dirname);
             
             // 创建子目录
@@ -556,26 +1095,12 @@ impl TocTreeNode {
 
             // 递归处理子节点
             for child in &self.children {
-                child.generate_txt_files_recursive_impl(
-                    epub,
-                    &child_dir,
-                    use_formatted_text,
-                    create_subdirs,
-                    depth + 1,
-                    file_paths,
-                )?;
+                child.collect_export_jobs(&child_dir, create_subdirs, numbering, jobs)?;
             }
         } else {
             // 不创建子目录，在当前目录中处理所有子节点
             for child in &self.children {
-                child.generate_txt_files_recursive_impl(
-                    epub,
-                    current_dir,
-                    use_formatted_text,
-                    create_subdirs,
-                    depth + 1,
-                    file_paths,
-                )?;
+                child.collect_export_jobs(current_dir, create_subdirs, numbering, jobs)?;
             }
         }
 
@@ -593,7 +1118,12 @@ impl TocTreeNode {
     /// 
     /// # 返回值
     /// * `String` - 安全的文件名（不包含扩展名）
-    fn generate_safe_filename(title: &str, id: &str, play_order: u32) -> String {
+    pub(crate) fn generate_safe_filename(title: &str, id: &str, play_order: u32) -> String {
+        Self::generate_prefixed_filename(title, id, play_order, &format!("{:03}", play_order))
+    }
+
+    /// 生成带指定序号前缀的安全文件名
+    fn generate_prefixed_filename(title: &str, id: &str, play_order: u32, prefix: &str) -> String {
         // 移除或替换不安全的字符
         let mut safe_title = title
             .chars()
@@ -631,8 +1161,8 @@ impl TocTreeNode {
             }
         }
 
-        // 添加播放顺序作为前缀，确保文件按顺序排列
-        format!("{:03}_{}", play_order, safe_title)
+        // 添加序号前缀，确保文件按顺序排列
+        format!("{}_{}", prefix, safe_title)
     }
 
     /// 创建文件内容
@@ -667,52 +1197,92 @@ impl TocTreeNode {
         file_content
     }
 
-    /// 将HTML转换为格式化文本
+    /// 将当前节点转换为JSON表示
     /// 
     /// # 参数
-    /// * `html` - HTML内容
+    /// * `epub` - EPUB阅读器的引用
+    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `include_children` - 是否递归包含子节点
     /// 
     /// # 返回值
-    /// * `String` - 格式化的文本内容
-    fn convert_html_to_formatted_text(html: &str) -> String {
+    /// * `Result<ChapterJson, EpubError>` - 章节的JSON表示
+    #[cfg(feature = "serde")]
+    pub fn to_chapter_json(
+        &self,
+        epub: &Epub,
+        use_formatted_text: bool,
+        include_children: bool,
+    ) -> Result<ChapterJson> {
+        let text = if use_formatted_text {
+            self.get_formatted_text_content(epub)?
+        } else {
+            self.get_text_content(epub)?
+        };
+
+        let mut children = Vec::new();
+        if include_children {
+            for child in &self.children {
+                children.push(child.to_chapter_json(epub, use_formatted_text, true)?);
+            }
+        }
+
+        Ok(ChapterJson {
+            title: self.title.clone(),
+            id: self.id.clone(),
+            src: self.src.clone(),
+            play_order: self.play_order,
+            word_count: count_words(&text),
+            text,
+            children,
+        })
+    }
+
+    /// 将HTML转换为格式化文本
+    /// 
+    /// # 参数
+    /// * `html` - HTML内容
+    /// 
+    /// # 返回值
+    /// * `String` - 格式化的文本内容
+    pub(crate) fn convert_html_to_formatted_text(html: &str, options: &TextOptions) -> String {
         // 解析HTML文档
         let document = Html::parse_document(html);
         
         // 选择body元素，如果没有body则使用整个文档
         let body_selector = Selector::parse("body").unwrap();
         let content = if let Some(body) = document.select(&body_selector).next() {
-            Self::extract_formatted_text_from_element(body)
+            Self::extract_formatted_text_from_element(body, options)
         } else {
             // 如果没有body标签，处理整个文档
-            Self::extract_formatted_text_from_document(&document)
+            Self::extract_formatted_text_from_document(&document, options)
         };
         
         // 清理多余的连续换行符，但保持段落间的分隔
         let cleaned = Self::clean_excessive_newlines(&content);
         
-        cleaned
+        options.apply(cleaned)
     }
 
     /// 从HTML元素中提取格式化文本
-    fn extract_formatted_text_from_element(element: scraper::ElementRef) -> String {
+    fn extract_formatted_text_from_element(element: scraper::ElementRef, options: &TextOptions) -> String {
         let mut result = String::new();
-        Self::process_element_for_formatted_text(element, &mut result);
+        Self::process_element_for_formatted_text(element, &mut result, options);
         result
     }
 
     /// 从HTML文档中提取格式化文本
-    fn extract_formatted_text_from_document(document: &Html) -> String {
+    fn extract_formatted_text_from_document(document: &Html, options: &TextOptions) -> String {
         let mut result = String::new();
         
         // 选择所有文本内容，跳过head部分
         let body_selector = Selector::parse("body").unwrap();
         if let Some(body) = document.select(&body_selector).next() {
-            Self::process_element_for_formatted_text(body, &mut result);
+            Self::process_element_for_formatted_text(body, &mut result, options);
         } else {
             // 如果没有body标签，处理整个文档但跳过head
             let not_head_selector = Selector::parse("body, :not(head):not(head *)").unwrap();
             for element in document.select(&not_head_selector) {
-                Self::process_element_for_formatted_text(element, &mut result);
+                Self::process_element_for_formatted_text(element, &mut result, options);
             }
         }
         
@@ -720,43 +1290,93 @@ impl TocTreeNode {
     }
 
     /// 处理HTML元素以提取格式化文本
-    fn process_element_for_formatted_text(element: scraper::ElementRef, result: &mut String) {
+    fn process_element_for_formatted_text(element: scraper::ElementRef, result: &mut String, options: &TextOptions) {
         let tag_name = element.value().name();
         
         // 跳过文档头部和脚本相关标签
-        // if matches!(tag_name, "head" | "script" | "style" | "meta" | "link" | "title" | "base" | "noscript") {
-        //     return;
-        // }
+        if matches!(tag_name, "head" | "script" | "style" | "meta" | "link" | 
+                  "title" | "base" | "noscript") {
+            return;
+        }
+        
+        if tag_name == "img" {
+            if let Some(text) = Self::image_text(element, options) {
+                result.push_str(&text);
+            }
+            return;
+        }
         
         // 跳过媒体标签和相关元素
-        // if matches!(tag_name, 
-        //     "img" | "svg" | "video" | "audio" | "canvas" | "embed" | "object" | 
-        //     "iframe" | "picture" | "source" | "track" | "param" | "area" | "map"
-        // ) {
-        //     return;
-        // }
+        if matches!(tag_name, 
+            "svg" | "video" | "audio" | "canvas" | "embed" | "object" | 
+            "iframe" | "picture" | "source" | "track" | "param" | "area" | "map"
+        ) {
+            return;
+        }
         
         // 跳过特定类型的表单输入元素（图像按钮等）
-        // if tag_name == "input" {
-        //     if let Some(input_type) = element.value().attr("type") {
-        //         if matches!(input_type, "image" | "file" | "hidden") {
-        //             return;
-        //         }
-        //     }
-        // }
-        if matches!(tag_name, "img"){
+        if tag_name == "input"
+            && let Some(input_type) = element.value().attr("type")
+            && matches!(input_type, "image" | "file" | "hidden")
+        {
+            return;
+        }
+        
+        // 跳过隐藏的元素
+        if Self::is_hidden_element(element) {
+            return;
+        }
+        
+        // 注音元素单独处理
+        if tag_name == "ruby" {
+            Self::process_ruby(element, result, options, Self::process_element_for_formatted_text);
+            return;
+        }
+        if matches!(tag_name, "rt" | "rp") {
+            return;
+        }
+        
+        if matches!(tag_name, "sup" | "sub") && options.scripts {
+            let mut script = String::new();
+            for child in element.children() {
+                if let Some(child_element) = scraper::ElementRef::wrap(child) {
+                    Self::process_element_for_formatted_text(child_element, &mut script, options);
+                } else if let Some(text) = child.value().as_text() {
+                    script.push_str(text);
+                }
+            }
+            result.push_str(&render_script(&script, tag_name == "sup"));
             return;
         }
         
+        // 列表项另起一行，按嵌套层级缩进后添加项目符号
+        if tag_name == "li"
+            && options.list_markers
+            && let Some((marker, depth)) = Self::list_item_marker(element)
+        {
+            let line_start = result.rfind('\n').map_or(0, |index| index + 1);
+            if result[line_start..].trim().is_empty() {
+                result.truncate(line_start);
+            } else {
+                result.push('\n');
+            }
+            result.push_str(&"  ".repeat(depth - 1));
+            result.push_str(&marker);
+        }
+        
         // 处理元素的文本内容
         for node in element.children() {
             match node.value() {
                 scraper::node::Node::Text(text) => {
+                    // 块级元素之间的缩进和换行已由块级元素的换行代替
+                    if text.trim().is_empty() && (result.is_empty() || result.ends_with('\n')) {
+                        continue;
+                    }
                     result.push_str(text);
                 }
                 scraper::node::Node::Element(_) => {
                     if let Some(child_element) = scraper::ElementRef::wrap(node) {
-                        Self::process_element_for_formatted_text(child_element, result);
+                        Self::process_element_for_formatted_text(child_element, result, options);
                     }
                 }
                 _ => {}
@@ -765,18 +1385,23 @@ impl TocTreeNode {
         
         // 根据标签类型添加格式
         match tag_name {
-            // 块级元素 - 在结束时添加换行
-            // "div" | "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
-            //     result.push('\n');
-            // }
-            // 列表和表格元素
-            // "ul" | "ol" | "table" | "tbody" | "thead" | "tr" => {
-            //     result.push('\n');
-            // }
-            // 表格单元格
-            // "td" | "th" => {
-            //     result.push('\t');
-            // }
+            // 块级元素 - 在结束时换行（已在行首时不重复换行）
+            "div" | "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" => {
+                if !result.is_empty() && !result.ends_with('\n') {
+                    result.push('\n');
+                }
+            }
+            // 表格行 - 去除最后一个单元格后的制表符并换行
+            "tr" => {
+                while result.ends_with('\t') {
+                    result.pop();
+                }
+                result.push('\n');
+            }
+            // 表格单元格 - 以制表符分隔
+            "td" | "th" => {
+                result.push('\t');
+            }
             // 换行标签
             "br" => {
                 result.push('\n');
@@ -817,44 +1442,45 @@ impl TocTreeNode {
     /// 
     /// # 返回值
     /// * `String` - 移除HTML标签后的纯文本
-    fn strip_html_tags(html: &str) -> String {
+    pub(crate) fn strip_html_tags(html: &str, options: &TextOptions) -> String {
         // 解析HTML文档
         let document = Html::parse_document(html);
         
         // 选择body元素，如果没有body则使用整个文档
         let body_selector = Selector::parse("body").unwrap();
         let text = if let Some(body) = document.select(&body_selector).next() {
-            Self::extract_text_from_element(body)
+            Self::extract_text_from_element(body, options)
         } else {
-            Self::extract_text_from_document(&document)
+            Self::extract_text_from_document(&document, options)
         };
         
         // 清理多余的空白字符
-        text.split_whitespace()
+        options.apply(text)
+            .split_whitespace()
             .collect::<Vec<_>>()
             .join(" ")
     }
 
     /// 从HTML元素中提取纯文本
-    fn extract_text_from_element(element: scraper::ElementRef) -> String {
+    fn extract_text_from_element(element: scraper::ElementRef, options: &TextOptions) -> String {
         let mut result = String::new();
-        Self::process_element_for_text(element, &mut result);
+        Self::process_element_for_text(element, &mut result, options);
         result
     }
 
     /// 从HTML文档中提取纯文本
-    fn extract_text_from_document(document: &Html) -> String {
+    fn extract_text_from_document(document: &Html, options: &TextOptions) -> String {
         let mut result = String::new();
         
         // 选择body元素，如果没有则处理整个文档
         let body_selector = Selector::parse("body").unwrap();
         if let Some(body) = document.select(&body_selector).next() {
-            Self::process_element_for_text(body, &mut result);
+            Self::process_element_for_text(body, &mut result, options);
         } else {
             // 如果没有body标签，使用通用选择器
             let all_selector = Selector::parse("*").unwrap();
             for element in document.select(&all_selector) {
-                Self::process_element_for_text(element, &mut result);
+                Self::process_element_for_text(element, &mut result, options);
                 break; // 只处理第一个元素（通常是html或body）
             }
         }
@@ -863,7 +1489,7 @@ impl TocTreeNode {
     }
 
     /// 处理HTML元素以提取纯文本
-    fn process_element_for_text(element: scraper::ElementRef, result: &mut String) {
+    fn process_element_for_text(element: scraper::ElementRef, result: &mut String, options: &TextOptions) {
         let tag_name = element.value().name();
         
         // 跳过文档头部和脚本相关标签
@@ -872,9 +1498,18 @@ impl TocTreeNode {
             return;
         }
         
+        if tag_name == "img" {
+            if let Some(text) = Self::image_text(element, options) {
+                result.push(' ');
+                result.push_str(&text);
+                result.push(' ');
+            }
+            return;
+        }
+        
         // 跳过媒体标签和相关元素
         if matches!(tag_name, 
-            "img" | "svg" | "video" | "audio" | "canvas" | "embed" | "object" | 
+            "svg" | "video" | "audio" | "canvas" | "embed" | "object" | 
             "iframe" | "picture" | "source" | "track" | "param" | "area" | "map"
         ) {
             return;
@@ -889,6 +1524,41 @@ impl TocTreeNode {
             }
         }
         
+        // 跳过隐藏的元素
+        if Self::is_hidden_element(element) {
+            return;
+        }
+        
+        // 注音元素单独处理
+        if tag_name == "ruby" {
+            Self::process_ruby(element, result, options, Self::process_element_for_text);
+            return;
+        }
+        if matches!(tag_name, "rt" | "rp") {
+            return;
+        }
+        
+        if matches!(tag_name, "sup" | "sub") && options.scripts {
+            let mut script = String::new();
+            for child in element.children() {
+                if let Some(child_element) = scraper::ElementRef::wrap(child) {
+                    Self::process_element_for_text(child_element, &mut script, options);
+                } else if let Some(text) = child.value().as_text() {
+                    script.push_str(text);
+                }
+            }
+            result.push_str(&render_script(&script, tag_name == "sup"));
+            return;
+        }
+        
+        if tag_name == "li"
+            && options.list_markers
+            && let Some((marker, _)) = Self::list_item_marker(element)
+        {
+            result.push(' ');
+            result.push_str(&marker);
+        }
+        
         // 处理元素的文本内容
         for node in element.children() {
             match node.value() {
@@ -897,7 +1567,7 @@ impl TocTreeNode {
                 }
                 scraper::node::Node::Element(_) => {
                     if let Some(child_element) = scraper::ElementRef::wrap(node) {
-                        Self::process_element_for_text(child_element, result);
+                        Self::process_element_for_text(child_element, result, options);
                     }
                 }
                 _ => {}
@@ -913,6 +1583,194 @@ impl TocTreeNode {
             _ => {}
         }
     }
+
+    /// 检查元素是否被隐藏
+    /// 
+    /// 带有`hidden`属性，或内联样式中声明了`display: none`的元素视为隐藏。
+    /// 只解析元素自身的`style`属性，不处理样式表中的规则。
+    pub(crate) fn is_hidden_element(element: scraper::ElementRef) -> bool {
+        let attrs = element.value();
+        if attrs.attr("hidden").is_some() {
+            return true;
+        }
+        attrs.attr("style").is_some_and(|style| {
+            style.split(';').any(|declaration| {
+                declaration.split_once(':').is_some_and(|(property, value)| {
+                    property.trim().eq_ignore_ascii_case("display")
+                        && value.trim().to_ascii_lowercase().starts_with("none")
+                })
+            })
+        })
+    }
+
+    /// 按选项生成图片在文本中的表示，隐藏的图片或选项为丢弃时返回None
+    fn image_text(element: scraper::ElementRef, options: &TextOptions) -> Option<String> {
+        if Self::is_hidden_element(element) {
+            return None;
+        }
+        let attrs = element.value();
+        options.images.render(attrs.attr("alt").unwrap_or(""), attrs.attr("src").unwrap_or(""))
+    }
+
+    /// 获取列表项的项目符号及其所在列表的嵌套层级（从1开始）
+    /// 
+    /// 父元素为`<ul>`时返回`- `，为`<ol>`时返回`N. `。序号从`start`属性（默认为1，
+    /// 带`reversed`属性时默认为列表项总数）开始逐项递增（`reversed`时递减），
+    /// 遇到带`value`属性的列表项时从该值重新计数。父元素不是列表时返回None。
+    fn list_item_marker(element: scraper::ElementRef) -> Option<(String, usize)> {
+        let list = element.parent().and_then(scraper::ElementRef::wrap)?;
+        let depth = element.ancestors()
+            .filter_map(scraper::ElementRef::wrap)
+            .filter(|ancestor| matches!(ancestor.value().name(), "ul" | "ol"))
+            .count();
+        
+        match list.value().name() {
+            "ul" => Some(("- ".to_string(), depth)),
+            "ol" => {
+                let items: Vec<_> = list.children()
+                    .filter_map(scraper::ElementRef::wrap)
+                    .filter(|item| item.value().name() == "li")
+                    .collect();
+                let reversed = list.value().attr("reversed").is_some();
+                let step = if reversed { -1 } else { 1 };
+                let parse_number = |value: Option<&str>| value.and_then(|v| v.trim().parse::<i64>().ok());
+                
+                let mut number = parse_number(list.value().attr("start"))
+                    .unwrap_or(if reversed { items.len() as i64 } else { 1 }) - step;
+                for item in items {
+                    number = parse_number(item.value().attr("value")).unwrap_or(number + step);
+                    if item.id() == element.id() {
+                        return Some((format!("{}. ", number), depth));
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// 处理注音元素（`<ruby>`）
+    /// 
+    /// 基础文本使用传入的处理函数提取；`<rt>`中的注音按选项丢弃或放在基础文本后的括号中，
+    /// `<rp>`（不支持注音时显示的括号）始终丢弃。多组基础文本与注音交替出现时逐组输出，
+    /// 例如`<ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>`输出为`漢(かん)字(じ)`。
+    fn process_ruby(
+        element: scraper::ElementRef,
+        result: &mut String,
+        options: &TextOptions,
+        process_child: fn(scraper::ElementRef, &mut String, &TextOptions),
+    ) {
+        for node in element.children() {
+            match node.value() {
+                scraper::node::Node::Text(text) => {
+                    result.push_str(text);
+                }
+                scraper::node::Node::Element(_) => {
+                    let Some(child_element) = scraper::ElementRef::wrap(node) else {
+                        continue;
+                    };
+                    match child_element.value().name() {
+                        "rp" => {}
+                        "rt" | "rtc" => {
+                            if let RubyMode::Annotated { open, close } = options.ruby {
+                                let annotation = child_element.text().collect::<String>();
+                                result.push(open);
+                                result.push_str(annotation.trim());
+                                result.push(close);
+                            }
+                        }
+                        _ => process_child(child_element, result, options),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// 批量导出中单个节点的导出任务
+struct ExportJob<'n> {
+    /// 要导出的节点
+    node: &'n TocTreeNode,
+    /// 输出目录
+    dir: PathBuf,
+    /// 文件名前缀
+    prefix: String,
+}
+
+impl ExportJob<'_> {
+    /// 执行导出任务
+    /// 
+    /// `skip_existing`为true且目标文件已存在并且非空时，不重新生成，状态为`ExportStatus::Skipped`。
+    fn run(
+        &self,
+        epub: &Epub,
+        format: ExportFormat,
+        skip_existing: bool,
+        extractor: Option<&dyn TextExtractor>,
+    ) -> Result<ExportedFile> {
+        let existing = self.node.prefixed_file_path(&self.dir, format, &self.prefix);
+        let (path, status, word_count) = if skip_existing && fs::metadata(&existing).is_ok_and(|meta| meta.is_file() && meta.len() > 0) {
+            (existing, ExportStatus::Skipped, None)
+        } else {
+            let content = self.node.render_content(epub, format, extractor)?;
+            let path = self.node.write_prefixed_file(&self.dir, format, &self.prefix, &content)?;
+            (path, ExportStatus::Written, Some(count_words(&content)))
+        };
+        Ok(ExportedFile {
+            id: self.node.id.clone(),
+            title: self.node.title.clone(),
+            path,
+            status,
+            word_count,
+            error: None,
+        })
+    }
+    
+    /// 记录导出失败的任务
+    #[cfg(feature = "serde")]
+    fn failed(&self, format: ExportFormat, error: EpubError) -> ExportedFile {
+        ExportedFile {
+            id: self.node.id.clone(),
+            title: self.node.title.clone(),
+            path: self.node.prefixed_file_path(&self.dir, format, &self.prefix),
+            status: ExportStatus::Failed,
+            word_count: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// 导出文件的全局编号计数器
+/// 
+/// 按先序遍历顺序为整棵目录树的节点分配连续序号，序号宽度至少为4位，
+/// 并随节点总数增加，保证按文件名排序即可还原阅读顺序。
+struct GlobalNumbering {
+    next: usize,
+    width: usize,
+}
+
+impl GlobalNumbering {
+    fn new(total_nodes: usize) -> Self {
+        Self {
+            next: 1,
+            width: total_nodes.to_string().len().max(4),
+        }
+    }
+
+    /// 获取下一个序号前缀
+    fn next_prefix(&mut self) -> String {
+        let prefix = format!("{:0width$}", self.next, width = self.width);
+        self.next += 1;
+        prefix
+    }
+}
+
+/// 默认的格式化文本转换，按选项处理注音、图片、列表等
+impl TextExtractor for TextOptions {
+    fn extract(&self, html: &str) -> String {
+        TocTreeNode::convert_html_to_formatted_text(html, self)
+    }
 }
 
 /// 目录树结构
@@ -931,6 +1789,8 @@ pub struct TocTree<'a> {
     pub epub: &'a Epub,
     /// 目录树来源
     pub source: TocTreeSource,
+    /// 格式化文本导出时使用的自定义转换器（None表示使用默认转换）
+    text_extractor: Option<Arc<dyn TextExtractor>>,
 }
 
 impl<'a> TocTree<'a> {
@@ -963,6 +1823,7 @@ impl<'a> TocTree<'a> {
             max_depth: None,
             epub,
             source: TocTreeSource::Unknown,
+            text_extractor: None,
         }
     }
     
@@ -980,6 +1841,7 @@ impl<'a> TocTree<'a> {
             max_depth: None,
             epub,
             source,
+            text_extractor: None,
         }
     }
 
@@ -1007,11 +1869,141 @@ impl<'a> TocTree<'a> {
         self
     }
 
+    /// 设置格式化文本导出时使用的HTML到文本转换器
+    /// 
+    /// 设置后，`ExportFormat::Formatted`格式的批量导出、合并导出以及
+    /// `get_node_formatted_text_content`都会使用该转换器，其他导出格式不受影响。
+    pub fn with_text_extractor(mut self, extractor: impl TextExtractor + 'static) -> Self {
+        self.text_extractor = Some(Arc::new(extractor));
+        self
+    }
+
     /// 添加根节点
     pub fn add_root(&mut self, node: TocTreeNode) {
         self.roots.push(node);
     }
 
+    /// 获取节点`src`所相对的目录
+    /// 
+    /// 来自nav文档的目录树相对于nav文档所在目录，其他目录树相对于NCX文件所在目录
+    /// （没有NCX文件时为OPF目录）。
+    fn source_base_dir(&self) -> Result<String> {
+        if self.source == TocTreeSource::Nav
+            && let Some(nav_dir) = self.epub.nav_directory()?
+        {
+            return Ok(nav_dir);
+        }
+        TocTreeNode::source_base_dir(self.epub)
+    }
+
+    /// 解析并缓存所有节点源文件的完整路径
+    /// 
+    /// 批量导出时每个节点都要读取章节内容，缓存后不必逐节点重复拼接和规范化路径。
+    /// 之后通过`add_root`、`add_child`添加的节点没有缓存，读取时按需解析。
+    fn cache_resolved_paths(&mut self) {
+        let Ok(base_dir) = self.source_base_dir() else {
+            return;
+        };
+        for root in &mut self.roots {
+            root.cache_resolved_paths(&base_dir);
+        }
+        self.cache_end_anchors();
+    }
+
+    /// 计算并缓存每个节点的截止锚点
+    /// 
+    /// 节点的截止锚点是先序遍历中之后第一个指向同一文件、且锚点不同的节点的锚点，
+    /// 使同一文件中的多个目录节点各自只提取属于自己的内容。
+    fn cache_end_anchors(&mut self) {
+        let mut keys = Vec::new();
+        for root in &self.roots {
+            root.collect_anchor_keys(&mut keys);
+        }
+        
+        let end_anchors: Vec<Option<String>> = keys.iter()
+            .enumerate()
+            .map(|(index, (file, fragment))| {
+                keys[index + 1..].iter()
+                    .find(|(other_file, other_fragment)| {
+                        other_file == file && other_fragment.is_some() && other_fragment != fragment
+                    })
+                    .and_then(|(_, other_fragment)| other_fragment.clone())
+            })
+            .collect();
+        
+        let mut end_anchors = end_anchors.into_iter();
+        for root in &mut self.roots {
+            root.assign_end_anchors(&mut end_anchors);
+        }
+    }
+
+    /// 使用章节内的标题丰富目录树
+    /// 
+    /// 对每个叶子章节，解析其中带`id`属性的`<h2>`至`<hN>`标题，
+    /// 并作为带锚点的子节点追加，从而得到更深、更易导航的目录。
+    /// 没有`id`的标题无法链接，因此会被忽略。
+    /// 
+    /// # 参数
+    /// * `epub` - EPUB阅读器引用
+    /// * `max_level` - 最大标题层级（2-6），例如3表示只使用`<h2>`和`<h3>`
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 操作结果，无法读取的章节会输出警告并跳过
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(mut toc_tree) = epub.toc_tree()? {
+    ///     toc_tree.enrich_with_headings(&epub, 3)?;
+    ///     println!("{}", toc_tree);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn enrich_with_headings(&mut self, epub: &Epub, max_level: u8) -> Result<()> {
+        for root in &mut self.roots {
+            root.enrich_with_headings(epub, max_level);
+        }
+        self.cache_end_anchors();
+        Ok(())
+    }
+
+    /// 使用自定义节点格式渲染目录树
+    /// 
+    /// 使用与 `Display` 相同的树状符号布局（├── └──）和深度限制，
+    /// 但每个节点的文本由调用者提供的闭包生成，便于输出词数、时长等自定义信息。
+    /// 
+    /// # 参数
+    /// * `f` - 节点格式化闭包，参数为节点和节点所在深度（根节点为0）
+    /// 
+    /// # 返回值
+    /// * `String` - 渲染结果（每个节点一行）
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let output = toc_tree.render_with(|node, depth| {
+    ///         format!("{} (第{}层, {}个子节点)", node.title, depth + 1, node.children.len())
+    ///     });
+    ///     print!("{}", output);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn render_with(&self, f: impl Fn(&TocTreeNode, u32) -> String) -> String {
+        let mut result = String::new();
+        for (index, root) in self.roots.iter().enumerate() {
+            let is_last = index == self.roots.len() - 1;
+            Self::render_symbols(root, 0, is_last, "", self.max_depth, &f, &mut result);
+        }
+        result
+    }
+
     /// 获取目录树的统计信息
     pub fn get_statistics(&self) -> TocStatistics {
         let mut total_nodes = 0;
@@ -1032,6 +2024,48 @@ impl<'a> TocTree<'a> {
         }
     }
 
+    /// 将目录树展开为按先序深度优先顺序排列的平铺列表
+    /// 
+    /// 顺序与目录树的显示顺序一致，根节点的深度为0。适合在界面中渲染可滚动的目录。
+    /// 
+    /// # 返回值
+    /// * `Vec<FlatTocEntry>` - 平铺的目录条目
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     for entry in toc_tree.flatten() {
+    ///         println!("{}{}", "  ".repeat(entry.depth as usize), entry.title);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn flatten(&self) -> Vec<FlatTocEntry> {
+        let mut entries = Vec::new();
+        for root in &self.roots {
+            Self::collect_flat_entries(root, 0, &mut entries);
+        }
+        entries
+    }
+
+    /// 递归收集平铺的目录条目
+    fn collect_flat_entries(node: &TocTreeNode, depth: u32, entries: &mut Vec<FlatTocEntry>) {
+        entries.push(FlatTocEntry {
+            title: node.title.clone(),
+            src: node.src.clone(),
+            play_order: node.play_order,
+            depth,
+            has_children: !node.children.is_empty(),
+        });
+        for child in &node.children {
+            Self::collect_flat_entries(child, depth + 1, entries);
+        }
+    }
+
     /// 获取所有章节路径
     pub fn get_all_paths(&self) -> Vec<String> {
         let mut paths = Vec::new();
@@ -1110,6 +2144,21 @@ impl<'a> TocTree<'a> {
         }
     }
 
+    /// 获取从根节点到指定节点的标题路径
+    /// 路径数组的含义与`get_node_by_path`相同，返回的标题依次为根节点、各级祖先节点和该节点本身，
+    /// 例如`[1, 3, 0]`可能返回`["第二部", "第四章", "第一节"]`，用于"当前位置"导航显示。
+    /// 如果路径为空或对应的节点不存在，则返回 None
+    pub fn breadcrumb(&self, path: &[usize]) -> Option<Vec<String>> {
+        let (&root_index, rest) = path.split_first()?;
+        let mut node = self.roots.get(root_index)?;
+        let mut titles = vec![node.title.clone()];
+        for &index in rest {
+            node = node.children.get(index)?;
+            titles.push(node.title.clone());
+        }
+        Some(titles)
+    }
+
     /// 获取第一个根节点
     pub fn get_first_node(&self) -> Option<&TocTreeNode> {
         self.get_node_by_path(&[0])
@@ -1160,44 +2209,7 @@ impl<'a> TocTree<'a> {
     /// # 返回值
     /// * `Result<String, EpubError>` - 成功时返回HTML内容，失败时返回错误
     pub fn get_node_html_content(&self, node: &TocTreeNode) -> Result<String> {
-        // 获取NCX文件的目录路径，因为NCX中的路径是相对于NCX文件的
-        let full_path = match self.epub.get_ncx_directory()? {
-            Some(ncx_dir) => {
-                if ncx_dir.is_empty() {
-                    // 如果NCX在根目录，直接使用src路径
-                    node.src.clone()
-                } else {
-                    // 使用PathBuf正确处理路径组合和规范化
-                    let mut path = PathBuf::from(ncx_dir);
-                    path.push(&node.src);
-                    
-                    // 规范化路径，处理 ../ 等相对路径组件
-                    TocTreeNode::normalize_path(&path)
-                }
-            }
-            None => {
-                // 如果没有NCX文件，回退到使用OPF目录（兼容性处理）
-                let opf_dir = self.epub.get_opf_directory()?;
-                if opf_dir.is_empty() {
-                    node.src.clone()
-                } else {
-                    // 使用PathBuf正确处理路径组合和规范化
-                    let mut path = PathBuf::from(opf_dir);
-                    path.push(&node.src);
-                    
-                    // 规范化路径，处理 ../ 等相对路径组件
-                    TocTreeNode::normalize_path(&path)
-                }
-            }
-        };
-        
-        // 从EPUB文件中提取HTML内容
-        self.epub.read_chapter_file(&full_path).map_err(|e| {
-            EpubError::InvalidEpub(format!(
-                "无法读取章节文件 '{}' (节点ID: {}, 标题: '{}'): {}",
-                full_path, node.id, node.title, e
-            ))
-        })
+        node.get_html_content(self.epub)
     }
 
     /// 获取指定节点的纯文本内容
@@ -1211,7 +2223,7 @@ impl<'a> TocTree<'a> {
         let html_content = self.get_node_html_content(node)?;
         
         // 简单的HTML标签移除
-        let text_content = TocTreeNode::strip_html_tags(&html_content);
+        let text_content = TocTreeNode::strip_html_tags(&html_content, &TextOptions::default());
         
         Ok(text_content)
     }
@@ -1224,12 +2236,7 @@ impl<'a> TocTree<'a> {
     /// # 返回值
     /// * `Result<String, EpubError>` - 成功时返回格式化文本内容，失败时返回错误
     pub fn get_node_formatted_text_content(&self, node: &TocTreeNode) -> Result<String> {
-        let html_content = self.get_node_html_content(node)?;
-        
-        // 使用智能HTML解析器转换为格式化文本
-        let formatted_text = TocTreeNode::convert_html_to_formatted_text(&html_content);
-        
-        Ok(formatted_text)
+        node.get_formatted_text_content_with_extractor(self.epub, self.text_extractor.as_deref())
     }
 
     /// 获取所有章节的HTML内容
@@ -1284,7 +2291,7 @@ impl<'a> TocTree<'a> {
             }
             Err(e) => {
                 // 记录错误但继续处理其他章节
-                eprintln!("警告: 无法读取章节 '{}' ({}): {}", node.title, node.id, e);
+                self.epub.warn(EpubWarning::new(format!("无法读取章节 '{}' ({}): {}", node.title, node.id, e), None));
             }
         }
         
@@ -1349,7 +2356,7 @@ impl<'a> TocTree<'a> {
             }
             Err(e) => {
                 // 记录错误但继续处理其他章节
-                eprintln!("警告: 无法读取章节文本 '{}' ({}): {}", node.title, node.id, e);
+                self.epub.warn(EpubWarning::new(format!("无法读取章节文本 '{}' ({}): {}", node.title, node.id, e), None));
             }
         }
         
@@ -1414,7 +2421,7 @@ impl<'a> TocTree<'a> {
             }
             Err(e) => {
                 // 记录错误但继续处理其他章节
-                eprintln!("警告: 无法读取章节格式化文本 '{}' ({}): {}", node.title, node.id, e);
+                self.epub.warn(EpubWarning::new(format!("无法读取章节格式化文本 '{}' ({}): {}", node.title, node.id, e), None));
             }
         }
         
@@ -1429,12 +2436,14 @@ impl<'a> TocTree<'a> {
     /// 为整个目录树生成txt文件
     /// 
     /// 该方法会为目录树中的所有节点生成对应的txt文件。
-    /// 支持创建分层目录结构来组织章节文件。
+    /// 支持创建分层目录结构来组织章节文件。需要跳过已存在的文件或区分跳过的文件时
+    /// 使用`generate_all_txt_files_with_options`。
     /// 
     /// # 参数
     /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
-    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `format` - 导出格式
     /// * `create_subdirs` - 是否根据目录树结构创建子目录
+    /// * `global_numbering` - 是否按先序遍历的全局序号为文件和子目录编号
     /// 
     /// # 返回值
     /// * `Result<Vec<PathBuf>, EpubError>` - 成功时返回所有生成的文件路径列表，失败时返回错误
@@ -1443,14 +2452,14 @@ impl<'a> TocTree<'a> {
     /// 
     /// ```rust
     /// use bookforge::epub::Epub;
-    /// use bookforge::epub::ncx::toc_tree::create_toc_tree_from_ncx;
+    /// use bookforge::epub::ncx::toc_tree::{create_toc_tree_from_ncx, ExportFormat};
     /// use std::path::Path;
     /// 
     /// let epub = Epub::from_path("book.epub")?;
     /// let ncx = epub.ncx()?.unwrap();
     /// let toc_tree = create_toc_tree_from_ncx(&ncx, &epub);
     /// 
-    /// match toc_tree.generate_all_txt_files(Some(Path::new("chapters")), true, true) {
+    /// match toc_tree.generate_all_txt_files(Some(Path::new("chapters")), ExportFormat::Formatted, true, false) {
     ///     Ok(file_paths) => {
     ///         println!("已生成 {} 个章节文件:", file_paths.len());
     ///         for path in file_paths {
@@ -1464,47 +2473,172 @@ impl<'a> TocTree<'a> {
     pub fn generate_all_txt_files(
         &self,
         output_dir: Option<&Path>,
-        use_formatted_text: bool,
+        format: ExportFormat,
         create_subdirs: bool,
+        global_numbering: bool,
     ) -> Result<Vec<PathBuf>> {
-        let mut all_file_paths = Vec::new();
-        
-        // 确定输出目录
-        let base_dir = output_dir.unwrap_or_else(|| Path::new("."));
-        
-        // 为所有根节点生成文件
-        for root in &self.roots {
-            let file_paths = root.generate_txt_files_recursive(
-                self.epub,
-                Some(base_dir),
-                use_formatted_text,
-                create_subdirs,
-            )?;
-            all_file_paths.extend(file_paths);
-        }
-        
-        Ok(all_file_paths)
+        let options = ExportOptions::new(format)
+            .with_create_subdirs(create_subdirs)
+            .with_global_numbering(global_numbering);
+        Ok(self.generate_all_txt_files_with_options(output_dir, &options)?.paths())
     }
 
-    /// 为整个目录树生成txt文件，并创建索引文件
+    /// 按导出选项为整个目录树生成文件
     /// 
-    /// 该方法不仅会为所有节点生成txt文件，还会创建一个包含所有章节信息的索引文件。
-    /// 索引文件包含目录结构和文件路径映射。
+    /// 与`generate_all_txt_files`相同，但返回每个节点的导出结果，
+    /// 启用`skip_existing`时可以区分新写入的文件和因已存在而跳过的文件，用于续传中断的导出。
+    /// 设置了`index_filename`时会在所有章节导出后生成索引文件；设置了`report_filename`时
+    /// 会生成JSON格式的导出报告，按节点列出导出状态、文件路径、字节数、词数和错误。
     /// 
     /// # 参数
     /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
-    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
-    /// * `create_subdirs` - 是否根据目录树结构创建子目录
-    /// * `index_filename` - 索引文件名，如果为None则使用默认名称
+    /// * `options` - 导出选项
     /// 
     /// # 返回值
-    /// * `Result<(Vec<PathBuf>, PathBuf), EpubError>` - 成功时返回(章节文件路径列表, 索引文件路径)，失败时返回错误
+    /// * `Result<ExportSummary, EpubError>` - 按先序遍历顺序排列的导出结果，未生成报告时任一章节失败即返回错误
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, ExportFormat, ExportOptions, ExportStatus};
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let options = ExportOptions::new(ExportFormat::Formatted).with_skip_existing(true);
+    ///     let summary = toc_tree.generate_all_txt_files_with_options(Some(Path::new("chapters")), &options)?;
+    ///     for file in &summary.files {
+    ///         if file.status == ExportStatus::Skipped {
+    ///             println!("已存在，跳过: {:?}", file.path);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_all_txt_files_with_options(
+        &self,
+        output_dir: Option<&Path>,
+        options: &ExportOptions,
+    ) -> Result<ExportSummary> {
+        let jobs = self.collect_export_jobs(output_dir, options.create_subdirs, options.global_numbering)?;
+        let mut files = Vec::with_capacity(jobs.len());
+        for job in &jobs {
+            match job.run(self.epub, options.format, options.skip_existing, self.text_extractor.as_deref()) {
+                Ok(file) => files.push(file),
+                #[cfg(feature = "serde")]
+                Err(e) if options.report_filename.is_some() => files.push(job.failed(options.format, e)),
+                Err(e) => return Err(e),
+            }
+        }
+        
+        let base_dir = output_dir.unwrap_or_else(|| Path::new("."));
+        let mut summary = ExportSummary {
+            files,
+            index_path: None,
+            report_path: None,
+        };
+        if let Some(index_filename) = &options.index_filename {
+            let index_path = base_dir.join(index_filename);
+            let index_content = self.create_index_content(&summary.paths(), base_dir, options.format)?;
+            fs::write(&index_path, index_content).map_err(|e| {
+                EpubError::InvalidEpub(format!(
+                    "无法写入索引文件 '{}': {}",
+                    index_path.display(),
+                    e
+                ))
+            })?;
+            summary.index_path = Some(index_path);
+        }
+        #[cfg(feature = "serde")]
+        if let Some(report_filename) = &options.report_filename {
+            summary.report_path = Some(self.write_export_report(&jobs, &summary.files, base_dir, report_filename)?);
+        }
+        
+        Ok(summary)
+    }
+
+    /// 使用多个线程为整个目录树生成txt文件
+    /// 
+    /// 参数、返回的文件路径顺序以及文件命名规则都与`generate_all_txt_files`相同。
+    /// 读取ZIP条目仍然需要获取归档锁而串行执行，但HTML到文本的转换和文件写入
+    /// 会在rayon线程池中并行进行，章节越多、内容越长，加速越明显。
+    /// 需要启用`parallel`特性。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式
+    /// * `create_subdirs` - 是否根据目录树结构创建子目录
+    /// * `global_numbering` - 是否按先序遍历的全局序号为文件和子目录编号
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<PathBuf>, EpubError>` - 按先序遍历顺序排列的文件路径，任一章节失败时返回错误
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, ExportFormat};
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let paths = toc_tree.generate_all_txt_files_parallel(Some(Path::new("chapters")), ExportFormat::Formatted, false, true)?;
+    ///     println!("已生成 {} 个章节文件", paths.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn generate_all_txt_files_parallel(
+        &self,
+        output_dir: Option<&Path>,
+        format: ExportFormat,
+        create_subdirs: bool,
+        global_numbering: bool,
+    ) -> Result<Vec<PathBuf>> {
+        use rayon::prelude::*;
+        
+        let jobs = self.collect_export_jobs(output_dir, create_subdirs, global_numbering)?;
+        jobs.par_iter()
+            .map(|job| job.run(self.epub, format, false, self.text_extractor.as_deref()).map(|file| file.path))
+            .collect()
+    }
+
+    /// 按先序遍历顺序收集所有节点的导出任务（全局编号在所有根节点之间连续）
+    fn collect_export_jobs(
+        &self,
+        output_dir: Option<&Path>,
+        create_subdirs: bool,
+        global_numbering: bool,
+    ) -> Result<Vec<ExportJob<'_>>> {
+        let base_dir = output_dir.unwrap_or_else(|| Path::new("."));
+        let mut numbering = global_numbering.then(|| GlobalNumbering::new(self.get_statistics().total_nodes));
+        
+        let mut jobs = Vec::new();
+        for root in &self.roots {
+            root.collect_export_jobs(base_dir, create_subdirs, &mut numbering, &mut jobs)?;
+        }
+        Ok(jobs)
+    }
+
+    /// 为整个目录树生成txt文件，并创建索引文件
+    /// 
+    /// 该方法不仅会为所有节点生成txt文件，还会创建一个包含所有章节信息的索引文件。
+    /// 索引文件包含目录结构和文件路径映射。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式
+    /// * `create_subdirs` - 是否根据目录树结构创建子目录
+    /// * `global_numbering` - 是否按先序遍历的全局序号为文件和子目录编号
+    /// * `index_filename` - 索引文件名，如果为None则使用默认名称
+    /// 
+    /// # 返回值
+    /// * `Result<(Vec<PathBuf>, PathBuf), EpubError>` - 成功时返回(章节文件路径列表, 索引文件路径)，失败时返回错误
     /// 
     /// # 使用示例
     /// 
     /// ```rust
     /// use bookforge::epub::Epub;
-    /// use bookforge::epub::ncx::toc_tree::create_toc_tree_from_ncx;
+    /// use bookforge::epub::ncx::toc_tree::{create_toc_tree_from_ncx, ExportFormat};
     /// use std::path::Path;
     /// 
     /// let epub = Epub::from_path("book.epub")?;
@@ -1513,8 +2647,9 @@ impl<'a> TocTree<'a> {
     /// 
     /// match toc_tree.generate_all_txt_files_with_index(
     ///     Some(Path::new("chapters")), 
+    ///     ExportFormat::Formatted, 
     ///     true, 
-    ///     true, 
+    ///     false,
     ///     Some("目录索引.txt")
     /// ) {
     ///     Ok((file_paths, index_path)) => {
@@ -1528,31 +2663,226 @@ impl<'a> TocTree<'a> {
     pub fn generate_all_txt_files_with_index(
         &self,
         output_dir: Option<&Path>,
-        use_formatted_text: bool,
+        format: ExportFormat,
         create_subdirs: bool,
+        global_numbering: bool,
         index_filename: Option<&str>,
     ) -> Result<(Vec<PathBuf>, PathBuf)> {
-        // 生成所有章节文件
-        let file_paths = self.generate_all_txt_files(output_dir, use_formatted_text, create_subdirs)?;
+        let options = ExportOptions::new(format)
+            .with_create_subdirs(create_subdirs)
+            .with_global_numbering(global_numbering)
+            .with_index(index_filename.unwrap_or("目录索引.txt"));
+        let summary = self.generate_all_txt_files_with_options(output_dir, &options)?;
+        let index_path = summary.index_path.clone().unwrap_or_default();
+        Ok((summary.paths(), index_path))
+    }
+
+    /// 按阅读顺序平铺导出所有章节，多个节点指向同一位置时只导出一次
+    /// 
+    /// 适用于目录中存在重复条目的EPUB：多个目录节点指向同一文件的同一锚点时，
+    /// 逐节点导出会得到大量内容相同的文件。该方法按先序遍历顺序使用全局序号命名文件，
+    /// 每个位置的内容只写入第一个指向它的节点对应的文件，其余节点只写入一个指向该文件的
+    /// 简短说明。指向同一文件不同锚点的节点各自导出自己的片段。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<PathBuf>, EpubError>` - 按阅读顺序排列的所有文件路径（包括说明文件）
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, ExportFormat};
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let paths = toc_tree.generate_deduplicated_txt_files(Some(Path::new("chapters")), ExportFormat::Plain)?;
+    ///     println!("已生成 {} 个文件", paths.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_deduplicated_txt_files(
+        &self,
+        output_dir: Option<&Path>,
+        format: ExportFormat,
+    ) -> Result<Vec<PathBuf>> {
+        let dir = output_dir.unwrap_or_else(|| Path::new("."));
         
-        // 确定输出目录和索引文件路径
-        let base_dir = output_dir.unwrap_or_else(|| Path::new("."));
-        let index_name = index_filename.unwrap_or("目录索引.txt");
-        let index_path = base_dir.join(index_name);
+        let mut nodes = Vec::new();
+        Self::collect_nodes_in_order(&self.roots, &mut nodes);
         
-        // 生成索引文件内容
-        let index_content = self.create_index_content(&file_paths, base_dir, use_formatted_text)?;
+        let mut numbering = GlobalNumbering::new(nodes.len());
+        // (源文件路径, 锚点) -> (第一个节点的标题, 导出的文件路径)
+        let mut exported: HashMap<(String, Option<&str>), (String, PathBuf)> = HashMap::new();
+        let mut file_paths = Vec::new();
         
-        // 写入索引文件
-        fs::write(&index_path, index_content).map_err(|e| {
-            EpubError::InvalidEpub(format!(
-                "无法写入索引文件 '{}': {}",
-                index_path.display(),
-                e
-            ))
-        })?;
+        for node in nodes {
+            let prefix = numbering.next_prefix();
+            let source = node.content_key();
+            
+            let file_path = match exported.get(&source) {
+                Some((first_title, first_path)) if !source.0.is_empty() => {
+                    let safe_filename = TocTreeNode::generate_prefixed_filename(&node.title, &node.id, node.play_order, &prefix);
+                    let file_path = dir.join(format!("{}.{}", safe_filename, format.extension()));
+                    let target = first_path.file_name().unwrap_or_default().to_string_lossy();
+                    let pointer = Self::create_pointer_content(format, &node.title, first_title, &target)?;
+                    fs::write(&file_path, pointer).map_err(|e| {
+                        EpubError::InvalidEpub(format!(
+                            "无法写入文件 '{}': {}",
+                            file_path.display(),
+                            e
+                        ))
+                    })?;
+                    file_path
+                }
+                _ => {
+                    let file_path = node.generate_txt_file_with_prefix(
+                        self.epub, Some(dir), format, &prefix, self.text_extractor.as_deref(),
+                    )?;
+                    exported.insert(source, (node.title.clone(), file_path.clone()));
+                    file_path
+                }
+            };
+            file_paths.push(file_path);
+        }
+        
+        Ok(file_paths)
+    }
+
+    /// 按EPUB内部的原始路径导出所有章节
+    /// 
+    /// 与按标题命名的导出不同，每个源文件写入`output_dir/<相对于OPF目录的原始路径>`，
+    /// 并按需创建子目录，保留HTML导出时章节之间的相对链接。多个目录节点指向同一
+    /// 源文件时只导出一次。不在目录中的脊柱文件不会被导出。
+    /// 
+    /// 路径中包含`..`或绝对路径等可能逃逸出输出目录的组件时返回错误。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式，为None时保留原始HTML；转换时文件扩展名替换为对应格式的扩展名
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<PathBuf>, EpubError>` - 按阅读顺序排列的所有文件路径
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     // 保留HTML，输出如 site/Text/chapter1.xhtml
+    ///     let paths = toc_tree.generate_mirrored_files(Some(Path::new("site")), None)?;
+    ///     println!("已生成 {} 个文件", paths.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_mirrored_files(
+        &self,
+        output_dir: Option<&Path>,
+        format: Option<ExportFormat>,
+    ) -> Result<Vec<PathBuf>> {
+        let dir = output_dir.unwrap_or_else(|| Path::new("."));
+        let opf_dir = self.epub.get_opf_directory()?;
+        let base_dir = self.source_base_dir()?;
+        let opf_prefix = if opf_dir.is_empty() { String::new() } else { format!("{}/", opf_dir) };
+        
+        let mut nodes = Vec::new();
+        Self::collect_nodes_in_order(&self.roots, &mut nodes);
+        
+        let mut exported = HashSet::new();
+        let mut file_paths = Vec::new();
+        
+        for node in nodes {
+            if node.src.is_empty() {
+                continue;
+            }
+            let source = resolve_href(&base_dir, &node.src);
+            if !exported.insert(source.clone()) {
+                continue;
+            }
+            
+            let relative = source.strip_prefix(&opf_prefix).unwrap_or(&source);
+            let mut file_path = dir.join(Self::mirror_relative_path(relative).ok_or_else(|| {
+                EpubError::InvalidEpub(format!("章节路径可能逃逸出输出目录: {}", node.src))
+            })?);
+            
+            let content = match format {
+                None => self.epub.read_chapter_file(&source)?,
+                Some(format) => {
+                    // 按文件导出，不能只使用第一个节点锚点对应的片段
+                    let whole_file = node.whole_file_node();
+                    file_path.set_extension(format.extension());
+                    whole_file.create_file_content(&whole_file.render_content(self.epub, format, self.text_extractor.as_deref())?)
+                }
+            };
+            
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| {
+                    EpubError::InvalidEpub(format!(
+                        "无法创建输出目录 '{}': {}",
+                        parent.display(),
+                        e
+                    ))
+                })?;
+            }
+            fs::write(&file_path, content).map_err(|e| {
+                EpubError::InvalidEpub(format!(
+                    "无法写入文件 '{}': {}",
+                    file_path.display(),
+                    e
+                ))
+            })?;
+            file_paths.push(file_path);
+        }
         
-        Ok((file_paths, index_path))
+        Ok(file_paths)
+    }
+
+    /// 将EPUB内部路径转换为输出目录下的相对路径
+    /// 
+    /// 只允许普通路径组件，包含`..`、根目录或盘符前缀时返回None，防止写出输出目录（zip-slip）。
+    fn mirror_relative_path(path: &str) -> Option<PathBuf> {
+        let path = Path::new(path);
+        if path.as_os_str().is_empty() {
+            return None;
+        }
+        path.components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)))
+            .then(|| path.to_path_buf())
+    }
+
+    /// 按先序遍历顺序收集所有节点
+    pub(crate) fn collect_nodes_in_order<'n>(nodes: &'n [TocTreeNode], result: &mut Vec<&'n TocTreeNode>) {
+        for node in nodes {
+            result.push(node);
+            Self::collect_nodes_in_order(&node.children, result);
+        }
+    }
+
+    /// 创建指向已导出文件的说明内容
+    fn create_pointer_content(format: ExportFormat, title: &str, target_title: &str, target_file: &str) -> Result<String> {
+        match format {
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => {
+                let pointer = serde_json::json!({
+                    "title": title,
+                    "see": target_file,
+                    "see_title": target_title,
+                });
+                serde_json::to_string_pretty(&pointer).map_err(|e| {
+                    EpubError::InternalError(format!("JSON序列化失败: {}", e))
+                })
+            }
+            _ => Ok(format!(
+                "{}\n\n本节内容与「{}」位于同一源文件，参见: {}\n",
+                title, target_title, target_file
+            )),
+        }
     }
 
     /// 将所有章节合并为一个txt文件
@@ -1594,6 +2924,53 @@ impl<'a> TocTree<'a> {
         output_dir: Option<&Path>,
         use_formatted_text: bool,
         filename: Option<&str>,
+    ) -> Result<PathBuf> {
+        self.write_merged_txt_file(output_dir, use_formatted_text, false, filename)
+    }
+
+    /// 将所有章节合并为一个txt文件，并去除各章首尾重复出现的样板文字
+    /// 
+    /// 与[`generate_merged_txt_file`](Self::generate_merged_txt_file)相同，但会尽力去除
+    /// 每章都出现的页眉页脚（如每章开头的书名），规则见
+    /// [`strip_boilerplate`](crate::epub::text::strip_boilerplate)。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `filename` - 自定义文件名，如果为None则使用书籍标题
+    /// 
+    /// # 返回值
+    /// * `Result<PathBuf, EpubError>` - 成功时返回生成的文件路径，失败时返回错误
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let file_path = toc_tree.generate_merged_txt_file_without_boilerplate(Some(Path::new("output")), true, None)?;
+    ///     println!("合并文件已保存到: {:?}", file_path);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_merged_txt_file_without_boilerplate(
+        &self,
+        output_dir: Option<&Path>,
+        use_formatted_text: bool,
+        filename: Option<&str>,
+    ) -> Result<PathBuf> {
+        self.write_merged_txt_file(output_dir, use_formatted_text, true, filename)
+    }
+
+    /// 合并txt文件的共同实现
+    fn write_merged_txt_file(
+        &self,
+        output_dir: Option<&Path>,
+        use_formatted_text: bool,
+        deduplicate_boilerplate: bool,
+        filename: Option<&str>,
     ) -> Result<PathBuf> {
         // 确定输出目录
         let dir = output_dir.unwrap_or_else(|| Path::new("."));
@@ -1621,14 +2998,23 @@ impl<'a> TocTree<'a> {
         let file_path = dir.join(format!("{}.txt", safe_filename));
 
         // 收集所有章节内容
-        let chapter_contents = if use_formatted_text {
+        let mut chapter_contents = if use_formatted_text {
             self.get_all_formatted_text_contents()?
         } else {
             self.get_all_text_contents()?
         };
+        if deduplicate_boilerplate {
+            let mut texts: Vec<String> = chapter_contents.iter_mut()
+                .map(|(_, _, text)| std::mem::take(text))
+                .collect();
+            strip_boilerplate(&mut texts);
+            for ((_, _, text), stripped) in chapter_contents.iter_mut().zip(texts) {
+                *text = stripped;
+            }
+        }
 
         // 创建合并文件内容
-        let merged_content = self.create_merged_file_content(&chapter_contents, use_formatted_text)?;
+        let merged_content = self.create_merged_file_content(&chapter_contents, use_formatted_text, None)?;
 
         // 写入文件
         fs::write(&file_path, merged_content).map_err(|e| {
@@ -1642,6 +3028,340 @@ impl<'a> TocTree<'a> {
         Ok(file_path)
     }
 
+    /// 为每个顶层节点（如多部作品中的每一部）生成一个合并文件
+    /// 
+    /// 介于逐章导出和整本合并之间：每个根节点及其所有子节点的内容合并到一个文件中。
+    /// 文件名由书籍标题、根节点序号和根节点标题组成（如`书名_01_第一部.txt`），
+    /// 避免不同书籍或同名部分之间的冲突。JSON格式时每个文件包含该部分的章节树。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `format` - 导出格式
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<PathBuf>, EpubError>` - 按根节点顺序排列的文件路径
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, ExportFormat};
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     for path in toc_tree.generate_merged_per_root(Some(Path::new("parts")), ExportFormat::Plain)? {
+    ///         println!("已生成: {:?}", path);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_merged_per_root(
+        &self,
+        output_dir: Option<&Path>,
+        format: ExportFormat,
+    ) -> Result<Vec<PathBuf>> {
+        let dir = output_dir.unwrap_or_else(|| Path::new("."));
+        fs::create_dir_all(dir).map_err(|e| {
+            EpubError::InvalidEpub(format!(
+                "无法创建输出目录 '{}': {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        
+        let book_name = Self::generate_safe_book_filename(self.title.as_deref().unwrap_or("merged_book"));
+        let width = self.roots.len().to_string().len().max(2);
+        let mut file_paths = Vec::new();
+        
+        for (index, root) in self.roots.iter().enumerate() {
+            let filename = format!(
+                "{}_{:0width$}_{}.{}",
+                book_name,
+                index + 1,
+                Self::generate_safe_book_filename(&root.title),
+                format.extension(),
+                width = width
+            );
+            let file_path = dir.join(filename);
+            
+            let content = match format {
+                #[cfg(feature = "serde")]
+                ExportFormat::Json => {
+                    let chapter_json = root.to_chapter_json(self.epub, true, true)?;
+                    serde_json::to_string_pretty(&chapter_json).map_err(|e| {
+                        EpubError::InternalError(format!("JSON序列化失败: {}", e))
+                    })?
+                }
+                ExportFormat::Markdown => self.markdown_book_content(Some(&root.title), std::slice::from_ref(root)),
+                _ => {
+                    let use_formatted_text = format == ExportFormat::Formatted;
+                    let mut chapter_contents = Vec::new();
+                    if use_formatted_text {
+                        self.collect_formatted_text_contents(root, &mut chapter_contents)?;
+                    } else {
+                        self.collect_text_contents(root, &mut chapter_contents)?;
+                    }
+                    self.create_merged_file_content(&chapter_contents, use_formatted_text, Some(&root.title))?
+                }
+            };
+            
+            fs::write(&file_path, content).map_err(|e| {
+                EpubError::InvalidEpub(format!(
+                    "无法写入合并文件 '{}': {}",
+                    file_path.display(),
+                    e
+                ))
+            })?;
+            file_paths.push(file_path);
+        }
+        
+        Ok(file_paths)
+    }
+
+    /// 将整本书导出为一个带目录链接的Markdown文件
+    /// 
+    /// 文件以书籍标题（`#`）开头，接着是按目录树嵌套的链接目录，之后按阅读顺序
+    /// 依次输出各章节：每个章节前有一个`<a id="chapter-N">`锚点（N为先序遍历序号）和
+    /// 按目录深度确定层级的章节标题，章节内的标题相应降级。多个节点指向同一源文件时，
+    /// 文件内容只在第一个节点下输出。无法读取的章节会被跳过并记录警告。
+    /// 
+    /// 内部链接只保留链接文本，图片链接解析为EPUB内的完整路径，规则见[`html_to_markdown`](crate::epub::markdown::html_to_markdown)。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `filename` - 自定义文件名（不含扩展名），如果为None则使用书籍标题
+    /// 
+    /// # 返回值
+    /// * `Result<PathBuf, EpubError>` - 成功时返回生成的文件路径，失败时返回错误
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let path = toc_tree.generate_markdown_book(Some(Path::new("docs")), None)?;
+    ///     println!("Markdown文件已保存到: {:?}", path);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generate_markdown_book(&self, output_dir: Option<&Path>, filename: Option<&str>) -> Result<PathBuf> {
+        let dir = output_dir.unwrap_or_else(|| Path::new("."));
+        
+        if !dir.exists() {
+            fs::create_dir_all(dir).map_err(|e| {
+                EpubError::InvalidEpub(format!(
+                    "无法创建输出目录 '{}': {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+        }
+        
+        let safe_filename = if let Some(name) = filename {
+            name.to_string()
+        } else if let Some(ref title) = self.title {
+            Self::generate_safe_book_filename(title)
+        } else {
+            "book".to_string()
+        };
+        let file_path = dir.join(format!("{}.md", safe_filename));
+        let content = self.markdown_book_content(self.title.as_deref(), &self.roots);
+        
+        fs::write(&file_path, content).map_err(|e| {
+            EpubError::InvalidEpub(format!(
+                "无法写入Markdown文件 '{}': {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+        
+        Ok(file_path)
+    }
+
+    /// 生成带目录链接的Markdown内容，格式见`generate_markdown_book`
+    fn markdown_book_content(&self, title: Option<&str>, roots: &[TocTreeNode]) -> String {
+        let mut nodes = Vec::new();
+        Self::collect_nodes_in_order(roots, &mut nodes);
+        
+        let mut content = String::new();
+        if let Some(title) = title {
+            content.push_str(&format!("# {}\n\n", title));
+        }
+        content.push_str("## 目录\n\n");
+        for (index, node) in nodes.iter().enumerate() {
+            content.push_str(&format!(
+                "{}- [{}](#chapter-{})\n",
+                "  ".repeat(node.depth as usize),
+                node.title.replace('[', "\\[").replace(']', "\\]"),
+                index + 1
+            ));
+        }
+        
+        let mut exported = HashSet::new();
+        for (index, node) in nodes.iter().enumerate() {
+            let level = (node.depth as usize + 2).min(6);
+            content.push_str(&format!("\n<a id=\"chapter-{}\"></a>\n\n{} {}\n", index + 1, "#".repeat(level), node.title));
+            
+            if !exported.insert(node.content_key()) {
+                continue;
+            }
+            match node.get_markdown_content(self.epub, level as u8) {
+                Ok(markdown) if !markdown.is_empty() => {
+                    content.push('\n');
+                    content.push_str(&markdown);
+                    content.push('\n');
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.epub.warn(EpubWarning::new(format!("无法转换章节 '{}' ({}): {}", node.title, node.id, e), None));
+                }
+            }
+        }
+        
+        content
+    }
+
+    /// 将整个目录树及章节内容导出为一个JSON文件
+    /// 
+    /// 生成的JSON包含书籍标题和完整的章节树，每个章节带有标题、ID、源文件、
+    /// 播放顺序、正文和词数。
+    /// 
+    /// # 参数
+    /// * `output_dir` - 输出目录路径，如果为None则使用当前目录
+    /// * `use_formatted_text` - 是否使用格式化文本，false则使用纯文本
+    /// * `filename` - 自定义文件名（不含扩展名），如果为None则使用 `book`
+    /// 
+    /// # 返回值
+    /// * `Result<PathBuf, EpubError>` - 成功时返回生成的文件路径，失败时返回错误
+    /// 
+    /// # 使用示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     let path = toc_tree.generate_json_file(Some(Path::new("output")), true, None)?;
+    ///     println!("JSON文件已保存到: {:?}", path);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn generate_json_file(
+        &self,
+        output_dir: Option<&Path>,
+        use_formatted_text: bool,
+        filename: Option<&str>,
+    ) -> Result<PathBuf> {
+        // 确定输出目录
+        let dir = output_dir.unwrap_or_else(|| Path::new("."));
+        
+        // 创建输出目录（如果不存在）
+        if !dir.exists() {
+            fs::create_dir_all(dir).map_err(|e| {
+                EpubError::InvalidEpub(format!(
+                    "无法创建输出目录 '{}': {}",
+                    dir.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let file_path = dir.join(format!("{}.json", filename.unwrap_or("book")));
+
+        // 收集所有章节
+        let mut chapters = Vec::new();
+        for root in &self.roots {
+            chapters.push(root.to_chapter_json(self.epub, use_formatted_text, true)?);
+        }
+
+        let book_json = BookJson {
+            title: self.title.clone(),
+            total_chapters: self.get_statistics().total_nodes,
+            chapters,
+        };
+
+        let json_content = serde_json::to_string_pretty(&book_json).map_err(|e| {
+            EpubError::InternalError(format!("JSON序列化失败: {}", e))
+        })?;
+
+        // 写入文件
+        fs::write(&file_path, json_content).map_err(|e| {
+            EpubError::InvalidEpub(format!(
+                "无法写入JSON文件 '{}': {}",
+                file_path.display(),
+                e
+            ))
+        })?;
+
+        Ok(file_path)
+    }
+
+    /// 写入批量导出的JSON报告
+    /// 
+    /// 每条记录对应一个导出任务，字节数从导出文件读取，词数取自导出时写入的内容，
+    /// 不会重新读取章节。
+    #[cfg(feature = "serde")]
+    fn write_export_report(
+        &self,
+        jobs: &[ExportJob<'_>],
+        files: &[ExportedFile],
+        dir: &Path,
+        filename: &str,
+    ) -> Result<PathBuf> {
+        let report_path = dir.join(filename);
+        
+        let entries = jobs.iter().zip(files).map(|(job, file)| {
+            let mut errors: Vec<String> = file.error.iter().cloned().collect();
+            let exported = file.status != ExportStatus::Failed;
+            let bytes = exported.then(|| match fs::metadata(&file.path) {
+                Ok(meta) => Some(meta.len()),
+                Err(e) => {
+                    errors.push(format!("无法读取导出文件信息: {}", e));
+                    None
+                }
+            }).flatten();
+            ExportReportEntry {
+                id: job.node.id.clone(),
+                title: job.node.title.clone(),
+                src: job.node.src.clone(),
+                status: file.status,
+                output: exported.then(|| file.path.strip_prefix(dir).unwrap_or(&file.path).display().to_string()),
+                bytes,
+                word_count: file.word_count,
+                errors,
+            }
+        }).collect();
+        
+        let report = ExportReport {
+            title: self.title.clone(),
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            total_files: files.iter().filter(|file| file.status != ExportStatus::Failed).count(),
+            entries,
+        };
+        
+        let json_content = serde_json::to_string_pretty(&report).map_err(|e| {
+            EpubError::InternalError(format!("JSON序列化失败: {}", e))
+        })?;
+        
+        fs::write(&report_path, json_content).map_err(|e| {
+            EpubError::InvalidEpub(format!(
+                "无法写入导出报告 '{}': {}",
+                report_path.display(),
+                e
+            ))
+        })?;
+        
+        Ok(report_path)
+    }
+
     /// 生成安全的书籍文件名
     fn generate_safe_book_filename(title: &str) -> String {
         // 移除或替换不安全的字符
@@ -1680,10 +3400,14 @@ impl<'a> TocTree<'a> {
     }
 
     /// 创建合并文件内容
+    /// 
+    /// `part_title`不为None时表示只合并了某个根节点的子树，头部会注明部分标题，
+    /// 章节总数按实际合并的章节计算。
     fn create_merged_file_content(
         &self,
         chapter_contents: &[(String, String, String)],
         use_formatted_text: bool,
+        part_title: Option<&str>,
     ) -> Result<String> {
         let mut content = String::new();
         
@@ -1697,8 +3421,14 @@ impl<'a> TocTree<'a> {
             content.push_str(&format!("书籍标题: {}\n", title));
         }
         
-        let stats = self.get_statistics();
-        content.push_str(&format!("章节总数: {}\n", stats.total_nodes));
+        let chapter_count = match part_title {
+            Some(part_title) => {
+                content.push_str(&format!("部分标题: {}\n", part_title));
+                chapter_contents.len()
+            }
+            None => self.get_statistics().total_nodes,
+        };
+        content.push_str(&format!("章节总数: {}\n", chapter_count));
         content.push_str(&format!("文本格式: {}\n", if use_formatted_text { "格式化文本" } else { "纯文本" }));
         
         // 获取当前时间
@@ -1743,7 +3473,7 @@ impl<'a> TocTree<'a> {
         
         // 添加文件尾部
         content.push_str("═══════════════════════════════════════\n");
-        content.push_str("                全书结束\n");
+        content.push_str(if part_title.is_some() { "                本部分结束\n" } else { "                全书结束\n" });
         content.push_str("═══════════════════════════════════════\n");
         content.push_str("Generated by BookForge EPUB Reader\n");
         content.push_str("═══════════════════════════════════════\n");
@@ -1756,7 +3486,7 @@ impl<'a> TocTree<'a> {
         &self,
         file_paths: &[PathBuf],
         base_dir: &Path,
-        use_formatted_text: bool,
+        format: ExportFormat,
     ) -> Result<String> {
         let mut content = String::new();
         
@@ -1774,7 +3504,7 @@ impl<'a> TocTree<'a> {
         content.push_str(&format!("章节总数: {}\n", stats.total_nodes));
         content.push_str(&format!("根章节数: {}\n", stats.root_count));
         content.push_str(&format!("最大深度: {}\n", stats.max_depth));
-        content.push_str(&format!("文本格式: {}\n", if use_formatted_text { "格式化文本" } else { "纯文本" }));
+        content.push_str(&format!("文本格式: {}\n", format.label()));
         // 获取当前时间
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -1828,38 +3558,16 @@ impl<'a> TocTree<'a> {
     fn render_tree_for_index(&self) -> String {
         let mut result = String::new();
         
-        // 渲染根节点
+        // 渲染根节点（不显示文件路径，不受深度限制）
+        let format_node = |node: &TocTreeNode, _depth: u32| format!("[{}] {}", node.play_order, node.title);
         for (index, root) in self.roots.iter().enumerate() {
             let is_last = index == self.roots.len() - 1;
-            self.render_node_for_index(root, 0, is_last, "", &mut result);
+            Self::render_symbols(root, 0, is_last, "", None, &format_node, &mut result);
         }
         
         result
     }
 
-    /// 为索引文件渲染单个节点
-    fn render_node_for_index(
-        &self,
-        node: &TocTreeNode,
-        current_depth: u32,
-        is_last: bool,
-        prefix: &str,
-        result: &mut String,
-    ) {
-        let current_prefix = if is_last { "└── " } else { "├── " };
-        
-        // 格式化节点内容（不显示文件路径）
-        let content = format!("[{}] {}", node.play_order, node.title);
-        result.push_str(&format!("{}{}{}\n", prefix, current_prefix, content));
-
-        // 渲染子节点
-        let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
-        for (index, child) in node.children.iter().enumerate() {
-            let is_child_last = index == node.children.len() - 1;
-            self.render_node_for_index(child, current_depth + 1, is_child_last, &child_prefix, result);
-        }
-    }
-
     /// 收集所有节点信息
     fn collect_node_info_list(&self) -> Vec<NodeInfo> {
         let mut node_info_list = Vec::new();
@@ -1919,28 +3627,47 @@ impl<'a> TocTree<'a> {
         prefix: &str,
         result: &mut String,
     ) {
-        let current_prefix = if is_last { "└── " } else { "├── " };
-        
-        // 格式化节点内容
-        let content = if self.show_paths {
-            format!("[{}] {} → {}", node.play_order, node.title, node.src)
-        } else {
-            format!("[{}] {}", node.play_order, node.title)
+        let format_node = |node: &TocTreeNode, _depth: u32| {
+            if self.show_paths {
+                format!("[{}] {} → {}", node.play_order, node.title, node.src)
+            } else {
+                format!("[{}] {}", node.play_order, node.title)
+            }
         };
-        
-        result.push_str(&format!("{}{}{}\n", prefix, current_prefix, content));
+        Self::render_symbols(node, current_depth, is_last, prefix, self.max_depth, &format_node, result);
+    }
 
-        // 渲染子节点
-        if let Some(max_depth) = self.max_depth {
-            if current_depth + 1 >= max_depth {
-                return;
-            }
+    /// 使用树状符号（├── └──）递归渲染节点，节点文本由格式化函数生成
+    /// 
+    /// # 参数
+    /// * `node` - 要渲染的节点
+    /// * `current_depth` - 当前深度（根节点为0）
+    /// * `is_last` - 是否为同级最后一个节点
+    /// * `prefix` - 当前行的前缀
+    /// * `max_depth` - 最大渲染深度（None表示不限制）
+    /// * `format_node` - 节点文本格式化函数，参数为节点和当前深度
+    /// * `result` - 输出字符串
+    fn render_symbols(
+        node: &TocTreeNode,
+        current_depth: u32,
+        is_last: bool,
+        prefix: &str,
+        max_depth: Option<u32>,
+        format_node: &dyn Fn(&TocTreeNode, u32) -> String,
+        result: &mut String,
+    ) {
+        if max_depth.is_some_and(|max_depth| current_depth >= max_depth) {
+            return;
         }
 
+        let current_prefix = if is_last { "└── " } else { "├── " };
+        result.push_str(&format!("{}{}{}\n", prefix, current_prefix, format_node(node, current_depth)));
+
+        // 渲染子节点
         let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
         for (index, child) in node.children.iter().enumerate() {
             let is_child_last = index == node.children.len() - 1;
-            self.render_node(child, current_depth + 1, is_child_last, &child_prefix, result);
+            Self::render_symbols(child, current_depth + 1, is_child_last, &child_prefix, max_depth, format_node, result);
         }
     }
 
@@ -1997,6 +3724,22 @@ impl<'a> Display for TocTree<'a> {
     }
 }
 
+/// 平铺的目录条目（见[`TocTree::flatten`]）
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FlatTocEntry {
+    /// 标题
+    pub title: String,
+    /// 源文件路径
+    pub src: String,
+    /// 播放顺序
+    pub play_order: u32,
+    /// 在目录树中的深度（根节点为0）
+    pub depth: u32,
+    /// 是否有子节点
+    pub has_children: bool,
+}
+
 /// 目录树统计信息
 #[derive(Debug, Clone)]
 pub struct TocStatistics {
@@ -2033,35 +3776,225 @@ impl Display for TocStatistics {
 
 /// 从NCX创建目录树
 pub fn create_toc_tree_from_ncx<'a>(ncx: &Ncx, epub: &'a Epub) -> TocTree<'a> {
-    let mut toc_tree = TocTree::new(epub);
+    let mut toc_tree = TocTree::new_with_source(epub, TocTreeSource::Ncx);
     
     // 设置文档标题
     toc_tree.title = ncx.get_title().map(|t| t.clone());
     
     // 转换导航点为目录树节点
     for nav_point in &ncx.nav_map.nav_points {
-        let toc_node = convert_nav_point_to_toc_node(nav_point, 0);
+        let toc_node = convert_nav_point_to_toc_node(nav_point, 0, epub);
         toc_tree.add_root(toc_node);
     }
+    toc_tree.cache_resolved_paths();
     
     toc_tree
 }
 
 /// 递归转换导航点为目录树节点
-fn convert_nav_point_to_toc_node(nav_point: &NavPoint, depth: u32) -> TocTreeNode {
+fn convert_nav_point_to_toc_node(nav_point: &NavPoint, depth: u32, epub: &Epub) -> TocTreeNode {
     let mut toc_node = TocTreeNode::new(
         nav_point.play_order,
-        nav_point.nav_label.text.clone(),
+        epub.clean_title(&nav_point.nav_label.text),
         nav_point.content.src.clone(),
         nav_point.id.clone(),
         depth,
-    );
+    ).with_class(nav_point.class.clone());
     
     // 转换子节点
     for child in &nav_point.children {
-        let child_node = convert_nav_point_to_toc_node(child, depth + 1);
+        let child_node = convert_nav_point_to_toc_node(child, depth + 1, epub);
         toc_node.add_child(child_node);
     }
     
     toc_node
-} 
\ No newline at end of file
+}
+
+/// 从EPUB3 nav文档创建目录树
+/// 
+/// 使用`epub:type="toc"`的`<nav>`元素（不存在时使用第一个`<nav>`），
+/// 将其中嵌套的`<ol>/<li>`结构转换为目录树节点。节点的`src`保持nav文档中的
+/// 原始链接（相对于nav文档所在目录），播放顺序按文档顺序从1开始编号。
+/// 
+/// # 参数
+/// * `nav_html` - nav文档的内容
+/// * `epub` - EPUB阅读器引用
+/// 
+/// # 返回值
+/// * `TocTree` - 目录树（nav文档中没有目录时为空树）
+pub fn create_toc_tree_from_nav<'a>(nav_html: &str, epub: &'a Epub) -> TocTree<'a> {
+    let mut toc_tree = TocTree::new_with_source(epub, TocTreeSource::Nav);
+    
+    let document = Html::parse_document(nav_html);
+    let nav_selector = Selector::parse("nav").unwrap();
+    let navs: Vec<_> = document.select(&nav_selector).collect();
+    let toc_nav = navs.iter()
+        .find(|nav| {
+            nav.value().attr("epub:type")
+                .map(|t| t.split_whitespace().any(|v| v == "toc"))
+                .unwrap_or(false)
+        })
+        .or_else(|| navs.first());
+    
+    let Some(toc_nav) = toc_nav else {
+        return toc_tree;
+    };
+    
+    // 标题取nav中的第一个标题元素
+    let heading_selector = Selector::parse("h1, h2, h3, h4, h5, h6").unwrap();
+    toc_tree.title = toc_nav.select(&heading_selector)
+        .next()
+        .map(|heading| collapse_whitespace(&heading.text().collect::<String>()))
+        .filter(|title| !title.is_empty());
+    
+    let mut play_order = 0;
+    if let Some(list) = child_elements(*toc_nav, "ol").next() {
+        for node in convert_nav_list_to_toc_nodes(list, 0, &mut play_order, epub) {
+            toc_tree.add_root(node);
+        }
+    }
+    toc_tree.cache_resolved_paths();
+    
+    toc_tree
+}
+
+/// 从EPUB3 nav文档解析页码列表
+/// 
+/// 使用`epub:type="page-list"`的`<nav>`元素，按文档顺序将其中每个带链接的`<li>`
+/// 转换为页面目标：页码值和标签均取链接文本，`content.src`为解析后的EPUB内完整路径
+/// （保留锚点），播放顺序从1开始编号，页面类型统一为`normal`。
+/// 
+/// # 参数
+/// * `nav_html` - nav文档的内容
+/// * `nav_dir` - nav文档所在目录（相对于EPUB根目录）
+/// 
+/// # 返回值
+/// * `Option<PageList>` - 页码列表，nav文档中没有页码列表或列表为空时返回None
+pub fn create_page_list_from_nav(nav_html: &str, nav_dir: &str) -> Option<PageList> {
+    let document = Html::parse_document(nav_html);
+    let nav_selector = Selector::parse("nav").unwrap();
+    let page_nav = document.select(&nav_selector).find(|nav| {
+        nav.value().attr("epub:type")
+            .is_some_and(|t| t.split_whitespace().any(|v| v == "page-list"))
+    })?;
+    
+    let mut page_list = PageList::new();
+    let heading_selector = Selector::parse("h1, h2, h3, h4, h5, h6").unwrap();
+    page_list.nav_label = page_nav.select(&heading_selector)
+        .next()
+        .map(|heading| collapse_whitespace(&heading.text().collect::<String>()))
+        .filter(|label| !label.is_empty())
+        .map(NavLabel::new);
+    
+    let link_selector = Selector::parse("li > a[href]").unwrap();
+    for link in page_nav.select(&link_selector) {
+        let href = link.value().attr("href").unwrap_or_default().trim();
+        let value = collapse_whitespace(&link.text().collect::<String>());
+        if href.is_empty() || value.is_empty() {
+            continue;
+        }
+        
+        let play_order = page_list.page_targets.len() as u32 + 1;
+        let src = match href.split_once('#') {
+            Some((_, fragment)) => format!("{}#{}", resolve_href(nav_dir, href), fragment),
+            None => resolve_href(nav_dir, href),
+        };
+        let id = link.value().attr("id")
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| format!("page-{}", play_order));
+        page_list.add_page_target(PageTarget::new(
+            id,
+            "normal".to_string(),
+            value.clone(),
+            play_order,
+            NavLabel::new(value),
+            NavContent::new(src),
+        ));
+    }
+    
+    (!page_list.page_targets.is_empty()).then_some(page_list)
+}
+
+/// 递归转换nav文档中的`<ol>`列表为目录树节点
+fn convert_nav_list_to_toc_nodes(
+    list: scraper::ElementRef,
+    depth: u32,
+    play_order: &mut u32,
+    epub: &Epub,
+) -> Vec<TocTreeNode> {
+    let mut nodes = Vec::new();
+    
+    for item in child_elements(list, "li") {
+        let label = child_elements(item, "a")
+            .next()
+            .or_else(|| child_elements(item, "span").next());
+        
+        *play_order += 1;
+        let title = label
+            .map(|label| epub.clean_title(&collapse_whitespace(&label.text().collect::<String>())))
+            .unwrap_or_default();
+        let src = label
+            .and_then(|label| label.value().attr("href"))
+            .unwrap_or_default()
+            .to_string();
+        let id = item.value().attr("id")
+            .or_else(|| label.and_then(|label| label.value().attr("id")))
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| format!("nav-{}", play_order));
+        
+        let class = item.value().attr("class").map(|class| class.to_string());
+        
+        let mut node = TocTreeNode::new(*play_order, title, src, id, depth).with_class(class);
+        if let Some(sub_list) = child_elements(item, "ol").next() {
+            for child in convert_nav_list_to_toc_nodes(sub_list, depth + 1, play_order, epub) {
+                node.add_child(child);
+            }
+        }
+        nodes.push(node);
+    }
+    
+    nodes
+}
+
+/// 获取元素中指定标签名的直接子元素
+fn child_elements<'b>(element: scraper::ElementRef<'b>, name: &'b str) -> impl Iterator<Item = scraper::ElementRef<'b>> {
+    element.children()
+        .filter_map(scraper::ElementRef::wrap)
+        .filter(move |child| child.value().name() == name)
+}
+
+/// 解码路径中的百分号转义（如`%20`、`%E7%AC%AC`）
+///
+/// 不完整或无效的转义保持原样；解码结果不是有效的UTF-8时返回原字符串。
+fn percent_decode(text: &str) -> String {
+    if !text.contains('%') {
+        return text.to_string();
+    }
+    
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| text.to_string())
+}
+
+/// 合并连续空白字符
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,138 @@
+//! 目录序列化模块
+//!
+//! 将目录树写回为NCX文件或EPUB3 nav文档。节点的ID、样式类以及`src`中的锚点
+//! 会原样保留，保证“解析 → 序列化 → 解析”得到结构相同的目录树。
+
+use quick_xml::escape::escape;
+
+use crate::epub::ncx::{TocTree, TocTreeNode};
+
+impl TocTree<'_> {
+    /// 将目录树序列化为NCX文件内容
+    ///
+    /// # 参数
+    /// * `uid` - 书籍唯一标识符（写入`dtb:uid`，应与OPF中的标识符一致）
+    ///
+    /// # 返回值
+    /// * `String` - NCX文件的XML内容
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    ///
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     std::fs::write("toc.ncx", toc_tree.to_ncx_xml("urn:uuid:1234"))?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_ncx_xml(&self, uid: &str) -> String {
+        let depth = if self.roots.is_empty() {
+            0
+        } else {
+            self.get_statistics().max_depth + 1
+        };
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<ncx xmlns=\"http://www.daisy.org/z3986/2005/ncx/\" version=\"2005-1\">\n");
+        xml.push_str("  <head>\n");
+        xml.push_str(&format!("    <meta name=\"dtb:uid\" content=\"{}\"/>\n", escape(uid)));
+        xml.push_str(&format!("    <meta name=\"dtb:depth\" content=\"{}\"/>\n", depth));
+        xml.push_str("    <meta name=\"dtb:totalPageCount\" content=\"0\"/>\n");
+        xml.push_str("    <meta name=\"dtb:maxPageNumber\" content=\"0\"/>\n");
+        xml.push_str("  </head>\n");
+        xml.push_str(&format!(
+            "  <docTitle>\n    <text>{}</text>\n  </docTitle>\n",
+            escape(self.title.as_deref().unwrap_or_default())
+        ));
+        xml.push_str("  <navMap>\n");
+        for root in &self.roots {
+            write_nav_point(root, 2, &mut xml);
+        }
+        xml.push_str("  </navMap>\n");
+        xml.push_str("</ncx>\n");
+        xml
+    }
+
+    /// 将目录树序列化为EPUB3 nav文档内容
+    ///
+    /// 生成包含`epub:type="toc"`目录的XHTML文档。节点ID和样式类写在`<li>`上，
+    /// 没有链接的节点使用`<span>`作为标签。
+    ///
+    /// # 返回值
+    /// * `String` - nav文档的XHTML内容
+    pub fn to_nav_xhtml(&self) -> String {
+        let title = escape(self.title.as_deref().unwrap_or("目录")).into_owned();
+
+        let mut xhtml = String::new();
+        xhtml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xhtml.push_str("<!DOCTYPE html>\n");
+        xhtml.push_str(
+            "<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n",
+        );
+        xhtml.push_str(&format!("<head>\n  <title>{}</title>\n</head>\n", title));
+        xhtml.push_str("<body>\n");
+        xhtml.push_str("  <nav epub:type=\"toc\" id=\"toc\">\n");
+        xhtml.push_str(&format!("    <h1>{}</h1>\n", title));
+        if !self.roots.is_empty() {
+            write_nav_list(&self.roots, 2, &mut xhtml);
+        }
+        xhtml.push_str("  </nav>\n");
+        xhtml.push_str("</body>\n");
+        xhtml.push_str("</html>\n");
+        xhtml
+    }
+}
+
+/// 写入单个navPoint及其子节点
+fn write_nav_point(node: &TocTreeNode, indent: usize, xml: &mut String) {
+    let pad = "  ".repeat(indent);
+    xml.push_str(&format!(
+        "{}<navPoint id=\"{}\" playOrder=\"{}\"{}>\n",
+        pad,
+        escape(&node.id),
+        node.play_order,
+        class_attribute(node)
+    ));
+    xml.push_str(&format!(
+        "{}  <navLabel>\n{}    <text>{}</text>\n{}  </navLabel>\n",
+        pad, pad, escape(&node.title), pad
+    ));
+    xml.push_str(&format!("{}  <content src=\"{}\"/>\n", pad, escape(&node.src)));
+    for child in &node.children {
+        write_nav_point(child, indent + 1, xml);
+    }
+    xml.push_str(&format!("{}</navPoint>\n", pad));
+}
+
+/// 写入nav文档中的`<ol>`列表
+fn write_nav_list(nodes: &[TocTreeNode], indent: usize, xhtml: &mut String) {
+    let pad = "  ".repeat(indent);
+    xhtml.push_str(&format!("{}<ol>\n", pad));
+    for node in nodes {
+        xhtml.push_str(&format!("{}  <li id=\"{}\"{}>", pad, escape(&node.id), class_attribute(node)));
+        if node.src.is_empty() {
+            xhtml.push_str(&format!("<span>{}</span>", escape(&node.title)));
+        } else {
+            xhtml.push_str(&format!("<a href=\"{}\">{}</a>", escape(&node.src), escape(&node.title)));
+        }
+        if node.children.is_empty() {
+            xhtml.push_str("</li>\n");
+        } else {
+            xhtml.push('\n');
+            write_nav_list(&node.children, indent + 2, xhtml);
+            xhtml.push_str(&format!("{}  </li>\n", pad));
+        }
+    }
+    xhtml.push_str(&format!("{}</ol>\n", pad));
+}
+
+/// 生成节点的`class`属性（没有样式类时为空）
+fn class_attribute(node: &TocTreeNode) -> String {
+    match &node.class {
+        Some(class) => format!(" class=\"{}\"", escape(class)),
+        None => String::new(),
+    }
+}
@@ -5,9 +5,10 @@
 use crate::epub::error::{EpubError, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
 
 /// 默认配置文件路径
-const DEFAULT_CONFIG_PATH: &str = "metadata.yaml";
+pub(crate) const DEFAULT_CONFIG_PATH: &str = "metadata.yaml";
 
 /// 单个元数据类型的标签配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,13 +84,64 @@ impl MetadataTagConfigs {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn from_file() -> Result<Self> {
-        let content = fs::read_to_string(DEFAULT_CONFIG_PATH)
-            .map_err(|e| EpubError::ConfigError(format!("无法读取配置文件: {}", e)))?;
+        Self::from_path(Path::new(DEFAULT_CONFIG_PATH))
+    }
+
+    /// 从指定的配置文件中加载元数据标签配置
+    /// 
+    /// # 参数
+    /// 
+    /// * `path` - YAML配置文件路径
+    /// 
+    /// # 返回值
+    /// 
+    /// * `Result<Self>` - 加载成功返回配置实例，失败返回错误
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use bookforge::epub::opf::MetadataTagConfigs;
+    /// let config = MetadataTagConfigs::from_path(Path::new("config/metadata.yaml"))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| EpubError::ConfigError(format!("无法读取配置文件 {}: {}", path.display(), e)))?;
         
         serde_yml::from_str(&content)
             .map_err(|e| EpubError::ConfigError(format!("配置文件格式错误: {}", e)))
     }
 
+    /// 从指定的配置文件中加载元数据标签配置，文件不存在时使用默认配置
+    /// 
+    /// 与`from_path`不同，配置文件缺失不视为错误；但文件存在却无法读取或格式错误时仍然返回错误，
+    /// 不会静默回退到默认配置。
+    /// 
+    /// # 参数
+    /// 
+    /// * `path` - YAML配置文件路径
+    /// 
+    /// # 返回值
+    /// 
+    /// * `Result<Self>` - 配置实例
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use bookforge::epub::opf::MetadataTagConfigs;
+    /// let config = MetadataTagConfigs::from_path_or_default(Path::new("metadata.yaml"))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_path_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::from_path(path)
+        } else {
+            Ok(Self::default_config())
+        }
+    }
+
     /// 生成默认配置文件到当前目录
     /// 
     /// 配置文件将生成为当前目录下的 `metadata.yaml`
@@ -106,6 +158,19 @@ impl MetadataTagConfigs {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn generate_default_config() -> Result<()> {
+        Self::write_default_config(Path::new(DEFAULT_CONFIG_PATH))
+    }
+
+    /// 将默认配置写入指定路径
+    /// 
+    /// # 参数
+    /// 
+    /// * `path` - 要生成的YAML配置文件路径
+    /// 
+    /// # 返回值
+    /// 
+    /// * `Result<()>` - 生成成功返回Ok，失败返回错误
+    pub fn write_default_config(path: &Path) -> Result<()> {
         let default_config = Self::default_config();
         let yaml_content = serde_yml::to_string(&default_config)
             .map_err(|e| EpubError::ConfigError(format!("序列化配置失败: {}", e)))?;
@@ -116,7 +181,7 @@ impl MetadataTagConfigs {
             yaml_content
         );
         
-        fs::write(DEFAULT_CONFIG_PATH, content_with_header)
+        fs::write(path, content_with_header)
             .map_err(|e| EpubError::ConfigError(format!("写入配置文件失败: {}", e)))?;
         
         Ok(())
@@ -180,9 +245,10 @@ impl MetadataTagConfigs {
         }
     }
 
-    /// 尝试从默认配置文件加载，如果文件不存在则先生成配置文件再加载
+    /// 创建使用内置默认标签的配置
     /// 
-    /// 配置文件为当前目录下的 `metadata.yaml`
+    /// 等同于`default_config()`，不会读取或写入任何文件。需要从配置文件加载时请使用
+    /// `from_path()`或`from_path_or_default()`。
     /// 
     /// # 返回值
     /// 
@@ -195,14 +261,6 @@ impl MetadataTagConfigs {
     /// let config = MetadataTagConfigs::new();
     /// ```
     pub fn new() -> Self {
-        // 首先尝试从文件加载
-        match Self::from_file() {
-            Ok(config) => config,
-            Err(_) => {
-                // 如果文件不存在，先生成默认配置文件
-                let _ = Self::generate_default_config();
-                Self::default_config()
-            }
-        }
+        Self::default_config()
     }
 } 
\ No newline at end of file
@@ -0,0 +1,26 @@
+//! 导览模块
+//! 
+//! 提供EPUB2包中`<guide>`元素（封面、目录、正文起点等结构性位置）的结构定义。
+
+/// `<guide>`中的单个`<reference>`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GuideReference {
+    /// 引用类型（如`cover`、`toc`、`text`、`title-page`）
+    pub reference_type: String,
+    /// 目标文件路径（相对于OPF文件，可能带锚点）
+    pub href: String,
+    /// 标题
+    pub title: Option<String>,
+}
+
+impl GuideReference {
+    /// 创建新的导览引用
+    pub fn new(reference_type: String, href: String, title: Option<String>) -> Self {
+        Self {
+            reference_type,
+            href,
+            title,
+        }
+    }
+}
@@ -4,6 +4,7 @@
 
 /// 清单项信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ManifestItem {
     /// 项目ID
     pub id: String,
@@ -69,4 +70,98 @@ impl ManifestItem {
     pub fn is_xhtml(&self) -> bool {
         self.media_type == "application/xhtml+xml"
     }
+
+    /// 检查是否为字体文件（包括EPUB 3.0中使用的旧式字体媒体类型）
+    pub fn is_font(&self) -> bool {
+        self.media_type.starts_with("font/")
+            || matches!(
+                self.media_type.as_str(),
+                "application/font-woff"
+                    | "application/font-sfnt"
+                    | "application/vnd.ms-opentype"
+                    | "application/x-font-ttf"
+                    | "application/x-font-otf"
+                    | "application/x-font-truetype"
+                    | "application/x-font-opentype"
+            )
+    }
+
+    /// 检查是否为音频文件
+    pub fn is_audio(&self) -> bool {
+        self.media_type.starts_with("audio/")
+    }
+
+    /// 检查是否为视频文件
+    pub fn is_video(&self) -> bool {
+        self.media_type.starts_with("video/")
+    }
+}
+
+/// 按媒体类型分组的清单项
+///
+/// 每组内按`href`排序。
+#[derive(Debug, Clone, Default)]
+pub struct ManifestCategories<'a> {
+    /// XHTML内容文档
+    pub documents: Vec<&'a ManifestItem>,
+    /// CSS样式表
+    pub stylesheets: Vec<&'a ManifestItem>,
+    /// 图片
+    pub images: Vec<&'a ManifestItem>,
+    /// 字体
+    pub fonts: Vec<&'a ManifestItem>,
+    /// 音频
+    pub audio: Vec<&'a ManifestItem>,
+    /// 视频
+    pub video: Vec<&'a ManifestItem>,
+    /// 其他（如NCX、JavaScript）
+    pub other: Vec<&'a ManifestItem>,
+}
+
+impl<'a> ManifestCategories<'a> {
+    /// 将清单项归入对应的分组
+    pub(crate) fn from_items<I: IntoIterator<Item = &'a ManifestItem>>(items: I) -> Self {
+        let mut categories = Self::default();
+        for item in items {
+            let group = if item.is_xhtml() {
+                &mut categories.documents
+            } else if item.is_css() {
+                &mut categories.stylesheets
+            } else if item.is_image() {
+                &mut categories.images
+            } else if item.is_font() {
+                &mut categories.fonts
+            } else if item.is_audio() {
+                &mut categories.audio
+            } else if item.is_video() {
+                &mut categories.video
+            } else {
+                &mut categories.other
+            };
+            group.push(item);
+        }
+        for group in [
+            &mut categories.documents,
+            &mut categories.stylesheets,
+            &mut categories.images,
+            &mut categories.fonts,
+            &mut categories.audio,
+            &mut categories.video,
+            &mut categories.other,
+        ] {
+            group.sort_by(|a, b| a.href.cmp(&b.href));
+        }
+        categories
+    }
+
+    /// 清单项总数
+    pub fn total(&self) -> usize {
+        self.documents.len()
+            + self.stylesheets.len()
+            + self.images.len()
+            + self.fonts.len()
+            + self.audio.len()
+            + self.video.len()
+            + self.other.len()
+    }
 } 
\ No newline at end of file
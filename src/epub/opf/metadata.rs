@@ -3,6 +3,7 @@
 //! 提供EPUB元数据的结构定义和处理功能。
 
 use crate::epub::opf::config::MetadataTagConfigs;
+use quick_xml::escape::escape;
 use std::collections::HashMap;
 
 /// 元数据值枚举，表示不同类型的元数据
@@ -60,6 +61,7 @@ pub struct Creator {
 
 /// 标识符信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier {
     /// 标识符值
     pub value: String,
@@ -69,6 +71,54 @@ pub struct Identifier {
     pub id: Option<String>,
 }
 
+/// 丛书/合集信息
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeriesInfo {
+    /// 丛书名称
+    pub name: String,
+    /// 合集类型（`series`或`set`），未声明时为`None`
+    pub collection_type: Option<String>,
+    /// 在丛书中的位置（如`1`、`2.5`）
+    pub position: Option<f64>,
+}
+
+/// 元数据完整性报告
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetadataCompleteness {
+    /// 已存在的字段
+    pub present: Vec<&'static str>,
+    /// 缺失的字段
+    pub missing: Vec<&'static str>,
+}
+
+impl MetadataCompleteness {
+    /// EPUB规范要求必须存在的字段
+    pub const REQUIRED_FIELDS: [&'static str; 3] = ["title", "identifier", "language"];
+
+    /// 检查是否所有字段都存在
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// 获取缺失的必需字段
+    pub fn missing_required(&self) -> Vec<&'static str> {
+        self.missing.iter()
+            .copied()
+            .filter(|field| Self::REQUIRED_FIELDS.contains(field))
+            .collect()
+    }
+
+    /// 获取完整度（已存在字段占全部字段的比例，0.0-1.0）
+    pub fn ratio(&self) -> f64 {
+        let total = self.present.len() + self.missing.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.present.len() as f64 / total as f64
+    }
+}
+
 /// OPF文件中的元数据信息
 #[derive(Debug, Clone)]
 pub struct Metadata {
@@ -76,6 +126,8 @@ pub struct Metadata {
     raw_metadata: HashMap<String, Vec<MetadataValue>>,
     /// 关联元数据映射：key为被精化的元素ID，value为精化信息列表
     refines_metadata: HashMap<String, Vec<MetaValue>>,
+    /// property meta的元素ID：key为(property, 在原始元数据列表中的位置)，用于关联refines元数据
+    property_ids: HashMap<(String, usize), String>,
     /// 元数据标签配置，用于查找对应的元数据
     tag_configs: MetadataTagConfigs,
 }
@@ -83,10 +135,16 @@ pub struct Metadata {
 impl Metadata {
     /// 创建新的元数据实例
     pub fn new() -> Self {
+        Self::with_tag_configs(MetadataTagConfigs::new())
+    }
+
+    /// 使用指定的标签配置创建元数据实例
+    pub fn with_tag_configs(tag_configs: MetadataTagConfigs) -> Self {
         Self {
             raw_metadata: HashMap::new(),
             refines_metadata: HashMap::new(),
-            tag_configs: MetadataTagConfigs::new(),
+            property_ids: HashMap::new(),
+            tag_configs,
         }
     }
 
@@ -110,11 +168,19 @@ impl Metadata {
 
     /// 添加基于property的meta元数据
     pub fn add_meta_property_based(&mut self, property: String, content: String) {
+        self.add_meta_property_based_with_id(property, content, None);
+    }
+
+    /// 添加带id的基于property的meta元数据，id用于关联精化它的refines元数据
+    pub fn add_meta_property_based_with_id(&mut self, property: String, content: String, id: Option<String>) {
         let metadata_value = MetadataValue::Meta(MetaValue::PropertyBased { content });
-        self.raw_metadata
-            .entry(property)
-            .or_insert_with(Vec::new)
-            .push(metadata_value);
+        let values = self.raw_metadata
+            .entry(property.clone())
+            .or_insert_with(Vec::new);
+        values.push(metadata_value);
+        if let Some(id) = id {
+            self.property_ids.insert((property, values.len() - 1), id);
+        }
     }
 
     /// 添加基于refines的meta元数据
@@ -162,10 +228,118 @@ impl Metadata {
         result
     }
 
+    /// 根据标签列表查找所有元数据值及其元素ID
+    fn find_all_with_ids_by_tags(&self, tags: &[String]) -> Vec<(&MetadataValue, Option<&str>)> {
+        let mut result = Vec::new();
+        for tag in tags {
+            if let Some(values) = self.raw_metadata.get(tag) {
+                result.extend(values.iter().enumerate().map(|(index, value)| (value, self.element_id(tag, index, value))));
+            }
+        }
+        result
+    }
+
+    /// 获取元数据值的元素ID（Dublin Core元素的`id`属性或property meta的`id`属性）
+    fn element_id<'a>(&'a self, tag: &str, index: usize, value: &'a MetadataValue) -> Option<&'a str> {
+        match value {
+            MetadataValue::DublinCore { attributes, .. } => attributes.get("id").map(String::as_str),
+            MetadataValue::Meta(MetaValue::PropertyBased { .. }) => {
+                self.property_ids.get(&(tag.to_string(), index)).map(String::as_str)
+            }
+            MetadataValue::Meta(_) => None,
+        }
+    }
+
     /// 获取标题
+    ///
+    /// 存在多个标题时，优先返回通过`title-type`精化为`main`的标题。
     pub fn title(&self) -> Option<String> {
-        self.find_by_tags(&self.tag_configs.title.tags)
-            .and_then(|v| self.extract_content(v))
+        let titles = self.find_all_with_ids_by_tags(&self.tag_configs.title.tags);
+        titles.iter()
+            .find(|(_, id)| self.refined_property(*id, "title-type").as_deref() == Some("main"))
+            .or_else(|| titles.first())
+            .and_then(|(value, _)| self.extract_content(value))
+    }
+
+    /// 获取用于排序的标题
+    ///
+    /// 依次查找标题的`file-as`精化（EPUB3）、`opf:file-as`属性（EPUB2）以及
+    /// Calibre的`<meta name="calibre:title_sort">`。
+    pub fn sort_title(&self) -> Option<String> {
+        let titles = self.find_all_with_ids_by_tags(&self.tag_configs.title.tags);
+        titles.iter()
+            .find_map(|(value, id)| {
+                self.refined_property(*id, "file-as").or_else(|| match value {
+                    MetadataValue::DublinCore { attributes, .. } => attributes.get("file-as").cloned(),
+                    MetadataValue::Meta(_) => None,
+                })
+            })
+            .or_else(|| {
+                self.raw_metadata.get("calibre:title_sort")
+                    .and_then(|values| values.first())
+                    .and_then(|value| self.extract_content(value))
+            })
+            .filter(|title| !title.trim().is_empty())
+    }
+
+    /// 获取所属丛书/合集信息
+    ///
+    /// 优先解析EPUB3的`belongs-to-collection` meta及其`collection-type`、`group-position`
+    /// 精化；存在多个合集时优先返回类型为`series`的合集。未声明时回退到Calibre的
+    /// `<meta name="calibre:series">`和`<meta name="calibre:series_index">`。
+    ///
+    /// # 返回值
+    /// * `Option<SeriesInfo>` - 丛书信息，未声明时为`None`
+    pub fn series(&self) -> Option<SeriesInfo> {
+        let mut collections: Vec<SeriesInfo> = self.raw_metadata.get("belongs-to-collection")
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                let name = self.extract_content(value)?.trim().to_string();
+                if name.is_empty() {
+                    return None;
+                }
+                let id = self.element_id("belongs-to-collection", index, value);
+                Some(SeriesInfo {
+                    name,
+                    collection_type: self.refined_property(id, "collection-type"),
+                    position: self.refined_property(id, "group-position")
+                        .and_then(|position| position.trim().parse().ok()),
+                })
+            })
+            .collect();
+        if !collections.is_empty() {
+            let index = collections.iter()
+                .position(|series| series.collection_type.as_deref() == Some("series"))
+                .unwrap_or(0);
+            return Some(collections.swap_remove(index));
+        }
+
+        let calibre_meta = |key: &str| {
+            self.raw_metadata.get(key)
+                .and_then(|values| values.first())
+                .and_then(|value| self.extract_content(value))
+                .map(|content| content.trim().to_string())
+                .filter(|content| !content.is_empty())
+        };
+        calibre_meta("calibre:series").map(|name| SeriesInfo {
+            name,
+            collection_type: Some("series".to_string()),
+            position: calibre_meta("calibre:series_index").and_then(|index| index.parse().ok()),
+        })
+    }
+
+    /// 查找精化指定元素的refines元数据中某个property的值
+    fn refined_property(&self, id: Option<&str>, property: &str) -> Option<String> {
+        self.refines_metadata.get(id?)?
+            .iter()
+            .find_map(|refines| match refines {
+                MetaValue::RefinesBased { property: refined, content, .. } if refined == property => {
+                    Some(content.trim().to_string())
+                }
+                _ => None,
+            })
     }
 
     /// 获取所有创建者
@@ -205,9 +379,31 @@ impl Metadata {
     }
 
     /// 获取出版日期
+    ///
+    /// 存在多个日期时，优先返回`opf:event="publication"`的日期，否则返回第一个。
     pub fn date(&self) -> Option<String> {
-        self.find_by_tags(&self.tag_configs.date.tags)
-            .and_then(|v| self.extract_content(v))
+        let dates = self.dates();
+        dates.iter()
+            .find(|(event, _)| event.as_deref() == Some("publication"))
+            .or(dates.first())
+            .map(|(_, value)| value.clone())
+    }
+
+    /// 获取所有日期及其事件类型
+    ///
+    /// 事件类型来自EPUB2的`opf:event`属性（如`publication`、`modification`、`creation`），
+    /// 没有该属性的日期事件类型为None。
+    pub fn dates(&self) -> Vec<(Option<String>, String)> {
+        self.find_all_by_tags(&self.tag_configs.date.tags)
+            .into_iter()
+            .filter_map(|v| {
+                let event = match v {
+                    MetadataValue::DublinCore { attributes, .. } => attributes.get("event").cloned(),
+                    MetadataValue::Meta(_) => None,
+                };
+                self.extract_content(v).map(|value| (event, value))
+            })
+            .collect()
     }
 
     /// 获取描述
@@ -242,6 +438,80 @@ impl Metadata {
             .and_then(|v| self.extract_content(v))
     }
 
+    /// 获取生成该EPUB的工具
+    ///
+    /// 依次查找`<meta name="generator">`、property以`generator`结尾的meta
+    /// （如`<meta property="ibooks:generator">`），以及角色为`bkp`（图书制作者）的
+    /// `dc:contributor`。
+    pub fn generator(&self) -> Option<String> {
+        let mut generator_keys: Vec<&String> = self.raw_metadata.keys()
+            .filter(|key| key.as_str() == "generator" || key.ends_with(":generator"))
+            .collect();
+        generator_keys.sort_by_key(|key| (key.as_str() != "generator", key.as_str()));
+
+        generator_keys.into_iter()
+            .flat_map(|key| &self.raw_metadata[key])
+            .filter(|value| matches!(value, MetadataValue::Meta(_)))
+            .filter_map(|value| self.extract_content(value))
+            .find(|content| !content.trim().is_empty())
+            .or_else(|| {
+                self.contributors().into_iter()
+                    .find(|contributor| contributor.role.as_deref() == Some("bkp"))
+                    .map(|contributor| contributor.name)
+            })
+    }
+
+    /// 检查元数据的完整性
+    /// 
+    /// 检查的字段包括：title、creator、language、identifier、publisher、date、description。
+    /// 
+    /// # 返回值
+    /// * `MetadataCompleteness` - 各字段存在与否的报告
+    pub fn completeness(&self) -> MetadataCompleteness {
+        let is_present = |value: Option<String>| value.is_some_and(|v| !v.trim().is_empty());
+        let checks = [
+            ("title", is_present(self.title())),
+            ("creator", !self.creators().is_empty()),
+            ("language", is_present(self.language())),
+            ("identifier", !self.identifiers().is_empty()),
+            ("publisher", is_present(self.publisher())),
+            ("date", is_present(self.date())),
+            ("description", is_present(self.description())),
+        ];
+
+        let mut completeness = MetadataCompleteness {
+            present: Vec::new(),
+            missing: Vec::new(),
+        };
+        for (field, present) in checks {
+            if present {
+                completeness.present.push(field);
+            } else {
+                completeness.missing.push(field);
+            }
+        }
+        completeness
+    }
+
+    /// 检查是否声明为固定版式
+    /// 
+    /// 识别EPUB3的`rendition:layout`为`pre-paginated`，以及Kindle/iBooks使用的
+    /// `<meta name="fixed-layout" content="true"/>`。
+    pub fn is_fixed_layout(&self) -> bool {
+        let has_meta = |key: &str, expected: &str| {
+            self.raw_metadata.get(key).is_some_and(|values| {
+                values.iter().any(|value| match value {
+                    MetadataValue::Meta(MetaValue::PropertyBased { content })
+                    | MetadataValue::Meta(MetaValue::NameBased { content }) => {
+                        content.trim().eq_ignore_ascii_case(expected)
+                    }
+                    _ => false,
+                })
+            })
+        };
+        has_meta("rendition:layout", "pre-paginated") || has_meta("fixed-layout", "true")
+    }
+
     /// 获取其他元数据
     pub fn other(&self) -> HashMap<String, String> {
         let mut other = HashMap::new();
@@ -377,6 +647,49 @@ impl Metadata {
         self.raw_metadata.get(tag)
     }
 
+    /// 根据存储的值和属性重建指定元素的XML片段
+    ///
+    /// 用于排查元数据解析异常：Dublin Core元素按`<dc:tag ...>值</dc:tag>`重建，
+    /// meta元素按name或property形式重建，带`id`的元素会附带精化它的refines meta。
+    /// 属性按名称排序输出；解析时属性只保留本地名，因此`opf:role`会显示为`role`。
+    ///
+    /// # 参数
+    /// * `tag` - 元素名（如"creator"、"dc:creator"、"cover"、"dcterms:modified"）
+    ///
+    /// # 返回值
+    /// * `Vec<String>` - 每个同名元素对应一个XML片段，不存在时为空
+    pub fn raw_xml_snippets(&self, tag: &str) -> Vec<String> {
+        let values = self.raw_metadata.get(tag)
+            .or_else(|| tag.strip_prefix("dc:").and_then(|local| self.raw_metadata.get(local)));
+        let Some(values) = values else {
+            return Vec::new();
+        };
+        let local_tag = tag.strip_prefix("dc:").unwrap_or(tag);
+
+        values.iter()
+            .map(|value| match value {
+                MetadataValue::DublinCore { value, attributes } => {
+                    let mut keys: Vec<&String> = attributes.keys().collect();
+                    keys.sort();
+                    let mut snippet = format!("<dc:{}", local_tag);
+                    for key in keys {
+                        snippet.push_str(&format!(" {}=\"{}\"", key, escape(&attributes[key])));
+                    }
+                    snippet.push_str(&format!(">{}</dc:{}>", escape(value), local_tag));
+
+                    if let Some(refines) = attributes.get("id").and_then(|id| self.refines_metadata.get(id)) {
+                        for meta in refines {
+                            snippet.push('\n');
+                            snippet.push_str(&meta_snippet(tag, meta));
+                        }
+                    }
+                    snippet
+                }
+                MetadataValue::Meta(meta) => meta_snippet(tag, meta),
+            })
+            .collect()
+    }
+
     /// 获取所有Dublin Core元数据
     pub fn get_dublin_core_metadata(&self) -> Vec<(String, String, HashMap<String, String>)> {
         let mut result = Vec::new();
@@ -449,4 +762,25 @@ impl Metadata {
 
         (dublin_core_count, name_based_count, property_based_count, refines_based_count)
     }
-} 
\ No newline at end of file
+}
+
+/// 重建单个meta元素的XML片段
+fn meta_snippet(tag: &str, meta: &MetaValue) -> String {
+    match meta {
+        MetaValue::NameBased { content } => {
+            format!("<meta name=\"{}\" content=\"{}\"/>", escape(tag), escape(content))
+        }
+        MetaValue::PropertyBased { content } => {
+            format!("<meta property=\"{}\">{}</meta>", escape(tag), escape(content))
+        }
+        MetaValue::RefinesBased { refines_id, property, content, scheme } => {
+            let scheme = scheme.as_ref()
+                .map(|scheme| format!(" scheme=\"{}\"", escape(scheme)))
+                .unwrap_or_default();
+            format!(
+                "<meta refines=\"#{}\" property=\"{}\"{}>{}</meta>",
+                escape(refines_id), escape(property), scheme, escape(content)
+            )
+        }
+    }
+}
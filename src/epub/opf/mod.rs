@@ -3,6 +3,7 @@
 //! 此模块提供EPUB文件中OPF包文件的解析功能，包括元数据、清单、脊柱等信息的提取。
 
 mod config;
+mod guide;
 mod metadata;
 mod manifest;
 mod spine;
@@ -14,9 +15,12 @@ pub use metadata::{
     Creator, 
     Identifier, 
     Metadata, 
+    MetadataCompleteness,
     MetadataValue, 
-    MetaValue
+    MetaValue,
+    SeriesInfo,
 };
-pub use manifest::ManifestItem;
-pub use spine::SpineItem;
+pub use guide::GuideReference;
+pub use manifest::{ManifestCategories, ManifestItem};
+pub use spine::{ReadingDirection, SpineItem, Spread};
 pub use parser::Opf; 
\ No newline at end of file
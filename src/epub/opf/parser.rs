@@ -2,15 +2,19 @@
 //! 
 //! 提供OPF（Open Packaging Format）文件的XML解析功能。
 
+use crate::epub::encoding::strip_bom;
 use crate::epub::error::{EpubError, Result};
 use crate::epub::opf::{
-    metadata::Metadata,
-    manifest::ManifestItem,
-    spine::SpineItem,
+    config::{DEFAULT_CONFIG_PATH, MetadataTagConfigs},
+    guide::GuideReference,
+    metadata::{Identifier, Metadata},
+    manifest::{ManifestCategories, ManifestItem},
+    spine::{ReadingDirection, SpineItem},
 };
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
 use std::collections::HashMap;
+use std::path::Path;
 
 /// OPF文件解析结果
 #[derive(Debug, Clone)]
@@ -25,10 +29,16 @@ pub struct Opf {
     pub spine: Vec<SpineItem>,
     /// 脊柱的目录引用
     pub spine_toc: Option<String>,
+    /// package元素的`unique-identifier`属性（指向某个`dc:identifier`的id）
+    pub unique_identifier: Option<String>,
+    /// spine元素的`page-progression-direction`属性（`ltr`、`rtl`或`default`）
+    pub page_progression_direction: Option<String>,
+    /// EPUB2 `<guide>`中的导览引用（按文档顺序）
+    pub guide: Vec<GuideReference>,
 }
 
 impl Opf {
-    /// 解析OPF文件内容
+    /// 使用默认的元数据标签配置解析OPF文件内容
     /// 
     /// # 参数
     /// * `xml_content` - OPF文件的XML内容
@@ -36,33 +46,49 @@ impl Opf {
     /// # 返回值
     /// * `Result<Opf, EpubError>` - 解析后的OPF信息
     pub fn parse_xml(xml_content: &str) -> Result<Opf> {
-        Self::parse_xml_with_config(xml_content)
+        Self::parse_xml_with_configs(xml_content, &MetadataTagConfigs::new())
     }
 
-    /// 使用指定的配置文件解析OPF文件内容
+    /// 使用当前目录下的配置文件（`metadata.yaml`，不存在时使用默认配置）解析OPF文件内容
     /// 
     /// # 参数
     /// * `xml_content` - OPF文件的XML内容
-    /// * `config_path` - 配置文件路径(可选)，如果不提供则使用默认配置
     /// 
     /// # 返回值
-    /// * `Result<Opf, EpubError>` - 解析后的OPF信息
+    /// * `Result<Opf, EpubError>` - 解析后的OPF信息，配置文件格式错误时返回错误
     pub fn parse_xml_with_config(xml_content: &str) -> Result<Opf> {
-        let mut reader = Reader::from_str(xml_content);
+        let configs = MetadataTagConfigs::from_path_or_default(Path::new(DEFAULT_CONFIG_PATH))?;
+        Self::parse_xml_with_configs(xml_content, &configs)
+    }
+
+    /// 使用指定的元数据标签配置解析OPF文件内容
+    /// 
+    /// # 参数
+    /// * `xml_content` - OPF文件的XML内容
+    /// * `configs` - 元数据标签配置
+    /// 
+    /// # 返回值
+    /// * `Result<Opf, EpubError>` - 解析后的OPF信息
+    pub fn parse_xml_with_configs(xml_content: &str, configs: &MetadataTagConfigs) -> Result<Opf> {
+        let mut reader = Reader::from_str(strip_bom(xml_content));
         reader.config_mut().trim_text(true);
         reader.config_mut().expand_empty_elements = true;
         
         let mut version = String::new();
-        let mut metadata = Metadata::new();
+        let mut metadata = Metadata::with_tag_configs(configs.clone());
         let mut manifest = HashMap::new();
         let mut spine = Vec::new();
         let mut spine_toc = None;
+        let mut unique_identifier = None;
+        let mut page_progression_direction = None;
+        let mut guide = Vec::new();
         
         let mut buf = Vec::new();
         let mut current_section = String::new();
         let mut text_content = String::new();
         let mut current_attributes = HashMap::new();
         let mut current_meta_property = String::new();
+        let mut current_meta_id = None;
         
         loop {
             match reader.read_event_into(&mut buf)? {
@@ -73,6 +99,7 @@ impl Opf {
                     match local_name.as_ref() {
                         "package" => {
                             version = Self::parse_package_version(e)?;
+                            unique_identifier = Self::parse_unique_identifier(e)?;
                         }
                         "metadata" => {
                             current_section = "metadata".to_string();
@@ -82,7 +109,14 @@ impl Opf {
                         }
                         "spine" => {
                             current_section = "spine".to_string();
-                            spine_toc = Self::parse_spine_toc(e)?;
+                            spine_toc = Self::parse_spine_attribute(e, b"toc")?;
+                            page_progression_direction = Self::parse_spine_attribute(e, b"page-progression-direction")?;
+                        }
+                        "guide" => {
+                            current_section = "guide".to_string();
+                        }
+                        "reference" if current_section == "guide" => {
+                            Self::parse_guide_reference(e, &mut guide)?;
                         }
                         "item" if current_section == "manifest" => {
                             Self::parse_manifest_item(e, &mut manifest)?;
@@ -91,7 +125,7 @@ impl Opf {
                             Self::parse_spine_item(e, &mut spine)?;
                         }
                         "meta" if current_section == "metadata" => {
-                            current_meta_property = Self::handle_meta_start_tag(e, &mut metadata)?;
+                            (current_meta_property, current_meta_id) = Self::handle_meta_start_tag(e, &mut metadata)?;
                             text_content.clear();
                         }
                         _ if current_section == "metadata" => {
@@ -116,6 +150,9 @@ impl Opf {
                         "itemref" if current_section == "spine" => {
                             Self::parse_spine_item(e, &mut spine)?;
                         }
+                        "reference" if current_section == "guide" => {
+                            Self::parse_guide_reference(e, &mut guide)?;
+                        }
                         _ => {}
                     }
                 }
@@ -124,7 +161,7 @@ impl Opf {
                     let local_name = String::from_utf8_lossy(local_name_bytes.as_ref());
                     
                     match local_name.as_ref() {
-                        "metadata" | "manifest" | "spine" => {
+                        "metadata" | "manifest" | "spine" | "guide" => {
                             current_section.clear();
                         }
                         "meta" if current_section == "metadata" && !current_meta_property.is_empty() => {
@@ -142,7 +179,11 @@ impl Opf {
                                     metadata.add_meta_refines_based(refines_id, property, text_content.trim().to_string(), scheme);
                                 }
                             } else {
-                                metadata.add_meta_property_based(current_meta_property.clone(), text_content.trim().to_string());
+                                metadata.add_meta_property_based_with_id(
+                                    current_meta_property.clone(),
+                                    text_content.trim().to_string(),
+                                    current_meta_id.take(),
+                                );
                             }
                             current_meta_property.clear();
                         }
@@ -167,6 +208,9 @@ impl Opf {
             manifest,
             spine,
             spine_toc,
+            unique_identifier,
+            page_progression_direction,
+            guide,
         })
     }
 
@@ -181,27 +225,39 @@ impl Opf {
         Ok(String::new())
     }
 
-    /// 解析spine元素的toc属性
-    fn parse_spine_toc(e: &quick_xml::events::BytesStart) -> Result<Option<String>> {
+    /// 解析package元素的unique-identifier属性
+    fn parse_unique_identifier(e: &quick_xml::events::BytesStart) -> Result<Option<String>> {
         for attr_result in e.attributes() {
             let attr = attr_result.map_err(|err| EpubError::XmlError(quick_xml::Error::InvalidAttr(err)))?;
-            if attr.key.local_name().as_ref() == b"toc" {
+            if attr.key.local_name().as_ref() == b"unique-identifier" {
+                return Ok(Some(String::from_utf8_lossy(&attr.value).to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 解析spine元素的指定属性（如`toc`、`page-progression-direction`）
+    fn parse_spine_attribute(e: &quick_xml::events::BytesStart, name: &[u8]) -> Result<Option<String>> {
+        for attr_result in e.attributes() {
+            let attr = attr_result.map_err(|err| EpubError::XmlError(quick_xml::Error::InvalidAttr(err)))?;
+            if attr.key.local_name().as_ref() == name {
                 return Ok(Some(String::from_utf8_lossy(&attr.value).to_string()));
             }
         }
         Ok(None)
     }
     
-    /// 处理meta开始标签，返回property属性值(如果存在)
+    /// 处理meta开始标签，返回property属性值(如果存在)及id属性值
     fn handle_meta_start_tag(
         e: &quick_xml::events::BytesStart,
         metadata: &mut Metadata,
-    ) -> Result<String> {
+    ) -> Result<(String, Option<String>)> {
         let mut name = String::new();
         let mut content = String::new();
         let mut property = String::new();
         let mut refines = String::new();
         let mut scheme = None;
+        let mut id = None;
         
         // 解析meta标签属性
         for attr_result in e.attributes() {
@@ -226,6 +282,9 @@ impl Opf {
                 b"scheme" => {
                     scheme = Some(String::from_utf8_lossy(&attr.value).to_string());
                 }
+                b"id" => {
+                    id = Some(String::from_utf8_lossy(&attr.value).to_string());
+                }
                 _ => {}
             }
         }
@@ -238,10 +297,10 @@ impl Opf {
         // 如果是refines类型的meta标签，等待获取文本内容
         if !refines.is_empty() && !property.is_empty() {
             // 这里我们返回特殊格式，包含refines信息，以便后续处理
-            return Ok(format!("refines:{}:{}:{}", refines, property, scheme.unwrap_or_default()));
+            return Ok((format!("refines:{}:{}:{}", refines, property, scheme.unwrap_or_default()), None));
         }
         
-        Ok(property)
+        Ok((property, id))
     }
     
     /// 处理空的meta标签
@@ -254,6 +313,7 @@ impl Opf {
         let mut property = String::new();
         let mut refines = String::new();
         let mut scheme = None;
+        let mut id = None;
         
         // 解析meta标签属性
         for attr_result in e.attributes() {
@@ -278,6 +338,9 @@ impl Opf {
                 b"scheme" => {
                     scheme = Some(String::from_utf8_lossy(&attr.value).to_string());
                 }
+                b"id" => {
+                    id = Some(String::from_utf8_lossy(&attr.value).to_string());
+                }
                 _ => {}
             }
         }
@@ -293,7 +356,7 @@ impl Opf {
         }
         // 处理property属性的meta标签(EPUB3格式，但没有文本内容的情况)
         else if !property.is_empty() && refines.is_empty() {
-            metadata.add_meta_property_based(property, String::new());
+            metadata.add_meta_property_based_with_id(property, String::new(), id);
         }
         
         Ok(())
@@ -381,10 +444,7 @@ impl Opf {
         e: &quick_xml::events::BytesStart,
         spine: &mut Vec<SpineItem>,
     ) -> Result<()> {
-        let mut spine_item = SpineItem {
-            idref: String::new(),
-            linear: true,
-        };
+        let mut spine_item = SpineItem::new(String::new());
         
         // 解析itemref属性
         for attr_result in e.attributes() {
@@ -397,6 +457,9 @@ impl Opf {
                     let linear_value = String::from_utf8_lossy(&attr.value);
                     spine_item.linear = linear_value != "no";
                 }
+                b"properties" => {
+                    spine_item.properties = Some(String::from_utf8_lossy(&attr.value).to_string());
+                }
                 _ => {}
             }
         }
@@ -408,6 +471,35 @@ impl Opf {
         Ok(())
     }
     
+    /// 解析guide中的reference元素
+    fn parse_guide_reference(
+        e: &quick_xml::events::BytesStart,
+        guide: &mut Vec<GuideReference>,
+    ) -> Result<()> {
+        let mut reference_type = String::new();
+        let mut href = String::new();
+        let mut title = None;
+        
+        for attr_result in e.attributes() {
+            let attr = attr_result.map_err(|e| EpubError::XmlError(quick_xml::Error::InvalidAttr(e)))?;
+            let value = attr.unescape_value()
+                .map(|value| value.to_string())
+                .unwrap_or_else(|_| String::from_utf8_lossy(&attr.value).to_string());
+            match attr.key.local_name().as_ref() {
+                b"type" => reference_type = value,
+                b"href" => href = value,
+                b"title" => title = Some(value).filter(|title| !title.trim().is_empty()),
+                _ => {}
+            }
+        }
+        
+        if !reference_type.is_empty() && !href.is_empty() {
+            guide.push(GuideReference::new(reference_type, href, title));
+        }
+        
+        Ok(())
+    }
+    
     /// 获取导航文档的路径
     /// 
     /// # 返回值
@@ -428,6 +520,27 @@ impl Opf {
             .map(|item| item.href.clone())
     }
     
+    /// 获取唯一标识符
+    /// 
+    /// 返回`id`与package元素的`unique-identifier`属性匹配的`dc:identifier`。
+    /// 
+    /// # 返回值
+    /// * `Option<Identifier>` - 唯一标识符，未声明或没有匹配的`dc:identifier`时为None
+    pub fn unique_identifier(&self) -> Option<Identifier> {
+        let unique_id = self.unique_identifier.as_deref()?;
+        self.metadata.identifiers()
+            .into_iter()
+            .find(|identifier| identifier.id.as_deref() == Some(unique_id))
+    }
+    
+    /// 获取书籍的翻页方向
+    /// 
+    /// # 返回值
+    /// * `ReadingDirection` - spine未声明`page-progression-direction`时为从左到右
+    pub fn reading_direction(&self) -> ReadingDirection {
+        ReadingDirection::from_page_progression(self.page_progression_direction.as_deref())
+    }
+    
     /// 获取封面路径(综合检查多种方式)
     /// 
     /// # 返回值
@@ -505,6 +618,14 @@ impl Opf {
             .map(|item| item.href.clone())
             .collect()
     }
+
+    /// 按媒体类型对清单项分组
+    /// 
+    /// # 返回值
+    /// * `ManifestCategories` - 文档、样式表、图片、字体、音频、视频和其他文件的分组
+    pub fn categorize_manifest(&self) -> ManifestCategories<'_> {
+        ManifestCategories::from_items(self.manifest.values())
+    }
 }
 
 #[cfg(test)]
@@ -520,6 +641,9 @@ mod tests {
             manifest: std::collections::HashMap::new(),
             spine: Vec::new(),
             spine_toc: None,
+            unique_identifier: None,
+            page_progression_direction: None,
+            guide: Vec::new(),
         };
 
         // 手动添加EPUB3标准的作者信息
@@ -613,4 +737,280 @@ mod tests {
         assert_eq!(opf.manifest.len(), 1);
         assert_eq!(opf.spine.len(), 1);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_bom_prefixed_opf() {
+        let opf_with_bom = concat!(
+            "\u{FEFF}",
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<package xmlns="http://www.idpf.org/2007/opf" version="2.0">"#,
+            r#"<metadata xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+            r#"<dc:title>BOM Book</dc:title>"#,
+            r#"</metadata>"#,
+            r#"<manifest></manifest>"#,
+            r#"<spine></spine>"#,
+            r#"</package>"#
+        );
+
+        let opf = Opf::parse_xml(opf_with_bom).expect("解析带BOM的OPF失败");
+        assert_eq!(opf.version, "2.0");
+        assert_eq!(opf.metadata.title(), Some("BOM Book".to_string()));
+    }
+
+    #[test]
+    fn test_spine_item_properties() {
+        let opf_xml = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+<metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>Manga</dc:title></metadata>
+<manifest>
+<item id="p1" href="p1.xhtml" media-type="application/xhtml+xml"/>
+<item id="p2" href="p2.xhtml" media-type="application/xhtml+xml"/>
+</manifest>
+<spine page-progression-direction="rtl">
+<itemref idref="p1" properties="page-spread-right"/>
+<itemref idref="p2" linear="no"/>
+</spine>
+</package>"#;
+
+        let opf = Opf::parse_xml(opf_xml).expect("解析OPF失败");
+        assert_eq!(opf.page_progression_direction.as_deref(), Some("rtl"));
+        assert_eq!(opf.reading_direction(), ReadingDirection::Rtl);
+        assert_eq!(opf.spine[0].spread(), Some(crate::epub::opf::Spread::Right));
+        assert_eq!(opf.spine[1].properties, None);
+        assert!(!opf.spine[1].linear);
+    }
+
+    #[test]
+    fn test_guide_references() {
+        let opf_xml = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+<metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>导览</dc:title></metadata>
+<manifest><item id="ch1" href="Text/ch1.xhtml" media-type="application/xhtml+xml"/></manifest>
+<spine><itemref idref="ch1"/></spine>
+<guide>
+<reference type="cover" href="Text/cover.xhtml" title="封面"/>
+<reference type="text" href="Text/ch1.xhtml#start" title="Tom &amp; Jerry"/>
+<reference type="toc" href=""/>
+</guide>
+</package>"#;
+
+        let opf = Opf::parse_xml(opf_xml).expect("解析OPF失败");
+        assert_eq!(opf.guide.len(), 2);
+        assert_eq!(opf.guide[0].reference_type, "cover");
+        assert_eq!(opf.guide[0].title.as_deref(), Some("封面"));
+        assert_eq!(opf.guide[1].href, "Text/ch1.xhtml#start");
+        assert_eq!(opf.guide[1].title.as_deref(), Some("Tom & Jerry"));
+        assert_eq!(opf.spine.len(), 1);
+    }
+
+    #[test]
+    fn test_unique_identifier() {
+        let opf_xml = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0" unique-identifier="uuid_id">
+<metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+<dc:title>标识符</dc:title>
+<dc:identifier id="isbn_id" opf:scheme="ISBN">978-7-000-00000-0</dc:identifier>
+<dc:identifier id="uuid_id" opf:scheme="UUID">urn:uuid:1234-abcd</dc:identifier>
+</metadata>
+<manifest></manifest>
+<spine></spine>
+</package>"#;
+
+        let opf = Opf::parse_xml(opf_xml).expect("解析OPF失败");
+        assert_eq!(opf.unique_identifier.as_deref(), Some("uuid_id"));
+        let identifier = opf.unique_identifier().unwrap();
+        assert_eq!(identifier.value, "urn:uuid:1234-abcd");
+        assert_eq!(identifier.id.as_deref(), Some("uuid_id"));
+
+        let unmatched = Opf::parse_xml(&opf_xml.replace(r#"unique-identifier="uuid_id""#, r#"unique-identifier="other""#))
+            .expect("解析OPF失败");
+        assert!(unmatched.unique_identifier().is_none());
+    }
+
+    #[test]
+    fn test_metadata_raw_xml_snippets() {
+        let mut metadata = Metadata::new();
+        let mut attributes = std::collections::HashMap::new();
+        attributes.insert("id".to_string(), "creator1".to_string());
+        attributes.insert("role".to_string(), "aut".to_string());
+        metadata.add_dublin_core("creator".to_string(), "Tom & Jerry".to_string(), attributes);
+        metadata.add_meta_refines_based("creator1".to_string(), "file-as".to_string(), "Jerry".to_string(), None);
+        metadata.add_meta_name_based("cover".to_string(), "cover-image".to_string());
+
+        assert_eq!(
+            metadata.raw_xml_snippets("dc:creator"),
+            vec![
+                "<dc:creator id=\"creator1\" role=\"aut\">Tom &amp; Jerry</dc:creator>\n\
+                 <meta refines=\"#creator1\" property=\"file-as\">Jerry</meta>"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            metadata.raw_xml_snippets("cover"),
+            vec!["<meta name=\"cover\" content=\"cover-image\"/>".to_string()]
+        );
+        assert!(metadata.raw_xml_snippets("publisher").is_empty());
+    }
+
+    #[test]
+    fn test_categorize_manifest() {
+        let mut opf = Opf {
+            version: "3.0".to_string(),
+            metadata: Metadata::new(),
+            manifest: std::collections::HashMap::new(),
+            spine: Vec::new(),
+            spine_toc: None,
+            unique_identifier: None,
+            page_progression_direction: None,
+            guide: Vec::new(),
+        };
+        for (id, href, media_type) in [
+            ("ch2", "text/ch2.xhtml", "application/xhtml+xml"),
+            ("ch1", "text/ch1.xhtml", "application/xhtml+xml"),
+            ("css", "style.css", "text/css"),
+            ("cover", "cover.jpg", "image/jpeg"),
+            ("font1", "fonts/a.otf", "application/vnd.ms-opentype"),
+            ("font2", "fonts/b.woff2", "font/woff2"),
+            ("audio", "audio/a.mp3", "audio/mpeg"),
+            ("video", "video/v.mp4", "video/mp4"),
+            ("ncx", "toc.ncx", "application/x-dtbncx+xml"),
+        ] {
+            opf.manifest.insert(id.to_string(), ManifestItem::new(id.to_string(), href.to_string(), media_type.to_string()));
+        }
+
+        let categories = opf.categorize_manifest();
+        let hrefs = |items: &[&ManifestItem]| items.iter().map(|item| item.href.clone()).collect::<Vec<_>>();
+        assert_eq!(hrefs(&categories.documents), vec!["text/ch1.xhtml", "text/ch2.xhtml"]);
+        assert_eq!(hrefs(&categories.stylesheets), vec!["style.css"]);
+        assert_eq!(hrefs(&categories.images), vec!["cover.jpg"]);
+        assert_eq!(hrefs(&categories.fonts), vec!["fonts/a.otf", "fonts/b.woff2"]);
+        assert_eq!(hrefs(&categories.audio), vec!["audio/a.mp3"]);
+        assert_eq!(hrefs(&categories.video), vec!["video/v.mp4"]);
+        assert_eq!(hrefs(&categories.other), vec!["toc.ncx"]);
+        assert_eq!(categories.total(), 9);
+    }
+
+    #[test]
+    fn test_metadata_dates_with_events() {
+        let opf_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf" xmlns:opf="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:date opf:event="creation">2018-05-01</dc:date>
+        <dc:date opf:event="publication">2019-03-15</dc:date>
+        <dc:date opf:event="modification">2021-11-02</dc:date>
+    </metadata>
+    <manifest/>
+    <spine/>
+</package>"#;
+        let opf = Opf::parse_xml(opf_xml).unwrap();
+        assert_eq!(opf.metadata.dates(), vec![
+            (Some("creation".to_string()), "2018-05-01".to_string()),
+            (Some("publication".to_string()), "2019-03-15".to_string()),
+            (Some("modification".to_string()), "2021-11-02".to_string()),
+        ]);
+        assert_eq!(opf.metadata.date().as_deref(), Some("2019-03-15"));
+
+        let opf_xml = opf_xml.replace(r#" opf:event="publication""#, "");
+        let opf = Opf::parse_xml(&opf_xml).unwrap();
+        assert_eq!(opf.metadata.dates()[1], (None, "2019-03-15".to_string()));
+        assert_eq!(opf.metadata.date().as_deref(), Some("2018-05-01"));
+    }
+
+    #[test]
+    fn test_parse_xml_with_configs_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("tags.yaml");
+        let mut configs = MetadataTagConfigs::default_config();
+        configs.title.tags = vec!["alternative".to_string(), "title".to_string()];
+        std::fs::write(&config_path, serde_yml::to_string(&configs).unwrap()).unwrap();
+
+        let configs = MetadataTagConfigs::from_path(&config_path).unwrap();
+        let opf_xml = r#"<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+        <dc:title>正式标题</dc:title>
+        <dcterms:alternative>通用书名</dcterms:alternative>
+    </metadata>
+    <manifest/>
+    <spine/>
+</package>"#;
+        let opf = Opf::parse_xml_with_configs(opf_xml, &configs).unwrap();
+        assert_eq!(opf.metadata.title().as_deref(), Some("通用书名"));
+
+        let missing = MetadataTagConfigs::from_path(&dir.path().join("missing.yaml"));
+        assert!(matches!(missing, Err(EpubError::ConfigError(_))));
+
+        let default_path = dir.path().join("default.yaml");
+        MetadataTagConfigs::write_default_config(&default_path).unwrap();
+        assert_eq!(MetadataTagConfigs::from_path(&default_path).unwrap().title.tags, vec!["title"]);
+
+        // 文件缺失时使用默认配置，格式错误时返回错误而不是静默回退
+        let fallback = MetadataTagConfigs::from_path_or_default(&dir.path().join("missing.yaml")).unwrap();
+        assert_eq!(fallback.title.tags, vec!["title"]);
+        let invalid_path = dir.path().join("invalid.yaml");
+        std::fs::write(&invalid_path, "title: [").unwrap();
+        assert!(matches!(MetadataTagConfigs::from_path_or_default(&invalid_path), Err(EpubError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_metadata_generator() {
+        let mut metadata = Metadata::new();
+        assert_eq!(metadata.generator(), None);
+
+        let mut attributes = std::collections::HashMap::new();
+        attributes.insert("role".to_string(), "bkp".to_string());
+        metadata.add_dublin_core("contributor".to_string(), "calibre (7.0)".to_string(), attributes);
+        assert_eq!(metadata.generator().as_deref(), Some("calibre (7.0)"));
+
+        metadata.add_meta_name_based("generator".to_string(), "Sigil 2.0".to_string());
+        assert_eq!(metadata.generator().as_deref(), Some("Sigil 2.0"));
+    }
+
+    #[test]
+    fn test_metadata_series_and_sort_title() {
+        use crate::epub::opf::SeriesInfo;
+
+        let opf_xml = r##"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="id">urn:uuid:1234</dc:identifier>
+    <dc:title id="subtitle">A Subtitle</dc:title>
+    <dc:title id="main-title">The Hobbit</dc:title>
+    <meta refines="#main-title" property="title-type">main</meta>
+    <meta refines="#main-title" property="file-as">Hobbit, The</meta>
+    <meta property="belongs-to-collection" id="set1">Tolkien Box Set</meta>
+    <meta refines="#set1" property="collection-type">set</meta>
+    <meta property="belongs-to-collection" id="series1">Middle-earth</meta>
+    <meta refines="#series1" property="collection-type">series</meta>
+    <meta refines="#series1" property="group-position">1.5</meta>
+  </metadata>
+  <manifest/>
+  <spine/>
+</package>"##;
+        let opf = Opf::parse_xml(opf_xml).expect("解析OPF失败");
+        let metadata = &opf.metadata;
+        assert_eq!(metadata.title().as_deref(), Some("The Hobbit"));
+        assert_eq!(metadata.sort_title().as_deref(), Some("Hobbit, The"));
+        assert_eq!(metadata.series(), Some(SeriesInfo {
+            name: "Middle-earth".to_string(),
+            collection_type: Some("series".to_string()),
+            position: Some(1.5),
+        }));
+        assert_eq!(metadata.raw_xml_snippets("belongs-to-collection"), vec![
+            "<meta property=\"belongs-to-collection\">Tolkien Box Set</meta>".to_string(),
+            "<meta property=\"belongs-to-collection\">Middle-earth</meta>".to_string(),
+        ]);
+
+        // Calibre导出的name形式meta
+        let mut calibre = Metadata::new();
+        calibre.add_meta_name_based("calibre:series".to_string(), "Discworld".to_string());
+        calibre.add_meta_name_based("calibre:series_index".to_string(), "3".to_string());
+        calibre.add_meta_name_based("calibre:title_sort".to_string(), "Equal Rites".to_string());
+        assert_eq!(calibre.series(), Some(SeriesInfo {
+            name: "Discworld".to_string(),
+            collection_type: Some("series".to_string()),
+            position: Some(3.0),
+        }));
+        assert_eq!(calibre.sort_title().as_deref(), Some("Equal Rites"));
+        assert_eq!(Metadata::new().series(), None);
+    }
+}
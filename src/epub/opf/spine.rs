@@ -2,13 +2,74 @@
 //! 
 //! 提供EPUB包中阅读顺序（脊柱）的结构定义。
 
+/// 固定版式页面的跨页位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "snake_case"))]
+pub enum Spread {
+    /// 放在跨页的左侧（page-spread-left）
+    Left,
+    /// 放在跨页的右侧（page-spread-right）
+    Right,
+    /// 居中单独显示（page-spread-center / rendition:page-spread-center）
+    Center,
+}
+
+impl Spread {
+    /// 从itemref的单个属性值解析跨页位置
+    /// 
+    /// # 参数
+    /// * `property` - 属性值，如`page-spread-left`或`rendition:page-spread-center`
+    /// 
+    /// # 返回值
+    /// * `Option<Spread>` - 跨页位置（属性不是跨页属性时返回None）
+    pub fn from_property(property: &str) -> Option<Self> {
+        let property = property.strip_prefix("rendition:").unwrap_or(property);
+        match property {
+            "page-spread-left" => Some(Spread::Left),
+            "page-spread-right" => Some(Spread::Right),
+            "page-spread-center" => Some(Spread::Center),
+            _ => None,
+        }
+    }
+}
+
+/// 书籍的翻页方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "snake_case"))]
+pub enum ReadingDirection {
+    /// 从左到右
+    #[default]
+    Ltr,
+    /// 从右到左（如日文竖排、阿拉伯文书籍）
+    Rtl,
+}
+
+impl ReadingDirection {
+    /// 从spine元素的`page-progression-direction`属性值解析翻页方向
+    /// 
+    /// # 参数
+    /// * `value` - 属性值（`ltr`、`rtl`或`default`），缺失时为None
+    /// 
+    /// # 返回值
+    /// * `ReadingDirection` - 只有`rtl`解析为从右到左，其余情况均为从左到右
+    pub fn from_page_progression(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some(value) if value.eq_ignore_ascii_case("rtl") => ReadingDirection::Rtl,
+            _ => ReadingDirection::Ltr,
+        }
+    }
+}
+
 /// 脊柱项信息(阅读顺序)
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SpineItem {
     /// 引用的清单项ID
     pub idref: String,
     /// 是否线性阅读
     pub linear: bool,
+    /// 属性(如page-spread-left等) EPUB3
+    pub properties: Option<String>,
 }
 
 impl SpineItem {
@@ -17,6 +78,7 @@ impl SpineItem {
         Self {
             idref,
             linear: true,
+            properties: None,
         }
     }
 
@@ -25,6 +87,7 @@ impl SpineItem {
         Self {
             idref,
             linear: false,
+            properties: None,
         }
     }
 
@@ -33,6 +96,7 @@ impl SpineItem {
         Self {
             idref,
             linear,
+            properties: None,
         }
     }
 
@@ -40,4 +104,53 @@ impl SpineItem {
     pub fn is_linear(&self) -> bool {
         self.linear
     }
-} 
\ No newline at end of file
+
+    /// 检查是否包含指定属性
+    pub fn has_property(&self, property: &str) -> bool {
+        if let Some(properties) = &self.properties {
+            properties.split_whitespace().any(|p| p == property)
+        } else {
+            false
+        }
+    }
+
+    /// 获取固定版式页面的跨页位置
+    pub fn spread(&self) -> Option<Spread> {
+        self.properties.as_ref()?
+            .split_whitespace()
+            .find_map(Spread::from_property)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_from_properties() {
+        let mut item = SpineItem::new("page1".to_string());
+        assert_eq!(item.spread(), None);
+
+        item.properties = Some("page-spread-left".to_string());
+        assert_eq!(item.spread(), Some(Spread::Left));
+
+        item.properties = Some("rendition:layout-pre-paginated page-spread-right".to_string());
+        assert_eq!(item.spread(), Some(Spread::Right));
+        assert!(item.has_property("rendition:layout-pre-paginated"));
+
+        item.properties = Some("rendition:page-spread-center".to_string());
+        assert_eq!(item.spread(), Some(Spread::Center));
+
+        item.properties = Some("facing-page-left".to_string());
+        assert_eq!(item.spread(), None);
+    }
+
+    #[test]
+    fn test_reading_direction_from_page_progression() {
+        assert_eq!(ReadingDirection::from_page_progression(Some("rtl")), ReadingDirection::Rtl);
+        assert_eq!(ReadingDirection::from_page_progression(Some(" RTL ")), ReadingDirection::Rtl);
+        assert_eq!(ReadingDirection::from_page_progression(Some("ltr")), ReadingDirection::Ltr);
+        assert_eq!(ReadingDirection::from_page_progression(Some("default")), ReadingDirection::Ltr);
+        assert_eq!(ReadingDirection::from_page_progression(None), ReadingDirection::Ltr);
+    }
+}
@@ -1,18 +1,50 @@
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs;
+use std::io::{self, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::Duration;
 use once_cell::sync::OnceCell;
+use sha2::{Digest, Sha256};
 use zip::ZipArchive;
 
 use crate::epub::error::{EpubError, Result};
 use crate::epub::container::Container;
-use crate::epub::opf::Opf;
-use crate::epub::ncx::{Ncx, TocTree, create_toc_tree_from_ncx};
+use crate::epub::encoding::decode_xml_bytes;
+use crate::epub::encryption::{
+    ENCRYPTION_PATH, EncryptedResource, IDPF_OBFUSCATION_ALGORITHM, deobfuscate_idpf, drm_scheme_name,
+    parse_encryption_xml,
+};
+use crate::epub::warning::EpubWarning;
+use crate::epub::block::{Block, extract_blocks};
+use crate::epub::search::{SearchHit, SearchOptions, find_matches, snippet};
+use crate::epub::cfi::{ResolvedPosition, format_cfi, parse_cfi};
+use crate::epub::landmark::{Landmark, parse_nav_landmarks};
+use crate::epub::classify::{
+    ClassificationSignals, ContentClassification, IMAGE_PAGE_MAX_TEXT_CHARS, classify_signals, count_page_images,
+};
+use crate::epub::content::{
+    ChapterLink, LinkKind, LinkRef, Quote, Table, attr_any_namespace, body_inner_html, declared_language, root_language, element_at_path, element_ids,
+    escape_html, extract_anchors, extract_blockquotes, extract_css_urls, extract_styles, extract_image_descriptions, extract_link_refs, extract_links, extract_resource_refs, extract_tables, has_scheme, heading_levels, parent_dir, resolve_href,
+    rewrite_link_attributes,
+};
+use crate::epub::opf::{
+    Identifier, ManifestCategories, ManifestItem, MetadataCompleteness, MetadataTagConfigs, Opf, ReadingDirection, Spread,
+};
+use crate::epub::text::{TextOptions, Tokenization, count_words, strip_boilerplate, strip_chapter_number, term_frequencies};
+use crate::epub::ncx::{
+    Ncx, NavDiff, NavPoint, NavSummary, PageList, TocTree, TocTreeNode,
+    compare_toc_trees, create_page_list_from_nav, create_toc_tree_from_nav, create_toc_tree_from_ncx,
+};
+use crate::epub::validation::{Severity, ValidationIssue, ValidationReport};
+use crate::epub::accessibility::{A11yFinding, A11yIssueKind, A11yReport, ImageAltInfo, ImageUsage, heading_level_skips};
+use scraper::{Html, Selector};
 
 pub struct Epub {
     /// ZIP文件归档（线程安全）
-    archive: Mutex<ZipArchive<File>>,
+    archive: Mutex<ZipArchive<Box<dyn ReadSeek>>>,
     /// 容器信息（懒加载）
     container: OnceCell<Container>,
     /// OPF包信息（懒加载）
@@ -23,8 +55,32 @@ pub struct Epub {
     book_info: OnceCell<BookInfo>,
     /// 路径缓存
     paths: OnceCell<EpubPaths>,
+    /// 单个文件解压后的最大字节数
+    max_entry_size: u64,
+    /// 读取过程中收集的警告
+    warnings: Mutex<Vec<EpubWarning>>,
+    /// 章节词数缓存（按章节id）
+    word_counts: Mutex<HashMap<String, usize>>,
+    /// 是否将警告输出到标准错误
+    print_warnings: bool,
+    /// 是否去除章节标题开头的序号
+    strip_chapter_numbers: bool,
+    /// 选中的版本（rendition）索引，为None时使用默认OPF
+    rendition: Option<usize>,
+    /// 打开时记录的警告数量（这些警告位于`warnings`开头）
+    open_warning_count: usize,
+    /// 解析OPF元数据时使用的标签配置
+    tag_configs: MetadataTagConfigs,
 }
 
+/// ZIP归档的数据源（文件、内存缓冲区等）
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// 单个文件解压后的默认最大字节数（100MB）
+pub const DEFAULT_MAX_ENTRY_SIZE: u64 = 100 * 1024 * 1024;
+
 /// EPUB文件路径信息
 #[derive(Debug, Clone)]
 struct EpubPaths {
@@ -35,6 +91,7 @@ struct EpubPaths {
 
 /// 书籍基本信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BookInfo {
     pub title: String,
     pub authors: Vec<String>,
@@ -42,15 +99,60 @@ pub struct BookInfo {
     pub publisher: Option<String>,
     pub isbn: Option<String>,
     pub description: Option<String>,
+    pub subjects: Vec<String>,
+    pub rights: Option<String>,
+    pub published_date: Option<String>,
+    pub identifiers: Vec<Identifier>,
+}
+
+impl Display for BookInfo {
+    /// 输出多行摘要：标题，以及存在时的作者、语言、出版社和ISBN（不含描述）
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "标题: {}", self.title)?;
+        if !self.authors.is_empty() {
+            write!(f, "\n作者: {}", self.authors.join(", "))?;
+        }
+        if let Some(language) = &self.language {
+            write!(f, "\n语言: {}", language)?;
+        }
+        if let Some(publisher) = &self.publisher {
+            write!(f, "\n出版社: {}", publisher)?;
+        }
+        if let Some(isbn) = &self.isbn {
+            write!(f, "\nISBN: {}", isbn)?;
+        }
+        Ok(())
+    }
+}
+
+/// 书籍元数据与结构的JSON表示（用于`Epub::to_json`）
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct EpubJson<'a> {
+    /// EPUB版本
+    version: &'a str,
+    /// 书籍基本信息
+    info: &'a BookInfo,
+    /// 目录树（没有NCX和nav文档时为null）
+    toc: Option<Vec<TocTreeNode>>,
+    /// 按id排序的清单项
+    manifest: Vec<&'a ManifestItem>,
+    /// 脊柱
+    spine: &'a [crate::epub::opf::SpineItem],
 }
 
 /// 章节信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ChapterInfo {
     pub id: String,
     pub title: String,
     pub path: String,
     pub order: Option<u32>,
+    /// 固定版式页面的跨页位置（来自itemref的properties）
+    pub spread: Option<Spread>,
+    /// 是否属于主阅读顺序（itemref的`linear="no"`时为false，如封面、脚注页）
+    pub linear: bool,
 }
 
 /// 章节内容
@@ -60,14 +162,114 @@ pub struct Chapter {
     pub content: String,
 }
 
+impl Chapter {
+    /// 获取章节的纯文本内容
+    /// 
+    /// 注音（`<ruby>`）只保留基础文本。
+    pub fn text(&self) -> String {
+        self.text_with_options(&TextOptions::default())
+    }
+    
+    /// 使用指定的提取选项获取章节的纯文本内容
+    /// 
+    /// # 参数
+    /// * `options` - 文本提取选项
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, RubyMode, TextOptions};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// let chapter = epub.chapter(&chapters[0])?;
+    /// // 输出形如 漢字(かんじ)
+    /// let options = TextOptions::new().with_ruby(RubyMode::parenthesized());
+    /// println!("{}", chapter.text_with_options(&options));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn text_with_options(&self, options: &TextOptions) -> String {
+        TocTreeNode::strip_html_tags(&self.content, options)
+    }
+}
+
+/// ZIP归档中单个条目的元数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZipEntryInfo {
+    /// 条目路径
+    pub name: String,
+    /// 压缩后的大小（字节）
+    pub compressed_size: u64,
+    /// 原始大小（字节）
+    pub uncompressed_size: u64,
+    /// 压缩方式（如`Stored`、`Deflated`）
+    pub compression_method: String,
+    /// CRC32校验值
+    pub crc32: u32,
+    /// 是否为目录
+    pub is_dir: bool,
+    /// 最后修改时间（`YYYY-MM-DD HH:MM:SS`，未记录或无效时为`None`）
+    pub last_modified: Option<String>,
+}
+
+/// 章节在全文中的位置范围
+#[derive(Debug, Clone)]
+pub struct ChapterSpan {
+    /// 章节信息
+    pub chapter: ChapterInfo,
+    /// 起始字节偏移（包含）
+    pub start: usize,
+    /// 结束字节偏移（不包含）
+    pub end: usize,
+}
+
+/// 全书的字数统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContentStatistics {
+    /// 总字符数（不含空白）
+    pub total_chars: usize,
+    /// 总词数（中日文每个字符计为一个词）
+    pub total_words: usize,
+    /// 按脊柱顺序排列的(章节标题, 字符数, 词数)
+    pub chapters: Vec<(String, usize, usize)>,
+}
+
+impl ContentStatistics {
+    /// 按指定的阅读速度估算阅读时间
+    /// 
+    /// # 参数
+    /// * `words_per_minute` - 每分钟阅读的词数（中文通常为300-500字）
+    /// 
+    /// # 返回值
+    /// * `Duration` - 估算的阅读时间（按秒取整），阅读速度为0时返回0
+    pub fn estimated_reading_time(&self, words_per_minute: u32) -> Duration {
+        if words_per_minute == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs((self.total_words as u64 * 60).div_ceil(words_per_minute as u64))
+    }
+}
+
 /// 图片资源信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ImageInfo {
     pub id: String,
     pub path: String,
     pub media_type: String,
 }
 
+/// 嵌入字体信息
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FontInfo {
+    pub id: String,
+    pub path: String,
+    pub media_type: String,
+    /// 是否在`META-INF/encryption.xml`中声明为加密或混淆
+    pub obfuscated: bool,
+}
+
 /// 封面图片
 #[derive(Debug)]
 pub struct CoverImage {
@@ -94,19 +296,241 @@ impl Epub {
     /// 此方法只验证基本的EPUB结构（mimetype文件），其他组件采用懒加载。
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let file = File::open(path)?;
-        let mut archive = ZipArchive::new(file)?;
-        
-        // 验证EPUB格式
-        Self::validate_epub_format(&mut archive)?;
+        Self::from_reader(file)
+    }
+    
+    /// 从任意可读取、可定位的数据源创建EPUB实例
+    /// 
+    /// # 参数
+    /// * `reader` - EPUB数据源（如已打开的文件或内存缓冲区）
+    /// 
+    /// # 返回值
+    /// * `Result<Epub>` - EPUB实例
+    /// 
+    /// # 错误
+    /// * 数据不是有效的ZIP归档
+    /// * mimetype验证失败
+    pub fn from_reader<R: Read + Seek + Send + 'static>(reader: R) -> Result<Self> {
+        Self::open_archive(Box::new(reader), false, DEFAULT_MAX_ENTRY_SIZE, true)
+    }
+    
+    /// 以宽松模式从文件路径创建EPUB实例
+    /// 
+    /// 实际中不少文件的mimetype缺失或内容错误（违反规范，但内容完全可读）。
+    /// mimetype验证失败时，此方法会改为检查`META-INF/container.xml`是否存在、
+    /// 能否解析，以及其指向的OPF文件是否存在；检查通过则继续打开，
+    /// 并通过`opened_with_warnings()`和`warnings()`报告被跳过的问题。
+    /// 
+    /// # 参数
+    /// * `path` - EPUB文件路径
+    /// 
+    /// # 返回值
+    /// * `Result<Epub>` - EPUB实例
+    /// 
+    /// # 错误
+    /// * 文件不存在或无法读取
+    /// * mimetype验证失败，且container.xml缺失、无法解析或指向的OPF不存在（返回mimetype的错误）
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path_lenient("imperfect.epub")?;
+    /// for warning in epub.opened_with_warnings() {
+    ///     println!("已忽略: {}", warning);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_path_lenient<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        Self::from_reader_lenient(file)
+    }
+    
+    /// 以宽松模式从任意可读取、可定位的数据源创建EPUB实例
+    /// 
+    /// 宽松模式的规则见[`from_path_lenient`](Self::from_path_lenient)。
+    /// 
+    /// # 参数
+    /// * `reader` - EPUB数据源（如已打开的文件或内存缓冲区）
+    /// 
+    /// # 返回值
+    /// * `Result<Epub>` - EPUB实例
+    pub fn from_reader_lenient<R: Read + Seek + Send + 'static>(reader: R) -> Result<Self> {
+        Self::open_archive(Box::new(reader), true, DEFAULT_MAX_ENTRY_SIZE, true)
+    }
+    
+    /// 按构建器的选项打开EPUB文件，打开过程中的读取和警告输出即遵循这些选项
+    pub(crate) fn open_path<P: AsRef<Path>>(path: P, lenient: bool, max_entry_size: u64, print_warnings: bool) -> Result<Self> {
+        let file = File::open(path)?;
+        Self::open_archive(Box::new(file), lenient, max_entry_size, print_warnings)
+    }
+    
+    /// 打开ZIP归档并验证EPUB格式
+    fn open_archive(reader: Box<dyn ReadSeek>, lenient: bool, max_entry_size: u64, print_warnings: bool) -> Result<Self> {
+        let archive = ZipArchive::new(reader)?;
         
-        Ok(Epub {
+        let mut epub = Epub {
             archive: Mutex::new(archive),
             container: OnceCell::new(),
             opf: OnceCell::new(),
             ncx: OnceCell::new(),
             book_info: OnceCell::new(),
             paths: OnceCell::new(),
-        })
+            max_entry_size,
+            warnings: Mutex::new(Vec::new()),
+            word_counts: Mutex::new(HashMap::new()),
+            print_warnings,
+            strip_chapter_numbers: false,
+            rendition: None,
+            open_warning_count: 0,
+            tag_configs: MetadataTagConfigs::new(),
+        };
+        
+        // 验证EPUB格式，宽松模式下改为验证container.xml
+        if let Err(e) = epub.validate_epub_format() {
+            if !lenient || epub.validate_container().is_err() {
+                return Err(e);
+            }
+            epub.warn(EpubWarning::new(
+                format!("{}，已根据container.xml继续打开", e),
+                Some("mimetype".to_string()),
+            ));
+        }
+        epub.open_warning_count = epub.warnings().len();
+        epub.check_drm()?;
+        
+        Ok(epub)
+    }
+    
+    /// 从内存中的字节数据创建EPUB实例
+    /// 
+    /// 适用于处理上传的文件等无需写入临时文件的场景。
+    /// 
+    /// # 参数
+    /// * `bytes` - EPUB文件的完整内容
+    /// 
+    /// # 返回值
+    /// * `Result<Epub>` - EPUB实例
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let bytes = std::fs::read("book.epub")?;
+    /// let epub = Epub::from_bytes(bytes)?;
+    /// println!("{}", epub.book_info()?.title);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Self::from_reader(Cursor::new(bytes))
+    }
+    
+    /// 设置是否去除章节标题开头的序号
+    pub(crate) fn set_strip_chapter_numbers(&mut self, strip: bool) {
+        self.strip_chapter_numbers = strip;
+    }
+    
+    /// 设置解析OPF元数据时使用的标签配置，已缓存的OPF和书籍信息会被清空
+    pub(crate) fn set_tag_configs(&mut self, configs: MetadataTagConfigs) {
+        self.tag_configs = configs;
+        self.opf.take();
+        self.book_info.take();
+    }
+    
+    /// 选择用于解析的版本（rendition）
+    /// 
+    /// 多版本EPUB（如同时提供固定版式和流式版式）在container.xml中声明了多个OPF，
+    /// 默认只使用第一个。选择其他版本后，OPF、NCX、书籍信息等缓存会被清空，
+    /// 之后的所有读取都基于所选版本的OPF。
+    /// 
+    /// # 参数
+    /// * `index` - 版本索引，对应`Container::rendition_paths()`中的位置
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 索引超出范围时返回错误
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let mut epub = Epub::from_path("book.epub")?;
+    /// let renditions = epub.container()?.rendition_paths().len();
+    /// if renditions > 1 {
+    ///     epub.select_rendition(1)?;
+    ///     println!("第二个版本的标题: {}", epub.book_info()?.title);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn select_rendition(&mut self, index: usize) -> Result<()> {
+        let count = self.container()?.rendition_paths().len();
+        if index >= count {
+            return Err(EpubError::ContainerParseError(
+                format!("版本索引{}超出范围（共{}个版本）", index, count)
+            ));
+        }
+        
+        self.rendition = Some(index);
+        self.paths.take();
+        self.opf.take();
+        self.ncx.take();
+        self.book_info.take();
+        if let Ok(word_counts) = self.word_counts.get_mut() {
+            word_counts.clear();
+        }
+        Ok(())
+    }
+    
+    /// 按当前设置清理章节标题
+    /// 
+    /// NCX、nav文档和章节内标题提取得到的标题都经过此方法，保证不同目录来源的标题一致。
+    pub(crate) fn clean_title(&self, title: &str) -> String {
+        if self.strip_chapter_numbers {
+            strip_chapter_number(title)
+        } else {
+            title.to_string()
+        }
+    }
+    
+    /// 记录一条警告，并按设置输出到标准错误
+    /// 
+    /// 同一文件可能被多次读取，完全相同的警告只记录和输出一次。
+    pub(crate) fn warn(&self, warning: EpubWarning) {
+        let Ok(mut warnings) = self.warnings.lock() else {
+            return;
+        };
+        if warnings.contains(&warning) {
+            return;
+        }
+        if self.print_warnings {
+            eprintln!("警告: {}", warning);
+        }
+        warnings.push(warning);
+    }
+    
+    /// 获取打开文件时跳过的格式问题
+    /// 
+    /// 只有通过`from_path_lenient()`等宽松模式打开、且mimetype验证失败时才非空。
+    /// 这些警告就是`warnings()`开头的部分，不会重复记录。
+    /// 
+    /// # 返回值
+    /// * `Vec<EpubWarning>` - 打开时的警告
+    pub fn opened_with_warnings(&self) -> Vec<EpubWarning> {
+        self.warnings().into_iter().take(self.open_warning_count).collect()
+    }
+    
+    /// 获取到目前为止收集的所有警告
+    /// 
+    /// 组件采用懒加载，NCX解析失败、编码声明错误等问题在首次读取相应文件时才会出现，
+    /// 因此返回的警告会随读取的内容增加。需要在打开时集中获取警告可使用
+    /// [`EpubBuilder::open_with_warnings`](crate::epub::EpubBuilder::open_with_warnings)。
+    /// 
+    /// # 返回值
+    /// * `Vec<EpubWarning>` - 按发生顺序排列的警告
+    pub fn warnings(&self) -> Vec<EpubWarning> {
+        self.warnings.lock().map(|warnings| warnings.clone()).unwrap_or_default()
     }
     
     /// 获取Container引用
@@ -122,20 +546,21 @@ impl Epub {
     
     /// 获取OPF引用
     /// 
+    /// 元数据使用内置的默认标签配置解析，可通过`EpubBuilder::metadata_tag_configs`指定其他配置。
+    /// 
     /// # 返回值
     /// * `Result<&Opf>` - OPF的不可变引用
     pub fn opf(&self) -> Result<&Opf> {
         self.opf.get_or_try_init(|| {
             let paths = self.paths()?;
             let opf_content = self.read_file(&paths.opf_path)?;
-            Opf::parse_xml(&opf_content)
+            Opf::parse_xml_with_configs(&opf_content, &self.tag_configs)
         })
     }
     
     /// 使用配置解析OPF
     /// 
-    /// # 参数
-    /// * `config_path` - 配置文件路径
+    /// 配置来自当前目录下的`metadata.yaml`（不存在时使用默认配置）。
     /// 
     /// # 返回值
     /// * `Result<&Opf>` - OPF的不可变引用
@@ -147,6 +572,35 @@ impl Epub {
         })
     }
     
+    /// 使用指定的元数据标签配置解析OPF
+    /// 
+    /// 与`opf()`不同，结果不会被缓存，每次调用都会重新解析，`book_info()`、`chapter_list()`
+    /// 等方法也不会使用该配置。需要全局生效时请使用`EpubBuilder::metadata_tag_configs`。
+    /// 
+    /// # 参数
+    /// * `configs` - 元数据标签配置
+    /// 
+    /// # 返回值
+    /// * `Result<Opf>` - 解析后的OPF
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use bookforge::{Epub, MetadataTagConfigs};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let configs = MetadataTagConfigs::from_path(Path::new("config/metadata.yaml"))?;
+    /// let opf = epub.opf_with_configs(&configs)?;
+    /// println!("{:?}", opf.metadata.title());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn opf_with_configs(&self, configs: &MetadataTagConfigs) -> Result<Opf> {
+        let paths = self.paths()?;
+        let opf_content = self.read_file(&paths.opf_path)?;
+        Opf::parse_xml_with_configs(&opf_content, configs)
+    }
+    
     /// 获取NCX引用（如果存在）
     /// 
     /// # 返回值
@@ -161,13 +615,13 @@ impl Epub {
                             match Ncx::parse_xml(&ncx_content) {
                                 Ok(ncx) => Ok(Some(ncx)),
                                 Err(e) => {
-                                    eprintln!("警告: NCX文件解析失败: {}", e);
+                                    self.warn(EpubWarning::new(format!("NCX文件解析失败: {}", e), Some(ncx_path.clone())));
                                     Ok(None)
                                 }
                             }
                         }
                         Err(e) => {
-                            eprintln!("警告: 无法读取NCX文件: {}", e);
+                            self.warn(EpubWarning::new(format!("无法读取NCX文件: {}", e), Some(ncx_path.clone())));
                             Ok(None)
                         }
                     }
@@ -190,7 +644,8 @@ impl Epub {
             let metadata = &opf.metadata;
             
             // 从标识符中查找ISBN
-            let isbn = metadata.identifiers()
+            let identifiers = metadata.identifiers();
+            let isbn = identifiers
                 .iter()
                 .find(|id| {
                     id.scheme.as_ref()
@@ -206,679 +661,6027 @@ impl Epub {
                 publisher: metadata.publisher(),
                 isbn,
                 description: metadata.description(),
+                subjects: metadata.subjects(),
+                rights: metadata.rights(),
+                published_date: metadata.date(),
+                identifiers,
             })
         })
     }
     
-    /// 获取EPUB版本信息
+    /// 将书籍元数据和结构导出为JSON
+    /// 
+    /// JSON包含以下字段（字段名均为snake_case）：
+    /// * `version` - EPUB版本
+    /// * `info` - 书籍基本信息（同`book_info()`）
+    /// * `toc` - 目录树节点（标题、源文件、ID、播放顺序、深度和子节点），没有目录时为`null`
+    /// * `manifest` - 按id排序的清单项
+    /// * `spine` - 脊柱项
     /// 
     /// # 返回值
-    /// * `Result<&String>` - EPUB版本字符串的不可变引用
-    pub fn version(&self) -> Result<&String> {
+    /// * `Result<String>` - 格式化的JSON字符串
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// println!("{}", epub.to_json()?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String> {
         let opf = self.opf()?;
-        Ok(&opf.version)
+        let mut manifest: Vec<&ManifestItem> = opf.manifest.values().collect();
+        manifest.sort_by(|a, b| a.id.cmp(&b.id));
+        
+        let json = EpubJson {
+            version: &opf.version,
+            info: self.book_info()?,
+            toc: self.toc_tree()?.map(|toc_tree| toc_tree.roots),
+            manifest,
+            spine: &opf.spine,
+        };
+        serde_json::to_string_pretty(&json).map_err(|e| {
+            EpubError::InternalError(format!("JSON序列化失败: {}", e))
+        })
     }
     
-    /// 检查是否包含NCX文件
+    /// 检查书籍元数据的完整性
+    /// 
+    /// `book_info()` 会在缺少标题时使用"未知标题"等默认值，此方法则明确报告
+    /// 哪些常用字段存在、哪些缺失，便于目录管理工具标记低质量的记录。
     /// 
     /// # 返回值
-    /// * `Result<bool>` - 是否包含NCX文件
-    pub fn has_ncx(&self) -> Result<bool> {
-        Ok(self.paths()?.ncx_path.is_some())
+    /// * `Result<MetadataCompleteness>` - 元数据完整性报告
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let completeness = epub.metadata_completeness()?;
+    /// if !completeness.is_complete() {
+    ///     println!("缺少字段: {:?}", completeness.missing);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn metadata_completeness(&self) -> Result<MetadataCompleteness> {
+        Ok(self.opf()?.metadata.completeness())
     }
     
-    /// 创建目录树（从NCX文件）
+    /// 获取生成该EPUB的工具（如Sigil、Calibre、InDesign、Pandoc）
     /// 
-    /// 从NCX文件构建目录树。目录树提供了章节的树形结构表示，支持层级导航和快速查找。
+    /// 可用于诊断问题，或针对特定工具的已知问题启用兼容处理。
     /// 
     /// # 返回值
-    /// * `Result<Option<TocTree>>` - 目录树实例（如果存在NCX文件）
-    /// 
-    /// # 性能说明
-    /// * 每次调用都会重新创建目录树
-    /// * 如果不存在NCX文件，则返回None
+    /// * `Result<Option<String>>` - 生成工具的名称（通常包含版本号），未声明时为`None`
     /// 
     /// # 示例
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use bookforge::Epub;
     /// 
     /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(generator) = epub.generator()? {
+    ///     println!("生成工具: {}", generator);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn generator(&self) -> Result<Option<String>> {
+        Ok(self.opf()?.metadata.generator())
+    }
+    
+    /// 获取指定元数据元素重建后的XML片段
     /// 
-    /// if let Some(toc_tree) = epub.toc_tree()? {
-    ///     println!("目录结构:");
-    ///     println!("{}", toc_tree);
+    /// 用于排查元数据解析异常，片段根据解析后存储的值和属性重建，
+    /// 反映的是解析器实际读取到的内容。
     /// 
-    ///     // 获取第一个章节
-    ///     if let Some(first_node) = toc_tree.get_first_node() {
-    ///         println!("第一章标题: {}", first_node.title);
-    ///     }
+    /// # 参数
+    /// * `tag` - 元素名（如"creator"、"dc:creator"、"cover"）
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<String>>` - 每个同名元素对应一个XML片段
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for snippet in epub.metadata_element_raw("dc:creator")? {
+    ///     println!("{}", snippet);
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn toc_tree(&self) -> Result<Option<TocTree>> {
-        // 使用NCX文件创建目录树
-        match self.ncx()? {
-            Some(ncx) => {
-                // 从NCX创建目录树
-                let toc_tree = create_toc_tree_from_ncx(ncx, self);
-                Ok(Some(toc_tree))
-            }
-            None => {
-                // 没有NCX文件，返回None
-                Ok(None)
-            }
-        }
+    pub fn metadata_element_raw(&self, tag: &str) -> Result<Vec<String>> {
+        Ok(self.opf()?.metadata.raw_xml_snippets(tag))
     }
     
-    /// 检查是否包含目录树
+    /// 获取书籍的唯一标识符
+    /// 
+    /// 返回package元素的`unique-identifier`属性所指向的`dc:identifier`，
+    /// 书中有多个标识符（如ISBN和UUID）时可以据此确定规范的那一个。
     /// 
     /// # 返回值
-    /// * `Result<bool>` - 是否包含目录树（基于是否存在NCX文件）
-    pub fn has_toc_tree(&self) -> Result<bool> {
-        // 检查是否有NCX文件
-        self.has_ncx()
+    /// * `Result<Option<Identifier>>` - 唯一标识符，未声明或没有匹配的`dc:identifier`时为None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(identifier) = epub.unique_identifier()? {
+    ///     println!("{} ({})", identifier.value, identifier.scheme.as_deref().unwrap_or("未知类型"));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn unique_identifier(&self) -> Result<Option<Identifier>> {
+        Ok(self.opf()?.unique_identifier())
     }
     
-    /// 获取章节信息列表
+    /// 获取书籍的标识符值
+    /// 
+    /// 优先返回唯一标识符，没有时回退到第一个`dc:identifier`，适合用于去重等需要
+    /// 一个稳定标识的场景。
     /// 
     /// # 返回值
-    /// * `Result<Vec<ChapterInfo>>` - 章节信息列表
-    pub fn chapter_list(&self) -> Result<Vec<ChapterInfo>> {
+    /// * `Result<Option<String>>` - 标识符值，书中没有任何`dc:identifier`时为None
+    pub fn identifier(&self) -> Result<Option<String>> {
         let opf = self.opf()?;
-        let mut chapters = Vec::new();
-        
-        for (order, spine_item) in opf.spine.iter().enumerate() {
-            if let Some(manifest_item) = opf.get_manifest_item(&spine_item.idref) {
-                // 从NCX中获取章节标题
-                let title = if let Ok(Some(ncx)) = self.ncx() {
-                    // 从NCX中查找对应的导航点
-                    self.find_chapter_title_in_ncx(ncx, &manifest_item.href)
-                        .unwrap_or_else(|| format!("章节 {}", order + 1))
-                } else {
-                    format!("章节 {}", order + 1)
-                };
-                
-                chapters.push(ChapterInfo {
-                    id: spine_item.idref.clone(),
-                    title,
-                    path: manifest_item.href.clone(),
-                    order: Some(order as u32 + 1),
-                });
-            }
-        }
-        
-        Ok(chapters)
+        Ok(opf.unique_identifier()
+            .or_else(|| opf.metadata.identifiers().into_iter().next())
+            .map(|identifier| identifier.value))
     }
     
-    /// 获取指定章节内容
+    /// 获取书籍的翻页方向
     /// 
-    /// # 参数
-    /// * `chapter_info` - 章节信息
+    /// 读取spine元素的`page-progression-direction`属性，日文、阿拉伯文等书籍通常声明为`rtl`。
+    /// 未声明或声明为`default`时为从左到右。
     /// 
     /// # 返回值
-    /// * `Result<Chapter>` - 章节内容
-    pub fn chapter(&self, chapter_info: &ChapterInfo) -> Result<Chapter> {
-        let paths = self.paths()?;
-        let full_path = if paths.opf_directory.is_empty() {
-            chapter_info.path.clone()
-        } else {
-            format!("{}/{}", paths.opf_directory, chapter_info.path)
-        };
-        
-        let content = self.read_file(&full_path)?;
-        
-        Ok(Chapter {
-            info: chapter_info.clone(),
-            content,
-        })
+    /// * `Result<ReadingDirection>` - 翻页方向
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, ReadingDirection};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if epub.reading_direction()? == ReadingDirection::Rtl {
+    ///     println!("从右向左翻页");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reading_direction(&self) -> Result<ReadingDirection> {
+        Ok(self.opf()?.reading_direction())
     }
     
-    /// 获取所有章节内容
+    /// 获取EPUB版本信息
     /// 
     /// # 返回值
-    /// * `Result<Vec<Chapter>>` - 所有章节内容
-    pub fn chapters(&self) -> Result<Vec<Chapter>> {
-        let chapter_list = self.chapter_list()?;
-        let mut chapters = Vec::new();
-        
-        for chapter_info in chapter_list {
-            match self.chapter(&chapter_info) {
-                Ok(chapter) => chapters.push(chapter),
-                Err(e) => {
-                    eprintln!("警告: 无法读取章节 {}: {}", chapter_info.path, e);
-                    continue;
-                }
-            }
-        }
-        
-        Ok(chapters)
+    /// * `Result<&String>` - EPUB版本字符串的不可变引用
+    pub fn version(&self) -> Result<&String> {
+        let opf = self.opf()?;
+        Ok(&opf.version)
     }
     
-    /// 获取图片资源列表
+    /// 检查是否包含NCX文件
     /// 
     /// # 返回值
-    /// * `Result<Vec<ImageInfo>>` - 图片资源信息列表
-    pub fn images(&self) -> Result<Vec<ImageInfo>> {
-        let opf = self.opf()?;
-        let mut images = Vec::new();
-        
-        for (id, item) in &opf.manifest {
-            if Self::is_image_media_type(&item.media_type) {
-                images.push(ImageInfo {
-                    id: id.clone(),
-                    path: item.href.clone(),
-                    media_type: item.media_type.clone(),
-                });
-            }
+    /// * `Result<bool>` - 是否包含NCX文件
+    pub fn has_ncx(&self) -> Result<bool> {
+        Ok(self.paths()?.ncx_path.is_some())
+    }
+    
+    /// 创建目录树
+    /// 
+    /// 优先从NCX文件构建目录树；没有NCX文件（或NCX无法解析）时，回退到EPUB3 nav文档中的
+    /// `epub:type="toc"`目录。目录树提供了章节的树形结构表示，支持层级导航和快速查找。
+    /// 
+    /// # 返回值
+    /// * `Result<Option<TocTree>>` - 目录树实例（如果存在NCX文件或包含目录条目的nav文档）
+    /// 
+    /// # 性能说明
+    /// * 每次调用都会重新创建目录树
+    /// * 如果NCX文件和nav文档都不存在，则返回None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// 
+    /// if let Some(toc_tree) = epub.toc_tree()? {
+    ///     println!("目录结构:");
+    ///     println!("{}", toc_tree);
+    /// 
+    ///     // 获取第一个章节
+    ///     if let Some(first_node) = toc_tree.get_first_node() {
+    ///         println!("第一章标题: {}", first_node.title);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn toc_tree(&self) -> Result<Option<TocTree>> {
+        if let Some(toc_tree) = self.ncx_toc_tree()? {
+            return Ok(Some(toc_tree));
         }
-        
-        Ok(images)
+        // 没有NCX文件，回退到nav文档
+        Ok(self.nav_toc_tree()?.filter(|toc_tree| !toc_tree.roots.is_empty()))
     }
     
-    /// 获取封面图片
+    /// 从NCX文件创建目录树
+    fn ncx_toc_tree(&self) -> Result<Option<TocTree<'_>>> {
+        Ok(self.ncx()?.map(|ncx| create_toc_tree_from_ncx(ncx, self)))
+    }
+    
+    /// 从EPUB3 nav文档创建目录树
     /// 
     /// # 返回值
-    /// * `Result<Option<CoverImage>>` - 封面图片（如果存在）
-    pub fn cover(&self) -> Result<Option<CoverImage>> {
-        let opf = self.opf()?;
-        let paths = self.paths()?;
-        
-        // 1. 尝试从OPF metadata中获取封面
-        if let Some(cover_path) = opf.get_cover_image_path() {
-            if let Some(cover) = self.extract_cover_image(&paths.opf_directory, &cover_path)? {
-                return Ok(Some(cover));
+    /// * `Result<Option<TocTree>>` - 目录树实例（如果存在可读取的nav文档）
+    /// 
+    /// # 注意
+    /// nav目录树节点的`src`相对于nav文档所在目录，而不是NCX目录。
+    pub fn nav_toc_tree(&self) -> Result<Option<TocTree<'_>>> {
+        let Some(nav_path) = self.nav_path()? else {
+            return Ok(None);
+        };
+        match self.read_file(&nav_path) {
+            Ok(nav_content) => Ok(Some(create_toc_tree_from_nav(&nav_content, self))),
+            Err(e) => {
+                self.warn(EpubWarning::new(format!("无法读取nav文档: {}", e), Some(nav_path)));
+                Ok(None)
             }
         }
-        
-        // 2. 尝试从manifest中查找cover-image属性
-        for (_, item) in &opf.manifest {
-            if let Some(properties) = &item.properties {
-                if properties.contains("cover-image") {
-                    if let Some(cover) = self.extract_cover_image(&paths.opf_directory, &item.href)? {
-                        return Ok(Some(cover));
-                    }
-                }
+    }
+    
+    /// 获取印刷版页码列表
+    /// 
+    /// 优先使用NCX中的`<pageList>`，没有时使用EPUB3 nav文档中`epub:type="page-list"`的
+    /// `<nav>`。两种来源中页面目标的`content.src`都解析为EPUB内的完整路径（保留锚点），
+    /// 可直接用于"跳转到第N页"。
+    /// 
+    /// # 返回值
+    /// * `Result<Option<PageList>>` - 页码列表，两种来源都没有时返回None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(page_list) = epub.page_list()? {
+    ///     if let Some(target) = page_list.find_page_target_by_value("215") {
+    ///         println!("第215页位于: {}", target.content.src);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn page_list(&self) -> Result<Option<PageList>> {
+        if let Some(mut page_list) = self.ncx()?.and_then(|ncx| ncx.get_page_list().cloned()) {
+            let ncx_dir = self.get_ncx_directory()?.unwrap_or_default();
+            for target in &mut page_list.page_targets {
+                let resolved = resolve_href(&ncx_dir, &target.content.src);
+                target.content.src = Self::with_fragment(&resolved, &target.content.src);
             }
+            return Ok(Some(page_list));
         }
         
-        // 3. 尝试常见的封面文件名
-        let common_cover_names = [
-            "cover.jpg", "cover.jpeg", "cover.png", "cover.gif",
-            "Cover.jpg", "Cover.jpeg", "Cover.png", "Cover.gif",
-        ];
-        
-        for &cover_name in &common_cover_names {
-            if let Some(cover) = self.extract_cover_image(&paths.opf_directory, cover_name)? {
-                return Ok(Some(cover));
+        let Some(nav_path) = self.nav_path()? else {
+            return Ok(None);
+        };
+        match self.read_file(&nav_path) {
+            Ok(nav_content) => Ok(create_page_list_from_nav(&nav_content, parent_dir(&nav_path))),
+            Err(e) => {
+                self.warn(EpubWarning::new(format!("无法读取nav文档: {}", e), Some(nav_path)));
+                Ok(None)
             }
         }
-        
-        Ok(None)
     }
     
-    /// 获取指定图片数据
+    /// 查找包含指定印刷页码的章节
+    /// 
+    /// 在`page_list()`返回的页码列表中按页面值查找页面目标，并返回其所在的脊柱章节。
     /// 
     /// # 参数
-    /// * `image_info` - 图片信息
+    /// * `value` - 页面值（如`215`、`xii`）
     /// 
     /// # 返回值
-    /// * `Result<Vec<u8>>` - 图片二进制数据
-    pub fn image_data(&self, image_info: &ImageInfo) -> Result<Vec<u8>> {
-        let paths = self.paths()?;
-        let full_path = if paths.opf_directory.is_empty() {
-            image_info.path.clone()
-        } else {
-            format!("{}/{}", paths.opf_directory, image_info.path)
+    /// * `Result<Option<ChapterInfo>>` - 章节信息，没有页码列表、页码不存在或目标不在脊柱中时返回None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(chapter_info) = epub.go_to_page("215")? {
+    ///     println!("第215页位于章节: {}", chapter_info.title);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn go_to_page(&self, value: &str) -> Result<Option<ChapterInfo>> {
+        let Some(page_list) = self.page_list()? else {
+            return Ok(None);
+        };
+        let Some(target) = page_list.find_page_target_by_value(value.trim()) else {
+            return Ok(None);
         };
+        // 页码目标已解析为完整路径，这里只需去掉锚点、查询参数并统一百分号转义
+        let target_path = resolve_href("", &target.content.src);
         
-        self.read_binary_file(&full_path)
+        for chapter_info in self.chapter_list()? {
+            if self.chapter_full_path(&chapter_info)? == target_path {
+                return Ok(Some(chapter_info));
+            }
+        }
+        Ok(None)
     }
     
-    /// 列出所有文件
+    /// 获取书籍的结构性位置（封面、目录、正文起点等）
+    /// 
+    /// 先读取EPUB3 nav文档中`epub:type="landmarks"`的`<nav>`，再补充EPUB2 `<guide>`中
+    /// nav文档未提供的类型。guide的类型会映射为EPUB3词汇（`text`映射为`bodymatter`），
+    /// 路径都解析为EPUB内的完整路径（保留锚点），阅读器可据此直接跳转到正文起点。
     /// 
     /// # 返回值
-    /// * `Result<Vec<String>>` - 文件路径列表
-    pub fn file_list(&self) -> Result<Vec<String>> {
-        let mut archive = self.archive.lock()
-            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+    /// * `Result<Vec<Landmark>>` - 结构性位置，两种来源都没有时为空
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(start) = epub.landmarks()?.iter().find(|landmark| landmark.is_bodymatter()) {
+    ///     println!("正文从这里开始: {}", start.href);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn landmarks(&self) -> Result<Vec<Landmark>> {
+        let mut landmarks = Vec::new();
+        if let Some(nav_path) = self.nav_path()? {
+            match self.read_file(&nav_path) {
+                Ok(nav_content) => landmarks = parse_nav_landmarks(&nav_content, parent_dir(&nav_path)),
+                Err(e) => self.warn(EpubWarning::new(format!("无法读取nav文档: {}", e), Some(nav_path))),
+            }
+        }
         
-        let mut files = Vec::new();
-        for i in 0..archive.len() {
-            let file = archive.by_index(i)?;
-            files.push(file.name().to_string());
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        for reference in &opf.guide {
+            let landmark = Landmark::from_guide_reference(reference, &paths.opf_directory);
+            if !landmarks.iter().any(|existing| existing.landmark_type == landmark.landmark_type) {
+                landmarks.push(landmark);
+            }
         }
         
-        Ok(files)
+        Ok(landmarks)
     }
     
-    /// 获取OPF目录路径
+    /// 比较NCX目录与EPUB3 nav文档目录
+    /// 
+    /// 同时包含NCX和nav文档的EPUB3书籍，两份目录本应一致，但实际中经常出现差异。
+    /// 此方法分别构建两棵目录树，并报告只出现在一方的条目、标题不一致的条目以及顺序差异。
     /// 
     /// # 返回值
-    /// * `Result<String>` - OPF文件所在目录的路径
-    pub fn get_opf_directory(&self) -> Result<String> {
-        let paths = self.paths()?;
-        Ok(paths.opf_directory.clone())
+    /// * `Result<Option<NavDiff>>` - 结构化的差异；只存在一种目录来源时返回None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(diff) = epub.compare_navigation()? {
+    ///     if !diff.is_empty() {
+    ///         print!("{}", diff);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn compare_navigation(&self) -> Result<Option<NavDiff>> {
+        let (Some(ncx_tree), Some(nav_tree)) = (self.ncx_toc_tree()?, self.nav_toc_tree()?) else {
+            return Ok(None);
+        };
+        let ncx_dir = self.get_ncx_directory()?.unwrap_or_default();
+        let nav_dir = self.nav_directory()?.unwrap_or_default();
+        
+        Ok(Some(compare_toc_trees(&ncx_tree, &ncx_dir, &nav_tree, &nav_dir)))
     }
     
-    /// 获取NCX文件目录路径
+    /// 获取脊柱、NCX和nav文档各自的条目数量
+    /// 
+    /// 不同导航来源对章节的划分经常不同，此方法一次性给出各来源的数量，
+    /// 便于快速发现"脊柱有50项而目录只有30项"之类的结构问题。
     /// 
     /// # 返回值
-    /// * `Result<Option<String>>` - NCX文件所在目录的路径（如果NCX文件存在）
-    pub fn get_ncx_directory(&self) -> Result<Option<String>> {
-        let paths = self.paths()?;
-        match &paths.ncx_path {
-            Some(ncx_path) => {
-                let ncx_directory = if let Some(last_slash) = ncx_path.rfind('/') {
-                    ncx_path[..last_slash].to_string()
-                } else {
-                    String::new()
-                };
-                Ok(Some(ncx_directory))
-            }
-            None => Ok(None),
-        }
+    /// * `Result<NavSummary>` - 各来源的数量及是否一致
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let summary = epub.navigation_summary()?;
+    /// println!("{}", summary);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn navigation_summary(&self) -> Result<NavSummary> {
+        let opf = self.opf()?;
+        Ok(NavSummary {
+            spine_total: opf.spine.len(),
+            spine_linear: opf.spine.iter().filter(|item| item.is_linear()).count(),
+            ncx_entries: self.ncx_toc_tree()?.map(|tree| tree.get_statistics().total_nodes),
+            nav_entries: self.nav_toc_tree()?.map(|tree| tree.get_statistics().total_nodes),
+        })
     }
     
-    /// 读取指定文件的内容（公开接口）
-    /// 
-    /// # 参数
-    /// * `filename` - 要读取的文件名
+    /// 检查是否包含目录树
     /// 
     /// # 返回值
-    /// * `Result<String>` - 文件内容
-    pub fn read_chapter_file(&self, filename: &str) -> Result<String> {
-        self.read_file(filename)
+    /// * `Result<bool>` - 是否包含目录树（基于是否存在NCX文件或nav文档）
+    pub fn has_toc_tree(&self) -> Result<bool> {
+        Ok(self.has_ncx()? || self.nav_path()?.is_some())
     }
     
-    // === 内部方法 ===
-    
-    /// 获取路径信息（懒加载）
-    fn paths(&self) -> Result<&EpubPaths> {
-        self.paths.get_or_try_init(|| {
-            let container = self.container()?;
-            let opf_path = container.get_opf_path()
-                .ok_or_else(|| EpubError::ContainerParseError(
-                    "container.xml中没有找到有效的rootfile".to_string()
-                ))?;
-            
-            let opf_directory = if let Some(last_slash) = opf_path.rfind('/') {
-                opf_path[..last_slash].to_string()
-            } else {
-                String::new()
-            };
-            
-            // 查找NCX路径
-            let ncx_path = self.find_ncx_path(&opf_path, &opf_directory)?;
-            
-            Ok(EpubPaths {
-                opf_path,
-                opf_directory,
-                ncx_path,
-            })
-        })
-    }
-    
-    /// 查找NCX文件路径
-    fn find_ncx_path(&self, opf_path: &str, opf_directory: &str) -> Result<Option<String>> {
-        // 首先尝试从OPF中获取
-        if let Ok(opf_content) = self.read_file(opf_path) {
-            if let Ok(opf) = Opf::parse_xml(&opf_content) {
-                if let Some(spine_toc) = &opf.spine_toc {
-                    if let Some(manifest_item) = opf.get_manifest_item(spine_toc) {
-                        let ncx_path = if opf_directory.is_empty() {
-                            manifest_item.href.clone()
-                        } else {
-                            format!("{}/{}", opf_directory, manifest_item.href)
-                        };
-                        return Ok(Some(ncx_path));
-                    }
-                }
-            }
-        }
-        
-        // 尝试常见路径
-        let common_paths = [
-            "toc.ncx",
-            "OEBPS/toc.ncx",
-            "content/toc.ncx",
-            "EPUB/toc.ncx",
-        ];
+    /// 获取章节信息列表
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ChapterInfo>>` - 章节信息列表
+    pub fn chapter_list(&self) -> Result<Vec<ChapterInfo>> {
+        let opf = self.opf()?;
+        let opf_directory = &self.paths()?.opf_directory;
+        let titles = self.toc_titles_by_path().unwrap_or_default();
+        let mut chapters = Vec::new();
         
-        for &path in &common_paths {
-            if self.file_exists(path) {
-                return Ok(Some(path.to_string()));
+        for (order, spine_item) in opf.spine.iter().enumerate() {
+            if let Some(manifest_item) = opf.get_manifest_item(&spine_item.idref) {
+                // 按规范化后的完整路径从目录中获取章节标题
+                let title = titles.get(&resolve_href(opf_directory, &manifest_item.href))
+                    .cloned()
+                    .unwrap_or_else(|| format!("章节 {}", order + 1));
+                
+                chapters.push(ChapterInfo {
+                    id: spine_item.idref.clone(),
+                    title,
+                    path: manifest_item.href.clone(),
+                    order: Some(order as u32 + 1),
+                    spread: spine_item.spread(),
+                    linear: spine_item.is_linear(),
+                });
             }
         }
         
-        Ok(None)
+        Ok(chapters)
     }
     
-
-    /// 从NCX中查找章节标题
-    fn find_chapter_title_in_ncx(&self, ncx: &Ncx, chapter_path: &str) -> Option<String> {
-        // 简化的实现，实际可能需要更复杂的匹配逻辑
-        for nav_point in &ncx.nav_map.nav_points {
-            if nav_point.content.src.contains(chapter_path) {
-                return Some(nav_point.nav_label.text.clone());
-            }
-            // 递归查找子导航点
-            if let Some(title) = self.find_title_in_nav_points(&nav_point.children, chapter_path) {
-                return Some(title);
-            }
-        }
-        None
+    /// 获取主阅读顺序中的章节列表
+    /// 
+    /// 与`chapter_list()`相同，但排除脊柱中`linear="no"`的辅助内容（如封面、脚注页），
+    /// 适合计算分页或阅读进度。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ChapterInfo>>` - 线性章节列表
+    pub fn linear_chapters(&self) -> Result<Vec<ChapterInfo>> {
+        Ok(self.chapter_list()?
+            .into_iter()
+            .filter(|chapter| chapter.linear)
+            .collect())
     }
     
-    /// 在导航点中递归查找标题
-    fn find_title_in_nav_points(&self, nav_points: &[crate::epub::ncx::NavPoint], chapter_path: &str) -> Option<String> {
-        for nav_point in nav_points {
-            if nav_point.content.src.contains(chapter_path) {
-                return Some(nav_point.nav_label.text.clone());
-            }
-            if let Some(title) = self.find_title_in_nav_points(&nav_point.children, chapter_path) {
-                return Some(title);
-            }
-        }
-        None
+    /// 获取脊柱顺序中的下一个章节
+    /// 
+    /// 按`chapter_list()`的阅读顺序导航，与目录树的结构无关，适合实现阅读器的"下一页"。
+    /// 
+    /// # 参数
+    /// * `current` - 当前章节信息
+    /// * `skip_non_linear` - 是否跳过非线性的脊柱项（`linear="no"`，如注释、附图）
+    /// 
+    /// # 返回值
+    /// * `Result<Option<ChapterInfo>>` - 下一个章节，已是最后一章或当前章节不在脊柱中时返回None
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let mut current = epub.chapter_list()?.into_iter().next();
+    /// while let Some(chapter_info) = current {
+    ///     println!("{}", chapter_info.title);
+    ///     current = epub.next_chapter(&chapter_info, true)?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn next_chapter(&self, current: &ChapterInfo, skip_non_linear: bool) -> Result<Option<ChapterInfo>> {
+        self.adjacent_chapter(current, true, skip_non_linear)
+    }
+    
+    /// 获取脊柱顺序中的上一个章节
+    /// 
+    /// # 参数
+    /// * `current` - 当前章节信息
+    /// * `skip_non_linear` - 是否跳过非线性的脊柱项（`linear="no"`）
+    /// 
+    /// # 返回值
+    /// * `Result<Option<ChapterInfo>>` - 上一个章节，已是第一章或当前章节不在脊柱中时返回None
+    pub fn prev_chapter(&self, current: &ChapterInfo, skip_non_linear: bool) -> Result<Option<ChapterInfo>> {
+        self.adjacent_chapter(current, false, skip_non_linear)
     }
     
-    /// 提取封面图片
-    fn extract_cover_image(&self, base_dir: &str, file_path: &str) -> Result<Option<CoverImage>> {
-        if !Self::is_image_file(file_path) {
+    /// 查找脊柱顺序中与当前章节相邻的章节
+    fn adjacent_chapter(&self, current: &ChapterInfo, forward: bool, skip_non_linear: bool) -> Result<Option<ChapterInfo>> {
+        let chapters = self.chapter_list()?;
+        
+        // 同一清单项可能在脊柱中出现多次，优先按位置匹配
+        let Some(position) = chapters.iter()
+            .position(|chapter| chapter.order == current.order && chapter.id == current.id)
+            .or_else(|| chapters.iter().position(|chapter| chapter.id == current.id))
+        else {
             return Ok(None);
-        }
+        };
         
-        let full_path = if base_dir.is_empty() {
-            file_path.to_string()
+        let accept = |chapter: &&ChapterInfo| !skip_non_linear || chapter.linear;
+        let adjacent = if forward {
+            chapters[position + 1..].iter().find(accept)
         } else {
-            format!("{}/{}", base_dir, file_path)
+            chapters[..position].iter().rev().find(accept)
         };
         
-        match self.read_binary_file(&full_path) {
-            Ok(data) => {
-                let format = Self::detect_image_format(&data, file_path);
-                let filename = file_path.split('/').last().unwrap_or(file_path).to_string();
-                
-                Ok(Some(CoverImage {
-                    data,
-                    format,
-                    filename,
-                }))
-            }
-            Err(_) => Ok(None),
-        }
+        Ok(adjacent.cloned())
     }
     
-    /// 检测图片格式
-    fn detect_image_format(data: &[u8], file_path: &str) -> String {
-        // 通过文件头检测
-        if data.len() >= 2 {
-            match &data[0..2] {
-                [0xFF, 0xD8] => return "jpeg".to_string(),
-                [0x89, 0x50] if data.len() >= 8 && &data[0..8] == b"\x89PNG\r\n\x1a\n" => return "png".to_string(),
-                [0x47, 0x49] if data.len() >= 6 && &data[0..6] == b"GIF87a" => return "gif".to_string(),
-                [0x47, 0x49] if data.len() >= 6 && &data[0..6] == b"GIF89a" => return "gif".to_string(),
-                _ => {}
-            }
+    /// 获取指定章节内容
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Chapter>` - 章节内容
+    pub fn chapter(&self, chapter_info: &ChapterInfo) -> Result<Chapter> {
+        let full_path = self.chapter_full_path(chapter_info)?;
+        let content = self.read_file(&full_path)?;
+        
+        Ok(Chapter {
+            info: chapter_info.clone(),
+            content,
+        })
+    }
+    
+    /// 获取章节的文本内容
+    /// 
+    /// 适用于脊柱中的任意章节，不依赖目录树（没有NCX或nav文档时同样可用）。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// * `formatted` - 是否保留段落、标题、列表等结构（与`ExportFormat::Formatted`导出的文本一致），
+    ///   为false时返回合并空白后的纯文本（与`Chapter::text()`一致）
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 章节文本
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// println!("{}", epub.chapter_text(&chapters[0], true)?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_text(&self, chapter_info: &ChapterInfo, formatted: bool) -> Result<String> {
+        let chapter = self.chapter(chapter_info)?;
+        if formatted {
+            Ok(TocTreeNode::convert_html_to_formatted_text(&chapter.content, &TextOptions::default()))
+        } else {
+            Ok(chapter.text())
         }
+    }
+    
+    /// 获取章节内容，并将其中的相对链接改写为EPUB内的绝对路径
+    /// 
+    /// 所有元素的`href`、`src`和`poster`属性中的内部链接都会被解析为以`/`开头的完整路径
+    /// （如`../images/x.jpg`改写为`/OEBPS/images/x.jpg`），锚点保留。外部链接、`data:`URI
+    /// 和纯锚点保持不变。适用于在WebView中显示章节：拦截以`/`开头的请求，
+    /// 去掉开头的`/`即可得到资源在EPUB中的路径。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Chapter>` - 链接已改写的章节内容
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// let chapter = epub.chapter_with_resolved_links(&chapters[0])?;
+    /// println!("{}", chapter.content);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_with_resolved_links(&self, chapter_info: &ChapterInfo) -> Result<Chapter> {
+        let mut chapter = self.chapter(chapter_info)?;
+        let chapter_path = self.chapter_full_path(chapter_info)?;
         
-        // 回退到文件扩展名
-        std::path::Path::new(file_path)
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("unknown")
-            .to_lowercase()
+        let links: HashMap<String, String> = extract_link_refs(&chapter.content)
+            .into_iter()
+            .map(|href| {
+                let absolute = format!("/{}", resolve_href(parent_dir(&chapter_path), &href));
+                let target = Self::with_fragment(&absolute, &href);
+                (href, target)
+            })
+            .collect();
+        chapter.content = rewrite_link_attributes(&chapter.content, &links);
+        
+        Ok(chapter)
     }
     
-    /// 检查是否为图片文件
-    fn is_image_file(file_path: &str) -> bool {
-        let lower_path = file_path.to_lowercase();
-        lower_path.ends_with(".jpg") || 
-        lower_path.ends_with(".jpeg") || 
-        lower_path.ends_with(".png") || 
-        lower_path.ends_with(".gif") || 
-        lower_path.ends_with(".webp") ||
-        lower_path.ends_with(".svg")
+    /// 获取全书的纯文本内容
+    /// 
+    /// 按阅读顺序拼接所有章节的纯文本，章节之间以空行分隔。
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 全书纯文本
+    pub fn full_text(&self) -> Result<String> {
+        Ok(self.full_text_indexed()?.0)
     }
     
-    /// 检查媒体类型是否为图片
-    fn is_image_media_type(media_type: &str) -> bool {
-        media_type.starts_with("image/")
+    /// 获取去除章节重复页眉页脚后的全书纯文本
+    /// 
+    /// 与`full_text()`相同，但会尽力去除在大多数章节首尾重复出现的行
+    /// （如每章都有的书名、作者），适用于生成干净的语料。检测规则见
+    /// [`strip_boilerplate`](crate::epub::text::strip_boilerplate)，可能误删正文，
+    /// 因此需要显式选择使用。
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 全书纯文本
+    pub fn full_text_without_boilerplate(&self) -> Result<String> {
+        let mut texts: Vec<String> = self.chapters()?.iter()
+            .map(|chapter| TocTreeNode::strip_html_tags(&chapter.content, &TextOptions::default()))
+            .collect();
+        strip_boilerplate(&mut texts);
+        Ok(texts.join("\n\n"))
     }
     
-    /// 检查文件是否存在
-    fn file_exists(&self, filename: &str) -> bool {
-        // 清理文件路径，去掉锚点和查询参数
-        let clean_path = self.clean_file_path(filename);
+    /// 统计全书的词频
+    /// 
+    /// 中日韩文字按单字统计，其他文字按单词统计并转换为小写。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<(String, usize)>>` - (词, 出现次数)，按次数从高到低排序
+    pub fn vocabulary(&self) -> Result<Vec<(String, usize)>> {
+        self.vocabulary_with(Tokenization::default())
+    }
+    
+    /// 使用指定的中日韩文字分词方式统计全书的词频
+    /// 
+    /// 需要统计单个章节时，可对 [`Chapter::text`] 的结果调用
+    /// [`term_frequencies`](crate::epub::text::term_frequencies)。
+    /// 
+    /// # 参数
+    /// * `tokenization` - 中日韩文字的分词方式
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<(String, usize)>>` - (词, 出现次数)，按次数从高到低排序
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, Tokenization};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for (term, count) in epub.vocabulary_with(Tokenization::Bigrams)?.iter().take(20) {
+    ///     println!("{}\t{}", term, count);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn vocabulary_with(&self, tokenization: Tokenization) -> Result<Vec<(String, usize)>> {
+        Ok(term_frequencies(&self.full_text()?, tokenization))
+    }
+    
+    /// 获取全书纯文本以及每个章节在其中的位置范围
+    /// 
+    /// 用于为外部全文搜索引擎建立索引：搜索命中的偏移量可以通过
+    /// 返回的章节范围换算回具体章节。偏移量为字节偏移，可直接用于
+    /// 字符串切片（`&text[span.start..span.end]`）。
+    /// 
+    /// # 返回值
+    /// * `Result<(String, Vec<ChapterSpan>)>` - 全书纯文本和章节范围列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let (text, spans) = epub.full_text_indexed()?;
+    /// if let Some(offset) = text.find("关键词") {
+    ///     if let Some(span) = spans.iter().find(|s| s.start <= offset && offset < s.end) {
+    ///         println!("在章节 {} 中找到", span.chapter.title);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn full_text_indexed(&self) -> Result<(String, Vec<ChapterSpan>)> {
+        let mut text = String::new();
+        let mut spans = Vec::new();
         
-        if let Ok(mut archive) = self.archive.lock() {
-            archive.by_name(&clean_path).is_ok()
-        } else {
-            false
+        for chapter in self.chapters()? {
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            let start = text.len();
+            text.push_str(&TocTreeNode::strip_html_tags(&chapter.content, &TextOptions::default()));
+            spans.push(ChapterSpan {
+                chapter: chapter.info,
+                start,
+                end: text.len(),
+            });
         }
+        
+        Ok((text, spans))
     }
     
-    /// 去掉URL中的锚点和查询参数部分
+    /// 统计全书及每个章节的字符数和词数
+    /// 
+    /// 按脊柱顺序逐章提取纯文本，词数的统计规则见
+    /// [`count_words`](crate::epub::text::count_words)。读取失败的章节会被跳过并记录警告。
+    /// 
+    /// # 返回值
+    /// * `Result<ContentStatistics>` - 字数统计
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let stats = epub.content_statistics()?;
+    /// println!("共 {} 字，预计阅读 {} 分钟", stats.total_chars, stats.estimated_reading_time(400).as_secs() / 60);
+    /// for (title, chars, _) in &stats.chapters {
+    ///     println!("{}: {} 字", title, chars);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn content_statistics(&self) -> Result<ContentStatistics> {
+        let mut statistics = ContentStatistics::default();
+        
+        for chapter in self.chapter_iter() {
+            let chapter = match chapter {
+                Ok(chapter) => chapter,
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), None));
+                    continue;
+                }
+            };
+            let text = chapter.text();
+            let chars = text.chars().filter(|c| !c.is_whitespace()).count();
+            let words = count_words(&text);
+            statistics.total_chars += chars;
+            statistics.total_words += words;
+            statistics.chapters.push((chapter.info.title, chars, words));
+        }
+        
+        Ok(statistics)
+    }
+    
+    /// 统计单个章节的词数
+    /// 
+    /// 去除HTML标签后按[`count_words`](crate::epub::text::count_words)的规则统计
+    /// （中日韩字符逐字计数），与`content_statistics()`中该章节的词数一致。
+    /// 结果按章节id缓存，重复查询不会再次读取章节内容。
     /// 
     /// # 参数
-    /// * `path` - 文件路径，可能包含锚点（#fragment）或查询参数
+    /// * `chapter_info` - 章节信息
     /// 
     /// # 返回值
-    /// * `String` - 清理后的文件路径
-    fn clean_file_path(&self, path: &str) -> String {
-        // 去掉锚点部分（#之后的内容）
-        let without_fragment = if let Some(hash_pos) = path.find('#') {
-            &path[..hash_pos]
+    /// * `Result<usize>` - 章节词数
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     println!("{}: {} 词", chapter_info.title, epub.chapter_word_count(&chapter_info)?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_word_count(&self, chapter_info: &ChapterInfo) -> Result<usize> {
+        let cached = self.word_counts.lock()
+            .ok()
+            .and_then(|word_counts| word_counts.get(&chapter_info.id).copied());
+        if let Some(words) = cached {
+            return Ok(words);
+        }
+        
+        let words = count_words(&self.chapter(chapter_info)?.text());
+        if let Ok(mut word_counts) = self.word_counts.lock() {
+            word_counts.insert(chapter_info.id.clone(), words);
+        }
+        Ok(words)
+    }
+    
+    /// 计算章节内容的哈希值
+    /// 
+    /// 对合并空白后的纯文本（与`Chapter::text()`一致）计算SHA-256，
+    /// 因此只有文字内容的变化会改变哈希值，HTML格式、空白或文件编码的差异不会。
+    /// 可用于同步批注时检测出版方是否悄悄修改了章节。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 64位小写十六进制哈希值
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     println!("{}: {}", chapter_info.id, epub.chapter_hash(&chapter_info)?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_hash(&self, chapter_info: &ChapterInfo) -> Result<String> {
+        let text = self.chapter(chapter_info)?.text();
+        Ok(format!("{:x}", Sha256::digest(text.as_bytes())))
+    }
+    
+    /// 计算全书内容指纹
+    /// 
+    /// 按脊柱顺序拼接各章节的`chapter_hash()`后再计算SHA-256。
+    /// 同一本书重新下载后（即使ZIP打包方式或元数据不同）指纹保持不变，
+    /// 任一章节的文字内容变化、章节增删或顺序调整都会改变指纹。
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 64位小写十六进制指纹
+    pub fn content_fingerprint(&self) -> Result<String> {
+        let mut hasher = Sha256::new();
+        for chapter_info in self.chapter_list()? {
+            hasher.update(self.chapter_hash(&chapter_info)?.as_bytes());
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+    
+    /// 在全书正文中搜索关键词
+    /// 
+    /// 有目录树时按目录顺序搜索各目录项的纯文本（多个目录项指向同一文件和锚点时只搜索一次），
+    /// 否则按脊柱顺序搜索各章节。匹配不区分大小写，重叠的匹配分别产生结果。
+    /// 读取失败的章节会被跳过并记录警告。
+    /// 
+    /// # 参数
+    /// * `query` - 关键词
+    /// * `options` - 搜索选项
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<SearchHit>>` - 按阅读顺序排列的命中结果
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, SearchOptions};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for hit in epub.search("rust", SearchOptions::new().with_whole_word(true))? {
+    ///     println!("[{}] {}", hit.chapter_title, hit.snippet);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<SearchHit>> {
+        let contents: Vec<(String, String, String)> = match self.toc_tree()? {
+            Some(toc_tree) => {
+                let mut nodes = Vec::new();
+                TocTree::collect_nodes_in_order(&toc_tree.roots, &mut nodes);
+                let mut searched = HashSet::new();
+                nodes.into_iter()
+                    .filter(|node| searched.insert(node.content_key()))
+                    .filter_map(|node| match toc_tree.get_node_text_content(node) {
+                        Ok(text) => Some((node.id.clone(), node.title.clone(), text)),
+                        Err(e) => {
+                            self.warn(EpubWarning::new(format!("无法读取章节文本 '{}' ({}): {}", node.title, node.id, e), None));
+                            None
+                        }
+                    })
+                    .collect()
+            }
+            None => {
+                let mut searched = HashSet::new();
+                self.chapter_iter()
+                    .filter_map(|chapter| match chapter {
+                        Ok(chapter) => searched.insert(chapter.info.path.clone()).then(|| {
+                            let text = chapter.text();
+                            (chapter.info.id, chapter.info.title, text)
+                        }),
+                        Err(e) => {
+                            self.warn(EpubWarning::new(format!("无法读取章节: {}", e), None));
+                            None
+                        }
+                    })
+                    .collect()
+            }
+        };
+        
+        let mut hits = Vec::new();
+        for (id, title, text) in &contents {
+            for (start, end) in find_matches(text, query, options) {
+                hits.push(SearchHit {
+                    chapter_id: id.clone(),
+                    chapter_title: title.clone(),
+                    offset: start,
+                    snippet: snippet(text, start, end),
+                });
+            }
+        }
+        
+        Ok(hits)
+    }
+    
+    /// 按阅读顺序逐章转换内容并写入输出
+    /// 
+    /// 每次只读取一个章节，转换结果之间以空行分隔，适用于翻译、批注等需要
+    /// 自定义处理后重新输出的场景。与`chapters()`不同，读取章节失败时会立即
+    /// 返回错误，而不是跳过该章节。
+    /// 
+    /// # 参数
+    /// * `out` - 输出目标
+    /// * `transform` - 章节转换函数，返回要写入的内容
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 读取章节或写入失败时返回错误
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let mut out = std::io::stdout();
+    /// epub.process_chapters(&mut out, |chapter| {
+    ///     format!("# {}\n{}", chapter.info.title, chapter.text())
+    /// })?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn process_chapters<W: Write>(&self, out: &mut W, mut transform: impl FnMut(&Chapter) -> String) -> Result<()> {
+        for (index, chapter_info) in self.chapter_list()?.iter().enumerate() {
+            let chapter = self.chapter(chapter_info)?;
+            if index > 0 {
+                out.write_all(b"\n\n")?;
+            }
+            out.write_all(transform(&chapter).as_bytes())?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+    
+    /// 获取章节中的所有超链接
+    /// 
+    /// 链接按文档顺序返回，并分类为外部链接、指向其他章节的链接、
+    /// 章节内锚点链接或无法识别的链接，可用于检查失效链接或构建章节引用关系图。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ChapterLink>>` - 章节中的链接列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Epub, LinkKind};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for link in epub.chapter_links(&chapter_info)? {
+    ///         if link.kind == LinkKind::External {
+    ///             println!("{}: {}", chapter_info.title, link.href);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_links(&self, chapter_info: &ChapterInfo) -> Result<Vec<ChapterLink>> {
+        let chapter = self.chapter(chapter_info)?;
+        let chapter_path = self.chapter_full_path(chapter_info)?;
+        let documents = self.content_document_paths()?;
+        Ok(extract_links(&chapter.content, &chapter_path, &documents))
+    }
+    
+    /// 获取全书所有章节中的内部超链接，并检查链接目标是否存在
+    /// 
+    /// 按阅读顺序遍历脊柱中的章节，外部链接（如`http:`、`mailto:`）会被跳过。
+    /// 目标文件按清单检查；带锚点的链接还会检查目标文档中是否存在对应`id`的元素，
+    /// 可用于发现失效的脚注链接等常见问题。无法读取的章节会记录警告并跳过。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<LinkRef>>` - 按章节和文档顺序排列的内部链接
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for link in epub.internal_links()? {
+    ///     if !link.target_exists || !link.anchor_exists {
+    ///         println!("{}: 失效链接 {}", link.source, link.href);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn internal_links(&self) -> Result<Vec<LinkRef>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        let manifest_paths: HashSet<String> = opf.manifest.values()
+            .map(|item| resolve_href(&paths.opf_directory, &item.href))
+            .collect();
+        let documents = self.content_document_paths()?;
+        // 文档路径 -> 文档中所有元素的id
+        let mut document_ids: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut links = Vec::new();
+        
+        for chapter_info in self.chapter_list()? {
+            let source = self.chapter_full_path(&chapter_info)?;
+            let chapter = match self.chapter(&chapter_info) {
+                Ok(chapter) => chapter,
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), Some(source)));
+                    continue;
+                }
+            };
+            document_ids.entry(source.clone()).or_insert_with(|| element_ids(&chapter.content));
+            
+            for link in extract_links(&chapter.content, &source, &documents) {
+                let href = link.href.trim();
+                if link.kind == LinkKind::External || href.is_empty() {
+                    continue;
+                }
+                
+                let target = if href.starts_with('#') {
+                    source.clone()
+                } else {
+                    resolve_href(parent_dir(&source), href)
+                };
+                let fragment = href.split_once('#')
+                    .map(|(_, fragment)| fragment.to_string())
+                    .filter(|fragment| !fragment.is_empty());
+                let target_exists = manifest_paths.contains(&target);
+                
+                let anchor_exists = match &fragment {
+                    None => true,
+                    Some(_) if !documents.contains(&target) => false,
+                    Some(fragment) => document_ids.entry(target.clone())
+                        .or_insert_with(|| self.read_chapter_file(&target)
+                            .map(|html| element_ids(&html))
+                            .unwrap_or_default())
+                        .contains(fragment),
+                };
+                
+                links.push(LinkRef {
+                    source: source.clone(),
+                    href: link.href,
+                    target,
+                    fragment,
+                    target_exists,
+                    anchor_exists,
+                });
+            }
+        }
+        
+        Ok(links)
+    }
+    
+    /// 获取章节的语言
+    /// 
+    /// 读取章节根元素（或`<body>`）上的`xml:lang`/`lang`属性，
+    /// 章节未声明语言时使用书籍元数据中的语言。根元素声明了语言时只解析到根元素为止，
+    /// 不会解析整个章节文档，适合为断字或TTS选择语言时频繁调用。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Option<String>>` - 语言代码，章节和书籍都未声明时为`None`
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     let language = epub.chapter_language(&chapter_info)?;
+    ///     println!("{}: {}", chapter_info.title, language.as_deref().unwrap_or("未知"));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_language(&self, chapter_info: &ChapterInfo) -> Result<Option<String>> {
+        let chapter = self.chapter(chapter_info)?;
+        if let Some(language) = root_language(&chapter.content) {
+            return Ok(Some(language));
+        }
+        match declared_language(&chapter.content) {
+            Some(language) => Ok(Some(language)),
+            None => Ok(self.opf()?.metadata.language()),
+        }
+    }
+    
+    /// 生成章节中某个元素的阅读位置标识（简化版CFI）
+    /// 
+    /// 只生成"脊柱项 + 元素步进"形式的CFI，如`epubcfi(/6/4[ch1]!/4/2[p1])`，
+    /// 不包含字符偏移，支持范围见[`cfi`](crate::epub::cfi)模块说明。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// * `element_path` - 从`<html>`元素开始的子元素索引路径（每级从0开始），
+    ///   如`[1, 0]`表示`<body>`的第一个子元素
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - CFI字符串，章节不在脊柱中或路径不存在时返回错误
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// let cfi = epub.cfi_for(&chapters[0], &[1, 0])?;
+    /// if let Some(position) = epub.resolve_cfi(&cfi)? {
+    ///     println!("{}: {}", position.chapter_info.title, position.text);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cfi_for(&self, chapter_info: &ChapterInfo, element_path: &[usize]) -> Result<String> {
+        let opf = self.opf()?;
+        let spine_index = opf.spine.iter()
+            .position(|spine_item| spine_item.idref == chapter_info.id)
+            .ok_or_else(|| EpubError::InvalidEpub(format!("章节不在脊柱中: {}", chapter_info.id)))?;
+        
+        let chapter = self.chapter(chapter_info)?;
+        let (element_ids, _) = element_at_path(&chapter.content, element_path)
+            .ok_or_else(|| EpubError::ParseError(format!(
+                "章节 {} 中不存在元素路径 {:?}", chapter_info.path, element_path
+            )))?;
+        
+        Ok(format_cfi(spine_index, &chapter_info.id, element_path, &element_ids))
+    }
+    
+    /// 解析由`cfi_for()`生成的阅读位置标识
+    /// 
+    /// 脊柱项带id断言时优先按id定位，以便在脊柱顺序变化后仍能找到章节；
+    /// 末尾的文本节点步进和字符偏移会被忽略，位置落在其父元素上。
+    /// 
+    /// # 参数
+    /// * `cfi` - CFI字符串
+    /// 
+    /// # 返回值
+    /// * `Result<Option<ResolvedPosition>>` - 解析后的位置，
+    ///   CFI不在支持范围内或无法定位时为`None`
+    pub fn resolve_cfi(&self, cfi: &str) -> Result<Option<ResolvedPosition>> {
+        let Some(parts) = parse_cfi(cfi) else {
+            return Ok(None);
+        };
+        
+        let opf = self.opf()?;
+        let spine_index = parts.idref.as_ref()
+            .and_then(|idref| opf.spine.iter().position(|spine_item| &spine_item.idref == idref))
+            .unwrap_or(parts.spine_index);
+        let Some(spine_item) = opf.spine.get(spine_index) else {
+            return Ok(None);
+        };
+        let Some(chapter_info) = self.chapter_list()?.into_iter().find(|chapter| chapter.id == spine_item.idref) else {
+            return Ok(None);
+        };
+        
+        let chapter = self.chapter(&chapter_info)?;
+        let Some((_, text)) = element_at_path(&chapter.content, &parts.element_path) else {
+            return Ok(None);
+        };
+        
+        Ok(Some(ResolvedPosition {
+            spine_index,
+            chapter_info,
+            element_path: parts.element_path,
+            text,
+        }))
+    }
+    
+    /// 获取章节中所有可作为链接目标的锚点
+    /// 
+    /// 返回章节正文中每个带`id`属性的元素及其附近文本，可用于生成指向具体段落的
+    /// 深层链接，或检查链接中的锚点是否存在。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<(String, String)>>` - 按文档顺序排列的(id, 附近文本)
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// for (id, text) in epub.chapter_anchors(&chapters[0])? {
+    ///     println!("{}#{}: {}", chapters[0].path, id, text);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_anchors(&self, chapter_info: &ChapterInfo) -> Result<Vec<(String, String)>> {
+        let chapter = self.chapter(chapter_info)?;
+        Ok(extract_anchors(&chapter.content))
+    }
+    
+    /// 获取章节中图片的链接与替代文本
+    /// 
+    /// 图片链接会解析为清单（manifest）中的href，可直接与`images()`返回的
+    /// `ImageInfo::path`对应；不在清单中的图片返回相对于OPF目录的路径。
+    /// 与[`Epub::image_accessibility`]相同，SVG中的`<image>`以及`aria-label`、`<title>`
+    /// 描述也会被读取。没有任何描述时替代文本为`None`；`alt=""`表示装饰性图片，
+    /// 返回空字符串，可用于无障碍检查时区分两者。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<(String, Option<String>)>>` - 按文档顺序排列的(图片href, 替代文本)
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for (src, alt) in epub.chapter_image_alts(&chapter_info)? {
+    ///         if alt.is_none() {
+    ///             println!("{}: 图片 {} 缺少替代文本", chapter_info.title, src);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_image_alts(&self, chapter_info: &ChapterInfo) -> Result<Vec<(String, Option<String>)>> {
+        let chapter = self.chapter(chapter_info)?;
+        let chapter_path = self.chapter_full_path(chapter_info)?;
+        self.resolve_image_alts(&chapter_path, &chapter.content)
+    }
+    
+    /// 提取已读取章节中图片的替代文本，并将图片链接解析为清单href
+    fn resolve_image_alts(&self, chapter_path: &str, html: &str) -> Result<Vec<(String, Option<String>)>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        
+        let manifest_hrefs: HashMap<String, &String> = opf.manifest.values()
+            .map(|item| (resolve_href(&paths.opf_directory, &item.href), &item.href))
+            .collect();
+        let opf_prefix = if paths.opf_directory.is_empty() {
+            String::new()
         } else {
-            path
+            format!("{}/", paths.opf_directory)
         };
         
-        // 去掉查询参数（?之后的内容）
-        let without_query = if let Some(query_pos) = without_fragment.find('?') {
-            &without_fragment[..query_pos]
-        } else {
-            without_fragment
-        };
+        Ok(extract_image_descriptions(html).into_iter()
+            .map(|(src, _, alt)| {
+                let full_path = resolve_href(parent_dir(chapter_path), &src);
+                let href = match manifest_hrefs.get(&full_path) {
+                    Some(href) => (*href).clone(),
+                    None => full_path.strip_prefix(&opf_prefix).unwrap_or(&full_path).to_string(),
+                };
+                (href, alt)
+            })
+            .collect())
+    }
+    
+    /// 汇总全书图片的替代文本，用于无障碍审查
+    /// 
+    /// 遍历每个章节中的`<img>`以及SVG中的`<image>`元素，读取`alt`、`aria-label`或
+    /// SVG的`<title>`作为替代文本，并按解析后的图片路径分组。同一张图片只要有一次
+    /// 使用缺少替代文本，`has_alt`即为`false`；`alt=""`的装饰性图片视为已提供替代文本。
+    /// 与[`Epub::chapter_image_alts`]不同，返回的路径相对于EPUB根目录。无法读取的章节
+    /// 会记录警告并跳过。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ImageAltInfo>>` - 按首次出现顺序排列的图片列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for image in epub.image_accessibility()?.iter().filter(|image| !image.has_alt) {
+    ///     for usage in image.missing_alt_usages() {
+    ///         println!("{} 在 {} 中缺少替代文本", image.path, usage.chapter_path);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn image_accessibility(&self) -> Result<Vec<ImageAltInfo>> {
+        let mut images: Vec<ImageAltInfo> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        
+        for chapter_info in self.chapter_list()? {
+            let chapter_path = self.chapter_full_path(&chapter_info)?;
+            let chapter = match self.chapter(&chapter_info) {
+                Ok(chapter) => chapter,
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), Some(chapter_path)));
+                    continue;
+                }
+            };
+            
+            for (src, element, alt) in extract_image_descriptions(&chapter.content) {
+                let path = resolve_href(parent_dir(&chapter_path), &src);
+                let index = *positions.entry(path.clone()).or_insert_with(|| {
+                    images.push(ImageAltInfo {
+                        path,
+                        usages: Vec::new(),
+                        has_alt: true,
+                    });
+                    images.len() - 1
+                });
+                
+                let image = &mut images[index];
+                image.has_alt &= alt.is_some();
+                image.usages.push(ImageUsage {
+                    chapter_path: chapter_path.clone(),
+                    element: element.to_string(),
+                    alt,
+                });
+            }
+        }
+        
+        Ok(images)
+    }
+    
+    /// 提取章节中的表格
+    /// 
+    /// 合并单元格（`colspan`/`rowspan`）会被展开为重复的单元格，使每个表格的
+    /// 所有行列数相同，原始的合并结构不会保留。嵌套表格作为独立的表格返回。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<Table>>` - 按文档顺序排列的表格
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for table in epub.chapter_tables(&chapter_info)? {
+    ///         println!("{}: {} 列, {} 行", chapter_info.title, table.headers.len(), table.rows.len());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_tables(&self, chapter_info: &ChapterInfo) -> Result<Vec<Table>> {
+        let chapter = self.chapter(chapter_info)?;
+        Ok(extract_tables(&chapter.content))
+    }
+    
+    /// 获取章节中的引文
+    /// 
+    /// 提取每个`<blockquote>`的正文（保留段落换行）及其出处，
+    /// 适用于摘录、书摘类应用。出处的识别规则见[`Quote`]。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<Quote>>` - 按文档顺序排列的引文
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for quote in epub.chapter_blockquotes(&chapter_info)? {
+    ///         println!("{}", quote.text);
+    ///         if let Some(cite) = &quote.cite {
+    ///             println!("—— {}", cite);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_blockquotes(&self, chapter_info: &ChapterInfo) -> Result<Vec<Quote>> {
+        let chapter = self.chapter(chapter_info)?;
+        Ok(extract_blockquotes(&chapter.content))
+    }
+    
+    /// 获取章节的结构化内容
+    /// 
+    /// 将章节HTML转换为标题、段落、图片、列表、引用和预格式文本组成的块序列，
+    /// 适合作为自定义渲染器的输入。转换规则见[`Block`]。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<Block>>` - 按文档顺序排列的内容块
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{Block, Epub};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for block in epub.chapter_blocks(&chapter_info)? {
+    ///         if let Block::Heading { level, text } = block {
+    ///             println!("{}{}", "  ".repeat(level as usize - 1), text);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_blocks(&self, chapter_info: &ChapterInfo) -> Result<Vec<Block>> {
+        let chapter = self.chapter(chapter_info)?;
+        Ok(extract_blocks(&chapter.content))
+    }
+    
+    /// 将章节中的每个表格导出为CSV文件
+    /// 
+    /// 文件名由章节序号、章节标题和表格序号组成，例如`003_数据_table1.csv`。
+    /// 章节中没有表格时不会创建任何文件。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// * `output_dir` - 输出目录（不存在时自动创建）
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<PathBuf>>` - 写入的CSV文件路径
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter_info in epub.chapter_list()? {
+    ///     for path in epub.export_tables_csv(&chapter_info, Path::new("tables"))? {
+    ///         println!("已导出: {}", path.display());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn export_tables_csv(&self, chapter_info: &ChapterInfo, output_dir: &Path) -> Result<Vec<PathBuf>> {
+        let tables = self.chapter_tables(chapter_info)?;
+        if tables.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        fs::create_dir_all(output_dir)?;
+        let base_name = TocTreeNode::generate_safe_filename(
+            &chapter_info.title,
+            &chapter_info.id,
+            chapter_info.order.unwrap_or(0),
+        );
+        
+        let mut paths = Vec::new();
+        for (index, table) in tables.iter().enumerate() {
+            let path = output_dir.join(format!("{}_table{}.csv", base_name, index + 1));
+            fs::write(&path, table.to_csv())?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+    
+    /// 导出包含所有依赖资源的独立章节
+    /// 
+    /// 将章节XHTML及其引用的样式表、图片、字体等资源（包括CSS中`url()`引用的资源）
+    /// 复制到输出目录，并将链接改写为平铺结构，使章节可以在浏览器中独立显示。
+    /// 同名资源会自动添加序号前缀以避免冲突。无法读取的资源会输出警告并跳过。
+    /// 
+    /// # 参数
+    /// * `chapter_info` - 章节信息
+    /// * `output_dir` - 输出目录（不存在时自动创建）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 操作结果
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// use std::path::Path;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chapters = epub.chapter_list()?;
+    /// epub.export_chapter_bundle(&chapters[0], Path::new("output/chapter1"))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn export_chapter_bundle(&self, chapter_info: &ChapterInfo, output_dir: &Path) -> Result<()> {
+        fs::create_dir_all(output_dir)?;
+        
+        let chapter = self.chapter(chapter_info)?;
+        let chapter_path = self.chapter_full_path(chapter_info)?;
+        let chapter_name = Self::file_name_of(&chapter_path).to_string();
+        
+        let mut bundled = HashMap::new();
+        let mut used_names = HashSet::from([chapter_name.clone()]);
+        let mut links = HashMap::new();
+        
+        for href in extract_resource_refs(&chapter.content) {
+            let full_path = resolve_href(parent_dir(&chapter_path), &href);
+            if let Some(name) = self.bundle_resource(&full_path, output_dir, &mut bundled, &mut used_names)? {
+                let target = Self::with_fragment(&name, &href);
+                links.insert(href, target);
+            }
+        }
+        let html = rewrite_link_attributes(&chapter.content, &links);
+        
+        fs::write(output_dir.join(chapter_name), html)?;
+        Ok(())
+    }
+    
+    /// 将全书合并为单个HTML文档
+    /// 
+    /// 按脊柱顺序将每个章节`<body>`的内容放入`<section id="chapter-{id}">`中，
+    /// 并在开头生成链接到各章节的`<nav>`目录，适用于打印或单页显示（合并TXT导出的HTML版本）。
+    /// 
+    /// * 指向书内章节的链接改写为文档内锚点：带锚点的链接指向原锚点，否则指向章节的`<section>`
+    /// * 图片等其他资源的链接改写为相对于EPUB根目录的路径
+    /// * 各章节引用的样式表去重后内联为`<style>`块，`<style>`元素的内容同样去重保留
+    /// 
+    /// 不同章节中重复的元素id不会被改名，此时锚点链接指向文档中第一个同名元素。
+    /// 无法读取的章节或样式表会输出警告并跳过。
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 完整的HTML文档
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// std::fs::write("book.html", epub.to_single_html()?)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_single_html(&self) -> Result<String> {
+        let info = self.book_info()?;
+        let chapters = self.chapter_list()?;
+        
+        let mut sections: HashMap<String, String> = HashMap::new();
+        for chapter_info in &chapters {
+            sections.entry(self.chapter_full_path(chapter_info)?)
+                .or_insert_with(|| format!("chapter-{}", chapter_info.id));
+        }
+        
+        let mut stylesheets = HashSet::new();
+        let mut styles: Vec<String> = Vec::new();
+        let mut toc = String::new();
+        let mut body = String::new();
+        let mut emitted = HashSet::new();
+        
+        for chapter_info in &chapters {
+            let chapter_path = self.chapter_full_path(chapter_info)?;
+            if !emitted.insert(chapter_path.clone()) {
+                continue;
+            }
+            let mut html = match self.read_file(&chapter_path) {
+                Ok(html) => html,
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), Some(chapter_path)));
+                    continue;
+                }
+            };
+            let chapter_dir = parent_dir(&chapter_path);
+            
+            let (links, inline_styles) = extract_styles(&html);
+            for href in links {
+                let css_path = resolve_href(chapter_dir, &href);
+                if !stylesheets.insert(css_path.clone()) {
+                    continue;
+                }
+                match self.read_file(&css_path) {
+                    Ok(css) => {
+                        let css = Self::resolve_css_urls(&css, parent_dir(&css_path));
+                        if !styles.contains(&css) {
+                            styles.push(css);
+                        }
+                    }
+                    Err(e) => self.warn(EpubWarning::new(format!("无法读取样式表: {}", e), Some(css_path))),
+                }
+            }
+            for css in inline_styles {
+                let css = Self::resolve_css_urls(&css, chapter_dir);
+                if !styles.contains(&css) {
+                    styles.push(css);
+                }
+            }
+            
+            let mut links = HashMap::new();
+            for href in extract_link_refs(&html) {
+                let full_path = resolve_href(chapter_dir, &href);
+                let target = match (sections.get(&full_path), href.split_once('#')) {
+                    (Some(_), Some((_, fragment))) if !fragment.is_empty() => format!("#{}", fragment),
+                    (Some(section_id), _) => format!("#{}", section_id),
+                    (None, _) => Self::with_fragment(&full_path, &href),
+                };
+                links.insert(href, target);
+            }
+            html = rewrite_link_attributes(&html, &links);
+            
+            let section_id = &sections[&chapter_path];
+            toc.push_str(&format!(
+                "<li><a href=\"#{}\">{}</a></li>\n",
+                escape_html(section_id, true),
+                escape_html(&chapter_info.title, false)
+            ));
+            body.push_str(&format!(
+                "<section id=\"{}\">\n{}\n</section>\n",
+                escape_html(section_id, true),
+                body_inner_html(&html).trim()
+            ));
+        }
+        
+        let mut document = String::from("<!DOCTYPE html>\n");
+        match &info.language {
+            Some(language) => document.push_str(&format!("<html lang=\"{}\">\n", escape_html(language, true))),
+            None => document.push_str("<html>\n"),
+        }
+        document.push_str("<head>\n<meta charset=\"utf-8\"/>\n");
+        document.push_str(&format!("<title>{}</title>\n", escape_html(&info.title, false)));
+        for css in &styles {
+            document.push_str(&format!("<style>\n{}\n</style>\n", css.trim()));
+        }
+        document.push_str("</head>\n<body>\n");
+        document.push_str(&format!("<nav id=\"toc\">\n<ol>\n{}</ol>\n</nav>\n", toc));
+        document.push_str(&body);
+        document.push_str("</body>\n</html>\n");
+        
+        Ok(document)
+    }
+    
+    /// 将CSS中`url()`引用的资源改写为相对于EPUB根目录的路径
+    fn resolve_css_urls(css: &str, base_dir: &str) -> String {
+        let mut css = css.to_string();
+        for url in extract_css_urls(&css) {
+            let full_path = resolve_href(base_dir, &url);
+            css = Self::rewrite_css_refs(&css, &url, &Self::with_fragment(&full_path, &url));
+        }
+        css
+    }
+    
+    /// 获取所有章节内容
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<Chapter>>` - 所有章节内容
+    pub fn chapters(&self) -> Result<Vec<Chapter>> {
+        let chapter_list = self.chapter_list()?;
+        let mut chapters = Vec::new();
+        
+        for chapter_info in chapter_list {
+            match self.chapter(&chapter_info) {
+                Ok(chapter) => chapters.push(chapter),
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), Some(chapter_info.path.clone())));
+                    continue;
+                }
+            }
+        }
+        
+        Ok(chapters)
+    }
+    
+    /// 按脊柱顺序逐个读取章节内容
+    /// 
+    /// 与`chapters()`不同，该方法只在每次调用`next()`时读取一个章节，
+    /// 适合以固定内存处理大型书籍。读取失败的章节产生`Err`，之后的`next()`
+    /// 会继续读取下一个章节；获取章节列表失败时只产生一个`Err`。
+    /// 
+    /// # 返回值
+    /// * `impl Iterator<Item = Result<Chapter>>` - 借用当前EPUB的章节迭代器
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for chapter in epub.chapter_iter() {
+    ///     match chapter {
+    ///         Ok(chapter) => println!("{}: {} 字节", chapter.info.title, chapter.content.len()),
+    ///         Err(e) => eprintln!("跳过章节: {}", e),
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chapter_iter(&self) -> impl Iterator<Item = Result<Chapter>> + '_ {
+        let (chapter_list, list_error) = match self.chapter_list() {
+            Ok(chapter_list) => (chapter_list, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        
+        list_error.map(Err).into_iter()
+            .chain(chapter_list.into_iter().map(move |chapter_info| self.chapter(&chapter_info)))
+    }
+    
+    /// 获取图片资源列表
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ImageInfo>>` - 图片资源信息列表
+    pub fn images(&self) -> Result<Vec<ImageInfo>> {
+        let opf = self.opf()?;
+        let mut images = Vec::new();
+        
+        for (id, item) in &opf.manifest {
+            if Self::is_image_media_type(&item.media_type) {
+                images.push(ImageInfo {
+                    id: id.clone(),
+                    path: item.href.clone(),
+                    media_type: item.media_type.clone(),
+                });
+            }
+        }
+        
+        Ok(images)
+    }
+    
+    /// 获取嵌入字体列表
+    /// 
+    /// 按清单中声明的媒体类型识别字体（`font/*`以及`application/vnd.ms-opentype`等旧式类型），
+    /// 按路径排序。`META-INF/encryption.xml`中引用的字体会标记为`obfuscated`。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<FontInfo>>` - 字体信息列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for font in epub.fonts()? {
+    ///     let data = epub.font_data(&font)?;
+    ///     println!("{} ({}, {} 字节)", font.path, font.media_type, data.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn fonts(&self) -> Result<Vec<FontInfo>> {
+        let paths = self.paths()?;
+        let encrypted = self.encrypted_resources()?;
+        
+        Ok(self.categorize_manifest()?.fonts
+            .into_iter()
+            .map(|item| {
+                let full_path = resolve_href(&paths.opf_directory, &item.href);
+                FontInfo {
+                    id: item.id.clone(),
+                    path: item.href.clone(),
+                    media_type: item.media_type.clone(),
+                    obfuscated: encrypted.iter().any(|resource| resource.uri == full_path),
+                }
+            })
+            .collect())
+    }
+    
+    /// 获取字体的二进制数据
+    /// 
+    /// 按IDPF算法（`http://www.idpf.org/2008/embedding`）混淆的字体会使用OPF的唯一标识符自动还原，
+    /// 其他算法（如Adobe的混淆算法）加密的字体按原样返回。
+    /// 
+    /// # 参数
+    /// * `font_info` - 字体信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<u8>>` - 字体文件数据
+    pub fn font_data(&self, font_info: &FontInfo) -> Result<Vec<u8>> {
+        let paths = self.paths()?;
+        let full_path = resolve_href(&paths.opf_directory, &font_info.path);
+        let mut data = self.read_binary_file(&full_path)?;
+        
+        let idpf_obfuscated = font_info.obfuscated && self.encrypted_resources()?.iter().any(|resource| {
+            resource.uri == full_path && resource.algorithm.as_deref() == Some(IDPF_OBFUSCATION_ALGORITHM)
+        });
+        if idpf_obfuscated {
+            let identifier = self.identifier()?
+                .ok_or_else(|| EpubError::InvalidEpub(format!("无法还原混淆字体，OPF中没有唯一标识符: {}", full_path)))?;
+            deobfuscate_idpf(&mut data, &identifier);
+        }
+        
+        Ok(data)
+    }
+    
+    /// 读取`META-INF/encryption.xml`中声明的加密资源
+    /// 
+    /// 文件不存在时返回空列表，解析失败时记录警告并返回空列表。
+    fn encrypted_resources(&self) -> Result<Vec<EncryptedResource>> {
+        if !self.file_exists(ENCRYPTION_PATH) {
+            return Ok(Vec::new());
+        }
+        let content = self.read_file(ENCRYPTION_PATH)?;
+        match parse_encryption_xml(&content) {
+            Ok(resources) => Ok(resources),
+            Err(e) => {
+                self.warn(EpubWarning::new(
+                    format!("encryption.xml解析失败: {}", e),
+                    Some(ENCRYPTION_PATH.to_string()),
+                ));
+                Ok(Vec::new())
+            }
+        }
+    }
+    
+    /// 检查脊柱中的章节是否被DRM加密
+    /// 
+    /// 只有encryption.xml声明了脊柱文档的加密时才视为DRM保护，
+    /// 仅混淆嵌入字体的书籍可以正常读取。OPF无法解析时跳过检查，由后续读取报告错误。
+    fn check_drm(&self) -> Result<()> {
+        let encrypted = self.encrypted_resources()?;
+        if encrypted.is_empty() {
+            return Ok(());
+        }
+        let (Ok(opf), Ok(paths)) = (self.opf(), self.paths()) else {
+            return Ok(());
+        };
+        
+        let encrypted_chapter = opf.spine.iter()
+            .filter_map(|spine_item| opf.get_manifest_item(&spine_item.idref))
+            .map(|item| resolve_href(&paths.opf_directory, &item.href))
+            .find_map(|chapter_path| encrypted.iter().find(|resource| resource.uri == chapter_path));
+        
+        match encrypted_chapter {
+            Some(resource) => Err(EpubError::DrmProtected(drm_scheme_name(
+                |path| self.file_exists(path),
+                resource.algorithm.as_deref(),
+            ))),
+            None => Ok(()),
+        }
+    }
+    
+    /// 获取封面图片
+    /// 
+    /// 封面的查找规则与`cover_info()`相同，找到后读取图片数据。
+    /// 某个候选图片无法读取（如超过大小上限）时继续尝试下一个候选。
+    /// 
+    /// # 返回值
+    /// * `Result<Option<CoverImage>>` - 封面图片（如果存在）
+    pub fn cover(&self) -> Result<Option<CoverImage>> {
+        let paths = self.paths()?;
+        
+        for cover_info in self.cover_lookup_order()? {
+            let full_path = resolve_href(&paths.opf_directory, &cover_info.path);
+            let Ok(data) = self.read_binary_file(&full_path) else {
+                continue;
+            };
+            let format = Self::detect_image_format(&data, &cover_info.path);
+            let filename = cover_info.path.split('/').last().unwrap_or(&cover_info.path).to_string();
+            
+            return Ok(Some(CoverImage {
+                data,
+                format,
+                filename,
+            }));
+        }
+        
+        Ok(None)
+    }
+    
+    /// 获取封面图片的信息而不读取图片数据
+    /// 
+    /// 依次尝试：
+    /// 1. OPF中声明的封面（清单项的`cover-image`属性或metadata中的`cover`）
+    /// 2. 清单中其他具有`cover-image`属性的图片
+    /// 3. 常见的封面文件名（如`cover.jpg`）
+    /// 
+    /// 只有存在于EPUB中的图片文件才会被选中。不在清单中的封面文件`id`为空，
+    /// 媒体类型根据扩展名推断。
+    /// 
+    /// # 返回值
+    /// * `Result<Option<ImageInfo>>` - 封面图片信息（如果存在）
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// if let Some(cover) = epub.cover_info()? {
+    ///     println!("{} ({})", cover.path, cover.media_type);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cover_info(&self) -> Result<Option<ImageInfo>> {
+        Ok(self.cover_lookup_order()?.into_iter().next())
+    }
+    
+    /// 按`cover_info()`的查找顺序列出所有存在的封面候选图片
+    fn cover_lookup_order(&self) -> Result<Vec<ImageInfo>> {
+        let opf = self.opf()?;
+        let mut candidates = Vec::new();
+        
+        // 1. OPF中声明的封面
+        if let Some(cover_path) = opf.get_cover_path()
+            && let Some(cover) = self.cover_image_info(opf, &cover_path)?
+        {
+            candidates.push(cover);
+        }
+        
+        // 2. 尝试从manifest中查找cover-image属性
+        for item in opf.manifest.values() {
+            if item.is_cover_image()
+                && let Some(cover) = self.cover_image_info(opf, &item.href)?
+            {
+                candidates.push(cover);
+            }
+        }
+        
+        // 3. 尝试常见的封面文件名
+        let common_cover_names = [
+            "cover.jpg", "cover.jpeg", "cover.png", "cover.gif",
+            "Cover.jpg", "Cover.jpeg", "Cover.png", "Cover.gif",
+        ];
+        
+        for &cover_name in &common_cover_names {
+            if let Some(cover) = self.cover_image_info(opf, cover_name)? {
+                candidates.push(cover);
+            }
+        }
+        
+        Ok(candidates)
+    }
+    
+    /// 获取可能的封面图片候选列表
+    /// 
+    /// 当书籍没有声明封面时，可以使用此方法获取按可能性排序的图片列表，
+    /// 由用户界面让用户自行选择，而不是像 `cover()` 那样直接选定一张。
+    /// 
+    /// 排序依据（按优先级）：
+    /// 1. 在OPF中声明为封面的图片
+    /// 2. 清单ID或路径中包含"cover"的图片
+    /// 3. 出现在第一个脊柱页面中的图片
+    /// 4. 图片尺寸（面积）更大的图片
+    /// 5. 路径的字典序（保证结果稳定）
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ImageInfo>>` - 按可能性从高到低排列的图片列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for (rank, image) in epub.cover_candidates()?.iter().enumerate().take(3) {
+    ///     println!("{}. {}", rank + 1, image.path);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn cover_candidates(&self) -> Result<Vec<ImageInfo>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        let declared_cover = opf.get_cover_path()
+            .map(|href| resolve_href(&paths.opf_directory, &href));
+        let first_page_images = self.first_page_image_paths()?;
+        
+        let mut candidates: Vec<(u32, u64, ImageInfo)> = Vec::new();
+        for image in self.images()? {
+            let full_path = resolve_href(&paths.opf_directory, &image.path);
+            
+            let mut score = 0;
+            if declared_cover.as_deref() == Some(full_path.as_str()) {
+                score += 4;
+            }
+            if image.id.to_lowercase().contains("cover") || image.path.to_lowercase().contains("cover") {
+                score += 2;
+            }
+            if first_page_images.contains(&full_path) {
+                score += 1;
+            }
+            
+            let area = self.read_binary_file_head(&full_path, 64 * 1024)
+                .ok()
+                .and_then(|data| Self::detect_image_dimensions(&data))
+                .map(|(width, height)| width as u64 * height as u64)
+                .unwrap_or(0);
+            
+            candidates.push((score, area, image));
+        }
+        
+        candidates.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(b.1.cmp(&a.1))
+                .then_with(|| a.2.path.cmp(&b.2.path))
+        });
+        
+        Ok(candidates.into_iter().map(|(_, _, image)| image).collect())
+    }
+    
+    /// 获取指定图片数据
+    /// 
+    /// # 参数
+    /// * `image_info` - 图片信息
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<u8>>` - 图片二进制数据
+    pub fn image_data(&self, image_info: &ImageInfo) -> Result<Vec<u8>> {
+        let paths = self.paths()?;
+        let full_path = resolve_href(&paths.opf_directory, &image_info.path);
+        self.read_binary_file(&full_path)
+    }
+    
+    /// 按清单id读取任意资源的二进制数据
+    /// 
+    /// 适用于字体、音频、SMIL等不属于图片的资源。
+    /// 
+    /// # 参数
+    /// * `id` - 资源在OPF清单中的id
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<u8>>` - 资源二进制数据，清单中不存在该id时返回`EpubError::InvalidEpub`
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let font = epub.resource_data("font1")?;
+    /// println!("{}: {} 字节", epub.resource_media_type("font1")?, font.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resource_data(&self, id: &str) -> Result<Vec<u8>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        let item = Self::manifest_item_by_id(opf, id)?;
+        self.read_binary_file(&resolve_href(&paths.opf_directory, &item.href))
+    }
+    
+    /// 读取资源的指定字节范围
+    /// 
+    /// 只缓冲请求的字节，适合以HTTP 206响应分段传输大型图片或媒体文件。
+    /// 压缩（deflate）条目无法随机访问，需要从头解压并丢弃`start`之前的数据，
+    /// 因此耗时与`start`成正比；内存占用只与`len`有关。
+    /// 
+    /// # 参数
+    /// * `path` - 资源路径（相对于EPUB根目录）
+    /// * `start` - 起始偏移（解压后的字节）
+    /// * `len` - 最多读取的字节数
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<u8>>` - 读取到的数据，范围超出文件末尾时只返回剩余部分（可能为空）
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let chunk = epub.resource_range("OEBPS/images/map.png", 1024 * 1024, 64 * 1024)?;
+    /// println!("读取了 {} 字节", chunk.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resource_range(&self, path: &str, start: u64, len: usize) -> Result<Vec<u8>> {
+        let clean_path = self.clean_file_path(path);
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let mut file = archive.by_name(&clean_path)?;
+        io::copy(&mut (&mut file).take(start), &mut io::sink())?;
+        
+        let mut buffer = Vec::with_capacity(len.min(file.size().saturating_sub(start) as usize));
+        file.take(len as u64).read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
+    
+    /// 按清单id获取资源声明的媒体类型
+    /// 
+    /// # 参数
+    /// * `id` - 资源在OPF清单中的id
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 清单中声明的`media-type`，不存在该id时返回`EpubError::InvalidEpub`
+    pub fn resource_media_type(&self, id: &str) -> Result<String> {
+        let opf = self.opf()?;
+        Ok(Self::manifest_item_by_id(opf, id)?.media_type.clone())
+    }
+    
+    /// 在清单中查找指定id的资源
+    fn manifest_item_by_id<'a>(opf: &'a Opf, id: &str) -> Result<&'a ManifestItem> {
+        opf.get_manifest_item(id)
+            .ok_or_else(|| EpubError::InvalidEpub(format!("清单中不存在资源: {}", id)))
+    }
+    
+    /// 按媒体类型对清单中的资源分组
+    /// 
+    /// 分组依据OPF声明的`media-type`而不是文件扩展名，见[`Opf::categorize_manifest`]。
+    /// 
+    /// # 返回值
+    /// * `Result<ManifestCategories>` - 文档、样式表、图片、字体、音频、视频和其他文件的分组
+    pub fn categorize_manifest(&self) -> Result<ManifestCategories<'_>> {
+        Ok(self.opf()?.categorize_manifest())
+    }
+    
+    /// 列出所有文件
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<String>>` - 文件路径列表
+    pub fn file_list(&self) -> Result<Vec<String>> {
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let mut files = Vec::new();
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)?;
+            files.push(file.name().to_string());
+        }
+        
+        Ok(files)
+    }
+    
+    /// 列出不在清单（manifest）中的文件
+    /// 
+    /// 如`mimetype`、`META-INF/container.xml`和OPF文件本身，以及打包时遗留的多余文件。
+    /// 目录条目不包含在内。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<String>>` - 按ZIP中的顺序排列的文件路径
+    pub fn non_manifest_files(&self) -> Result<Vec<String>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        let manifest_paths: HashSet<String> = opf.manifest.values()
+            .map(|item| resolve_href(&paths.opf_directory, &item.href))
+            .collect();
+        
+        Ok(self.file_list()?
+            .into_iter()
+            .filter(|file| !file.ends_with('/') && !manifest_paths.contains(file))
+            .collect())
+    }
+    
+    /// 判断书籍的内容类型（漫画、文字或图文混排）
+    /// 
+    /// 综合固定版式声明、图片与正文文字的体积比例以及以图片为主的页面比例进行判断，
+    /// 结果中包含所有判断依据。这是启发式判断，对特殊排版的书籍可能不准确。
+    /// 
+    /// # 返回值
+    /// * `Result<ContentClassification>` - 内容类型及判断依据
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::{ContentKind, Epub};
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let classification = epub.classify()?;
+    /// if classification.kind == ContentKind::Comic {
+    ///     println!("漫画，共 {} 页", classification.signals.pages);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn classify(&self) -> Result<ContentClassification> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        
+        let spine_items: Vec<_> = opf.spine.iter()
+            .filter_map(|spine_item| opf.get_manifest_item(&spine_item.idref).map(|item| (spine_item, item)))
+            .collect();
+        let all_pages_fixed = !spine_items.is_empty()
+            && spine_items.iter().all(|(spine_item, _)| spine_item.has_property("rendition:layout-pre-paginated"));
+        
+        let mut signals = ClassificationSignals {
+            fixed_layout: opf.metadata.is_fixed_layout() || all_pages_fixed,
+            ..Default::default()
+        };
+        
+        let image_paths: HashSet<String> = opf.manifest.values()
+            .filter(|item| item.is_image())
+            .map(|item| resolve_href(&paths.opf_directory, &item.href))
+            .collect();
+        signals.image_bytes = self.entries()?.iter()
+            .filter(|entry| image_paths.contains(&entry.name))
+            .map(|entry| entry.uncompressed_size)
+            .sum();
+        
+        for (_, manifest_item) in spine_items {
+            let Ok(content) = self.read_file(&resolve_href(&paths.opf_directory, &manifest_item.href)) else {
+                continue;
+            };
+            let text = TocTreeNode::strip_html_tags(&content, &TextOptions::default());
+            let text_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+            
+            signals.pages += 1;
+            signals.text_bytes += text.len() as u64;
+            if text_chars < IMAGE_PAGE_MAX_TEXT_CHARS && count_page_images(&content) > 0 {
+                signals.image_pages += 1;
+            }
+        }
+        
+        Ok(ContentClassification {
+            kind: classify_signals(&signals),
+            signals,
+        })
+    }
+    
+    /// 列出所有ZIP条目及其元数据
+    /// 
+    /// 比`file_list()`提供更多信息，适用于诊断和体积分析等工具。结果按路径排序。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<ZipEntryInfo>>` - 条目元数据列表
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// for entry in epub.entries()? {
+    ///     println!("{} {} -> {} ({})", entry.name, entry.uncompressed_size,
+    ///         entry.compressed_size, entry.compression_method);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn entries(&self) -> Result<Vec<ZipEntryInfo>> {
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i)?;
+            entries.push(ZipEntryInfo {
+                name: file.name().to_string(),
+                compressed_size: file.compressed_size(),
+                uncompressed_size: file.size(),
+                compression_method: file.compression().to_string(),
+                crc32: file.crc32(),
+                is_dir: file.is_dir(),
+                last_modified: file.last_modified().map(|time| time.to_string()),
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        Ok(entries)
+    }
+    
+    /// 校验所有ZIP条目的完整性
+    /// 
+    /// 完整解压每个条目，并将内容的CRC32与ZIP目录中记录的值比较。损坏的EPUB在
+    /// 读取时可能只表现为个别章节读取失败，该方法可集中报告所有损坏的条目。
+    /// 无法解压（如使用了不支持的压缩方式）的条目同样视为校验失败。
+    /// 
+    /// # 返回值
+    /// * `Result<Vec<String>>` - 校验失败的条目名称（按路径排序），全部通过时为空
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let corrupted = epub.verify_integrity()?;
+    /// if !corrupted.is_empty() {
+    ///     println!("以下文件已损坏: {}", corrupted.join(", "));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_integrity(&self) -> Result<Vec<String>> {
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let mut failed = Vec::new();
+        for i in 0..archive.len() {
+            let name = archive.by_index_raw(i)?.name().to_string();
+            // zip在完整读取条目后校验CRC32，不匹配时返回读取错误
+            let verified = archive.by_index(i)
+                .map_err(EpubError::from)
+                .and_then(|mut file| std::io::copy(&mut file, &mut std::io::sink()).map_err(EpubError::from));
+            if verified.is_err() {
+                failed.push(name);
+            }
+        }
+        failed.sort();
+        
+        Ok(failed)
+    }
+    
+    /// 获取OPF目录路径
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - OPF文件所在目录的路径
+    pub fn get_opf_directory(&self) -> Result<String> {
+        let paths = self.paths()?;
+        Ok(paths.opf_directory.clone())
+    }
+    
+    /// 获取NCX文件目录路径
+    /// 
+    /// # 返回值
+    /// * `Result<Option<String>>` - NCX文件所在目录的路径（如果NCX文件存在）
+    pub fn get_ncx_directory(&self) -> Result<Option<String>> {
+        let paths = self.paths()?;
+        match &paths.ncx_path {
+            Some(ncx_path) => {
+                let ncx_directory = if let Some(last_slash) = ncx_path.rfind('/') {
+                    ncx_path[..last_slash].to_string()
+                } else {
+                    String::new()
+                };
+                Ok(Some(ncx_directory))
+            }
+            None => Ok(None),
+        }
+    }
+    
+    /// 读取指定文件的内容（公开接口）
+    /// 
+    /// # 参数
+    /// * `filename` - 要读取的文件名
+    /// 
+    /// # 返回值
+    /// * `Result<String>` - 文件内容
+    pub fn read_chapter_file(&self, filename: &str) -> Result<String> {
+        self.read_file(filename)
+    }
+    
+    /// 校验EPUB结构
+    /// 
+    /// 收集所有发现的问题而不是在第一个问题处失败。以错误级别报告：
+    /// * 脊柱项的`idref`在清单中不存在
+    /// * 清单项的`href`在ZIP中不存在（远程资源除外）
+    /// * 脊柱`toc`属性引用的NCX或清单中声明的nav文档不存在
+    /// 
+    /// 以警告级别报告：
+    /// * package元素缺少`unique-identifier`，或没有与之匹配的`dc:identifier`
+    /// * 声明的封面文件不存在
+    /// * NCX声明的`dtb:depth`与导航点的实际嵌套深度不一致
+    /// * 脊柱中未出现在任何目录里的章节（只能翻页到达，无法从目录跳转）
+    /// * 目录中指向脊柱之外文件的条目
+    /// 
+    /// 如果书籍没有任何目录，则不进行最后两项检查。
+    /// 
+    /// # 返回值
+    /// * `Result<ValidationReport>` - 校验报告
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let report = epub.validate()?;
+    /// for issue in report.warnings() {
+    ///     println!("{}", issue);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn validate(&self) -> Result<ValidationReport> {
+        let mut report = ValidationReport::new();
+        self.check_package_structure(&mut report)?;
+        self.check_navigation_coverage(&mut report)?;
+        Ok(report)
+    }
+    
+    /// 检查EPUB的无障碍出版问题
+    /// 
+    /// 与`validate()`的结构校验不同，这里检查影响辅助技术使用的问题：
+    /// * 元数据缺少`dc:language`（错误）
+    /// * EPUB3书籍缺少nav导航文档（错误）
+    /// * 元数据缺少`schema:accessibilityFeature`（警告）
+    /// * 图片缺少`alt`属性（警告，`alt=""`的装饰性图片不会被报告）
+    /// * 章节内标题跳级（警告）
+    /// 
+    /// # 返回值
+    /// * `Result<A11yReport>` - 无障碍检查报告
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust,no_run
+    /// use bookforge::Epub;
+    /// 
+    /// let epub = Epub::from_path("book.epub")?;
+    /// let report = epub.accessibility_report()?;
+    /// print!("{}", report);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn accessibility_report(&self) -> Result<A11yReport> {
+        let opf = self.opf()?;
+        let mut report = A11yReport::new();
+        
+        if opf.metadata.language().is_none() {
+            report.push(A11yFinding::new(
+                A11yIssueKind::MissingLanguage,
+                Severity::Error,
+                "元数据中没有dc:language".to_string(),
+                None,
+            ));
+        }
+        if opf.version.starts_with('3') && opf.get_nav_path().is_none() {
+            report.push(A11yFinding::new(
+                A11yIssueKind::MissingNavDocument,
+                Severity::Error,
+                "EPUB3书籍的清单中没有nav导航文档".to_string(),
+                None,
+            ));
+        }
+        if !opf.metadata.raw_metadata().contains_key("schema:accessibilityFeature") {
+            report.push(A11yFinding::new(
+                A11yIssueKind::MissingAccessibilityFeature,
+                Severity::Warning,
+                "元数据中没有schema:accessibilityFeature".to_string(),
+                None,
+            ));
+        }
+        
+        for chapter_info in self.chapter_list()? {
+            let chapter_path = self.chapter_full_path(&chapter_info)?;
+            let chapter = match self.chapter(&chapter_info) {
+                Ok(chapter) => chapter,
+                Err(e) => {
+                    self.warn(EpubWarning::new(format!("无法读取章节: {}", e), Some(chapter_path)));
+                    continue;
+                }
+            };
+            
+            let missing_alts = self.resolve_image_alts(&chapter_path, &chapter.content)?
+                .into_iter()
+                .filter(|(_, alt)| alt.is_none());
+            for (src, _) in missing_alts {
+                report.push(A11yFinding::new(
+                    A11yIssueKind::MissingAltText,
+                    Severity::Warning,
+                    format!("图片 {} 没有替代文本", src),
+                    Some(chapter_path.clone()),
+                ));
+            }
+            
+            for (previous, current) in heading_level_skips(&heading_levels(&chapter.content)) {
+                report.push(A11yFinding::new(
+                    A11yIssueKind::HeadingLevelSkip,
+                    Severity::Warning,
+                    format!("标题从h{}跳到h{}", previous, current),
+                    Some(chapter_path.clone()),
+                ));
+            }
+        }
+        
+        Ok(report)
+    }
+    
+    // === 内部方法 ===
+    
+    /// 获取路径信息（懒加载）
+    fn paths(&self) -> Result<&EpubPaths> {
+        self.paths.get_or_try_init(|| {
+            let container = self.container()?;
+            let opf_path = match self.rendition {
+                Some(index) => container.rendition_paths().get(index).map(|rf| rf.full_path.clone()),
+                None => container.get_opf_path(),
+            };
+            let opf_path = opf_path
+                .ok_or_else(|| EpubError::ContainerParseError(
+                    "container.xml中没有找到有效的rootfile".to_string()
+                ))?;
+            
+            let opf_directory = if let Some(last_slash) = opf_path.rfind('/') {
+                opf_path[..last_slash].to_string()
+            } else {
+                String::new()
+            };
+            
+            // 查找NCX路径
+            let ncx_path = self.find_ncx_path(&opf_path, &opf_directory)?;
+            
+            Ok(EpubPaths {
+                opf_path,
+                opf_directory,
+                ncx_path,
+            })
+        })
+    }
+    
+    /// 查找NCX文件路径
+    fn find_ncx_path(&self, opf_path: &str, opf_directory: &str) -> Result<Option<String>> {
+        // 首先尝试从OPF中获取
+        if let Ok(opf_content) = self.read_file(opf_path) {
+            if let Ok(opf) = Opf::parse_xml(&opf_content) {
+                if let Some(spine_toc) = &opf.spine_toc {
+                    if let Some(manifest_item) = opf.get_manifest_item(spine_toc) {
+                        return Ok(Some(resolve_href(opf_directory, &manifest_item.href)));
+                    }
+                }
+            }
+        }
+        
+        // 尝试常见路径
+        let common_paths = [
+            "toc.ncx",
+            "OEBPS/toc.ncx",
+            "content/toc.ncx",
+            "EPUB/toc.ncx",
+        ];
+        
+        for &path in &common_paths {
+            if self.file_exists(path) {
+                return Ok(Some(path.to_string()));
+            }
+        }
+        
+        Ok(None)
+    }
+    
+
+    /// 检查清单、脊柱、导航文件、唯一标识符和封面的引用完整性
+    fn check_package_structure(&self, report: &mut ValidationReport) -> Result<()> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        let opf_path = Some(paths.opf_path.clone());
+        
+        for spine_item in &opf.spine {
+            if opf.get_manifest_item(&spine_item.idref).is_none() {
+                report.push(ValidationIssue::error(
+                    format!("脊柱项 '{}' 在清单中不存在", spine_item.idref),
+                    opf_path.clone(),
+                ));
+            }
+        }
+        
+        // 导航文件单独报告，清单检查中跳过以免重复
+        let mut navigation_ids = HashSet::new();
+        if let Some(toc_id) = &opf.spine_toc {
+            match opf.get_manifest_item(toc_id) {
+                Some(item) => {
+                    navigation_ids.insert(item.id.as_str());
+                    let ncx_path = resolve_href(&paths.opf_directory, &item.href);
+                    if !self.file_exists(&ncx_path) {
+                        report.push(ValidationIssue::error("NCX文件不存在".to_string(), Some(ncx_path)));
+                    }
+                }
+                None => report.push(ValidationIssue::error(
+                    format!("脊柱的toc属性引用的清单项 '{}' 不存在", toc_id),
+                    opf_path.clone(),
+                )),
+            }
+        }
+        if let Some(item) = opf.manifest.values().find(|item| item.is_nav()) {
+            navigation_ids.insert(item.id.as_str());
+            let nav_path = resolve_href(&paths.opf_directory, &item.href);
+            if !self.file_exists(&nav_path) {
+                report.push(ValidationIssue::error("nav导航文档不存在".to_string(), Some(nav_path)));
+            }
+        }
+        
+        let mut manifest_items: Vec<&ManifestItem> = opf.manifest.values()
+            .filter(|item| !navigation_ids.contains(item.id.as_str()) && !has_scheme(&item.href))
+            .collect();
+        manifest_items.sort_by(|a, b| a.href.cmp(&b.href));
+        for item in manifest_items {
+            let full_path = resolve_href(&paths.opf_directory, &item.href);
+            if !self.file_exists(&full_path) {
+                report.push(ValidationIssue::error(
+                    format!("清单项 '{}' 引用的文件不存在", item.id),
+                    Some(full_path),
+                ));
+            }
+        }
+        
+        match &opf.unique_identifier {
+            Some(unique_id) => {
+                if opf.unique_identifier().is_none() {
+                    report.push(ValidationIssue::warning(
+                        format!("unique-identifier '{}' 没有匹配的dc:identifier", unique_id),
+                        opf_path.clone(),
+                    ));
+                }
+            }
+            None => report.push(ValidationIssue::warning(
+                "package元素缺少unique-identifier属性".to_string(),
+                opf_path.clone(),
+            )),
+        }
+        
+        if let Some(cover_href) = opf.get_cover_path().filter(|href| !has_scheme(href)) {
+            let cover_path = resolve_href(&paths.opf_directory, &cover_href);
+            if !self.file_exists(&cover_path) {
+                report.push(ValidationIssue::warning("声明的封面文件不存在".to_string(), Some(cover_path)));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 检查脊柱与目录的一致性
+    fn check_navigation_coverage(&self, report: &mut ValidationReport) -> Result<()> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        
+        // 收集目录中引用的所有文件
+        let mut toc_paths = Vec::new();
+        let mut has_toc = false;
+        if let Some(ncx) = self.ncx()? {
+            has_toc = true;
+            if let (Some(declared), actual) = ncx.depth_info() && declared != actual {
+                report.push(ValidationIssue::warning(
+                    format!("NCX声明的深度为{}，实际嵌套深度为{}", declared, actual),
+                    paths.ncx_path.clone(),
+                ));
+            }
+            let ncx_dir = self.get_ncx_directory()?.unwrap_or_default();
+            Self::collect_nav_point_paths(&ncx.nav_map.nav_points, &ncx_dir, &mut toc_paths);
+        }
+        if let Some(nav_paths) = self.nav_toc_paths()? {
+            has_toc = true;
+            toc_paths.extend(nav_paths);
+        }
+        
+        if !has_toc {
+            return Ok(());
+        }
+        
+        let toc_set: HashSet<&str> = toc_paths.iter().map(|p| p.as_str()).collect();
+        let mut spine_set = HashSet::new();
+        
+        for spine_item in &opf.spine {
+            let Some(manifest_item) = opf.get_manifest_item(&spine_item.idref) else {
+                continue;
+            };
+            let full_path = resolve_href(&paths.opf_directory, &manifest_item.href);
+            if !toc_set.contains(full_path.as_str()) {
+                report.push(ValidationIssue::warning(
+                    format!("脊柱项 '{}' 未出现在目录中", spine_item.idref),
+                    Some(full_path.clone()),
+                ));
+            }
+            spine_set.insert(full_path);
+        }
+        
+        let mut reported = HashSet::new();
+        for toc_path in &toc_paths {
+            if !spine_set.contains(toc_path) && reported.insert(toc_path.as_str()) {
+                report.push(ValidationIssue::warning(
+                    "目录项指向脊柱之外的文件".to_string(),
+                    Some(toc_path.clone()),
+                ));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 递归收集导航点引用的文件路径
+    fn collect_nav_point_paths(nav_points: &[NavPoint], base_dir: &str, result: &mut Vec<String>) {
+        for nav_point in nav_points {
+            if !nav_point.content.src.is_empty() {
+                result.push(resolve_href(base_dir, &nav_point.content.src));
+            }
+            Self::collect_nav_point_paths(&nav_point.children, base_dir, result);
+        }
+    }
+    
+    /// 获取EPUB3 nav文档目录中引用的文件路径
+    /// 
+    /// # 返回值
+    /// * `Result<Option<Vec<String>>>` - 文件路径列表（如果存在nav文档）
+    fn nav_toc_paths(&self) -> Result<Option<Vec<String>>> {
+        let Some(nav_tree) = self.nav_toc_tree()? else {
+            return Ok(None);
+        };
+        let nav_dir = self.nav_directory()?.unwrap_or_default();
+        
+        let hrefs = nav_tree.get_all_paths()
+            .iter()
+            .filter(|href| !href.is_empty() && !href.contains("://") && !href.starts_with('#'))
+            .map(|href| resolve_href(&nav_dir, href))
+            .collect();
+        
+        Ok(Some(hrefs))
+    }
+    
+    /// 获取EPUB3 nav文档的完整路径（如果存在）
+    fn nav_path(&self) -> Result<Option<String>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        Ok(opf.get_nav_path().map(|href| resolve_href(&paths.opf_directory, &href)))
+    }
+    
+    /// 获取EPUB3 nav文档所在目录（如果存在）
+    pub(crate) fn nav_directory(&self) -> Result<Option<String>> {
+        Ok(self.nav_path()?.map(|path| parent_dir(&path).to_string()))
+    }
+    
+    /// 获取章节文件的完整路径
+    fn chapter_full_path(&self, chapter_info: &ChapterInfo) -> Result<String> {
+        let paths = self.paths()?;
+        Ok(resolve_href(&paths.opf_directory, &chapter_info.path))
+    }
+    
+    /// 获取清单中所有内容文档（XHTML/HTML）的完整路径
+    fn content_document_paths(&self) -> Result<HashSet<String>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        Ok(opf.manifest.values()
+            .filter(|item| item.is_xhtml() || item.media_type == "text/html")
+            .map(|item| resolve_href(&paths.opf_directory, &item.href))
+            .collect())
+    }
+    
+    /// 复制单个资源到导出目录（CSS中引用的资源会被递归复制）
+    /// 
+    /// # 返回值
+    /// * `Result<Option<String>>` - 资源在导出目录中的文件名，资源无法读取时返回None
+    fn bundle_resource(
+        &self,
+        full_path: &str,
+        output_dir: &Path,
+        bundled: &mut HashMap<String, String>,
+        used_names: &mut HashSet<String>,
+    ) -> Result<Option<String>> {
+        if let Some(name) = bundled.get(full_path) {
+            return Ok(Some(name.clone()));
+        }
+        
+        let data = match self.read_binary_file(full_path) {
+            Ok(data) => data,
+            Err(e) => {
+                self.warn(EpubWarning::new(format!("无法读取资源: {}", e), Some(full_path.to_string())));
+                return Ok(None);
+            }
+        };
+        
+        // 生成不冲突的平铺文件名
+        let base_name = Self::file_name_of(full_path);
+        let mut name = base_name.to_string();
+        let mut counter = 2;
+        while !used_names.insert(name.clone()) {
+            name = format!("{}_{}", counter, base_name);
+            counter += 1;
+        }
+        bundled.insert(full_path.to_string(), name.clone());
+        
+        if full_path.to_lowercase().ends_with(".css") {
+            let mut css = String::from_utf8_lossy(&data).into_owned();
+            for url in extract_css_urls(&css.clone()) {
+                let resource_path = resolve_href(parent_dir(full_path), &url);
+                if let Some(resource_name) = self.bundle_resource(&resource_path, output_dir, bundled, used_names)? {
+                    css = Self::rewrite_css_refs(&css, &url, &Self::with_fragment(&resource_name, &url));
+                }
+            }
+            fs::write(output_dir.join(&name), css)?;
+        } else {
+            fs::write(output_dir.join(&name), data)?;
+        }
+        
+        Ok(Some(name))
+    }
+    
+    /// 获取路径中的文件名部分
+    fn file_name_of(path: &str) -> &str {
+        path.rsplit('/').next().unwrap_or(path)
+    }
+    
+    /// 保留原始链接中的锚点
+    fn with_fragment(name: &str, original_href: &str) -> String {
+        match original_href.split_once('#') {
+            Some((_, fragment)) => format!("{}#{}", name, fragment),
+            None => name.to_string(),
+        }
+    }
+    
+    /// 改写CSS中`url()`的链接
+    fn rewrite_css_refs(css: &str, old_url: &str, new_url: &str) -> String {
+        css.replace(&format!("url({})", old_url), &format!("url({})", new_url))
+            .replace(&format!("url(\"{}\")", old_url), &format!("url(\"{}\")", new_url))
+            .replace(&format!("url('{}')", old_url), &format!("url('{}')", new_url))
+    }
+    
+    /// 收集目录中每个文件对应的标题
+    /// 
+    /// 优先使用NCX，没有NCX时使用EPUB3 nav文档。键为规范化后的完整路径（不含锚点），
+    /// 多个目录项指向同一文件时取先序遍历中的第一个。
+    fn toc_titles_by_path(&self) -> Result<HashMap<String, String>> {
+        let mut titles = HashMap::new();
+        
+        if let Some(ncx) = self.ncx()? {
+            let ncx_dir = self.get_ncx_directory()?.unwrap_or_default();
+            self.collect_ncx_titles(&ncx.nav_map.nav_points, &ncx_dir, &mut titles);
+        } else if let Some(toc_tree) = self.nav_toc_tree()? {
+            let nav_dir = self.nav_directory()?.unwrap_or_default();
+            for entry in toc_tree.flatten() {
+                titles.entry(resolve_href(&nav_dir, &entry.src)).or_insert(entry.title);
+            }
+        }
+        
+        Ok(titles)
+    }
+    
+    /// 递归收集NCX导航点的标题
+    fn collect_ncx_titles(&self, nav_points: &[NavPoint], base_dir: &str, titles: &mut HashMap<String, String>) {
+        for nav_point in nav_points {
+            titles.entry(resolve_href(base_dir, &nav_point.content.src))
+                .or_insert_with(|| self.clean_title(&nav_point.nav_label.text));
+            self.collect_ncx_titles(&nav_point.children, base_dir, titles);
+        }
+    }
+    
+    /// 检查封面候选路径，构造封面图片信息
+    /// 
+    /// # 参数
+    /// * `opf` - OPF数据
+    /// * `href` - 相对于OPF目录的候选路径
+    /// 
+    /// # 返回值
+    /// * `Result<Option<ImageInfo>>` - 路径不是图片或文件不存在时返回None
+    fn cover_image_info(&self, opf: &Opf, href: &str) -> Result<Option<ImageInfo>> {
+        if !Self::is_image_file(href) {
+            return Ok(None);
+        }
+        let paths = self.paths()?;
+        if !self.file_exists(&resolve_href(&paths.opf_directory, href)) {
+            return Ok(None);
+        }
+        
+        let manifest_item = opf.manifest.values().find(|item| item.href == href);
+        Ok(Some(ImageInfo {
+            id: manifest_item.map(|item| item.id.clone()).unwrap_or_default(),
+            path: href.to_string(),
+            media_type: manifest_item
+                .map(|item| item.media_type.clone())
+                .unwrap_or_else(|| Self::image_media_type_from_extension(href)),
+        }))
+    }
+    
+    /// 根据扩展名推断图片的媒体类型
+    fn image_media_type_from_extension(file_path: &str) -> String {
+        let extension = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match extension.as_str() {
+            "jpg" | "jpeg" => "image/jpeg".to_string(),
+            "svg" => "image/svg+xml".to_string(),
+            other => format!("image/{}", other),
+        }
+    }
+    
+    /// 检测图片格式
+    fn detect_image_format(data: &[u8], file_path: &str) -> String {
+        // 通过文件头检测
+        if data.len() >= 2 {
+            match &data[0..2] {
+                [0xFF, 0xD8] => return "jpeg".to_string(),
+                [0x89, 0x50] if data.len() >= 8 && &data[0..8] == b"\x89PNG\r\n\x1a\n" => return "png".to_string(),
+                [0x47, 0x49] if data.len() >= 6 && &data[0..6] == b"GIF87a" => return "gif".to_string(),
+                [0x47, 0x49] if data.len() >= 6 && &data[0..6] == b"GIF89a" => return "gif".to_string(),
+                _ => {}
+            }
+        }
+        
+        // 回退到文件扩展名
+        std::path::Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("unknown")
+            .to_lowercase()
+    }
+    
+    /// 从图片文件头中解析图片尺寸（支持PNG、GIF和JPEG）
+    /// 
+    /// # 返回值
+    /// * `Option<(u32, u32)>` - 宽度和高度
+    fn detect_image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+        if data.len() >= 24 && data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+            let height = u32::from_be_bytes(data[20..24].try_into().ok()?);
+            return Some((width, height));
+        }
+        
+        if data.len() >= 10 && (data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+            let width = u16::from_le_bytes([data[6], data[7]]) as u32;
+            let height = u16::from_le_bytes([data[8], data[9]]) as u32;
+            return Some((width, height));
+        }
+        
+        if data.starts_with(&[0xFF, 0xD8]) {
+            // 遍历JPEG段，查找SOF段中的尺寸信息
+            let mut pos = 2;
+            while pos + 9 < data.len() {
+                if data[pos] != 0xFF {
+                    return None;
+                }
+                let marker = data[pos + 1];
+                let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+                let is_sof = matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+                if is_sof {
+                    let height = u16::from_be_bytes([data[pos + 5], data[pos + 6]]) as u32;
+                    let width = u16::from_be_bytes([data[pos + 7], data[pos + 8]]) as u32;
+                    return Some((width, height));
+                }
+                pos += 2 + length;
+            }
+        }
+        
+        None
+    }
+    
+    /// 获取第一个脊柱页面中引用的图片路径
+    fn first_page_image_paths(&self) -> Result<HashSet<String>> {
+        let opf = self.opf()?;
+        let paths = self.paths()?;
+        
+        let Some(first_item) = opf.spine.first()
+            .and_then(|spine_item| opf.get_manifest_item(&spine_item.idref))
+        else {
+            return Ok(HashSet::new());
+        };
+        
+        let page_path = resolve_href(&paths.opf_directory, &first_item.href);
+        let Ok(content) = self.read_file(&page_path) else {
+            return Ok(HashSet::new());
+        };
+        
+        let document = Html::parse_document(&content);
+        let selector = Selector::parse("img[src], image").unwrap();
+        let page_dir = parent_dir(&page_path);
+        
+        Ok(document.select(&selector)
+            .filter_map(|element| {
+                let attrs = element.value();
+                attrs.attr("src").or_else(|| attr_any_namespace(attrs, "href"))
+            })
+            .map(|src| resolve_href(page_dir, src))
+            .collect())
+    }
+    
+    /// 检查是否为图片文件
+    fn is_image_file(file_path: &str) -> bool {
+        let lower_path = file_path.to_lowercase();
+        lower_path.ends_with(".jpg") || 
+        lower_path.ends_with(".jpeg") || 
+        lower_path.ends_with(".png") || 
+        lower_path.ends_with(".gif") || 
+        lower_path.ends_with(".webp") ||
+        lower_path.ends_with(".svg")
+    }
+    
+    /// 检查媒体类型是否为图片
+    fn is_image_media_type(media_type: &str) -> bool {
+        media_type.starts_with("image/")
+    }
+    
+    /// 检查文件是否存在
+    fn file_exists(&self, filename: &str) -> bool {
+        // 清理文件路径，去掉锚点和查询参数
+        let clean_path = self.clean_file_path(filename);
+        
+        if let Ok(mut archive) = self.archive.lock() {
+            archive.by_name(&clean_path).is_ok()
+        } else {
+            false
+        }
+    }
+    
+    /// 去掉URL中的锚点和查询参数部分
+    /// 
+    /// # 参数
+    /// * `path` - 文件路径，可能包含锚点（#fragment）或查询参数
+    /// 
+    /// # 返回值
+    /// * `String` - 清理后的文件路径
+    fn clean_file_path(&self, path: &str) -> String {
+        // 去掉锚点部分（#之后的内容）
+        let without_fragment = if let Some(hash_pos) = path.find('#') {
+            &path[..hash_pos]
+        } else {
+            path
+        };
+        
+        // 去掉查询参数（?之后的内容）
+        let without_query = if let Some(query_pos) = without_fragment.find('?') {
+            &without_fragment[..query_pos]
+        } else {
+            without_fragment
+        };
+        
+        without_query.to_string()
+    }
+    
+    /// 读取文本文件
+    fn read_file(&self, filename: &str) -> Result<String> {
+        // 清理文件路径，去掉锚点和查询参数
+        let clean_path = self.clean_file_path(filename);
+        
+        let buffer = self.read_entry_limited(&clean_path)?;
+        let (content, warning) = decode_xml_bytes(&buffer, &clean_path)?;
+        if let Some(message) = warning {
+            self.warn(EpubWarning::new(message, Some(clean_path)));
+        }
+        Ok(content)
+    }
+    
+    /// 读取二进制文件
+    fn read_binary_file(&self, filename: &str) -> Result<Vec<u8>> {
+        // 清理文件路径，去掉锚点和查询参数
+        let clean_path = self.clean_file_path(filename);
+        
+        self.read_entry_limited(&clean_path)
+    }
+    
+    /// 读取ZIP条目的全部内容，超过大小上限时返回错误
+    /// 
+    /// 先检查条目声明的解压大小，再在读取时限制实际读取的字节数，
+    /// 防止声明大小与实际内容不符的恶意文件（压缩炸弹）耗尽内存。
+    fn read_entry_limited(&self, clean_path: &str) -> Result<Vec<u8>> {
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let file = archive.by_name(clean_path)?;
+        let too_large = || EpubError::InvalidEpub(format!(
+            "资源过大: {}（上限 {} 字节）",
+            clean_path, self.max_entry_size
+        ));
+        if file.size() > self.max_entry_size {
+            return Err(too_large());
+        }
+        
+        let mut buffer = Vec::new();
+        file.take(self.max_entry_size + 1).read_to_end(&mut buffer)?;
+        if buffer.len() as u64 > self.max_entry_size {
+            return Err(too_large());
+        }
+        Ok(buffer)
+    }
+    
+    /// 读取二进制文件的开头部分
+    fn read_binary_file_head(&self, filename: &str, max_len: u64) -> Result<Vec<u8>> {
+        let clean_path = self.clean_file_path(filename);
+        
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        
+        let file = archive.by_name(&clean_path)?;
+        let mut buffer = Vec::new();
+        file.take(max_len).read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
+    
+    /// 验证EPUB格式
+    fn validate_epub_format(&self) -> Result<()> {
+        if !self.file_exists("mimetype") {
+            return Err(EpubError::MissingMimetype);
+        }
+        
+        let buffer = self.read_entry_limited("mimetype")?;
+        let content = String::from_utf8_lossy(&buffer);
+        let content = content.trim();
+        let expected_mimetype = "application/epub+zip";
+        
+        if content != expected_mimetype {
+            return Err(EpubError::InvalidMimetype {
+                expected: expected_mimetype.to_string(),
+                found: content.to_string(),
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// 验证container.xml存在、可以解析，且指向的OPF文件存在
+    fn validate_container(&self) -> Result<()> {
+        let content = self.read_file("META-INF/container.xml")?;
+        let opf_path = Container::parse_xml(&content)?
+            .get_opf_path()
+            .ok_or_else(|| EpubError::ContainerParseError(
+                "container.xml中没有找到有效的rootfile".to_string()
+            ))?;
+        let mut archive = self.archive.lock()
+            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        archive.by_name(&opf_path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use zip::{ZipWriter, write::FileOptions};
+    use crate::epub::classify::ContentKind;
+    use crate::epub::text::{RubyMode, TextNormalization};
+
+    fn create_test_epub(path: &str) -> Result<()> {
+        let file = File::create(path)?;
+        let mut zip = ZipWriter::new(file);
+        
+        // mimetype
+        zip.start_file("mimetype", FileOptions::<()>::default())?;
+        zip.write_all(b"application/epub+zip")?;
+        
+        // container.xml
+        zip.start_file("META-INF/container.xml", FileOptions::<()>::default())?;
+        let container_xml = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+        zip.write_all(container_xml.as_bytes())?;
+        
+        // content.opf
+        zip.start_file("OEBPS/content.opf", FileOptions::<()>::default())?;
+        let opf_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf" unique-identifier="BookId">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>测试书籍</dc:title>
+        <dc:creator>测试作者</dc:creator>
+        <dc:language>zh-CN</dc:language>
+        <dc:identifier id="BookId">test-book-001</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter2" href="text/chapter2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="chapter1"/>
+        <itemref idref="chapter2"/>
+    </spine>
+</package>"#;
+        zip.write_all(opf_xml.as_bytes())?;
+        
+        // chapter1.xhtml
+        zip.start_file("OEBPS/text/chapter1.xhtml", FileOptions::<()>::default())?;
+        let chapter1 = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>第一章</title></head>
+<body><h1>第一章</h1><p>这是第一章的内容。</p></body>
+</html>"#;
+        zip.write_all(chapter1.as_bytes())?;
+        
+        // chapter2.xhtml
+        zip.start_file("OEBPS/text/chapter2.xhtml", FileOptions::<()>::default())?;
+        let chapter2 = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>第二章</title></head>
+<body><h1>第二章</h1><p>这是第二章的内容。</p></body>
+</html>"#;
+        zip.write_all(chapter2.as_bytes())?;
+        
+        zip.finish()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_epub_creation() {
+        let test_file = "test_epub_creation.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(epub.container().is_ok());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_book_info() {
+        let test_file = "test_book_info.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let info = epub.book_info().unwrap();
+        
+        assert_eq!(info.title, "测试书籍");
+        assert_eq!(info.authors, vec!["测试作者"]);
+        assert!(info.to_string().starts_with("标题: 测试书籍\n作者: 测试作者"));
+        assert!(!info.to_string().contains("ISBN"));
+        assert_eq!(info.identifiers.len(), 1);
+        assert_eq!(info.identifiers[0].value, "test-book-001");
+        assert!(info.subjects.is_empty());
+        assert_eq!(info.published_date, None);
+        
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(info).unwrap();
+            let decoded: BookInfo = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.title, info.title);
+            assert_eq!(decoded.authors, info.authors);
+        }
+        
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_book_info_extended.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:opf="http://www.idpf.org/2007/opf">
+        <dc:title>扩展信息</dc:title>
+        <dc:subject>历史</dc:subject>
+        <dc:subject>传记</dc:subject>
+        <dc:rights>版权所有</dc:rights>
+        <dc:date opf:event="modification">2020-05-01</dc:date>
+        <dc:date opf:event="publication">2019-01-01</dc:date>
+        <dc:identifier opf:scheme="ISBN">978-7-000-00000-0</dc:identifier>
+    </metadata>
+    <manifest><item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/></manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf), ("OEBPS/ch1.xhtml", "<html><body/></html>")]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let info = epub.book_info().unwrap();
+        assert_eq!(info.subjects, vec!["历史", "传记"]);
+        assert_eq!(info.rights.as_deref(), Some("版权所有"));
+        assert_eq!(info.published_date.as_deref(), Some("2019-01-01"));
+        assert_eq!(info.isbn.as_deref(), Some("978-7-000-00000-0"));
+        assert_eq!(info.identifiers[0].scheme.as_deref(), Some("ISBN"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_metadata_completeness() {
+        let test_file = "test_metadata_completeness.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = crate::epub::EpubBuilder::new()
+            .warn_incomplete_metadata(true)
+            .open(test_file)
+            .unwrap();
+        let completeness = epub.metadata_completeness().unwrap();
+        assert_eq!(completeness.present, vec!["title", "creator", "language", "identifier"]);
+        assert_eq!(completeness.missing, vec!["publisher", "date", "description"]);
+        assert!(completeness.missing_required().is_empty());
+        assert!(!completeness.is_complete());
+        
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_metadata_missing.epub";
+        let opf = r#"<?xml version="1.0"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf"><manifest></manifest><spine></spine></package>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let completeness = epub.metadata_completeness().unwrap();
+        assert!(completeness.present.is_empty());
+        assert_eq!(completeness.missing_required(), vec!["title", "language", "identifier"]);
+        assert_eq!(completeness.ratio(), 0.0);
+        assert_eq!(epub.book_info().unwrap().title, "未知标题");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapters() {
+        let test_file = "test_chapters.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapters().unwrap();
+        
+        assert_eq!(chapters.len(), 2);
+        assert!(chapters[0].content.contains("第一章"));
+        assert!(chapters[1].content.contains("第二章"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_iter() {
+        let test_file = "test_chapter_iter.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>迭代测试</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="missing" href="missing.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="ch2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="missing"/><itemref idref="ch2"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/ch1.xhtml", "<html><body><p>一</p></body></html>"),
+            ("OEBPS/ch2.xhtml", "<html><body><p>二</p></body></html>"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let results: Vec<Result<Chapter>> = epub.chapter_iter().collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap().content.contains("一"));
+        assert!(results[1].is_err());
+        assert!(results[2].as_ref().unwrap().content.contains("二"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_content_statistics() {
+        let test_file = "test_content_statistics.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let stats = epub.content_statistics().unwrap();
+        assert_eq!(stats.chapters.len(), 2);
+        assert_eq!(stats.chapters[0].0, "第一章");
+        assert_eq!(stats.total_chars, stats.chapters.iter().map(|(_, chars, _)| chars).sum::<usize>());
+        assert_eq!(stats.total_words, stats.chapters.iter().map(|(_, _, words)| words).sum::<usize>());
+        
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(epub.chapter_word_count(&chapters[0]).unwrap(), stats.chapters[0].2);
+        assert!(epub.word_counts.lock().unwrap().contains_key(&chapters[0].id));
+        assert_eq!(epub.chapter_word_count(&chapters[0]).unwrap(), stats.chapters[0].2);
+        
+        let stats = ContentStatistics { total_words: 450, ..Default::default() };
+        assert_eq!(stats.estimated_reading_time(300), Duration::from_secs(90));
+        assert_eq!(stats.estimated_reading_time(0), Duration::ZERO);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let test_file = "test_to_json.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&epub.to_json().unwrap()).unwrap();
+        assert_eq!(json["version"], "2.0");
+        assert_eq!(json["info"]["title"], "测试书籍（带NCX）");
+        assert_eq!(json["toc"][0]["title"], "第一章");
+        assert_eq!(json["toc"][0]["play_order"], 1);
+        assert!(json["toc"][0]["children"].as_array().unwrap().is_empty());
+        assert!(json["toc"][0].get("resolved_path").is_none());
+        assert_eq!(json["spine"][0]["idref"], "chapter1");
+        assert!(json["manifest"].as_array().unwrap().iter().any(|item| item["media_type"] == "application/x-dtbncx+xml"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_search() {
+        let test_file = "test_search.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let hits = epub.search("章的内容", SearchOptions::new()).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].chapter_title, "第一章");
+        assert_eq!(hits[1].chapter_title, "第二章");
+        assert!(hits[0].snippet.contains("这是第一章的内容"));
+        
+        let text = epub.toc_tree().unwrap().unwrap().roots[0].get_text_content(&epub).unwrap();
+        assert_eq!(&text[hits[0].offset..hits[0].offset + "章的内容".len()], "章的内容");
+        assert!(epub.search("不存在的关键词", SearchOptions::new()).unwrap().is_empty());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_search_identical_chapters() {
+        let test_file = "test_search_identical_chapters.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>幕间</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="part1" href="part1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="part2" href="part2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="part1"/><itemref idref="part2"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="navpoint-1" playOrder="1">
+            <navLabel><text>幕间一</text></navLabel>
+            <content src="part1.xhtml"/>
+        </navPoint>
+        <navPoint id="navpoint-2" playOrder="2">
+            <navLabel><text>幕间一（重复）</text></navLabel>
+            <content src="part1.xhtml"/>
+        </navPoint>
+        <navPoint id="navpoint-3" playOrder="3">
+            <navLabel><text>幕间二</text></navLabel>
+            <content src="part2.xhtml"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        let body = "<html><body><p>幕间休息</p></body></html>";
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/part1.xhtml", body),
+            ("OEBPS/part2.xhtml", body),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let titles: Vec<String> = epub.search("休息", SearchOptions::new()).unwrap()
+            .into_iter()
+            .map(|hit| hit.chapter_title)
+            .collect();
+        assert_eq!(titles, vec!["幕间一", "幕间二"]);
+        
+        // 没有目录时按脊柱顺序搜索
+        let opf = opf.replace(r#"<spine toc="ncx">"#, "<spine>").replace(r#"<item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>"#, "");
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf.as_str()),
+            ("OEBPS/part1.xhtml", body),
+            ("OEBPS/part2.xhtml", body),
+        ]).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        let ids: Vec<String> = epub.search("休息", SearchOptions::new()).unwrap()
+            .into_iter()
+            .map(|hit| hit.chapter_id)
+            .collect();
+        assert_eq!(ids, vec!["part1", "part2"]);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_next_and_prev_chapter() {
+        let test_file = "test_next_and_prev_chapter.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>导航测试</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="notes" href="notes.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="ch2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="notes" linear="no"/><itemref idref="ch2"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let id = |chapter: Option<ChapterInfo>| chapter.map(|chapter| chapter.id);
+        
+        assert_eq!(id(epub.next_chapter(&chapters[0], false).unwrap()).as_deref(), Some("notes"));
+        assert_eq!(id(epub.next_chapter(&chapters[0], true).unwrap()).as_deref(), Some("ch2"));
+        assert_eq!(id(epub.prev_chapter(&chapters[2], true).unwrap()).as_deref(), Some("ch1"));
+        assert_eq!(id(epub.prev_chapter(&chapters[1], true).unwrap()).as_deref(), Some("ch1"));
+        assert!(epub.next_chapter(&chapters[2], false).unwrap().is_none());
+        assert!(epub.prev_chapter(&chapters[0], false).unwrap().is_none());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_linear_chapters() {
+        let test_file = "test_linear_chapters.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>线性测试</dc:title></metadata>
+    <manifest>
+        <item id="cover" href="cover.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="notes" href="notes.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="cover" linear="no"/><itemref idref="ch1"/><itemref idref="notes" linear="no"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let flags: Vec<bool> = epub.chapter_list().unwrap().iter().map(|chapter| chapter.linear).collect();
+        assert_eq!(flags, vec![false, true, false]);
+        
+        let linear = epub.linear_chapters().unwrap();
+        assert_eq!(linear.len(), 1);
+        assert_eq!(linear[0].id, "ch1");
+        assert_eq!(linear[0].order, Some(2));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_with_resolved_links() {
+        let test_file = "test_chapter_with_resolved_links.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>链接测试</dc:title></metadata>
+    <manifest><item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/></manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let chapter = r##"<html><head><link rel="stylesheet" href="../styles/main.css"/></head><body>
+<img src="../images/x.jpg"/><img src="data:image/png;base64,AAAA"/>
+<a href="ch2.xhtml#sec1">下一章</a><a href="#top">顶部</a><a href="https://example.com">外链</a>
+</body></html>"##;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/ch1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let content = epub.chapter_with_resolved_links(&chapters[0]).unwrap().content;
+        assert!(content.contains(r#"href="/OEBPS/styles/main.css""#));
+        assert!(content.contains(r#"src="/OEBPS/images/x.jpg""#));
+        assert!(content.contains(r#"href="/OEBPS/text/ch2.xhtml#sec1""#));
+        assert!(content.contains(r#"src="data:image/png;base64,AAAA""#));
+        assert!(content.contains(r##"href="#top""##));
+        assert!(content.contains(r#"href="https://example.com""#));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_page_list_from_nav() {
+        let test_file = "test_page_list_from_nav.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>页码测试</dc:title></metadata>
+    <manifest>
+        <item id="nav" href="nav/toc.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter2" href="text/chapter%201.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="chapter1"/><itemref idref="chapter2"/></spine>
+</package>"#;
+        let nav = r##"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="toc"><ol><li><a href="../text/chapter1.xhtml">第一章</a></li></ol></nav>
+<nav epub:type="page-list" hidden=""><h2>页码</h2><ol>
+    <li><a href="../text/chapter1.xhtml#page1">1</a></li>
+    <li><a href="../text/chapter1.xhtml#page2">2</a></li>
+    <li><a href="../text/chapter%201.xhtml?v=1#page3">3</a></li>
+</ol></nav>
+</body></html>"##;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/nav/toc.xhtml", nav),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let page_list = epub.page_list().unwrap().unwrap();
+        assert_eq!(page_list.nav_label.as_ref().unwrap().text, "页码");
+        assert_eq!(page_list.page_targets.len(), 3);
+        let target = page_list.find_page_target_by_value("2").unwrap();
+        assert_eq!(target.play_order, 2);
+        assert_eq!(target.nav_label.text, "2");
+        assert_eq!(target.content.src, "OEBPS/text/chapter1.xhtml#page2");
+        assert_eq!(epub.go_to_page("2").unwrap().unwrap().id, "chapter1");
+        // 带查询参数和百分号转义的页码目标
+        assert_eq!(epub.go_to_page("3").unwrap().unwrap().id, "chapter2");
+        assert!(epub.go_to_page("99").unwrap().is_none());
+        
+        // 没有页码列表的书籍
+        let test_file_without = "test_page_list_from_nav_without.epub";
+        create_test_epub_with_ncx(test_file_without).unwrap();
+        assert!(Epub::from_path(test_file_without).unwrap().page_list().unwrap().is_none());
+        assert!(Epub::from_path(test_file_without).unwrap().go_to_page("1").unwrap().is_none());
+        
+        let _ = fs::remove_file(test_file);
+        let _ = fs::remove_file(test_file_without);
+    }
+
+    #[test]
+    fn test_from_path_lenient() {
+        let test_file = "test_from_path_lenient.epub";
+        let write_zip = |files: &[(&str, &str)]| {
+            let mut zip = ZipWriter::new(File::create(test_file).unwrap());
+            for (name, content) in files {
+                zip.start_file(*name, FileOptions::<()>::default()).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        };
+        let container = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles><rootfile full-path="content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+        let opf = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>缺少mimetype</dc:title></metadata>
+    <manifest></manifest><spine></spine>
+</package>"#;
+        
+        write_zip(&[("META-INF/container.xml", container), ("content.opf", opf)]);
+        assert!(matches!(Epub::from_path(test_file), Err(EpubError::MissingMimetype)));
+        let epub = Epub::from_path_lenient(test_file).unwrap();
+        assert_eq!(epub.book_info().unwrap().title, "缺少mimetype");
+        assert_eq!(epub.opened_with_warnings().len(), 1);
+        assert_eq!(epub.opened_with_warnings()[0].path.as_deref(), Some("mimetype"));
+        assert_eq!(epub.warnings(), epub.opened_with_warnings());
+        
+        let epub = crate::epub::EpubBuilder::new().lenient(true).print_warnings(false).open(test_file).unwrap();
+        assert_eq!(epub.opened_with_warnings().len(), 1);
+        assert_eq!(epub.warnings().len(), 1);
+        // container.xml同样受单个文件大小上限的限制
+        assert!(crate::epub::EpubBuilder::new().lenient(true).max_entry_size(64).open(test_file).is_err());
+        
+        // mimetype错误且container.xml指向的OPF不存在时仍然失败
+        write_zip(&[("mimetype", "application/zip"), ("META-INF/container.xml", container)]);
+        assert!(matches!(Epub::from_path_lenient(test_file), Err(EpubError::InvalidMimetype { .. })));
+        
+        // 有效文件不产生警告
+        create_test_epub(test_file).unwrap();
+        assert!(Epub::from_path_lenient(test_file).unwrap().opened_with_warnings().is_empty());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_select_rendition() {
+        let test_file = "test_select_rendition.epub";
+        let container = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="fixed/content.opf" media-type="application/oebps-package+xml"/>
+        <rootfile full-path="reflow/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+        let opf = |title: &str| format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{}</dc:title></metadata>
+    <manifest><item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/></manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#, title);
+        let fixed_opf = opf("固定版式");
+        let reflow_opf = opf("流式版式");
+        create_epub_from_files(test_file, &[
+            ("META-INF/container.xml", container),
+            ("fixed/content.opf", &fixed_opf),
+            ("fixed/ch1.xhtml", "<html><body><p>图片页</p></body></html>"),
+            ("reflow/content.opf", &reflow_opf),
+            ("reflow/ch1.xhtml", "<html><body><p>正文</p></body></html>"),
+        ]).unwrap();
+        
+        let mut epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.book_info().unwrap().title, "固定版式");
+        
+        epub.select_rendition(1).unwrap();
+        assert_eq!(epub.book_info().unwrap().title, "流式版式");
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(epub.chapter_text(&chapters[0], false).unwrap(), "正文");
+        assert!(epub.select_rendition(2).is_err());
+        
+        let epub = crate::epub::EpubBuilder::new().rendition(1).open(test_file).unwrap();
+        assert_eq!(epub.book_info().unwrap().title, "流式版式");
+        assert!(crate::epub::EpubBuilder::new().rendition(5).open(test_file).is_err());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_builder_metadata_tag_configs() {
+        let test_file = "test_builder_metadata_tag_configs.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let mut configs = MetadataTagConfigs::default_config();
+        configs.title.tags = vec!["creator".to_string()];
+        let epub = crate::epub::EpubBuilder::new()
+            .metadata_tag_configs(configs)
+            .open(test_file)
+            .unwrap();
+        // 缓存的OPF及依赖它的书籍信息都使用指定的配置
+        assert_eq!(epub.opf().unwrap().metadata.title().as_deref(), Some("测试作者"));
+        assert_eq!(epub.book_info().unwrap().title, "测试作者");
+        
+        assert_eq!(Epub::from_path(test_file).unwrap().book_info().unwrap().title, "测试书籍");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_landmarks() {
+        let test_file = "test_landmarks.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>导览测试</dc:title></metadata>
+    <manifest>
+        <item id="nav" href="nav/toc.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="chapter1"/></spine>
+    <guide>
+        <reference type="text" href="text/chapter1.xhtml" title="正文"/>
+        <reference type="cover" href="text/cover.xhtml" title="封面"/>
+    </guide>
+</package>"#;
+        let nav = r##"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="toc"><ol><li><a href="../text/chapter1.xhtml">第一章</a></li></ol></nav>
+<nav epub:type="landmarks"><ol>
+    <li><a epub:type="bodymatter" href="../text/chapter1.xhtml#start">开始阅读</a></li>
+</ol></nav>
+</body></html>"##;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/nav/toc.xhtml", nav),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let landmarks = epub.landmarks().unwrap();
+        assert_eq!(landmarks.len(), 2);
+        // nav文档优先，guide只补充缺少的类型
+        assert_eq!(landmarks[0].title, "开始阅读");
+        assert_eq!(landmarks[0].href, "OEBPS/text/chapter1.xhtml#start");
+        assert!(landmarks[0].is_bodymatter());
+        assert_eq!(landmarks[1].landmark_type, "cover");
+        assert_eq!(landmarks[1].href, "OEBPS/text/cover.xhtml");
+        
+        let test_file_without = "test_landmarks_without.epub";
+        create_test_epub_with_ncx(test_file_without).unwrap();
+        assert!(Epub::from_path(test_file_without).unwrap().landmarks().unwrap().is_empty());
+        
+        let _ = fs::remove_file(test_file);
+        let _ = fs::remove_file(test_file_without);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let test_file = "test_from_bytes.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let bytes = fs::read(test_file).unwrap();
+        let _ = fs::remove_file(test_file);
+        
+        let epub = Epub::from_bytes(bytes.clone()).unwrap();
+        assert_eq!(epub.book_info().unwrap().title, "测试书籍（带NCX）");
+        let chapters = epub.chapter_list().unwrap();
+        assert!(epub.chapter(&chapters[0]).unwrap().content.contains("这是第一章的内容"));
+        
+        let epub = Epub::from_reader(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(epub.chapter_list().unwrap().len(), 2);
+        
+        assert!(Epub::from_bytes(b"not a zip".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_fonts() {
+        let test_file = "test_fonts.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>字体测试</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="serif" href="fonts/serif.otf" media-type="application/vnd.ms-opentype"/>
+        <item id="sans" href="fonts/sans.woff2" media-type="font/woff2"/>
+    </manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let encryption = r#"<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container" xmlns:enc="http://www.w3.org/2001/04/xmlenc#">
+    <enc:EncryptedData>
+        <enc:EncryptionMethod Algorithm="http://www.idpf.org/2008/embedding"/>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/serif.otf"/></enc:CipherData>
+    </enc:EncryptedData>
+</encryption>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("META-INF/encryption.xml", encryption),
+            ("OEBPS/fonts/serif.otf", "OTTO-serif"),
+            ("OEBPS/fonts/sans.woff2", "wOF2-sans"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let fonts = epub.fonts().unwrap();
+        let summary: Vec<(&str, &str, bool)> = fonts.iter()
+            .map(|font| (font.id.as_str(), font.media_type.as_str(), font.obfuscated))
+            .collect();
+        assert_eq!(summary, vec![
+            ("sans", "font/woff2", false),
+            ("serif", "application/vnd.ms-opentype", true),
+        ]);
+        assert_eq!(epub.font_data(&fonts[0]).unwrap(), b"wOF2-sans");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_drm_protected_chapters() {
+        let test_file = "test_drm_protected_chapters.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>加密书籍</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="serif" href="fonts/serif.otf" media-type="font/otf"/>
+    </manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let encryption = r#"<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container" xmlns:enc="http://www.w3.org/2001/04/xmlenc#">
+    <enc:EncryptedData>
+        <enc:EncryptionMethod Algorithm="http://www.w3.org/2001/04/xmlenc#aes128-cbc"/>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/serif.otf"/></enc:CipherData>
+    </enc:EncryptedData>
+    <enc:EncryptedData>
+        <enc:EncryptionMethod Algorithm="http://www.w3.org/2001/04/xmlenc#aes128-cbc"/>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/text/ch1.xhtml"/></enc:CipherData>
+    </enc:EncryptedData>
+</encryption>"#;
+        let files = [
+            ("OEBPS/content.opf", opf),
+            ("META-INF/encryption.xml", encryption),
+            ("OEBPS/text/ch1.xhtml", "\u{1}\u{2}加密内容"),
+        ];
+        
+        create_epub_from_files(test_file, &files).unwrap();
+        match Epub::from_path(test_file) {
+            Err(EpubError::DrmProtected(scheme)) => {
+                assert_eq!(scheme, "未知方案，加密算法: http://www.w3.org/2001/04/xmlenc#aes128-cbc");
+            }
+            _ => panic!("应返回DrmProtected错误"),
+        }
+        
+        let mut adept_files = files.to_vec();
+        adept_files.push(("META-INF/rights.xml", "<adept:rights xmlns:adept=\"http://ns.adobe.com/adept\"/>"));
+        create_epub_from_files(test_file, &adept_files).unwrap();
+        assert!(matches!(
+            Epub::from_path(test_file),
+            Err(EpubError::DrmProtected(scheme)) if scheme == "Adobe ADEPT"
+        ));
+        
+        // 只加密字体时可以正常打开
+        let font_only = encryption.replacen("OEBPS/text/ch1.xhtml", "OEBPS/fonts/other.otf", 1);
+        let mut font_files = files.to_vec();
+        font_files[1].1 = &font_only;
+        font_files.push(("META-INF/rights.xml", "<adept:rights xmlns:adept=\"http://ns.adobe.com/adept\"/>"));
+        create_epub_from_files(test_file, &font_files).unwrap();
+        assert!(Epub::from_path(test_file).is_ok());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_font_data_deobfuscates_idpf_fonts() {
+        let test_file = "test_font_data_deobfuscates_idpf_fonts.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>混淆字体</dc:title>
+        <dc:identifier id="isbn">978-7-000-00000-0</dc:identifier>
+        <dc:identifier id="uid">
+            urn:uuid:1234-abcd
+        </dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="serif" href="fonts/serif.otf" media-type="font/otf"/>
+    </manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let encryption = r#"<encryption xmlns="urn:oasis:names:tc:opendocument:xmlns:container" xmlns:enc="http://www.w3.org/2001/04/xmlenc#">
+    <enc:EncryptedData>
+        <enc:EncryptionMethod Algorithm="http://www.idpf.org/2008/embedding"/>
+        <enc:CipherData><enc:CipherReference URI="OEBPS/fonts/serif.otf"/></enc:CipherData>
+    </enc:EncryptedData>
+</encryption>"#;
+        
+        // 密钥为SHA-1("urn:uuid:1234-abcd")，前1040个字节与之循环异或
+        let key: [u8; 20] = [
+            0x9e, 0x62, 0x5b, 0xcf, 0xff, 0xfe, 0x3b, 0xda, 0x73, 0x8b,
+            0xfe, 0x5b, 0x0f, 0x23, 0xcc, 0x60, 0x6f, 0x81, 0xd6, 0x11,
+        ];
+        let mut font = b"OTTO".to_vec();
+        font.extend((0..2000).map(|i| (i % 251) as u8));
+        let obfuscated: Vec<u8> = font.iter().enumerate()
+            .map(|(i, byte)| if i < 1040 { byte ^ key[i % key.len()] } else { *byte })
+            .collect();
+        assert_ne!(&obfuscated[..4], b"OTTO");
+        
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf.as_bytes().to_vec()),
+            ("META-INF/encryption.xml", encryption.as_bytes().to_vec()),
+            ("OEBPS/fonts/serif.otf", obfuscated),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let fonts = epub.fonts().unwrap();
+        assert!(fonts[0].obfuscated);
+        let data = epub.font_data(&fonts[0]).unwrap();
+        assert_eq!(&data[..4], b"OTTO");
+        assert_eq!(data, font);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_resource_range() {
+        let test_file = "test_resource_range.epub";
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", br#"<package xmlns="http://www.idpf.org/2007/opf" version="3.0"><metadata/><manifest/><spine/></package>"#.to_vec()),
+            ("OEBPS/images/large.bin", data.clone()),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.resource_range("OEBPS/images/large.bin", 0, 10).unwrap(), data[..10]);
+        assert_eq!(epub.resource_range("OEBPS/images/large.bin", 54_321, 4096).unwrap(), data[54_321..54_321 + 4096]);
+        assert_eq!(epub.resource_range("OEBPS/images/large.bin", 99_990, 100).unwrap(), data[99_990..]);
+        assert!(epub.resource_range("OEBPS/images/large.bin", 200_000, 10).unwrap().is_empty());
+        assert!(epub.resource_range("OEBPS/images/missing.bin", 0, 10).is_err());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_percent_encoded_manifest_hrefs() {
+        let test_file = "test_percent_encoded_manifest_hrefs.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>编码路径</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc%20file.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="ch1" href="chapter%201.xhtml" media-type="application/xhtml+xml"/>
+        <item id="img" href="images/%E5%9B%BE.png" media-type="image/png"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="ch1"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="np-1" playOrder="1">
+            <navLabel><text>第一章</text></navLabel>
+            <content src="chapter%201.xhtml"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc file.ncx", ncx),
+            ("OEBPS/chapter 1.xhtml", "<html><body><p>带空格的文件名</p></body></html>"),
+            ("OEBPS/images/图.png", "png"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(chapters[0].title, "第一章");
+        assert!(epub.chapter(&chapters[0]).unwrap().content.contains("带空格的文件名"));
+        assert_eq!(epub.chapter_text(&chapters[0], false).unwrap(), "带空格的文件名");
+        assert!(epub.ncx().unwrap().is_some());
+        
+        let images = epub.images().unwrap();
+        assert_eq!(epub.image_data(&images[0]).unwrap(), b"png");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_list_titles_match_exact_paths() {
+        let test_file = "test_chapter_list_titles_match_exact_paths.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>相似路径</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="p2ch1" href="part2/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="extra" href="extra.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="ch1"/><itemref idref="p2ch1"/><itemref idref="extra"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <docTitle><text>相似路径</text></docTitle>
+    <navMap>
+        <navPoint id="n1" playOrder="1"><navLabel><text>第二部第一章</text></navLabel><content src="part2/ch1.xhtml"/></navPoint>
+        <navPoint id="n2" playOrder="2"><navLabel><text>第一章</text></navLabel><content src="./ch1.xhtml#start"/></navPoint>
+    </navMap>
+</ncx>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf), ("OEBPS/toc.ncx", ncx)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let titles: Vec<String> = epub.chapter_list().unwrap().into_iter().map(|chapter| chapter.title).collect();
+        assert_eq!(titles, vec!["第一章", "第二部第一章", "章节 3"]);
+        
+        // 没有NCX时使用nav文档中的标题
+        let test_file_nav = "test_chapter_list_titles_from_nav.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>nav标题</dc:title></metadata>
+    <manifest>
+        <item id="nav" href="nav/nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let nav = r#"<html><body><nav epub:type="toc"><ol><li><a href="../text/ch1.xhtml">序章</a></li></ol></nav></body></html>"#;
+        create_epub_from_files(test_file_nav, &[("OEBPS/content.opf", opf), ("OEBPS/nav/nav.xhtml", nav)]).unwrap();
+        
+        let epub = Epub::from_path(test_file_nav).unwrap();
+        assert_eq!(epub.chapter_list().unwrap()[0].title, "序章");
+        
+        let _ = fs::remove_file(test_file);
+        let _ = fs::remove_file(test_file_nav);
+    }
+
+    #[test]
+    fn test_chapter_text_without_toc() {
+        let test_file = "test_chapter_text_without_toc.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>无目录</dc:title></metadata>
+    <manifest><item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/></manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        let chapter = r#"<html><head><title>不应出现</title><style>p { color: red; }</style></head>
+<body><h1>第一章</h1><p>第一段 &amp; 实体</p><p>第二段</p></body></html>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf), ("OEBPS/ch1.xhtml", chapter)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(epub.toc_tree().unwrap().is_none());
+        let chapters = epub.chapter_list().unwrap();
+        
+        let formatted = epub.chapter_text(&chapters[0], true).unwrap();
+        assert!(formatted.contains("第一段 & 实体\n第二段"));
+        assert!(formatted.contains("第二段"));
+        assert!(!formatted.contains("color"));
+        assert!(!formatted.contains("不应出现"));
+        
+        let plain = epub.chapter_text(&chapters[0], false).unwrap();
+        assert_eq!(plain, epub.chapter(&chapters[0]).unwrap().text());
+        assert!(!plain.contains('<'));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_internal_links() {
+        let test_file = "test_internal_links.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>链接检查</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="notes" href="text/notes.xhtml" media-type="application/xhtml+xml"/>
+        <item id="img" href="images/a.png" media-type="image/png"/>
+        <item id="lost" href="text/lost.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="notes" linear="no"/><itemref idref="lost"/></spine>
+</package>"#;
+        let chapter = r##"<html><body>
+<p id="p1">正文<a href="notes.xhtml#n1">1</a><a href="notes.xhtml#n2">2</a></p>
+<a href="#p1">回到开头</a><a href="#missing">失效锚点</a>
+<a href="../images/a.png">图片</a><a href="gone.xhtml">失效文件</a>
+<a href="https://example.com">外部</a><a href="mailto:a@example.com">邮件</a>
+</body></html>"##;
+        let notes = r#"<html><body><aside id="n1">注释一</aside></body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/ch1.xhtml", chapter),
+            ("OEBPS/text/notes.xhtml", notes),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let links = epub.internal_links().unwrap();
+        let summary: Vec<(&str, &str, Option<&str>, bool, bool)> = links.iter()
+            .map(|link| (
+                link.href.as_str(),
+                link.target.as_str(),
+                link.fragment.as_deref(),
+                link.target_exists,
+                link.anchor_exists,
+            ))
+            .collect();
+        assert_eq!(summary, vec![
+            ("notes.xhtml#n1", "OEBPS/text/notes.xhtml", Some("n1"), true, true),
+            ("notes.xhtml#n2", "OEBPS/text/notes.xhtml", Some("n2"), true, false),
+            ("#p1", "OEBPS/text/ch1.xhtml", Some("p1"), true, true),
+            ("#missing", "OEBPS/text/ch1.xhtml", Some("missing"), true, false),
+            ("../images/a.png", "OEBPS/images/a.png", None, true, true),
+            ("gone.xhtml", "OEBPS/text/gone.xhtml", None, false, true),
+        ]);
+        assert!(links.iter().all(|link| link.source == "OEBPS/text/ch1.xhtml"));
+        // 缺失的章节记录警告并跳过
+        assert!(epub.warnings().iter().any(|warning| warning.path.as_deref() == Some("OEBPS/text/lost.xhtml")));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_resource_data() {
+        let test_file = "test_resource_data.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>资源测试</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="font1" href="fonts/serif.otf" media-type="font/otf"/>
+    </manifest>
+    <spine><itemref idref="ch1"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/ch1.xhtml", "<html><body><p>正文</p></body></html>"),
+            ("OEBPS/fonts/serif.otf", "OTTO"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.resource_data("font1").unwrap(), b"OTTO");
+        assert_eq!(epub.resource_media_type("font1").unwrap(), "font/otf");
+        assert!(matches!(epub.resource_data("missing"), Err(EpubError::InvalidEpub(_))));
+        assert!(matches!(epub.resource_media_type("missing"), Err(EpubError::InvalidEpub(_))));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_full_text_indexed() {
+        let test_file = "test_full_text_indexed.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let (text, spans) = epub.full_text_indexed().unwrap();
+        
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].start, 0);
+        assert_eq!(&text[spans[0].start..spans[0].end], "第一章 这是第一章的内容。");
+        assert_eq!(&text[spans[1].start..spans[1].end], "第二章 这是第二章的内容。");
+        assert_eq!(spans[1].chapter.id, "chapter2");
+        
+        let offset = text.find("第二章的内容").unwrap();
+        let span = spans.iter().find(|s| s.start <= offset && offset < s.end).unwrap();
+        assert_eq!(span.chapter.id, "chapter2");
+        assert_eq!(epub.full_text().unwrap(), text);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let test_file = "test_vocabulary.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let vocabulary = epub.vocabulary().unwrap();
+        let count_of = |term: &str| vocabulary.iter().find(|(t, _)| t == term).map(|(_, c)| *c);
+        
+        // 两章各出现两次"章"
+        assert_eq!(vocabulary[0], ("章".to_string(), 4));
+        assert_eq!(count_of("二"), Some(2));
+        
+        let bigrams = epub.vocabulary_with(Tokenization::Bigrams).unwrap();
+        assert!(bigrams.iter().any(|(term, count)| term == "内容" && *count == 2));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_toc_tree_creation() {
+        let test_file = "test_toc_tree.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        
+        // 测试检查是否有目录树
+        assert!(epub.has_toc_tree().unwrap());
+        
+        // 第一次调用 - 创建目录树
+        let toc_tree1 = epub.toc_tree().unwrap();
+        assert!(toc_tree1.is_some());
+        
+        // 第二次调用 - 再次创建目录树
+        let toc_tree2 = epub.toc_tree().unwrap();
+        assert!(toc_tree2.is_some());
+        
+        // 验证目录树内容
+        let toc_tree = toc_tree1.unwrap();
+        assert_eq!(toc_tree.roots.len(), 2); // 应该有两个根节点
+        assert_eq!(toc_tree.roots[0].title, "第一章");
+        assert_eq!(toc_tree.roots[1].title, "第二章");
+        
+        // 验证第二个目录树也有相同内容
+        let toc_tree2 = toc_tree2.unwrap();
+        assert_eq!(toc_tree2.roots.len(), 2);
+        assert_eq!(toc_tree2.roots[0].title, "第一章");
+        assert_eq!(toc_tree2.roots[1].title, "第二章");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_toc_tree_render_with() {
+        let test_file = "test_toc_render_with.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut toc_tree = epub.toc_tree().unwrap().unwrap();
+        toc_tree.roots[0].add_child(TocTreeNode::new(3, "第一节".to_string(), "text/chapter1.xhtml#s1".to_string(), "s1".to_string(), 1));
+        
+        let output = toc_tree.render_with(|node, depth| format!("{}@{}", node.title, depth));
+        assert_eq!(output, "├── 第一章@0\n│   └── 第一节@1\n└── 第二章@0\n");
+        
+        let display = format!("{}", toc_tree.with_max_depth(Some(1)));
+        assert!(display.contains("├── [1] 第一章 → text/chapter1.xhtml"));
+        assert!(!display.contains("第一节"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_custom_text_extractor() {
+        let test_file = "test_custom_text_extractor.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap()
+            .with_text_extractor(|html: &str| format!("自定义转换: {}", html.contains("<body")));
+        let node = &toc_tree.roots[0];
+        
+        assert_eq!(toc_tree.get_node_formatted_text_content(node).unwrap(), "自定义转换: true");
+        assert_eq!(
+            node.get_formatted_text_content_with_extractor(&epub, None).unwrap(),
+            node.get_formatted_text_content(&epub).unwrap()
+        );
+        let options = TextOptions::default();
+        assert_eq!(
+            node.get_formatted_text_content_with_extractor(&epub, Some(&options)).unwrap(),
+            node.get_formatted_text_content(&epub).unwrap()
+        );
+        
+        let format = crate::epub::ExportFormat::Formatted;
+        let paths = toc_tree.generate_all_txt_files(Some(output_dir.path()), format, false, false).unwrap();
+        assert_eq!(paths.len(), 2);
+        for path in &paths {
+            assert!(fs::read_to_string(path).unwrap().contains("自定义转换: true"));
+        }
+        
+        // 其他导出格式不受影响
+        let plain = toc_tree.generate_all_txt_files(
+            Some(output_dir.path()), crate::epub::ExportFormat::Plain, false, false,
+        ).unwrap();
+        assert!(!fs::read_to_string(&plain[0]).unwrap().contains("自定义转换"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_generate_json_file() {
+        let test_file = "test_generate_json.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let json_path = toc_tree.generate_json_file(Some(output_dir.path()), true, None).unwrap();
+        assert!(json_path.ends_with("book.json"));
+        
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["chapters"].as_array().unwrap().len(), 2);
+        assert_eq!(json["chapters"][0]["title"], "第一章");
+        assert_eq!(json["chapters"][0]["src"], "text/chapter1.xhtml");
+        assert_eq!(json["chapters"][0]["play_order"], 1);
+        assert!(json["chapters"][0]["text"].as_str().unwrap().contains("这是第一章的内容"));
+        assert!(json["chapters"][0]["word_count"].as_u64().unwrap() > 0);
+        
+        // 单章节导出为json文件
+        let chapter_path = toc_tree.roots[1]
+            .generate_txt_file(&epub, Some(output_dir.path()), crate::epub::ExportFormat::Json)
+            .unwrap();
+        assert_eq!(chapter_path.extension().unwrap(), "json");
+        let chapter: serde_json::Value = serde_json::from_str(&fs::read_to_string(&chapter_path).unwrap()).unwrap();
+        assert_eq!(chapter["id"], "navpoint-2");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_validate_navigation_coverage() {
+        let test_file = "test_validate_navigation.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(epub.validate().unwrap().is_empty());
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_validate_orphans.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf" unique-identifier="BookId">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>孤立章节</dc:title>
+        <dc:identifier id="BookId">orphans-001</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter2" href="text/chapter2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx">
+        <itemref idref="chapter1"/>
+        <itemref idref="chapter2"/>
+    </spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="navpoint-1" playOrder="1">
+            <navLabel><text>第一章</text></navLabel>
+            <content src="text/chapter1.xhtml#start"/>
+        </navPoint>
+        <navPoint id="navpoint-2" playOrder="2">
+            <navLabel><text>附录</text></navLabel>
+            <content src="text/appendix.xhtml"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/text/chapter1.xhtml", "<html><body><p>一</p></body></html>"),
+            ("OEBPS/text/chapter2.xhtml", "<html><body><p>二</p></body></html>"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let report = epub.validate().unwrap();
+        assert!(report.is_valid());
+        let warnings = report.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].path.as_deref(), Some("OEBPS/text/chapter2.xhtml"));
+        assert_eq!(warnings[1].path.as_deref(), Some("OEBPS/text/appendix.xhtml"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_validate_package_structure() {
+        let test_file = "test_validate_package_structure.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>结构损坏</dc:title>
+        <dc:identifier id="other">broken-001</dc:identifier>
+        <meta name="cover" content="cover-img"/>
+    </metadata>
+    <manifest>
+        <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="style" href="styles/missing.css" media-type="text/css"/>
+        <item id="cover-img" href="images/cover.jpg" media-type="image/jpeg"/>
+        <item id="font" href="https://example.com/font.woff2" media-type="font/woff2"/>
+    </manifest>
+    <spine toc="ncx">
+        <itemref idref="chapter1"/>
+        <itemref idref="ghost"/>
+    </spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/chapter1.xhtml", "<html><body><p>一</p></body></html>"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let report = epub.validate().unwrap();
+        assert!(!report.is_valid());
+        
+        let errors: Vec<(&str, Option<&str>)> = report.errors().iter()
+            .map(|issue| (issue.message.as_str(), issue.path.as_deref()))
+            .collect();
+        assert_eq!(errors, vec![
+            ("脊柱项 'ghost' 在清单中不存在", Some("OEBPS/content.opf")),
+            ("NCX文件不存在", Some("OEBPS/toc.ncx")),
+            ("nav导航文档不存在", Some("OEBPS/nav.xhtml")),
+            ("清单项 'cover-img' 引用的文件不存在", Some("OEBPS/images/cover.jpg")),
+            ("清单项 'style' 引用的文件不存在", Some("OEBPS/styles/missing.css")),
+        ]);
+        
+        let warnings: Vec<&str> = report.warnings().iter().map(|issue| issue.message.as_str()).collect();
+        assert_eq!(&warnings[..2], ["unique-identifier 'uid' 没有匹配的dc:identifier", "声明的封面文件不存在"]);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_enrich_with_headings() {
+        let test_file = "test_enrich_headings.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf" unique-identifier="BookId">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>标题</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="chapter1"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="navpoint-1" playOrder="1">
+            <navLabel><text>第一章</text></navLabel>
+            <content src="text/chapter1.xhtml"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        let chapter = r#"<html><body>
+<h1 id="top">第一章</h1>
+<h2 id="s1">第一节</h2>
+<h3 id="s1-1">小节</h3>
+<h4 id="deep">过深</h4>
+<h2>没有id</h2>
+<h2 id="s2">第二节</h2>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/text/chapter1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut toc_tree = epub.toc_tree().unwrap().unwrap();
+        toc_tree.enrich_with_headings(&epub, 3).unwrap();
+        
+        let chapter = &toc_tree.roots[0];
+        assert_eq!(chapter.children.len(), 2);
+        assert_eq!(chapter.children[0].title, "第一节");
+        assert_eq!(chapter.children[0].src, "text/chapter1.xhtml#s1");
+        assert_eq!(chapter.children[0].depth, 1);
+        assert_eq!(chapter.children[0].children.len(), 1);
+        assert_eq!(chapter.children[0].children[0].src, "text/chapter1.xhtml#s1-1");
+        assert_eq!(chapter.children[0].children[0].depth, 2);
+        assert_eq!(chapter.children[1].title, "第二节");
+        assert!(toc_tree.find_by_src("text/chapter1.xhtml#deep").is_none());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_toc_node_content_by_fragment() {
+        let test_file = "test_toc_node_content_by_fragment.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>单文件书籍</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="part1" href="text/part1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="part1"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="np-0" playOrder="1">
+            <navLabel><text>前言</text></navLabel>
+            <content src="text/part1.xhtml"/>
+        </navPoint>
+        <navPoint id="np-1" playOrder="2">
+            <navLabel><text>第一章</text></navLabel>
+            <content src="text/part1.xhtml#ch1"/>
+            <navPoint id="np-2" playOrder="3">
+                <navLabel><text>第一节</text></navLabel>
+                <content src="text/part1.xhtml#s1"/>
+            </navPoint>
+        </navPoint>
+        <navPoint id="np-3" playOrder="4">
+            <navLabel><text>第二章</text></navLabel>
+            <content src="text/part1.xhtml#ch2"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        let chapter = r#"<html><body>
+<p>前言内容</p>
+<h1 id="ch1">第一章</h1>
+<p>第一章导语</p>
+<h2 id="s1">第一节</h2>
+<p>第一节内容</p>
+<h1 id="ch2">第二章</h1>
+<p>第二章内容</p>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/text/part1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let text = |src: &str| toc_tree.find_by_src(src).unwrap().get_text_content(&epub).unwrap();
+        
+        let preface = text("text/part1.xhtml");
+        assert!(preface.contains("前言内容"));
+        assert!(!preface.contains("第一章"));
+        
+        let first = text("text/part1.xhtml#ch1");
+        assert!(first.contains("第一章导语"));
+        assert!(!first.contains("第一节内容"));
+        
+        let section = text("text/part1.xhtml#s1");
+        assert!(section.contains("第一节内容"));
+        assert!(!section.contains("第二章"));
+        
+        let last = text("text/part1.xhtml#ch2");
+        assert!(last.contains("第二章内容"));
+        assert!(!last.contains("前言内容"));
+        
+        // 手动创建的节点没有截止锚点，从锚点截取到文件末尾
+        let manual = TocTreeNode::new(9, "手动".to_string(), "text/part1.xhtml#s1".to_string(), "manual".to_string(), 0);
+        let manual_text = manual.get_text_content(&epub).unwrap();
+        assert!(manual_text.contains("第一节内容") && manual_text.contains("第二章内容"));
+        assert!(!manual_text.contains("第一章导语"));
+        
+        // 按文件去重的导出不能丢失同一文件中后续锚点节点的内容
+        let output_dir = tempfile::tempdir().unwrap();
+        let markdown_path = toc_tree.generate_markdown_book(Some(output_dir.path()), None).unwrap();
+        let markdown = fs::read_to_string(markdown_path).unwrap();
+        for expected in ["前言内容", "第一章导语", "第一节内容", "第二章内容"] {
+            assert_eq!(markdown.matches(expected).count(), 1, "{}", expected);
+        }
+        
+        let paths = toc_tree
+            .generate_deduplicated_txt_files(Some(output_dir.path()), crate::epub::ExportFormat::Plain)
+            .unwrap();
+        let contents: Vec<String> = paths.iter().map(|path| fs::read_to_string(path).unwrap()).collect();
+        assert!(contents[2].contains("第一节内容") && !contents[2].contains("参见"));
+        assert!(contents[3].contains("第二章内容") && !contents[3].contains("参见"));
+        
+        let mirrored = toc_tree
+            .generate_mirrored_files(Some(&output_dir.path().join("site")), Some(crate::epub::ExportFormat::Plain))
+            .unwrap();
+        assert_eq!(mirrored.len(), 1);
+        let mirrored_text = fs::read_to_string(&mirrored[0]).unwrap();
+        assert!(mirrored_text.contains("前言内容") && mirrored_text.contains("第二章内容"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_bom_prefixed_opf_in_archive() {
+        let test_file = "test_bom_opf.epub";
+        let opf = "\u{FEFF}<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<package version=\"3.0\" xmlns=\"http://www.idpf.org/2007/opf\">
+    <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\"><dc:title>带BOM的书</dc:title></metadata>
+    <manifest></manifest>
+    <spine></spine>
+</package>";
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf)]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.version().unwrap(), "3.0");
+        assert_eq!(epub.book_info().unwrap().title, "带BOM的书");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_cover_info() {
+        let test_file = "test_cover_info.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>封面信息</dc:title>
+        <meta name="cover" content="cover-img"/>
+    </metadata>
+    <manifest>
+        <item id="page1" href="text/page1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="cover-img" href="images/front.png" media-type="image/png"/>
+    </manifest>
+    <spine><itemref idref="page1"/></spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/images/front.png", "\u{89}PNG"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let info = epub.cover_info().unwrap().unwrap();
+        assert_eq!(
+            (info.id.as_str(), info.path.as_str(), info.media_type.as_str()),
+            ("cover-img", "images/front.png", "image/png"),
+        );
+        assert_eq!(epub.cover().unwrap().unwrap().filename, "front.png");
+        
+        // 声明的封面无法读取时继续尝试常见文件名
+        let oversized = "x".repeat(4096);
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/images/front.png", oversized.as_str()),
+            ("OEBPS/cover.jpg", "jpeg"),
+        ]).unwrap();
+        let epub = crate::epub::EpubBuilder::new().max_entry_size(2048).open(test_file).unwrap();
+        assert_eq!(epub.cover_info().unwrap().unwrap().path, "images/front.png");
+        assert_eq!(epub.cover().unwrap().unwrap().filename, "cover.jpg");
+        
+        // 未声明封面时回退到常见文件名，不在清单中的文件根据扩展名推断媒体类型
+        let opf = opf.replace(r#"<meta name="cover" content="cover-img"/>"#, "");
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf.as_str()),
+            ("OEBPS/cover.jpg", "jpeg"),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let info = epub.cover_info().unwrap().unwrap();
+        assert_eq!(
+            (info.id.as_str(), info.path.as_str(), info.media_type.as_str()),
+            ("", "cover.jpg", "image/jpeg"),
+        );
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_cover_candidates() {
+        fn png(width: u32, height: u32) -> Vec<u8> {
+            let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+            data.extend_from_slice(&width.to_be_bytes());
+            data.extend_from_slice(&height.to_be_bytes());
+            data
+        }
+        
+        let test_file = "test_cover_candidates.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="2.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>封面</dc:title></metadata>
+    <manifest>
+        <item id="page1" href="text/page1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="img1" href="images/a.png" media-type="image/png"/>
+        <item id="img2" href="images/big.png" media-type="image/png"/>
+        <item id="img3" href="images/small.png" media-type="image/png"/>
+        <item id="img4" href="images/Cover-art.png" media-type="image/png"/>
+    </manifest>
+    <spine><itemref idref="page1"/></spine>
+</package>"#;
+        let page = r#"<html><body><img src="../images/a.png"/></body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf.as_bytes().to_vec()),
+            ("OEBPS/text/page1.xhtml", page.as_bytes().to_vec()),
+            ("OEBPS/images/a.png", png(100, 100)),
+            ("OEBPS/images/big.png", png(500, 500)),
+            ("OEBPS/images/small.png", png(10, 10)),
+            ("OEBPS/images/Cover-art.png", png(20, 20)),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let candidates: Vec<String> = epub.cover_candidates().unwrap()
+            .into_iter()
+            .map(|image| image.path)
+            .collect();
+        assert_eq!(candidates, vec![
+            "images/Cover-art.png",
+            "images/a.png",
+            "images/big.png",
+            "images/small.png",
+        ]);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_compare_navigation() {
+        let test_file = "test_compare_navigation_ncx_only.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(epub.compare_navigation().unwrap().is_none());
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_compare_navigation.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>导航比较</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="nav" href="text/nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter2" href="text/chapter2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="chapter1"/><itemref idref="chapter2"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="n1" playOrder="1"><navLabel><text>第一章</text></navLabel><content src="text/chapter1.xhtml"/></navPoint>
+        <navPoint id="n2" playOrder="2"><navLabel><text>第二章</text></navLabel><content src="text/chapter2.xhtml"/></navPoint>
+        <navPoint id="n3" playOrder="3"><navLabel><text>第三章</text></navLabel><content src="text/chapter3.xhtml"/></navPoint>
+    </navMap>
+</ncx>"#;
+        let nav = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="landmarks"><ol><li><a href="chapter1.xhtml">开始</a></li></ol></nav>
+<nav epub:type="toc"><h1>目录</h1><ol>
+    <li><a href="chapter2.xhtml">第二章</a></li>
+    <li><a href="chapter1.xhtml">第1章</a>
+        <ol><li><a href="appendix.xhtml">附录</a></li></ol>
+    </li>
+</ol></nav>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/text/nav.xhtml", nav),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let nav_tree = epub.nav_toc_tree().unwrap().unwrap();
+        assert_eq!(nav_tree.title.as_deref(), Some("目录"));
+        assert_eq!(nav_tree.roots.len(), 2);
+        assert_eq!(nav_tree.roots[1].children[0].play_order, 3);
+        assert_eq!(nav_tree.roots[1].children[0].depth, 1);
+        
+        let diff = epub.compare_navigation().unwrap().unwrap();
+        assert!(!diff.is_empty());
+        assert_eq!(diff.only_in_ncx.len(), 1);
+        assert_eq!(diff.only_in_ncx[0].src, "OEBPS/text/chapter3.xhtml");
+        assert_eq!(diff.only_in_nav.len(), 1);
+        assert_eq!(diff.only_in_nav[0].title, "附录");
+        assert_eq!(diff.title_mismatches.len(), 1);
+        assert_eq!(diff.title_mismatches[0].ncx_title, "第一章");
+        assert_eq!(diff.title_mismatches[0].nav_title, "第1章");
+        assert_eq!(diff.order_mismatches.len(), 2);
+        assert_eq!(diff.order_mismatches[0].ncx_src, "OEBPS/text/chapter1.xhtml");
+        assert_eq!(diff.order_mismatches[0].nav_src, "OEBPS/text/chapter2.xhtml");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_export_chapter_bundle() {
+        let test_file = "test_chapter_bundle.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>资源</dc:title></metadata>
+    <manifest>
+        <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="chapter1"/></spine>
+</package>"#;
+        let chapter = r#"<html><head><link rel="stylesheet" type="text/css" href="../styles/main.css"/></head>
+<body><img src="../images/a.png" alt="../images/a.png"/><img src='../other/a.png'/><img src="../images/missing.png"/></body></html>"#;
+        let css = r#"@font-face { src: url("../fonts/book.otf"); } body { background: url(../images/a.png); }"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/chapter1.xhtml", chapter),
+            ("OEBPS/styles/main.css", css),
+            ("OEBPS/images/a.png", "png-a"),
+            ("OEBPS/other/a.png", "png-b"),
+            ("OEBPS/fonts/book.otf", "font"),
+        ]).unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        epub.export_chapter_bundle(&chapters[0], output_dir.path()).unwrap();
+        
+        let html = fs::read_to_string(output_dir.path().join("chapter1.xhtml")).unwrap();
+        assert!(html.contains(r#"href="main.css""#));
+        assert!(html.contains(r#"<img src="a.png" alt="../images/a.png"/>"#));
+        assert!(html.contains("src='2_a.png'"));
+        assert!(html.contains(r#"src="../images/missing.png""#));
+        assert_eq!(fs::read_to_string(output_dir.path().join("a.png")).unwrap(), "png-a");
+        assert_eq!(fs::read_to_string(output_dir.path().join("2_a.png")).unwrap(), "png-b");
+        assert_eq!(fs::read_to_string(output_dir.path().join("book.otf")).unwrap(), "font");
+        
+        let css = fs::read_to_string(output_dir.path().join("main.css")).unwrap();
+        assert!(css.contains(r#"url("book.otf")"#));
+        assert!(css.contains("url(a.png)"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_hash() {
+        let test_file = "test_chapter_hash.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>哈希</dc:title></metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="ch2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="ch2"/></spine>
+</package>"#;
+        let build = |chapter2: &str| {
+            create_epub_from_files(test_file, &[
+                ("OEBPS/content.opf", opf),
+                ("OEBPS/ch1.xhtml", "<html><body><p>abc</p></body></html>"),
+                ("OEBPS/ch2.xhtml", chapter2),
+            ]).unwrap();
+            Epub::from_path(test_file).unwrap()
+        };
+        
+        let epub = build("<html><body><p>第二章</p></body></html>");
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(
+            epub.chapter_hash(&chapters[0]).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let hash = epub.chapter_hash(&chapters[1]).unwrap();
+        let fingerprint = epub.content_fingerprint().unwrap();
+        assert_eq!(fingerprint.len(), 64);
+        
+        // 只有格式和空白不同时哈希不变
+        let epub = build("<html>\n<body>\n  <div><span>第二章</span></div>\n</body></html>");
+        assert_eq!(epub.chapter_hash(&chapters[1]).unwrap(), hash);
+        assert_eq!(epub.content_fingerprint().unwrap(), fingerprint);
+        
+        let epub = build("<html><body><p>第二章（修订）</p></body></html>");
+        assert_ne!(epub.chapter_hash(&chapters[1]).unwrap(), hash);
+        assert_ne!(epub.content_fingerprint().unwrap(), fingerprint);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_to_single_html() {
+        let test_file = "test_to_single_html.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>合并 &amp; 测试</dc:title><dc:language>zh</dc:language></metadata>
+    <manifest>
+        <item id="ch1" href="Text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="Text/ch2.xhtml" media-type="application/xhtml+xml"/>
+        <item id="css" href="Styles/main.css" media-type="text/css"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="ch2"/></spine>
+</package>"#;
+        let chapter1 = r#"<html><head><link rel="stylesheet" href="../Styles/main.css"/></head>
+<body><p>见<a href="ch2.xhtml">下一章</a>和<a href="ch2.xhtml#note">注释</a></p><img src="../Images/a.png"/></body></html>"#;
+        let chapter2 = r#"<html><head><link rel="stylesheet" href="../Styles/main.css"/><style>p { margin: 0; }</style></head>
+<body><p id="note">注释内容</p></body></html>"#;
+        let css = "body { background: url(../Images/bg.png); }";
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/Text/ch1.xhtml", chapter1),
+            ("OEBPS/Text/ch2.xhtml", chapter2),
+            ("OEBPS/Styles/main.css", css),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let html = epub.to_single_html().unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"zh\">"));
+        assert!(html.contains("<title>合并 &amp; 测试</title>"));
+        assert_eq!(html.matches("<style>").count(), 2);
+        assert!(html.contains("url(OEBPS/Images/bg.png)"));
+        assert!(html.contains(r##"<a href="#chapter-ch1">"##));
+        assert!(html.contains(r#"<section id="chapter-ch2">"#));
+        assert!(html.contains(r##"<a href="#chapter-ch2">下一章</a>"##));
+        assert!(html.contains(r##"<a href="#note">注释</a>"##));
+        assert!(html.contains(r#"<img src="OEBPS/Images/a.png">"#));
+        assert!(html.find("chapter-ch1\">").unwrap() < html.find("<section id=\"chapter-ch1\">").unwrap());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_ruby_text() {
+        let test_file = "test_chapter_ruby_text.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>注音测试</dc:title>
+        <dc:identifier id="uid">ruby-test</dc:identifier>
+        <dc:language>ja</dc:language>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+    </spine>
+</package>"#;
+        let chapter = r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+<p><ruby>漢字<rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>を<ruby>読<rt>よ</rt></ruby>む</p>
+<p>“Quote”&nbsp;—&nbsp;end…</p>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/ch1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let chapter = epub.chapter(&chapters[0]).unwrap();
+        
+        assert_eq!(chapter.text(), "漢字を読む “Quote” — end…");
+        let options = TextOptions::new().with_ruby(RubyMode::parenthesized());
+        assert_eq!(chapter.text_with_options(&options), "漢字(かんじ)を読(よ)む “Quote” — end…");
+        let options = TextOptions::new().with_normalization(TextNormalization::Typographic);
+        assert_eq!(chapter.text_with_options(&options), "漢字を読む \"Quote\" -- end...");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_export_tables_csv() {
+        let test_file = "test_export_tables_csv.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>表格测试</dc:title>
+        <dc:identifier id="uid">table-test</dc:identifier>
+        <dc:language>zh</dc:language>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+    </spine>
+</package>"#;
+        let chapter = r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+<table><tr><th>城市</th><th>人口</th></tr><tr><td>北京</td><td>21,893,095</td></tr></table>
+<table><tr><td>x</td></tr></table>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/ch1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = epub.export_tables_csv(&chapters[0], output_dir.path()).unwrap();
+        
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].file_name().unwrap(), "001_章节 1_table1.csv");
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "城市,人口\r\n北京,\"21,893,095\"\r\n");
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "x\r\n");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_entries() {
+        let test_file = "test_entries.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let entries = epub.entries().unwrap();
+        
+        assert_eq!(entries.len(), epub.file_list().unwrap().len());
+        assert_eq!(epub.non_manifest_files().unwrap(), vec![
+            "mimetype".to_string(),
+            "META-INF/container.xml".to_string(),
+            "OEBPS/content.opf".to_string(),
+        ]);
+        assert!(entries.windows(2).all(|pair| pair[0].name <= pair[1].name));
+        
+        let mimetype = entries.iter().find(|e| e.name == "mimetype").unwrap();
+        assert_eq!(mimetype.uncompressed_size, b"application/epub+zip".len() as u64);
+        assert_eq!(mimetype.crc32, 0x2CAB616F);
+        assert!(!mimetype.is_dir);
+        assert!(mimetype.last_modified.is_some());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_language() {
+        let test_file = "test_chapter_language.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>多语言测试</dc:title>
+        <dc:identifier id="uid">lang-test</dc:identifier>
+        <dc:language>zh-CN</dc:language>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="ch2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+        <itemref idref="ch2"/>
+    </spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/ch1.xhtml", r#"<html xmlns="http://www.w3.org/1999/xhtml" xml:lang="en"><body><p>Hello</p></body></html>"#),
+            ("OEBPS/ch2.xhtml", r#"<html xmlns="http://www.w3.org/1999/xhtml"><body><p>你好</p></body></html>"#),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(epub.chapter_language(&chapters[0]).unwrap().as_deref(), Some("en"));
+        assert_eq!(epub.chapter_language(&chapters[1]).unwrap().as_deref(), Some("zh-CN"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_accessibility_report() {
+        let test_file = "test_accessibility_report.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>无障碍测试</dc:title>
+        <dc:identifier id="uid">a11y-test</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="broken" href="text/missing.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="img1" href="images/a.png" media-type="image/png"/>
+    </manifest>
+    <spine>
+        <itemref idref="broken"/>
+        <itemref idref="ch1"/>
+    </spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/text/ch1.xhtml", r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+                <h1>第一章</h1><h3>小节</h3>
+                <img src="../images/a.png"/><img src="../images/b.png" alt="示意图"/>
+                <img src="../images/rule.png" alt=""/>
+            </body></html>"#),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        assert_eq!(epub.chapter_image_alts(&chapters[1]).unwrap(), vec![
+            ("images/a.png".to_string(), None),
+            ("images/b.png".to_string(), Some("示意图".to_string())),
+            ("images/rule.png".to_string(), Some(String::new())),
+        ]);
+        
+        let report = epub.accessibility_report().unwrap();
+        let kinds: Vec<A11yIssueKind> = report.findings.iter().map(|finding| finding.kind).collect();
+        assert_eq!(kinds, vec![
+            A11yIssueKind::MissingLanguage,
+            A11yIssueKind::MissingNavDocument,
+            A11yIssueKind::MissingAccessibilityFeature,
+            A11yIssueKind::MissingAltText,
+            A11yIssueKind::HeadingLevelSkip,
+        ]);
+        assert_eq!(report.errors().len(), 2);
+        assert_eq!(report.of_kind(A11yIssueKind::MissingAltText)[0].path.as_deref(), Some("OEBPS/text/ch1.xhtml"));
+        assert!(epub.warnings().iter().any(|warning| warning.path.as_deref() == Some("OEBPS/text/missing.xhtml")));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_image_accessibility() {
+        let test_file = "test_image_accessibility.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>图片替代文本</dc:title>
+        <dc:identifier id="uid">alt-test</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="cover" href="cover.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="text/missing.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="cover"/>
+        <itemref idref="ch1"/>
+        <itemref idref="ch2"/>
+    </spine>
+</package>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/cover.xhtml", r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+                <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+                    <title>封面</title><image xlink:href="images/cover.jpg"/>
+                </svg>
+                <img src="images/logo.png" alt="标志"/>
+            </body></html>"#),
+            ("OEBPS/text/ch1.xhtml", r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+                <img src="../images/logo.png"/>
+                <img src="../images/map.png" aria-label="地图"/>
+                <img src="../images/rule.png" alt=""/>
+            </body></html>"#),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let images = epub.image_accessibility().unwrap();
+        let summary: Vec<(&str, bool, usize)> = images.iter()
+            .map(|image| (image.path.as_str(), image.has_alt, image.usages.len()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("OEBPS/images/cover.jpg", true, 1),
+            ("OEBPS/images/logo.png", false, 2),
+            ("OEBPS/images/map.png", true, 1),
+            ("OEBPS/images/rule.png", true, 1),
+        ]);
+        assert_eq!(images[3].usages[0].alt.as_deref(), Some(""));
+        assert!(epub.warnings().iter().any(|warning| warning.path.as_deref() == Some("OEBPS/text/missing.xhtml")));
+        assert_eq!(images[0].usages[0].element, "image");
+        assert_eq!(images[0].usages[0].alt.as_deref(), Some("封面"));
+        assert_eq!(images[1].missing_alt_usages(), vec![&ImageUsage {
+            chapter_path: "OEBPS/text/ch1.xhtml".to_string(),
+            element: "img".to_string(),
+            alt: None,
+        }]);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_cfi_for_and_resolve() {
+        let test_file = "test_cfi.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let cfi = epub.cfi_for(&chapters[1], &[1, 0]).unwrap();
+        assert!(cfi.starts_with("epubcfi(/6/4["));
+        
+        let position = epub.resolve_cfi(&cfi).unwrap().unwrap();
+        assert_eq!(position.spine_index, 1);
+        assert_eq!(position.chapter_info.id, chapters[1].id);
+        assert_eq!(position.element_path, vec![1, 0]);
+        assert!(!position.text.is_empty());
+        
+        assert!(epub.cfi_for(&chapters[1], &[1, 99]).is_err());
+        assert!(epub.resolve_cfi("epubcfi(/6/40!/4)").unwrap().is_none());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_export_mirrored_paths() {
+        let test_file = "test_export_mirrored_paths.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut first = TocTreeNode::new(1, "甲".to_string(), "text/chapter1.xhtml".to_string(), "a".to_string(), 0);
+        first.add_child(TocTreeNode::new(2, "甲一".to_string(), "text/chapter1.xhtml#s1".to_string(), "a1".to_string(), 1));
+        let second = TocTreeNode::new(3, "乙".to_string(), "text/chapter2.xhtml".to_string(), "b".to_string(), 0);
+        let mut toc_tree = TocTree::new(&epub);
+        toc_tree.add_root(first);
+        toc_tree.add_root(second);
+        
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = toc_tree.generate_mirrored_files(Some(output_dir.path()), None).unwrap();
+        assert_eq!(paths, vec![
+            output_dir.path().join("text").join("chapter1.xhtml"),
+            output_dir.path().join("text").join("chapter2.xhtml"),
+        ]);
+        assert_eq!(
+            fs::read_to_string(&paths[0]).unwrap(),
+            epub.read_chapter_file("OEBPS/text/chapter1.xhtml").unwrap()
+        );
+        
+        let paths = toc_tree
+            .generate_mirrored_files(Some(output_dir.path()), Some(crate::epub::ExportFormat::Plain))
+            .unwrap();
+        assert_eq!(paths[1], output_dir.path().join("text").join("chapter2.txt"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_process_chapters() {
+        let test_file = "test_process_chapters.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut out = Vec::new();
+        epub.process_chapters(&mut out, |chapter| chapter.info.id.to_uppercase()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "CHAPTER1\n\nCHAPTER2");
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_generate_merged_per_root() {
+        let test_file = "test_generate_merged_per_root.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut first = TocTreeNode::new(1, "第一部".to_string(), "text/chapter1.xhtml".to_string(), "p1".to_string(), 0);
+        first.add_child(TocTreeNode::new(2, "第一章".to_string(), "text/chapter2.xhtml".to_string(), "c1".to_string(), 1));
+        let second = TocTreeNode::new(3, "第二部".to_string(), "text/chapter2.xhtml".to_string(), "p2".to_string(), 0);
+        let mut toc_tree = TocTree::new(&epub);
+        toc_tree.title = Some("丛书".to_string());
+        toc_tree.add_root(first);
+        toc_tree.add_root(second);
+        
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = toc_tree
+            .generate_merged_per_root(Some(output_dir.path()), crate::epub::ExportFormat::Plain)
+            .unwrap();
+        assert_eq!(paths, vec![
+            output_dir.path().join("丛书_01_第一部.txt"),
+            output_dir.path().join("丛书_02_第二部.txt"),
+        ]);
+        let first_part = fs::read_to_string(&paths[0]).unwrap();
+        assert!(first_part.contains("部分标题: 第一部"));
+        assert!(first_part.contains("章节总数: 2"));
+        assert!(first_part.contains("第 2 章: 第一章"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let test_file = "test_verify_integrity.epub";
+        create_test_epub(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(epub.verify_integrity().unwrap().is_empty());
+        drop(epub);
+        
+        // 写入未压缩的条目后篡改其内容，使CRC32不再匹配
+        {
+            let file = File::create(test_file).unwrap();
+            let mut zip = ZipWriter::new(file);
+            let stored = FileOptions::<()>::default().compression_method(zip::CompressionMethod::Stored);
+            zip.start_file("mimetype", stored).unwrap();
+            zip.write_all(b"application/epub+zip").unwrap();
+            zip.start_file("OEBPS/damaged.txt", stored).unwrap();
+            zip.write_all(b"original-content").unwrap();
+            zip.finish().unwrap();
+        }
+        let mut bytes = fs::read(test_file).unwrap();
+        let offset = bytes.windows(16).position(|window| window == b"original-content").unwrap();
+        bytes[offset] = b'O';
+        fs::write(test_file, bytes).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.verify_integrity().unwrap(), vec!["OEBPS/damaged.txt".to_string()]);
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_export_global_numbering() {
+        let test_file = "test_export_global_numbering.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        // 不同分支中的播放顺序重复
+        let mut first = TocTreeNode::new(1, "甲".to_string(), "text/chapter1.xhtml".to_string(), "a".to_string(), 0);
+        first.add_child(TocTreeNode::new(1, "甲一".to_string(), "text/chapter2.xhtml".to_string(), "a1".to_string(), 1));
+        let second = TocTreeNode::new(1, "乙".to_string(), "text/chapter2.xhtml".to_string(), "b".to_string(), 0);
+        let mut toc_tree = TocTree::new(&epub);
+        toc_tree.add_root(first);
+        toc_tree.add_root(second);
+        
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = toc_tree
+            .generate_all_txt_files(Some(output_dir.path()), crate::epub::ExportFormat::Plain, true, true)
+            .unwrap();
+        let relative: Vec<PathBuf> = paths.iter()
+            .map(|path| path.strip_prefix(output_dir.path()).unwrap().to_path_buf())
+            .collect();
+        
+        assert_eq!(relative, vec![
+            PathBuf::from("0001_甲.txt"),
+            PathBuf::from("0001_甲").join("0002_甲一.txt"),
+            PathBuf::from("0003_乙.txt"),
+        ]);
+        
+        // 跳过已存在的文件：保留手动修改过的内容，返回完整的路径列表并标明跳过的文件
+        fs::write(&paths[0], "已修改").unwrap();
+        fs::remove_file(&paths[2]).unwrap();
+        let options = crate::epub::ExportOptions::new(crate::epub::ExportFormat::Plain)
+            .with_create_subdirs(true)
+            .with_global_numbering(true)
+            .with_skip_existing(true)
+            .with_index("index.txt");
+        let resumed = toc_tree.generate_all_txt_files_with_options(Some(output_dir.path()), &options).unwrap();
+        assert_eq!(resumed.paths(), paths);
+        let statuses: Vec<crate::epub::ExportStatus> = resumed.files.iter().map(|file| file.status).collect();
+        assert_eq!(statuses, vec![
+            crate::epub::ExportStatus::Skipped,
+            crate::epub::ExportStatus::Skipped,
+            crate::epub::ExportStatus::Written,
+        ]);
+        assert_eq!(resumed.skipped()[0].id, "a");
+        assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "已修改");
+        assert!(paths[2].exists());
+        assert_eq!(resumed.index_path, Some(output_dir.path().join("index.txt")));
+        assert!(output_dir.path().join("index.txt").exists());
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_classify() {
+        let test_file = "test_classify.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>漫画测试</dc:title>
+        <dc:identifier id="uid">comic-test</dc:identifier>
+        <meta property="rendition:layout">pre-paginated</meta>
+    </metadata>
+    <manifest>
+        <item id="p1" href="p1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="p2" href="p2.xhtml" media-type="application/xhtml+xml"/>
+        <item id="img1" href="img/1.jpg" media-type="image/jpeg"/>
+        <item id="img2" href="img/2.jpg" media-type="image/jpeg"/>
+    </manifest>
+    <spine>
+        <itemref idref="p1"/>
+        <itemref idref="p2"/>
+    </spine>
+</package>"#;
+        let image = vec![0u8; 4096];
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf.as_bytes()),
+            ("OEBPS/p1.xhtml", br#"<html><body><img src="img/1.jpg"/></body></html>"#.as_slice()),
+            ("OEBPS/p2.xhtml", br#"<html><body><svg><image xlink:href="img/2.jpg"/></svg></body></html>"#.as_slice()),
+            ("OEBPS/img/1.jpg", image.as_slice()),
+            ("OEBPS/img/2.jpg", image.as_slice()),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let classification = epub.classify().unwrap();
+        assert_eq!(classification.kind, ContentKind::Comic);
+        assert!(classification.signals.fixed_layout);
+        assert_eq!(classification.signals.pages, 2);
+        assert_eq!(classification.signals.image_pages, 2);
+        assert_eq!(classification.signals.image_bytes, 8192);
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_classify_text.epub";
+        create_test_epub(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.classify().unwrap().kind, ContentKind::Text);
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_chapter_text_skips_hidden_elements() {
+        let test_file = "test_chapter_text_skips_hidden_elements.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>隐藏内容测试</dc:title>
+        <dc:identifier id="uid">hidden-test</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+    </spine>
+</package>"#;
+        let chapter = r#"<html xmlns="http://www.w3.org/1999/xhtml"><body>
+<p>可见的正文</p>
+<div hidden="hidden"><p>隐藏的译文</p></div>
+<p style="color: red; DISPLAY : None !important">隐藏的注释</p>
+<p style="display:block">另一段正文</p>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/ch1.xhtml", chapter),
+        ]).unwrap();
+        
+        let epub = Epub::from_path(test_file).unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        let chapter = epub.chapter(&chapters[0]).unwrap();
+        assert_eq!(chapter.text(), "可见的正文 另一段正文");
+        
+        let formatted = TocTreeNode::convert_html_to_formatted_text(&chapter.content, &TextOptions::default());
+        assert!(formatted.contains("另一段正文"));
+        assert!(!formatted.contains("隐藏"));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_text_list_markers() {
+        let html = r#"<html><body>
+<p>步骤：</p>
+<ol start="3"><li>准备<ol><li>洗净</li><li value="5">切块</li><li>装盘</li></ol></li><li>烹饪</li></ol>
+<ul><li>提示</li></ul>
+<ol reversed=""><li>三</li><li>二</li></ol>
+</body></html>"#;
+        let options = TextOptions::new().with_list_markers(true);
+        let formatted = TocTreeNode::convert_html_to_formatted_text(html, &options);
+        assert_eq!(formatted, "步骤：\n3. 准备\n  1. 洗净\n  5. 切块\n  6. 装盘\n4. 烹饪\n- 提示\n2. 三\n1. 二");
+        
+        let plain = TocTreeNode::strip_html_tags(html, &options);
+        assert_eq!(plain, "步骤： 3. 准备 1. 洗净 5. 切块 6. 装盘 4. 烹饪 - 提示 2. 三 1. 二");
+        
+        // 默认不添加项目符号
+        assert!(!TocTreeNode::strip_html_tags(html, &TextOptions::default()).contains("3. "));
+    }
+    
+    #[test]
+    fn test_formatted_text_block_boundaries() {
+        let html = r#"<html><body><h1>第一章</h1><p>第一段<em>强调</em>与<a href="x.xhtml">链接</a></p><div><p>第二段</p></div><p>第三<span>段</span></p>
+<table>
+    <tr><th>名称</th><th>数量</th></tr>
+    <tr><td>苹果</td><td>3</td></tr>
+</table>
+<p>末段</p></body></html>"#;
+        let formatted = TocTreeNode::convert_html_to_formatted_text(html, &TextOptions::default());
+        assert_eq!(formatted, "第一章\n第一段强调与链接\n第二段\n第三段\n名称\t数量\n苹果\t3\n末段");
+    }
+    
+    #[test]
+    fn test_formatted_text_skips_scripts_and_styles() {
+        let html = r#"<html><head><title>标题</title></head><body><style>p { color: red; }</style>
+<p>正文</p><script>var x = 1;</script><svg><text>矢量</text></svg><p>结尾</p></body></html>"#;
+        let formatted = TocTreeNode::convert_html_to_formatted_text(html, &TextOptions::default());
+        assert_eq!(formatted, "正文\n结尾");
+        assert_eq!(TocTreeNode::strip_html_tags(html, &TextOptions::default()), "正文 结尾");
+    }
+    
+    #[test]
+    fn test_text_image_policy() {
+        use crate::epub::text::ImagePolicy;
+        
+        let html = r#"<html><body><p>前文<img src="images/a.png" alt="地图"/>后文</p><p><img src="images/b.png"/></p></body></html>"#;
+        assert_eq!(TocTreeNode::strip_html_tags(html, &TextOptions::default()), "前文后文");
+        
+        let options = TextOptions::new().with_images(ImagePolicy::placeholder("[图片: {alt}]"));
+        assert_eq!(TocTreeNode::convert_html_to_formatted_text(html, &options), "前文[图片: 地图]后文\n[图片: ]");
+        
+        let options = TextOptions::new().with_images(ImagePolicy::placeholder("[IMAGE: {src}]"));
+        assert_eq!(
+            TocTreeNode::strip_html_tags(html, &options),
+            "前文 [IMAGE: images/a.png] 后文 [IMAGE: images/b.png]"
+        );
+        
+        let options = TextOptions::new().with_images(ImagePolicy::AltTextOnly);
+        assert_eq!(TocTreeNode::strip_html_tags(html, &options), "前文 地图 后文");
+    }
+    
+    #[test]
+    fn test_text_scripts() {
+        let html = r##"<html><body><p>H<sub>2</sub>O 与 x<sup>2</sup> + a<sub>ij</sub>，见注<sup><a href="#n1">1</a></sup>。</p></body></html>"##;
+        assert_eq!(TocTreeNode::strip_html_tags(html, &TextOptions::default()), "H2O 与 x2 + aij，见注1。");
+        
+        let options = TextOptions::new().with_scripts(true);
+        assert_eq!(TocTreeNode::strip_html_tags(html, &options), "H₂O 与 x² + a_{ij}，见注¹。");
+        assert_eq!(TocTreeNode::convert_html_to_formatted_text(html, &options), "H₂O 与 x² + a_{ij}，见注¹。");
+    }
+
+    #[test]
+    fn test_open_with_warnings() {
+        let test_file = "test_open_with_warnings.epub";
+        let opf = r#"<?xml version="1.0" encoding="GBK"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="2.0" unique-identifier="uid">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <dc:title>警告测试</dc:title>
+        <dc:identifier id="uid">warning-test</dc:identifier>
+    </metadata>
+    <manifest>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine>
+        <itemref idref="ch1"/>
+    </spine>
+</package>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf)]).unwrap();
+        
+        let (epub, warnings) = crate::epub::EpubBuilder::new()
+            .print_warnings(false)
+            .warn_incomplete_metadata(true)
+            .open_with_warnings(test_file)
+            .unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].path.as_deref(), Some("OEBPS/content.opf"));
+        assert!(warnings[0].message.contains("gbk"));
+        assert!(warnings[1].message.contains("元数据不完整"));
         
-        without_query.to_string()
+        // 之后读取失败的章节同样会被收集
+        assert!(epub.chapters().unwrap().is_empty());
+        assert_eq!(epub.warnings().len(), 3);
+        
+        let _ = fs::remove_file(test_file);
     }
-    
-    /// 读取文本文件
-    fn read_file(&self, filename: &str) -> Result<String> {
-        // 清理文件路径，去掉锚点和查询参数
-        let clean_path = self.clean_file_path(filename);
+
+    #[test]
+    fn test_strip_chapter_numbers() {
+        let test_file = "test_strip_chapter_numbers.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>标题清理</dc:title></metadata>
+    <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="chapter1" href="chapter1.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine toc="ncx"><itemref idref="chapter1"/></spine>
+</package>"#;
+        let ncx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <navMap>
+        <navPoint id="n1" playOrder="1"><navLabel><text>第一章 开端</text></navLabel><content src="chapter1.xhtml"/></navPoint>
+    </navMap>
+</ncx>"#;
+        let nav = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="toc"><ol><li><a href="chapter1.xhtml">1. 开端</a></li></ol></nav>
+</body></html>"#;
+        let chapter = r#"<html><body><h1>第一章 开端</h1><h2 id="s1">第一节：相遇</h2></body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/toc.ncx", ncx),
+            ("OEBPS/nav.xhtml", nav),
+            ("OEBPS/chapter1.xhtml", chapter),
+        ]).unwrap();
         
-        let mut archive = self.archive.lock()
-            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        // 默认保留原始标题
+        let epub = Epub::from_path(test_file).unwrap();
+        assert_eq!(epub.chapter_list().unwrap()[0].title, "第一章 开端");
         
-        let mut file = archive.by_name(&clean_path)?;
-        let mut content = String::new();
-        file.read_to_string(&mut content)?;
-        Ok(content)
+        let epub = crate::epub::EpubBuilder::new()
+            .strip_chapter_numbers(true)
+            .open(test_file)
+            .unwrap();
+        assert_eq!(epub.chapter_list().unwrap()[0].title, "开端");
+        let mut toc_tree = epub.toc_tree().unwrap().unwrap();
+        toc_tree.enrich_with_headings(&epub, 2).unwrap();
+        assert_eq!(toc_tree.roots[0].title, "开端");
+        assert_eq!(toc_tree.roots[0].children[0].title, "相遇");
+        assert_eq!(epub.nav_toc_tree().unwrap().unwrap().roots[0].title, "开端");
+        
+        let _ = fs::remove_file(test_file);
     }
-    
-    /// 读取二进制文件
-    fn read_binary_file(&self, filename: &str) -> Result<Vec<u8>> {
-        // 清理文件路径，去掉锚点和查询参数
-        let clean_path = self.clean_file_path(filename);
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_export_report() {
+        use crate::epub::{ExportFormat, ExportOptions, ExportStatus};
         
-        let mut archive = self.archive.lock()
-            .map_err(|_| EpubError::InternalError("无法获取文件归档锁".to_string()))?;
+        let test_file = "test_export_report.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
         
-        let mut file = archive.by_name(&clean_path)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)?;
-        Ok(buffer)
+        let epub = Epub::from_path(test_file).unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        // 目标路径被目录占用，写入失败
+        fs::create_dir(output_dir.path().join("002_第二章.txt")).unwrap();
+        
+        let options = ExportOptions::new(ExportFormat::Plain).with_report("export_report.json");
+        let summary = toc_tree.generate_all_txt_files_with_options(Some(output_dir.path()), &options).unwrap();
+        let report_path = summary.report_path.clone().unwrap();
+        assert_eq!(report_path, output_dir.path().join("export_report.json"));
+        assert_eq!(summary.failed().len(), 1);
+        assert_eq!(summary.failed()[0].title, "第二章");
+        
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["total_files"], 1);
+        let entries = report["entries"].as_array().unwrap();
+        assert_eq!(entries[0]["title"], "第一章");
+        assert_eq!(entries[0]["src"], "text/chapter1.xhtml");
+        assert_eq!(entries[0]["status"], "written");
+        assert_eq!(entries[0]["output"], "001_第一章.txt");
+        assert!(entries[0]["bytes"].as_u64().unwrap() > 0);
+        assert!(entries[0]["word_count"].as_u64().unwrap() > 0);
+        assert!(entries[0].get("errors").is_none());
+        // 失败的节点记录状态和错误，不中断导出
+        assert_eq!(entries[1]["status"], "failed");
+        assert!(entries[1]["output"].is_null());
+        assert_eq!(entries[1]["errors"].as_array().unwrap().len(), 1);
+        
+        // 续传时已存在的文件记录为跳过
+        let options = options.with_skip_existing(true);
+        let summary = toc_tree.generate_all_txt_files_with_options(Some(output_dir.path()), &options).unwrap();
+        assert_eq!(summary.files[0].status, ExportStatus::Skipped);
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["entries"][0]["status"], "skipped");
+        assert!(report["entries"][0]["bytes"].as_u64().unwrap() > 0);
+        
+        // 不生成报告时失败直接返回错误
+        let options = ExportOptions::new(ExportFormat::Plain);
+        assert!(toc_tree.generate_all_txt_files_with_options(Some(output_dir.path()), &options).is_err());
+        
+        let _ = fs::remove_file(test_file);
     }
-    
-    /// 验证EPUB格式
-    fn validate_epub_format(archive: &mut ZipArchive<File>) -> Result<()> {
-        let mimetype_file = archive.by_name("mimetype");
+
+    #[test]
+    fn test_generate_markdown_book() {
+        let test_file = "test_generate_markdown_book.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
         
-        match mimetype_file {
-            Ok(mut file) => {
-                let mut content = String::new();
-                file.read_to_string(&mut content)?;
-                
-                let content = content.trim();
-                let expected_mimetype = "application/epub+zip";
-                
-                if content != expected_mimetype {
-                    return Err(EpubError::InvalidMimetype {
-                        expected: expected_mimetype.to_string(),
-                        found: content.to_string(),
-                    });
-                }
-                
-                Ok(())
-            }
-            Err(_) => Err(EpubError::MissingMimetype),
-        }
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut toc_tree = epub.toc_tree().unwrap().unwrap();
+        toc_tree.roots[0].add_child(TocTreeNode::new(3, "第一节".to_string(), "./text/chapter1.xhtml".to_string(), "s1".to_string(), 1));
+        let output_dir = tempfile::tempdir().unwrap();
+        let path = toc_tree.generate_markdown_book(Some(output_dir.path()), None).unwrap();
+        assert_eq!(path, output_dir.path().join("测试书籍（带NCX）.md"));
+        
+        let markdown = fs::read_to_string(&path).unwrap();
+        assert!(markdown.starts_with("# 测试书籍（带NCX）\n\n## 目录\n\n- [第一章](#chapter-1)\n  - [第一节](#chapter-2)\n- [第二章](#chapter-3)\n"));
+        assert!(markdown.contains("<a id=\"chapter-2\"></a>\n\n### 第一节\n"));
+        // 章节内标题降级，同一位置的内容只输出一次
+        assert!(markdown.contains("## 第一章\n\n### 第一章\n\n这是第一章的内容。\n"));
+        assert_eq!(markdown.matches("这是第一章的内容。").count(), 1);
+        
+        let _ = fs::remove_file(test_file);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::{self, File};
-    use std::io::Write;
-    use zip::{ZipWriter, write::FileOptions};
+    #[test]
+    fn test_navigation_summary() {
+        let test_file = "test_navigation_summary.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        let summary = epub.navigation_summary().unwrap();
+        assert_eq!(summary, crate::epub::NavSummary {
+            spine_total: 2,
+            spine_linear: 2,
+            ncx_entries: Some(2),
+            nav_entries: None,
+        });
+        assert!(summary.agrees());
+        let _ = fs::remove_file(test_file);
+        
+        let test_file = "test_navigation_summary_mismatch.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>导航概览</dc:title></metadata>
+    <manifest>
+        <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="cover" href="cover.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter1" href="chapter1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="chapter2" href="chapter2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="cover" linear="no"/><itemref idref="chapter1"/><itemref idref="chapter2"/></spine>
+</package>"#;
+        let nav = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="toc"><ol><li><a href="chapter1.xhtml">第一章</a></li></ol></nav>
+</body></html>"#;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf), ("OEBPS/nav.xhtml", nav)]).unwrap();
+        let summary = Epub::from_path(test_file).unwrap().navigation_summary().unwrap();
+        assert_eq!((summary.spine_total, summary.spine_linear), (3, 2));
+        assert_eq!((summary.ncx_entries, summary.nav_entries), (None, Some(1)));
+        assert!(!summary.agrees());
+        assert_eq!(summary.to_string(), "脊柱: 3 (线性 2), NCX: 无, nav: 1 - 不一致");
+        let _ = fs::remove_file(test_file);
+    }
 
-    fn create_test_epub(path: &str) -> Result<()> {
-        let file = File::create(path)?;
-        let mut zip = ZipWriter::new(file);
+    #[test]
+    fn test_toc_tree_cached_paths() {
+        let test_file = "test_toc_tree_cached_paths.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
         
-        // mimetype
-        zip.start_file("mimetype", FileOptions::<()>::default())?;
-        zip.write_all(b"application/epub+zip")?;
+        let epub = Epub::from_path(test_file).unwrap();
+        let mut toc_tree = epub.toc_tree().unwrap().unwrap();
+        assert_eq!(toc_tree.roots[0].resolved_path(), Some("OEBPS/text/chapter1.xhtml"));
+        assert_eq!(toc_tree.roots[1].resolved_path(), Some("OEBPS/text/chapter2.xhtml"));
         
-        // container.xml
-        zip.start_file("META-INF/container.xml", FileOptions::<()>::default())?;
-        let container_xml = r#"<?xml version="1.0"?>
-<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
-    <rootfiles>
-        <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
-    </rootfiles>
-</container>"#;
-        zip.write_all(container_xml.as_bytes())?;
+        // 手动添加的节点按需解析
+        let manual = TocTreeNode::new(3, "手动".to_string(), "text/chapter2.xhtml".to_string(), "m".to_string(), 0);
+        assert_eq!(manual.resolved_path(), None);
+        assert_eq!(
+            manual.get_html_content(&epub).unwrap(),
+            toc_tree.roots[1].get_html_content(&epub).unwrap()
+        );
         
-        // content.opf
-        zip.start_file("OEBPS/content.opf", FileOptions::<()>::default())?;
-        let opf_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
-<package version="3.0" xmlns="http://www.idpf.org/2007/opf" unique-identifier="BookId">
-    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
-        <dc:title>测试书籍</dc:title>
-        <dc:creator>测试作者</dc:creator>
-        <dc:language>zh-CN</dc:language>
-        <dc:identifier id="BookId">test-book-001</dc:identifier>
-    </metadata>
+        // 从章节标题生成的子节点沿用父节点的路径
+        toc_tree.enrich_with_headings(&epub, 3).unwrap();
+        for child in &toc_tree.roots[0].children {
+            assert_eq!(child.resolved_path(), Some("OEBPS/text/chapter1.xhtml"));
+        }
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_toc_tree_falls_back_to_nav() {
+        let test_file = "test_toc_tree_falls_back_to_nav.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>仅nav导航</dc:title></metadata>
     <manifest>
+        <item id="nav" href="nav/toc.xhtml" media-type="application/xhtml+xml" properties="nav"/>
         <item id="chapter1" href="text/chapter1.xhtml" media-type="application/xhtml+xml"/>
         <item id="chapter2" href="text/chapter2.xhtml" media-type="application/xhtml+xml"/>
     </manifest>
-    <spine>
-        <itemref idref="chapter1"/>
-        <itemref idref="chapter2"/>
-    </spine>
+    <spine><itemref idref="chapter1"/><itemref idref="chapter2"/></spine>
 </package>"#;
-        zip.write_all(opf_xml.as_bytes())?;
+        let nav = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+<nav epub:type="toc"><ol>
+    <li><a href="../text/chapter1.xhtml">第一章</a>
+        <ol><li><a href="../text/chapter1.xhtml#sec2">第二节</a></li></ol>
+    </li>
+    <li><a href="../text/chapter2.xhtml">第二章</a></li>
+</ol></nav>
+</body></html>"#;
+        create_epub_from_files(test_file, &[
+            ("OEBPS/content.opf", opf),
+            ("OEBPS/nav/toc.xhtml", nav),
+            ("OEBPS/text/chapter1.xhtml", "<html><body><p>第一章正文</p><h2 id=\"sec2\">第二节</h2></body></html>"),
+            ("OEBPS/text/chapter2.xhtml", "<html><body><p>第二章正文</p></body></html>"),
+        ]).unwrap();
         
-        // chapter1.xhtml
-        zip.start_file("OEBPS/text/chapter1.xhtml", FileOptions::<()>::default())?;
-        let chapter1 = r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE html>
-<html xmlns="http://www.w3.org/1999/xhtml">
-<head><title>第一章</title></head>
-<body><h1>第一章</h1><p>这是第一章的内容。</p></body>
-</html>"#;
-        zip.write_all(chapter1.as_bytes())?;
+        let epub = Epub::from_path(test_file).unwrap();
+        assert!(!epub.has_ncx().unwrap());
+        assert!(epub.has_toc_tree().unwrap());
         
-        // chapter2.xhtml
-        zip.start_file("OEBPS/text/chapter2.xhtml", FileOptions::<()>::default())?;
-        let chapter2 = r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE html>
-<html xmlns="http://www.w3.org/1999/xhtml">
-<head><title>第二章</title></head>
-<body><h1>第二章</h1><p>这是第二章的内容。</p></body>
-</html>"#;
-        zip.write_all(chapter2.as_bytes())?;
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        assert_eq!(toc_tree.source, crate::epub::ncx::TocTreeSource::Nav);
+        assert_eq!(toc_tree.roots.len(), 2);
+        let section = &toc_tree.roots[0].children[0];
+        assert_eq!((section.play_order, section.depth), (2, 1));
+        assert_eq!(section.resolved_path(), Some("OEBPS/text/chapter1.xhtml"));
+        assert_eq!(toc_tree.roots[1].get_text_content(&epub).unwrap(), "第二章正文");
         
-        zip.finish()?;
-        Ok(())
+        // 导出同样可以使用nav目录
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = toc_tree
+            .generate_all_txt_files(Some(output_dir.path()), crate::epub::ExportFormat::Plain, false, false)
+            .unwrap();
+        assert_eq!(paths.len(), 3);
+        
+        // 只有nav文档时不进行NCX与nav的比较
+        assert!(epub.compare_navigation().unwrap().is_none());
+        
+        let _ = fs::remove_file(test_file);
     }
 
     #[test]
-    fn test_epub_creation() {
-        let test_file = "test_epub_creation.epub";
-        create_test_epub(test_file).unwrap();
+    fn test_markdown_export_format() {
+        let test_file = "test_markdown_export_format.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
         
         let epub = Epub::from_path(test_file).unwrap();
-        assert!(epub.container().is_ok());
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        
+        let path = toc_tree.roots[0]
+            .generate_txt_file(&epub, Some(output_dir.path()), crate::epub::ExportFormat::Markdown)
+            .unwrap();
+        assert_eq!(path.extension().unwrap(), "md");
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# 第一章\n\n这是第一章的内容。"));
+        
+        let parts = toc_tree
+            .generate_merged_per_root(Some(output_dir.path()), crate::epub::ExportFormat::Markdown)
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+        let part = fs::read_to_string(&parts[1]).unwrap();
+        assert!(part.starts_with("# 第二章\n\n## 目录\n\n- [第二章](#chapter-1)\n"));
         
         let _ = fs::remove_file(test_file);
     }
 
+    #[cfg(feature = "parallel")]
     #[test]
-    fn test_book_info() {
-        let test_file = "test_book_info.epub";
-        create_test_epub(test_file).unwrap();
+    fn test_generate_all_txt_files_parallel() {
+        let test_file = "test_generate_all_txt_files_parallel.epub";
+        let chapter_count = 40;
+        let manifest: String = (1..=chapter_count)
+            .map(|i| format!(r#"<item id="c{i}" href="c{i}.xhtml" media-type="application/xhtml+xml"/>"#))
+            .collect();
+        let spine: String = (1..=chapter_count).map(|i| format!(r#"<itemref idref="c{i}"/>"#)).collect();
+        let opf = format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<package version="3.0" xmlns="http://www.idpf.org/2007/opf">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>并行导出</dc:title></metadata>
+    <manifest><item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>{manifest}</manifest>
+    <spine>{spine}</spine>
+</package>"#);
+        let nav_items: String = (1..=chapter_count)
+            .map(|i| format!(r#"<li><a href="c{i}.xhtml">第{i}章</a></li>"#))
+            .collect();
+        let nav = format!(r#"<html><body><nav epub:type="toc"><ol>{nav_items}</ol></nav></body></html>"#);
+        let paragraphs = "<p>这是一段用于测试并行导出的<em>正文</em>内容。</p>".repeat(500);
+        let chapters: Vec<(String, String)> = (1..=chapter_count)
+            .map(|i| (format!("OEBPS/c{i}.xhtml"), format!("<html><body><h1>第{i}章</h1>{paragraphs}</body></html>")))
+            .collect();
+        
+        let mut files: Vec<(&str, &str)> = vec![("OEBPS/content.opf", &opf), ("OEBPS/nav.xhtml", &nav)];
+        files.extend(chapters.iter().map(|(name, content)| (name.as_str(), content.as_str())));
+        create_epub_from_files(test_file, &files).unwrap();
         
         let epub = Epub::from_path(test_file).unwrap();
-        let info = epub.book_info().unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let sequential_dir = tempfile::tempdir().unwrap();
+        let parallel_dir = tempfile::tempdir().unwrap();
+        let format = crate::epub::ExportFormat::Formatted;
         
-        assert_eq!(info.title, "测试书籍");
-        assert_eq!(info.authors, vec!["测试作者"]);
+        // 粗略的基准：加速比取决于CPU核心数（单核环境下两者接近），因此只输出耗时而不做断言
+        let start = std::time::Instant::now();
+        let sequential = toc_tree.generate_all_txt_files(Some(sequential_dir.path()), format, false, true).unwrap();
+        let sequential_time = start.elapsed();
+        let start = std::time::Instant::now();
+        let parallel = toc_tree.generate_all_txt_files_parallel(Some(parallel_dir.path()), format, false, true).unwrap();
+        let parallel_time = start.elapsed();
+        eprintln!(
+            "导出{}章: 串行 {:?}, 并行 {:?} ({} 个线程)",
+            chapter_count, sequential_time, parallel_time, rayon::current_num_threads()
+        );
+        
+        assert_eq!(parallel.len(), chapter_count);
+        for (sequential_path, parallel_path) in sequential.iter().zip(&parallel) {
+            assert_eq!(
+                sequential_path.strip_prefix(sequential_dir.path()).unwrap(),
+                parallel_path.strip_prefix(parallel_dir.path()).unwrap()
+            );
+            assert_eq!(fs::read(sequential_path).unwrap(), fs::read(parallel_path).unwrap());
+        }
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_epub() {
+        let test_file = "test_async_epub.epub";
+        create_test_epub_with_ncx(test_file).unwrap();
+        
+        let epub = crate::epub::AsyncEpub::open(test_file).await.unwrap();
+        let chapters = epub.as_sync().chapter_list().unwrap();
+        let chapter = epub.chapter(&chapters[0]).await.unwrap();
+        assert_eq!(chapter.content, epub.as_sync().chapter(&chapters[0]).unwrap().content);
+        
+        let html = epub.read_chapter_file("OEBPS/text/chapter2.xhtml").await.unwrap();
+        assert!(html.contains("第二章"));
+        assert!(epub.read_chapter_file("OEBPS/missing.xhtml").await.is_err());
+        assert!(epub.cover().await.unwrap().is_none());
         
         let _ = fs::remove_file(test_file);
     }
 
     #[test]
-    fn test_chapters() {
-        let test_file = "test_chapters.epub";
-        create_test_epub(test_file).unwrap();
+    fn test_toc_tree_flatten() {
+        let test_file = "test_toc_tree_flatten.epub";
+        let opf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+    <metadata xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>平铺目录</dc:title></metadata>
+    <manifest>
+        <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+        <item id="ch1" href="ch1.xhtml" media-type="application/xhtml+xml"/>
+        <item id="ch2" href="ch2.xhtml" media-type="application/xhtml+xml"/>
+    </manifest>
+    <spine><itemref idref="ch1"/><itemref idref="ch2"/></spine>
+</package>"#;
+        let nav = r##"<html><body><nav epub:type="toc"><ol>
+<li><a href="ch1.xhtml">第一章</a><ol>
+    <li><a href="ch1.xhtml#s1">第一节</a><ol><li><a href="ch1.xhtml#s1-1">小节</a></li></ol></li>
+    <li><a href="ch1.xhtml#s2">第二节</a></li>
+</ol></li>
+<li><a href="ch2.xhtml">第二章</a></li>
+</ol></nav></body></html>"##;
+        create_epub_from_files(test_file, &[("OEBPS/content.opf", opf), ("OEBPS/nav.xhtml", nav)]).unwrap();
         
         let epub = Epub::from_path(test_file).unwrap();
-        let chapters = epub.chapters().unwrap();
+        let toc_tree = epub.toc_tree().unwrap().unwrap();
+        let entries = toc_tree.flatten();
+        let summary: Vec<(&str, &str, u32, bool)> = entries.iter()
+            .map(|entry| (entry.title.as_str(), entry.src.as_str(), entry.depth, entry.has_children))
+            .collect();
+        assert_eq!(summary, vec![
+            ("第一章", "ch1.xhtml", 0, true),
+            ("第一节", "ch1.xhtml#s1", 1, true),
+            ("小节", "ch1.xhtml#s1-1", 2, false),
+            ("第二节", "ch1.xhtml#s2", 1, false),
+            ("第二章", "ch2.xhtml", 0, false),
+        ]);
+        assert_eq!(entries.iter().map(|entry| entry.title.clone()).collect::<Vec<_>>(), toc_tree.get_all_titles());
         
-        assert_eq!(chapters.len(), 2);
-        assert!(chapters[0].content.contains("第一章"));
-        assert!(chapters[1].content.contains("第二章"));
+        assert_eq!(toc_tree.breadcrumb(&[0, 0, 0]).unwrap(), vec!["第一章", "第一节", "小节"]);
+        assert_eq!(toc_tree.breadcrumb(&[1]).unwrap(), vec!["第二章"]);
+        assert!(toc_tree.breadcrumb(&[0, 2]).is_none());
+        assert!(toc_tree.breadcrumb(&[]).is_none());
         
         let _ = fs::remove_file(test_file);
     }
 
+    /// 提取目录树的结构（标题、链接、ID、样式类和子节点），用于比较
+    fn toc_structure(nodes: &[TocTreeNode]) -> Vec<(String, String, String, Option<String>, u32, usize)> {
+        let mut result = Vec::new();
+        for node in nodes {
+            result.push((node.title.clone(), node.src.clone(), node.id.clone(), node.class.clone(), node.depth, node.children.len()));
+            result.extend(toc_structure(&node.children));
+        }
+        result
+    }
+
     #[test]
-    fn test_toc_tree_creation() {
-        let test_file = "test_toc_tree.epub";
+    fn test_toc_serialization_round_trip() {
+        let test_file = "test_toc_serialization_round_trip.epub";
+        create_test_epub(test_file).unwrap();
+        let epub = Epub::from_path(test_file).unwrap();
+        
+        let ncx_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+    <head><meta name="dtb:uid" content="uid-1"/></head>
+    <docTitle><text>往返 &amp; 测试</text></docTitle>
+    <navMap>
+        <navPoint id="p1" playOrder="1" class="part">
+            <navLabel><text>第一部</text></navLabel>
+            <content src="text/part1.xhtml"/>
+            <navPoint id="c1" playOrder="2" class="chapter">
+                <navLabel><text>第一章 &lt;上&gt;</text></navLabel>
+                <content src="text/part1.xhtml#ch1"/>
+            </navPoint>
+        </navPoint>
+        <navPoint id="p2" playOrder="3">
+            <navLabel><text>附录</text></navLabel>
+            <content src="text/appendix.xhtml#sec-2"/>
+        </navPoint>
+    </navMap>
+</ncx>"#;
+        let original = create_toc_tree_from_ncx(&Ncx::parse_xml(ncx_xml).unwrap(), &epub);
+        assert_eq!(original.roots[0].class.as_deref(), Some("part"));
+        
+        let ncx_again = create_toc_tree_from_ncx(
+            &Ncx::parse_xml(&original.to_ncx_xml("uid-1")).unwrap(),
+            &epub,
+        );
+        assert_eq!(ncx_again.title, original.title);
+        assert_eq!(toc_structure(&ncx_again.roots), toc_structure(&original.roots));
+        let orders: Vec<u32> = ncx_again.roots.iter().map(|node| node.play_order).collect();
+        assert_eq!(orders, vec![1, 3]);
+        
+        let nav_again = create_toc_tree_from_nav(&original.to_nav_xhtml(), &epub);
+        assert_eq!(nav_again.title, original.title);
+        assert_eq!(toc_structure(&nav_again.roots), toc_structure(&original.roots));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_max_entry_size() {
+        let test_file = "test_max_entry_size.epub";
+        create_test_epub(test_file).unwrap();
+        
+        let epub = crate::epub::EpubBuilder::new()
+            .max_entry_size(4096)
+            .open(test_file)
+            .unwrap();
+        let chapters = epub.chapter_list().unwrap();
+        assert!(epub.chapter(&chapters[0]).is_ok());
+        
+        let epub = crate::epub::EpubBuilder::new()
+            .max_entry_size(20)
+            .open(test_file)
+            .unwrap();
+        match epub.opf() {
+            Err(EpubError::InvalidEpub(message)) => assert!(message.starts_with("资源过大")),
+            other => panic!("应当因资源过大而失败: {:?}", other.map(|_| ())),
+        }
+        
+        // 打开时验证的mimetype同样受大小上限限制
+        let mut zip = ZipWriter::new(File::create(test_file).unwrap());
+        zip.start_file("mimetype", FileOptions::<()>::default()).unwrap();
+        zip.write_all(format!("application/epub+zip{}", " ".repeat(100)).as_bytes()).unwrap();
+        zip.finish().unwrap();
+        assert!(matches!(
+            crate::epub::EpubBuilder::new().max_entry_size(64).open(test_file),
+            Err(EpubError::InvalidEpub(message)) if message.starts_with("资源过大")
+        ));
+        
+        let _ = fs::remove_file(test_file);
+    }
+
+    #[test]
+    fn test_generate_deduplicated_txt_files() {
+        let test_file = "test_generate_deduplicated_txt_files.epub";
         create_test_epub_with_ncx(test_file).unwrap();
         
         let epub = Epub::from_path(test_file).unwrap();
+        let mut first = TocTreeNode::new(1, "第一章".to_string(), "text/chapter1.xhtml".to_string(), "c1".to_string(), 0);
+        first.add_child(TocTreeNode::new(2, "第一节".to_string(), "text/chapter1.xhtml#s1".to_string(), "s1".to_string(), 1));
+        let second = TocTreeNode::new(3, "第二章".to_string(), "./text/chapter2.xhtml".to_string(), "c2".to_string(), 0);
+        let repeated = TocTreeNode::new(4, "第一章重复".to_string(), "./text/chapter1.xhtml".to_string(), "c1-again".to_string(), 0);
+        let mut toc_tree = TocTree::new(&epub);
+        toc_tree.add_root(first);
+        toc_tree.add_root(second);
+        toc_tree.add_root(repeated);
         
-        // 测试检查是否有目录树
-        assert!(epub.has_toc_tree().unwrap());
+        let output_dir = tempfile::tempdir().unwrap();
+        let paths = toc_tree
+            .generate_deduplicated_txt_files(Some(output_dir.path()), crate::epub::ExportFormat::Plain)
+            .unwrap();
+        let names: Vec<String> = paths.iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["0001_第一章.txt", "0002_第一节.txt", "0003_第二章.txt", "0004_第一章重复.txt"]);
         
-        // 第一次调用 - 创建目录树
-        let toc_tree1 = epub.toc_tree().unwrap();
-        assert!(toc_tree1.is_some());
+        assert!(fs::read_to_string(&paths[0]).unwrap().contains("这是第一章的内容"));
+        // 锚点不同的节点导出自己的内容，而不是说明文件
+        assert!(!fs::read_to_string(&paths[1]).unwrap().contains("参见"));
+        assert!(fs::read_to_string(&paths[2]).unwrap().contains("这是第二章的内容"));
+        let pointer = fs::read_to_string(&paths[3]).unwrap();
+        assert!(pointer.contains("0001_第一章.txt"));
+        assert!(!pointer.contains("这是第一章的内容"));
         
-        // 第二次调用 - 再次创建目录树
-        let toc_tree2 = epub.toc_tree().unwrap();
-        assert!(toc_tree2.is_some());
+        let _ = fs::remove_file(test_file);
+    }
+
+    /// 使用给定的文件创建测试EPUB（自动添加mimetype和container.xml）
+    fn create_epub_from_files<C: AsRef<[u8]>>(path: &str, files: &[(&str, C)]) -> Result<()> {
+        let file = File::create(path)?;
+        let mut zip = ZipWriter::new(file);
         
-        // 验证目录树内容
-        let toc_tree = toc_tree1.unwrap();
-        assert_eq!(toc_tree.roots.len(), 2); // 应该有两个根节点
-        assert_eq!(toc_tree.roots[0].title, "第一章");
-        assert_eq!(toc_tree.roots[1].title, "第二章");
+        zip.start_file("mimetype", FileOptions::<()>::default())?;
+        zip.write_all(b"application/epub+zip")?;
         
-        // 验证第二个目录树也有相同内容
-        let toc_tree2 = toc_tree2.unwrap();
-        assert_eq!(toc_tree2.roots.len(), 2);
-        assert_eq!(toc_tree2.roots[0].title, "第一章");
-        assert_eq!(toc_tree2.roots[1].title, "第二章");
+        // 未提供container.xml时使用指向OEBPS/content.opf的默认容器
+        if !files.iter().any(|(name, _)| *name == "META-INF/container.xml") {
+            zip.start_file("META-INF/container.xml", FileOptions::<()>::default())?;
+            let container_xml = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+    <rootfiles>
+        <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+    </rootfiles>
+</container>"#;
+            zip.write_all(container_xml.as_bytes())?;
+        }
         
-        let _ = fs::remove_file(test_file);
+        for (name, content) in files {
+            zip.start_file(*name, FileOptions::<()>::default())?;
+            zip.write_all(content.as_ref())?;
+        }
+        
+        zip.finish()?;
+        Ok(())
     }
 
     fn create_test_epub_with_ncx(path: &str) -> Result<()> {
@@ -0,0 +1,132 @@
+//! 全文搜索模块
+//!
+//! 提供在章节纯文本中查找关键词并生成上下文片段的功能。
+//!
+//! 匹配不区分大小写，按字符逐一比较（不做Unicode规范化），
+//! 每个起始位置都会单独检查，因此重叠的匹配会分别产生结果。
+
+/// 上下文片段在匹配位置两侧保留的最大字符数
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// 全文搜索选项
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SearchOptions {
+    /// 是否只匹配完整单词（匹配两侧不能紧邻字母、数字或下划线）
+    pub whole_word: bool,
+}
+
+impl SearchOptions {
+    /// 创建默认选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置是否只匹配完整单词
+    ///
+    /// 中日韩文字也属于字母，因此该选项主要用于拉丁字母等以空格分词的文字。
+    pub fn with_whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+}
+
+/// 全文搜索的单个命中结果
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SearchHit {
+    /// 章节id（目录项id，没有目录时为脊柱项的idref）
+    pub chapter_id: String,
+    /// 章节标题
+    pub chapter_title: String,
+    /// 匹配在章节纯文本中的起始字节偏移
+    pub offset: usize,
+    /// 匹配位置两侧各约40个字符的上下文，连续空白合并为一个空格
+    pub snippet: String,
+}
+
+/// 查找文本中所有匹配的位置
+///
+/// # 参数
+/// * `text` - 被搜索的文本
+/// * `query` - 关键词，为空时不产生匹配
+/// * `options` - 搜索选项
+///
+/// # 返回值
+/// * `Vec<(usize, usize)>` - 按位置排列的(起始字节偏移, 结束字节偏移)
+pub(crate) fn find_matches(text: &str, query: &str, options: SearchOptions) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    text.char_indices()
+        .filter_map(|(start, _)| Some((start, start + match_len_at(&text[start..], query)?)))
+        .filter(|&(start, end)| !options.whole_word || is_word_boundary(text, start, end))
+        .collect()
+}
+
+/// 检查文本开头是否与关键词匹配（不区分大小写），返回匹配的字节长度
+fn match_len_at(text: &str, query: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for query_char in query.chars() {
+        let (_, text_char) = text_chars.next()?;
+        if text_char != query_char && !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+/// 检查匹配范围两侧是否为单词边界
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word_char)
+        && !text[end..].chars().next().is_some_and(is_word_char)
+}
+
+/// 生成匹配位置的上下文片段
+///
+/// # 参数
+/// * `text` - 被搜索的文本
+/// * `start` - 匹配的起始字节偏移
+/// * `end` - 匹配的结束字节偏移
+///
+/// # 返回值
+/// * `String` - 包含匹配内容及两侧上下文的片段
+pub(crate) fn snippet(text: &str, start: usize, end: usize) -> String {
+    let before = text[..start].char_indices()
+        .rev()
+        .take(SNIPPET_CONTEXT_CHARS)
+        .last()
+        .map_or(start, |(index, _)| index);
+    let after = text[end..].char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map_or(text.len(), |(index, _)| end + index);
+
+    text[before..after].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matches() {
+        let options = SearchOptions::new();
+        assert_eq!(find_matches("Rust rust RUST", "rust", options), vec![(0, 4), (5, 9), (10, 14)]);
+        assert_eq!(find_matches("aaa", "aa", options), vec![(0, 2), (1, 3)]);
+        assert_eq!(find_matches("中文和中文", "中文", options), vec![(0, 6), (9, 15)]);
+        assert!(find_matches("abc", "", options).is_empty());
+
+        let whole_word = options.with_whole_word(true);
+        assert_eq!(find_matches("cat concat cat_1 cat.", "cat", whole_word), vec![(0, 3), (17, 20)]);
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = format!("{}关键词\n\n{}", "前".repeat(50), "后".repeat(50));
+        let start = text.find("关键词").unwrap();
+        let snippet = snippet(&text, start, start + "关键词".len());
+        assert_eq!(snippet, format!("{}关键词 {}", "前".repeat(40), "后".repeat(38)));
+        assert_eq!(super::snippet("短文本", 0, 3), "短文本");
+    }
+}
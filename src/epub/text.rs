@@ -0,0 +1,904 @@
+//! 文本处理工具模块
+//!
+//! 提供从章节中提取出的纯文本的统计、按显示宽度换行等辅助功能。
+
+use std::collections::{HashMap, HashSet};
+
+/// 注音（`<ruby>`）的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RubyMode {
+    /// 只保留基础文本，丢弃`<rt>`/`<rp>`中的注音
+    #[default]
+    BaseOnly,
+    /// 保留注音，放在基础文本之后的指定括号中，例如`漢字(かんじ)`
+    Annotated {
+        /// 左括号
+        open: char,
+        /// 右括号
+        close: char,
+    },
+}
+
+impl RubyMode {
+    /// 使用圆括号保留注音，例如`漢字(かんじ)`
+    pub fn parenthesized() -> Self {
+        RubyMode::Annotated { open: '(', close: ')' }
+    }
+}
+
+/// 排版字符的规范化方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextNormalization {
+    /// 保留原始字符
+    #[default]
+    Off,
+    /// 将排版字符替换为ASCII等价形式，见 [`normalize_typography`]
+    Typographic,
+}
+
+/// 图片（`<img>`）的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImagePolicy {
+    /// 丢弃图片
+    #[default]
+    Drop,
+    /// 用占位文本代替图片，模板中的`{alt}`和`{src}`分别替换为替代文本和图片链接，
+    /// 例如`[图片: {alt}]`
+    Placeholder {
+        /// 占位文本模板
+        template: &'static str,
+    },
+    /// 只保留替代文本，没有替代文本的图片被丢弃
+    AltTextOnly,
+}
+
+impl ImagePolicy {
+    /// 使用指定模板生成占位文本
+    pub fn placeholder(template: &'static str) -> Self {
+        ImagePolicy::Placeholder { template }
+    }
+
+    /// 按策略生成图片在文本中的表示，返回None表示丢弃
+    ///
+    /// # 参数
+    /// * `alt` - 替代文本（缺失时为空字符串）
+    /// * `src` - 图片链接
+    pub fn render(&self, alt: &str, src: &str) -> Option<String> {
+        let alt = alt.trim();
+        match self {
+            ImagePolicy::Drop => None,
+            ImagePolicy::Placeholder { template } => {
+                // 一次扫描替换两个占位符，替代文本中的`{src}`不会被再次替换
+                let mut result = String::with_capacity(template.len());
+                let mut rest = *template;
+                while let Some(start) = rest.find('{') {
+                    result.push_str(&rest[..start]);
+                    rest = &rest[start..];
+                    if let Some(after) = rest.strip_prefix("{alt}") {
+                        result.push_str(alt);
+                        rest = after;
+                    } else if let Some(after) = rest.strip_prefix("{src}") {
+                        result.push_str(src.trim());
+                        rest = after;
+                    } else {
+                        result.push('{');
+                        rest = &rest[1..];
+                    }
+                }
+                result.push_str(rest);
+                Some(result)
+            }
+            ImagePolicy::AltTextOnly => (!alt.is_empty()).then(|| alt.to_string()),
+        }
+    }
+}
+
+/// 章节文本提取选项
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextOptions {
+    /// 注音的处理方式
+    pub ruby: RubyMode,
+    /// 排版字符的规范化方式
+    pub normalization: TextNormalization,
+    /// 是否为列表项添加项目符号（无序列表为`- `，有序列表为`N. `）
+    pub list_markers: bool,
+    /// 图片的处理方式
+    pub images: ImagePolicy,
+    /// 是否保留上标（`<sup>`）和下标（`<sub>`）标记
+    pub scripts: bool,
+}
+
+impl TextOptions {
+    /// 创建默认选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置注音的处理方式
+    pub fn with_ruby(mut self, ruby: RubyMode) -> Self {
+        self.ruby = ruby;
+        self
+    }
+
+    /// 设置排版字符的规范化方式
+    pub fn with_normalization(mut self, normalization: TextNormalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// 设置是否为列表项添加项目符号
+    ///
+    /// 有序列表的序号遵循`<ol>`的`start`、`reversed`属性以及`<li>`的`value`属性；
+    /// 格式化文本中每个列表项另起一行，嵌套列表每层缩进两个空格。
+    pub fn with_list_markers(mut self, list_markers: bool) -> Self {
+        self.list_markers = list_markers;
+        self
+    }
+
+    /// 设置图片的处理方式
+    pub fn with_images(mut self, images: ImagePolicy) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// 设置是否保留上标和下标标记
+    ///
+    /// 启用后，上下标文本中的字符都有对应的Unicode上下标字符时直接替换（如`H₂O`、`x²`），
+    /// 否则使用`^{...}`/`_{...}`表示。不启用时上下标文本与正文直接拼接。
+    pub fn with_scripts(mut self, scripts: bool) -> Self {
+        self.scripts = scripts;
+        self
+    }
+
+    /// 按选项对提取出的文本进行后处理
+    pub(crate) fn apply(&self, text: String) -> String {
+        match self.normalization {
+            TextNormalization::Off => text,
+            TextNormalization::Typographic => normalize_typography(&text),
+        }
+    }
+}
+
+/// 自定义的HTML到文本转换
+///
+/// 用于替换格式化文本导出时的默认转换规则（如保留`<hr>`作为分隔线、自定义注音格式）。
+/// `TextOptions`实现了该trait，对应默认的格式化文本转换；
+/// 签名为`Fn(&str) -> String`的闭包也可以直接作为转换器使用。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use bookforge::{Epub, ExportFormat};
+///
+/// let epub = Epub::from_path("book.epub")?;
+/// if let Some(toc_tree) = epub.toc_tree()? {
+///     let toc_tree = toc_tree.with_text_extractor(|html: &str| html.replace("<hr/>", "\n***\n"));
+///     toc_tree.generate_all_txt_files(None, ExportFormat::Formatted, false, false)?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait TextExtractor: Send + Sync {
+    /// 将章节HTML转换为文本
+    fn extract(&self, html: &str) -> String;
+}
+
+impl<F> TextExtractor for F
+where
+    F: Fn(&str) -> String + Send + Sync,
+{
+    fn extract(&self, html: &str) -> String {
+        self(html)
+    }
+}
+
+/// 将排版字符替换为ASCII等价形式
+///
+/// 适用于搜索索引、自然语言处理等需要统一字符的场景：
+/// - 弯引号 `‘’‚‛` → `'`，`“”„‟` → `"`
+/// - 短破折号 `–` → `-`，长破折号 `—`、`―` → `--`
+/// - 不换行空格（`&nbsp;`）及窄不换行空格 → 普通空格
+/// - 省略号 `…` → `...`
+///
+/// # 参数
+/// * `text` - 原始文本
+///
+/// # 返回值
+/// * `String` - 规范化后的文本
+pub fn normalize_typography(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => result.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => result.push('"'),
+            '\u{2013}' => result.push('-'),
+            '\u{2014}' | '\u{2015}' => result.push_str("--"),
+            '\u{00A0}' | '\u{202F}' | '\u{2007}' => result.push(' '),
+            '\u{2026}' => result.push_str("..."),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// 将上标或下标文本转换为便于阅读的纯文本形式
+///
+/// 所有字符都有对应的Unicode上下标字符时返回替换后的文本，
+/// 否则上标返回`^{text}`，下标返回`_{text}`。
+///
+/// # 参数
+/// * `text` - 上标或下标中的文本（首尾空白会被去除）
+/// * `superscript` - true表示上标，false表示下标
+///
+/// # 返回值
+/// * `String` - 转换后的文本，空文本返回空字符串
+pub fn render_script(text: &str, superscript: bool) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+    let mapping = if superscript { superscript_char } else { subscript_char };
+    match text.chars().map(mapping).collect::<Option<String>>() {
+        Some(mapped) => mapped,
+        None if superscript => format!("^{{{}}}", text),
+        None => format!("_{{{}}}", text),
+    }
+}
+
+/// 获取字符对应的Unicode上标字符
+fn superscript_char(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4'..='9' => char::from_u32(0x2070 + (c as u32 - '0' as u32))?,
+        '+' => '⁺',
+        '-' | '−' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'i' => 'ⁱ',
+        'n' => 'ⁿ',
+        _ => return None,
+    })
+}
+
+/// 获取字符对应的Unicode下标字符
+fn subscript_char(c: char) -> Option<char> {
+    Some(match c {
+        '0'..='9' => char::from_u32(0x2080 + (c as u32 - '0' as u32))?,
+        '+' => '₊',
+        '-' | '−' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'o' => 'ₒ',
+        'x' => 'ₓ',
+        'h' => 'ₕ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'p' => 'ₚ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        _ => return None,
+    })
+}
+
+/// 判断字符是否为中日文字符（汉字、平假名、片假名）
+///
+/// 这些文字之间通常没有空格分隔，统计时每个字符视为一个词。
+pub fn is_cjk_char(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'     // CJK统一汉字
+        | '\u{3400}'..='\u{4DBF}'   // CJK扩展A
+        | '\u{20000}'..='\u{2EBEF}' // CJK扩展B-F
+        | '\u{F900}'..='\u{FAFF}'   // CJK兼容汉字
+        | '\u{3040}'..='\u{309F}'   // 平假名
+        | '\u{30A0}'..='\u{30FF}'   // 片假名
+    )
+}
+
+/// 统计文本的词数
+///
+/// 中日文字符每个字符计为一个词，其他文字按连续的字母数字序列计词。
+///
+/// # 参数
+/// * `text` - 要统计的文本
+///
+/// # 返回值
+/// * `usize` - 词数
+pub fn count_words(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        if is_cjk_char(c) {
+            count += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+        }
+    }
+
+    count
+}
+
+/// 中日韩文字的分词方式（用于词频统计）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tokenization {
+    /// 按单个字符统计
+    #[default]
+    Characters,
+    /// 按相邻两个字符组成的二元组统计（只有一个字符的片段按单字统计）
+    Bigrams,
+}
+
+/// 统计文本中的词频
+///
+/// 中日韩文字按选择的分词方式切分；其他文字按连续的字母数字切分并转换为小写，
+/// 标点和空白作为分隔符。
+///
+/// # 参数
+/// * `text` - 纯文本
+/// * `tokenization` - 中日韩文字的分词方式
+///
+/// # 返回值
+/// * `Vec<(String, usize)>` - (词, 出现次数)，按次数从高到低排序，次数相同时按词排序
+pub fn term_frequencies(text: &str, tokenization: Tokenization) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    for c in text.chars().chain(std::iter::once(' ')) {
+        if is_cjk_char(c) {
+            flush_word(&mut word, &mut counts);
+            cjk_run.push(c);
+        } else {
+            flush_cjk_run(&mut cjk_run, tokenization, &mut counts);
+            if c.is_alphanumeric() {
+                word.extend(c.to_lowercase());
+            } else {
+                flush_word(&mut word, &mut counts);
+            }
+        }
+    }
+
+    let mut frequencies: Vec<(String, usize)> = counts.into_iter().collect();
+    frequencies.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    frequencies
+}
+
+/// 将已累积的非中日韩单词计入词频
+fn flush_word(word: &mut String, counts: &mut HashMap<String, usize>) {
+    if !word.is_empty() {
+        *counts.entry(std::mem::take(word)).or_insert(0) += 1;
+    }
+}
+
+/// 将已累积的连续中日韩文字按分词方式计入词频
+fn flush_cjk_run(run: &mut Vec<char>, tokenization: Tokenization, counts: &mut HashMap<String, usize>) {
+    match tokenization {
+        Tokenization::Bigrams if run.len() >= 2 => {
+            for pair in run.windows(2) {
+                *counts.entry(pair.iter().collect()).or_insert(0) += 1;
+            }
+        }
+        _ => {
+            for c in run.iter() {
+                *counts.entry(c.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+    run.clear();
+}
+
+/// 获取字符在终端中的显示宽度
+///
+/// 中日韩文字及全角符号占2列，其他字符占1列，控制字符占0列。
+pub fn char_width(c: char) -> usize {
+    if c.is_control() {
+        0
+    } else if is_cjk_char(c)
+        || matches!(c,
+            '\u{1100}'..='\u{115F}'   // 韩文字母
+            | '\u{2E80}'..='\u{303E}' // CJK部首及标点
+            | '\u{3130}'..='\u{318F}' // 韩文兼容字母
+            | '\u{3200}'..='\u{33FF}' // CJK带圈字符及兼容字符
+            | '\u{AC00}'..='\u{D7A3}' // 韩文音节
+            | '\u{FE30}'..='\u{FE4F}' // CJK兼容形式
+            | '\u{FF00}'..='\u{FF60}' // 全角字符
+            | '\u{FFE0}'..='\u{FFE6}' // 全角符号
+        )
+    {
+        2
+    } else {
+        1
+    }
+}
+
+/// 获取字符串在终端中的显示宽度
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// 按显示宽度对文本进行换行
+///
+/// 宽字符（中日韩文字等）按2列计算，可以在任意两个宽字符之间换行；
+/// 西文单词尽量保持完整，只有单词本身超过行宽时才会被拆分。
+/// 原文中的换行符会被保留。
+///
+/// # 参数
+/// * `text` - 要换行的文本
+/// * `width` - 每行最大显示宽度（0表示不换行）
+///
+/// # 返回值
+/// * `String` - 换行后的文本
+pub fn wrap_text(text: &str, width: usize) -> String {
+    if width == 0 {
+        return text.to_string();
+    }
+
+    text.lines()
+        .map(|line| wrap_line(line, width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 对单行文本进行换行
+fn wrap_line(line: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for token in split_wrap_tokens(line) {
+        let token_width = display_width(token);
+        let is_space = token.chars().all(char::is_whitespace);
+
+        if current_width + token_width <= width {
+            current.push_str(token);
+            current_width += token_width;
+            continue;
+        }
+
+        // 行尾的空白直接丢弃
+        if is_space {
+            push_line(&mut result, &mut current);
+            current_width = 0;
+            continue;
+        }
+
+        if current_width > 0 {
+            push_line(&mut result, &mut current);
+            current_width = 0;
+        }
+
+        // 单个词超过行宽时按字符拆分
+        for c in token.chars() {
+            let w = char_width(c);
+            if current_width + w > width && current_width > 0 {
+                push_line(&mut result, &mut current);
+                current_width = 0;
+            }
+            current.push(c);
+            current_width += w;
+        }
+    }
+
+    result.push_str(current.trim_end());
+    result
+}
+
+/// 将当前行写入结果并清空
+fn push_line(result: &mut String, current: &mut String) {
+    result.push_str(current.trim_end());
+    result.push('\n');
+    current.clear();
+}
+
+/// 将文本拆分为换行单元：连续空白、单个宽字符或连续的其他字符
+fn split_wrap_tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let kind = token_kind(c);
+        if kind == 2 {
+            tokens.push(&line[i..i + c.len_utf8()]);
+            start = i + c.len_utf8();
+            continue;
+        }
+        match chars.peek() {
+            Some(&(_, next)) if token_kind(next) == kind => {}
+            Some(&(next_i, _)) => {
+                tokens.push(&line[start..next_i]);
+                start = next_i;
+            }
+            None => tokens.push(&line[start..]),
+        }
+    }
+
+    tokens
+}
+
+/// 换行单元的类型：0为空白，1为普通字符，2为宽字符
+fn token_kind(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if char_width(c) == 2 {
+        2
+    } else {
+        1
+    }
+}
+
+/// 判定重复页眉页脚时每章检查的首尾非空行数
+const BOILERPLATE_SCAN_LINES: usize = 3;
+
+/// 判定为重复页眉页脚所需的最低章节占比
+const BOILERPLATE_MIN_SHARE: f64 = 0.8;
+
+/// 进行重复页眉页脚检测所需的最少章节数（按不同内容计）
+const BOILERPLATE_MIN_CHAPTERS: usize = 3;
+
+/// 去除各章节开头和结尾重复出现的样板文字（如每章都有的书名、作者页眉）
+///
+/// 这是尽力而为的启发式处理：比较每章开头和结尾各3个非空行，在至少80%的章节中
+/// 出现的行被视为样板文字，并从出现它的章节首尾去除。内容完全相同的章节只计一次，
+/// 不同内容的章节少于3个时不做任何处理。正文中恰好与样板文字相同的首尾行也会被去除。
+///
+/// # 参数
+/// * `texts` - 按阅读顺序排列的各章节文本，原地修改
+pub fn strip_boilerplate(texts: &mut [String]) {
+    let (leading, trailing) = {
+        let mut distinct: Vec<&str> = texts.iter().map(String::as_str).collect();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if distinct.len() < BOILERPLATE_MIN_CHAPTERS {
+            return;
+        }
+        (
+            shared_edge_lines(&distinct, |text| edge_lines(text.lines())),
+            shared_edge_lines(&distinct, |text| edge_lines(text.lines().rev())),
+        )
+    };
+    if leading.is_empty() && trailing.is_empty() {
+        return;
+    }
+
+    for text in texts.iter_mut() {
+        if let Some(stripped) = strip_edge_lines(text, &leading, &trailing) {
+            *text = stripped;
+        }
+    }
+}
+
+/// 获取首（或尾）部的非空行
+fn edge_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    lines.map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(BOILERPLATE_SCAN_LINES)
+        .collect()
+}
+
+/// 统计在足够多章节首（或尾）部出现的行
+fn shared_edge_lines<'a>(texts: &[&'a str], edge: fn(&'a str) -> Vec<&'a str>) -> HashSet<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for text in texts {
+        let lines: HashSet<&str> = edge(text).into_iter().collect();
+        for line in lines {
+            *counts.entry(line).or_insert(0) += 1;
+        }
+    }
+
+    let threshold = texts.len() as f64 * BOILERPLATE_MIN_SHARE;
+    counts.into_iter()
+        .filter(|(_, count)| *count as f64 >= threshold)
+        .map(|(line, _)| line.to_string())
+        .collect()
+}
+
+/// 去除首尾的样板行，没有可去除的行时返回None
+fn strip_edge_lines(text: &str, leading: &HashSet<String>, trailing: &HashSet<String>) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let is_blank = |index: usize| lines[index].trim().is_empty();
+
+    let mut start = 0;
+    let mut removed = 0;
+    let mut checked = 0;
+    while start < lines.len() && checked < BOILERPLATE_SCAN_LINES {
+        if is_blank(start) {
+            start += 1;
+            continue;
+        }
+        if !leading.contains(lines[start].trim()) {
+            break;
+        }
+        start += 1;
+        removed += 1;
+        checked += 1;
+    }
+
+    let mut end = lines.len();
+    checked = 0;
+    while end > start && checked < BOILERPLATE_SCAN_LINES {
+        if is_blank(end - 1) {
+            end -= 1;
+            continue;
+        }
+        if !trailing.contains(lines[end - 1].trim()) {
+            break;
+        }
+        end -= 1;
+        removed += 1;
+        checked += 1;
+    }
+
+    if removed == 0 {
+        return None;
+    }
+    while start < end && is_blank(start) {
+        start += 1;
+    }
+    while end > start && is_blank(end - 1) {
+        end -= 1;
+    }
+    Some(lines[start..end].join("\n"))
+}
+
+/// 去除标题开头的章节序号
+///
+/// 支持以下形式（阿拉伯数字、全角数字和中文数字均可）：
+/// * `第N章`（单位也可为节/回/卷/部/篇/集/幕/话），如`第一章 开端`、`第12回：开端`
+/// * `Chapter 1: Beginning`、`Part IV - Beginning`（小写罗马数字后必须有标点分隔）
+/// * `1. 开端`、`一、开端`（序号后必须有标点分隔）、`1.2.3 开端`（多级序号会被整体去除）
+///
+/// 去除后标题为空时（如标题只有`第一章`）保留原标题。
+///
+/// # 参数
+/// * `title` - 原始标题
+///
+/// # 返回值
+/// * `String` - 去除序号并修剪空白后的标题
+pub fn strip_chapter_number(title: &str) -> String {
+    let title = title.trim();
+    let rest = strip_cjk_chapter_prefix(title)
+        .or_else(|| strip_latin_chapter_prefix(title))
+        .or_else(|| strip_bare_number_prefix(title));
+    match rest {
+        Some(rest) if !rest.is_empty() => rest.to_string(),
+        _ => title.to_string(),
+    }
+}
+
+/// 章节序号后允许出现的分隔符
+fn is_title_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ':' | '：' | '、' | '.' | '．' | '·' | '-' | '—' | '_')
+}
+
+/// 判断字符是否可以作为章节序号（数字或中文数字）
+fn is_numeral_char(c: char) -> bool {
+    c.is_ascii_digit()
+        || ('０'..='９').contains(&c)
+        || "零〇一二三四五六七八九十百千万两壹贰叁肆伍陆柒捌玖拾佰仟".contains(c)
+}
+
+/// 去除`第N章`形式的前缀
+fn strip_cjk_chapter_prefix(title: &str) -> Option<&str> {
+    let rest = title.strip_prefix('第')?;
+    let numeral_end = rest.find(|c: char| !is_numeral_char(c))?;
+    if numeral_end == 0 {
+        return None;
+    }
+    let rest = &rest[numeral_end..];
+    let unit = rest.chars().next().filter(|c| "章节回卷部篇集幕话".contains(*c))?;
+    Some(rest[unit.len_utf8()..].trim_start_matches(is_title_separator))
+}
+
+/// 去除`Chapter N`形式的前缀
+fn strip_latin_chapter_prefix(title: &str) -> Option<&str> {
+    let (word, rest) = title.split_once(char::is_whitespace)?;
+    if !["chapter", "part"].contains(&word.to_lowercase().as_str()) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let numeral_end = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+    let numeral = &rest[..numeral_end];
+    let after = &rest[numeral_end..];
+    // 小写的罗马数字容易与普通单词混淆（如`Part mix up`），只在其后紧跟标点或标题结束时识别
+    let standalone = after.trim_start().chars().next().is_none_or(|c| is_title_separator(c) && !c.is_whitespace());
+    let is_numeral = !numeral.is_empty()
+        && (numeral.chars().all(|c| c.is_ascii_digit())
+            || (is_roman_numeral(numeral)
+                && (numeral.chars().all(|c| c.is_ascii_uppercase())
+                    || (numeral.chars().all(|c| c.is_ascii_lowercase()) && standalone))));
+    if !is_numeral {
+        return None;
+    }
+    Some(after.trim_start_matches(is_title_separator))
+}
+
+/// 判断字符串是否为规范写法的罗马数字（1-3999，不区分大小写）
+fn is_roman_numeral(numeral: &str) -> bool {
+    const SYMBOLS: [(u32, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"),
+        (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+    let upper = numeral.to_ascii_uppercase();
+    let mut rest = upper.as_str();
+    let mut value = 0;
+    while let Some(&(symbol_value, symbol)) = SYMBOLS.iter().find(|(_, symbol)| rest.starts_with(symbol)) {
+        value += symbol_value;
+        rest = &rest[symbol.len()..];
+    }
+    if !rest.is_empty() || value == 0 || value > 3999 {
+        return false;
+    }
+
+    // 按数值重新生成规范写法，排除`IIII`、`VX`、`CIVIL`等非法组合
+    let mut canonical = String::new();
+    for (symbol_value, symbol) in SYMBOLS {
+        while value >= symbol_value {
+            canonical.push_str(symbol);
+            value -= symbol_value;
+        }
+    }
+    canonical == upper
+}
+
+/// 去除`1. `、`一、`、`1.2 `形式的前缀
+fn strip_bare_number_prefix(title: &str) -> Option<&str> {
+    let numeral_end = title.find(|c: char| !is_numeral_char(c))?;
+    if numeral_end == 0 {
+        return None;
+    }
+    let mut rest = &title[numeral_end..];
+
+    // 多级序号（如`1.2.3`）需要整体去除，否则`1.1 概述`会变成`1 概述`
+    let mut is_multilevel = false;
+    while let Some(after_dot) = rest.strip_prefix(['.', '．']) {
+        let segment_end = after_dot.find(|c: char| !is_numeral_char(c)).unwrap_or(after_dot.len());
+        if segment_end == 0 {
+            break;
+        }
+        rest = &after_dot[segment_end..];
+        is_multilevel = true;
+    }
+
+    let separator = rest.chars().next()?;
+    if matches!(separator, '.' | '．' | '、' | ':' | '：') {
+        Some(rest[separator.len_utf8()..].trim_start_matches(is_title_separator))
+    } else if is_multilevel && is_title_separator(separator) {
+        Some(rest.trim_start_matches(is_title_separator))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_term_frequencies() {
+        let text = "The cat, the hat. 猫和猫";
+        let frequencies = term_frequencies(text, Tokenization::Characters);
+        assert_eq!(frequencies[0], ("the".to_string(), 2));
+        assert_eq!(frequencies[1], ("猫".to_string(), 2));
+        assert!(frequencies.contains(&("和".to_string(), 1)));
+        assert!(frequencies.contains(&("hat".to_string(), 1)));
+
+        let frequencies = term_frequencies("学习学习 学", Tokenization::Bigrams);
+        assert_eq!(frequencies, vec![
+            ("学习".to_string(), 2),
+            ("习学".to_string(), 1),
+            ("学".to_string(), 1),
+        ]);
+    }
+
+    #[test]
+    fn test_image_policy_render() {
+        let policy = ImagePolicy::placeholder("[{alt}]({src}) {other}");
+        assert_eq!(policy.render(" 地图 ", " a.png "), Some("[地图](a.png) {other}".to_string()));
+        // 替代文本中的占位符不会被再次替换
+        assert_eq!(policy.render("{src}", "a.png"), Some("[{src}](a.png) {other}".to_string()));
+        assert_eq!(ImagePolicy::Drop.render("地图", "a.png"), None);
+        assert_eq!(ImagePolicy::AltTextOnly.render(" ", "a.png"), None);
+    }
+
+    #[test]
+    fn test_normalize_typography() {
+        assert_eq!(normalize_typography("\u{2018}a\u{2019} \u{201A}b\u{201B}"), "'a' 'b'");
+        assert_eq!(normalize_typography("\u{201C}a\u{201D} \u{201E}b\u{201F}"), "\"a\" \"b\"");
+        assert_eq!(normalize_typography("1\u{2013}2"), "1-2");
+        assert_eq!(normalize_typography("a\u{2014}b\u{2015}c"), "a--b--c");
+        assert_eq!(normalize_typography("a\u{00A0}b\u{202F}c"), "a b c");
+        assert_eq!(normalize_typography("等等\u{2026}"), "等等...");
+        assert_eq!(normalize_typography("普通文本"), "普通文本");
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("Hello, world! 你好"), 4);
+    }
+
+    #[test]
+    fn test_wrap_cjk_text() {
+        assert_eq!(display_width("中文abc"), 7);
+        assert_eq!(wrap_text("这是一段很长的中文文本", 10), "这是一段很\n长的中文文\n本");
+        // 宽字符不能被拆到半个位置
+        assert_eq!(wrap_text("中文中文", 5), "中文\n中文");
+    }
+
+    #[test]
+    fn test_wrap_mixed_text() {
+        assert_eq!(wrap_text("hello world foo", 11), "hello world\nfoo");
+        assert_eq!(wrap_text("使用Rust编写", 8), "使用Rust\n编写");
+        assert_eq!(wrap_text("abcdefghij", 4), "abcd\nefgh\nij");
+        assert_eq!(wrap_text("第一行\n第二行", 4), "第一\n行\n第二\n行");
+        assert_eq!(wrap_text("不换行", 0), "不换行");
+    }
+
+    #[test]
+    fn test_strip_boilerplate() {
+        let mut texts: Vec<String> = (1..=5)
+            .map(|i| format!("某某小说\n作者：张三\n\n第{}章\n正文{}\n\n版权所有", i, i))
+            .collect();
+        texts[4] = "第五章\n正文5".to_string();
+        strip_boilerplate(&mut texts);
+        assert_eq!(texts[0], "第1章\n正文1");
+        assert_eq!(texts[4], "第五章\n正文5");
+
+        // 内容相同的章节只计一次，不足3个不同章节时不处理
+        let mut same = vec!["页眉\n内容".to_string(); 4];
+        strip_boilerplate(&mut same);
+        assert_eq!(same[0], "页眉\n内容");
+    }
+
+    #[test]
+    fn test_strip_chapter_number() {
+        assert_eq!(strip_chapter_number("第一章 开端"), "开端");
+        assert_eq!(strip_chapter_number("第12回：开端"), "开端");
+        assert_eq!(strip_chapter_number("第三百二十一节·开端"), "开端");
+        assert_eq!(strip_chapter_number("Chapter 3: The Beginning"), "The Beginning");
+        assert_eq!(strip_chapter_number("PART IV - Finale"), "Finale");
+        assert_eq!(strip_chapter_number("1. 开端"), "开端");
+        assert_eq!(strip_chapter_number("十二、开端"), "开端");
+        assert_eq!(strip_chapter_number("1.1 概述"), "概述");
+        assert_eq!(strip_chapter_number("2.3.4. 细节"), "细节");
+        assert_eq!(strip_chapter_number("chapter iv: the end"), "the end");
+        assert_eq!(strip_chapter_number("Part XII Finale"), "Finale");
+        // 不是规范罗马数字的单词、没有标点分隔的小写罗马数字和小数不会被当作序号
+        assert_eq!(strip_chapter_number("Part Civil War"), "Part Civil War");
+        assert_eq!(strip_chapter_number("Part IIII Finale"), "Part IIII Finale");
+        assert_eq!(strip_chapter_number("part mix up"), "part mix up");
+        assert_eq!(strip_chapter_number("1.5倍速"), "1.5倍速");
+        // 只有序号或没有序号的标题保持不变
+        assert_eq!(strip_chapter_number("第一章"), "第一章");
+        assert_eq!(strip_chapter_number(" 开端 "), "开端");
+        assert_eq!(strip_chapter_number("1984"), "1984");
+        assert_eq!(strip_chapter_number("第一人称"), "第一人称");
+        assert_eq!(strip_chapter_number("Chapterhouse"), "Chapterhouse");
+    }
+
+    #[test]
+    fn test_render_script() {
+        assert_eq!(render_script("2", true), "²");
+        assert_eq!(render_script("2", false), "₂");
+        assert_eq!(render_script("n+1", true), "ⁿ⁺¹");
+        assert_eq!(render_script("-10", true), "⁻¹⁰");
+        assert_eq!(render_script("max", false), "ₘₐₓ");
+        assert_eq!(render_script("ab", true), "^{ab}");
+        assert_eq!(render_script("i,j", false), "_{i,j}");
+        assert_eq!(render_script(" ", true), "");
+    }
+}
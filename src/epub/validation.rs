@@ -0,0 +1,120 @@
+//! EPUB校验模块
+//!
+//! 提供EPUB结构校验结果的数据结构定义。
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// 校验问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// 错误（书籍可能无法正常阅读）
+    Error,
+    /// 警告（书籍可以阅读，但结构不一致）
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Severity::Error => write!(f, "错误"),
+            Severity::Warning => write!(f, "警告"),
+        }
+    }
+}
+
+/// 单个校验问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// 严重程度
+    pub severity: Severity,
+    /// 问题描述
+    pub message: String,
+    /// 相关文件路径（相对于EPUB根目录）
+    pub path: Option<String>,
+}
+
+impl ValidationIssue {
+    /// 创建错误级别的问题
+    pub fn error(message: String, path: Option<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+            path,
+        }
+    }
+
+    /// 创建警告级别的问题
+    pub fn warning(message: String, path: Option<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+            path,
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.path {
+            Some(path) => write!(f, "[{}] {} ({})", self.severity, self.message, path),
+            None => write!(f, "[{}] {}", self.severity, self.message),
+        }
+    }
+}
+
+/// 校验报告
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// 发现的所有问题
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// 创建空的校验报告
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加问题
+    pub fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+
+    /// 检查是否没有任何错误级别的问题
+    pub fn is_valid(&self) -> bool {
+        !self.issues.iter().any(|issue| issue.severity == Severity::Error)
+    }
+
+    /// 检查是否没有任何问题
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// 获取所有错误
+    pub fn errors(&self) -> Vec<&ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error).collect()
+    }
+
+    /// 获取所有警告
+    pub fn warnings(&self) -> Vec<&ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning).collect()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.issues.is_empty() {
+            return writeln!(f, "未发现问题");
+        }
+        writeln!(
+            f,
+            "校验结果: {} 个错误, {} 个警告",
+            self.errors().len(),
+            self.warnings().len()
+        )?;
+        for issue in &self.issues {
+            writeln!(f, "  {}", issue)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+//! 警告信息模块
+//!
+//! 提供读取EPUB过程中遇到的可恢复问题（如NCX解析失败、编码声明错误）的数据结构定义。
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// 读取EPUB时遇到的可恢复问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpubWarning {
+    /// 问题描述
+    pub message: String,
+    /// 相关文件路径（相对于EPUB根目录）
+    pub path: Option<String>,
+}
+
+impl EpubWarning {
+    /// 创建警告
+    pub fn new(message: String, path: Option<String>) -> Self {
+        Self { message, path }
+    }
+}
+
+impl Display for EpubWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.path {
+            Some(path) => write!(f, "{} ({})", self.message, path),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
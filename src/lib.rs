@@ -5,6 +5,13 @@ pub mod epub;
 /// EPUB文件读取器（主要接口）
 pub use epub::Epub;
 
+/// EPUB打开选项构建器
+pub use epub::EpubBuilder;
+
+/// 异步EPUB读取器
+#[cfg(feature = "async")]
+pub use epub::AsyncEpub;
+
 /// 错误处理
 pub use epub::{EpubError, Result};
 
@@ -14,10 +21,31 @@ pub use epub::{EpubError, Result};
 pub use epub::BookInfo;
 
 /// 章节信息和内容
-pub use epub::{ChapterInfo, Chapter};
+pub use epub::{ChapterInfo, Chapter, ChapterSpan, ContentStatistics};
 
 /// 图片资源信息
-pub use epub::{ImageInfo, CoverImage};
+pub use epub::{ImageInfo, FontInfo, CoverImage};
+
+/// ZIP条目元数据
+pub use epub::ZipEntryInfo;
+
+/// 阅读位置
+pub use epub::ResolvedPosition;
+
+/// 结构性位置（封面、目录、正文起点等）
+pub use epub::Landmark;
+
+/// 内容类型分类
+pub use epub::{ClassificationSignals, ContentClassification, ContentKind};
+
+/// 文本提取与统计选项
+pub use epub::{ImagePolicy, RubyMode, TextExtractor, TextNormalization, TextOptions, Tokenization};
+/// 章节链接与表格
+pub use epub::{ChapterLink, LinkKind, LinkRef, Quote, Table};
+/// 结构化内容块
+pub use epub::Block;
+/// 全文搜索
+pub use epub::{SearchHit, SearchOptions};
 
 // === 底层组件（高级用法） ===
 
@@ -28,10 +56,16 @@ pub use epub::{Container, RootFile};
 pub use epub::{
     Opf, 
     Metadata, 
+    MetadataCompleteness,
     Creator, 
     Identifier, 
+    SeriesInfo,
+    GuideReference,
+    ManifestCategories,
     ManifestItem, 
+    ReadingDirection,
     SpineItem,
+    Spread,
     MetadataTagConfig,
     MetadataTagConfigs,
 };
@@ -47,9 +81,31 @@ pub use epub::{
     TocTreeNode, 
     TocTreeStyle, 
     TocStatistics,
+    FlatTocEntry,
+    ExportFormat,
+    ExportOptions,
+    ExportStatus,
+    ExportSummary,
+    ExportedFile,
+    NavDiff,
+    NavDiffEntry,
+    NavSummary,
+    TitleMismatch,
+    OrderMismatch,
     create_toc_tree_from_ncx,
+    create_toc_tree_from_nav,
+    create_page_list_from_nav,
 };
 
+/// 无障碍检查组件
+pub use epub::{A11yFinding, A11yIssueKind, A11yReport, ImageAltInfo, ImageUsage};
+
+/// 读取警告
+pub use epub::EpubWarning;
+
+/// 校验组件
+pub use epub::{Severity, ValidationIssue, ValidationReport};
+
 
 
 // === 库信息 ===
@@ -3,7 +3,8 @@
 //! 一个现代化的EPUB文件信息查看器，支持查看书籍信息、章节、封面等功能。
 
 use clap::{Parser, ValueEnum};
-use bookforge::{Epub, Result, EpubError};
+use bookforge::{Epub, EpubBuilder, ExportOptions, MetadataTagConfigs, Result, EpubError};
+use bookforge::epub::text::wrap_text;
 use std::process;
 
 #[derive(Parser)]
@@ -23,6 +24,11 @@ struct Args {
     #[arg(short = 'I', long, help = "显示书籍基本信息")]
     info: bool,
     
+    /// 以JSON格式输出书籍信息
+    #[cfg(feature = "serde")]
+    #[arg(long, help = "以JSON格式输出书籍元数据、目录树、清单和脊柱（忽略其他显示选项）")]
+    json: bool,
+    
     /// 显示章节列表
     #[arg(short = 'c', long, help = "显示章节列表")]
     chapters: bool,
@@ -39,6 +45,10 @@ struct Args {
     #[arg(short = 'i', long, help = "显示图片资源列表")]
     images: bool,
     
+    /// 显示页码列表
+    #[arg(long, help = "显示印刷版页码列表（页面值、标签和目标位置）")]
+    pages: bool,
+    
     /// 列出所有文件
     #[arg(short = 'l', long, help = "列出EPUB中的所有文件")]
     list: bool,
@@ -47,6 +57,18 @@ struct Args {
     #[arg(short = 't', long, help = "显示目录树结构")]
     toc: bool,
     
+    /// 目录树显示样式
+    #[arg(long, value_enum, default_value = "tree", help = "目录树的显示样式")]
+    toc_style: TocStyle,
+    
+    /// 目录树不显示文件路径
+    #[arg(long, help = "显示目录树时不显示各节点的文件路径")]
+    toc_no_paths: bool,
+    
+    /// 目录树最大显示深度
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), help = "目录树最多显示的层数（1表示只显示顶层）")]
+    toc_max_depth: Option<u32>,
+    
     /// 内容显示格式
     #[arg(long, value_enum, default_value = "summary", help = "章节内容的显示格式")]
     format: ContentFormat,
@@ -55,6 +77,10 @@ struct Args {
     #[arg(long, default_value = "1000", help = "章节内容最大显示字符数（0表示不限制）")]
     max_length: usize,
     
+    /// 章节内容换行宽度
+    #[arg(long, value_name = "WIDTH", help = "按指定显示宽度对章节内容自动换行（中日韩文字按2列计算）")]
+    wrap: Option<usize>,
+    
     /// 导出所有章节为txt文件
     #[arg(long, help = "将所有章节导出为txt文件")]
     export_txt: bool,
@@ -75,13 +101,58 @@ struct Args {
     #[arg(long, help = "根据目录树结构创建子目录")]
     create_subdirs: bool,
     
+    /// 使用全局序号命名导出文件
+    #[arg(long, help = "按目录树先序遍历的全局序号（0001_、0002_…）命名导出文件，便于展平子目录后保持阅读顺序")]
+    global_numbering: bool,
+    
+    /// 跳过已存在的导出文件
+    #[arg(long, help = "跳过已存在且非空的章节文件，用于继续被中断的导出")]
+    skip_existing: bool,
+    
+    /// 合并指向同一文件的章节
+    #[arg(long, help = "按阅读顺序平铺导出，多个目录项指向同一源文件时只导出一次（忽略 --create-subdirs 和 --with-index）")]
+    dedupe_files: bool,
+    
+    /// 按原始路径导出
+    #[arg(long, help = "按EPUB内部的原始路径导出章节，保留章节间的相对链接（忽略 --create-subdirs、--dedupe-files 和 --with-index）")]
+    mirror_paths: bool,
+    
+    /// 按原始路径导出时保留HTML
+    #[arg(long, requires = "mirror_paths", help = "与 --mirror-paths 一起使用，保留原始HTML而不转换为 --export-format 指定的格式")]
+    keep_html: bool,
+    
     /// 生成索引文件
     #[arg(long, help = "生成包含目录结构的索引文件")]
     with_index: bool,
     
+    /// 生成导出报告
+    #[cfg(feature = "serde")]
+    #[arg(long, conflicts_with_all = ["mirror_paths", "dedupe_files"], help = "生成export_report.json，列出每个章节的导出状态、路径、字节数、词数和错误（单个章节失败时继续导出）")]
+    with_report: bool,
+    
     /// 将所有章节合并为一个txt文件
     #[arg(long, help = "将所有章节合并为一个txt文件，以书籍标题命名")]
     merge_txt: bool,
+    
+    /// 合并时去除重复的页眉页脚
+    #[arg(long, requires = "merge_txt", conflicts_with_all = ["per_part", "markdown"], help = "合并为txt时尽力去除大多数章节首尾重复出现的行（如每章的书名、作者页眉）")]
+    strip_boilerplate: bool,
+    
+    /// 按顶层目录分别合并
+    #[arg(long, requires = "merge_txt", help = "为每个顶层目录项（如多部作品中的每一部）分别生成一个合并文件")]
+    per_part: bool,
+    
+    /// 合并为Markdown文件
+    #[arg(long, requires = "merge_txt", conflicts_with = "per_part", help = "合并为带目录链接的Markdown文件（忽略 --export-format）")]
+    markdown: bool,
+    
+    /// 去除章节标题中的序号
+    #[arg(long, help = "去除章节标题开头的序号（如“第一章”、“Chapter 1”），使标题和导出文件名更简洁")]
+    strip_chapter_numbers: bool,
+    
+    /// 元数据标签配置文件
+    #[arg(long, value_name = "PATH", help = "从指定的YAML文件加载元数据标签配置（默认使用内置配置）")]
+    metadata_config: Option<String>,
 }
 
 #[derive(ValueEnum, Clone)]
@@ -92,12 +163,48 @@ enum ContentFormat {
     Full,
 }
 
+#[derive(ValueEnum, Clone)]
+enum TocStyle {
+    /// 树状符号（├── └──）
+    Tree,
+    /// 缩进和项目符号（• ）
+    Indented,
+}
+
+impl TocStyle {
+    /// 转换为库中的目录树样式
+    fn to_library_style(&self) -> bookforge::TocTreeStyle {
+        match self {
+            TocStyle::Tree => bookforge::TocTreeStyle::TreeSymbols,
+            TocStyle::Indented => bookforge::TocTreeStyle::Indented,
+        }
+    }
+}
+
 #[derive(ValueEnum, Clone)]
 enum ExportFormat {
     /// 格式化文本（保持HTML结构）
     Formatted,
     /// 纯文本（移除所有HTML标签）
     Plain,
+    /// Markdown（每章一个md文件，合并时生成带目录链接的md文件）
+    Markdown,
+    /// 结构化JSON（每章一个json文件，合并时生成book.json）
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+impl ExportFormat {
+    /// 转换为库中的导出格式
+    fn to_library_format(&self) -> bookforge::ExportFormat {
+        match self {
+            ExportFormat::Formatted => bookforge::ExportFormat::Formatted,
+            ExportFormat::Plain => bookforge::ExportFormat::Plain,
+            ExportFormat::Markdown => bookforge::ExportFormat::Markdown,
+            #[cfg(feature = "serde")]
+            ExportFormat::Json => bookforge::ExportFormat::Json,
+        }
+    }
 }
 
 fn main() {
@@ -110,13 +217,26 @@ fn main() {
 }
 
 fn run(args: &Args) -> Result<()> {
+    let mut builder = EpubBuilder::new().strip_chapter_numbers(args.strip_chapter_numbers);
+    if let Some(path) = &args.metadata_config {
+        builder = builder.metadata_tag_configs(MetadataTagConfigs::from_path(std::path::Path::new(path))?);
+    }
+    
+    // JSON输出只包含JSON本身，便于其他工具解析
+    #[cfg(feature = "serde")]
+    if args.json {
+        let epub = builder.open(&args.epub_file)?;
+        println!("{}", epub.to_json()?);
+        return Ok(());
+    }
+    
     println!("🔍 正在分析EPUB文件: {}", args.epub_file);
     
     // 打开EPUB文件
-    let epub = Epub::from_path(&args.epub_file)?;
+    let epub = builder.open(&args.epub_file)?;
     
     // 如果没有指定任何选项，显示基本信息
-    if !args.info && !args.chapters && args.chapter.is_none() && !args.cover && !args.images && !args.list && !args.toc && !args.export_txt && args.export_chapter.is_none() && !args.merge_txt {
+    if !args.info && !args.chapters && args.chapter.is_none() && !args.cover && !args.images && !args.pages && !args.list && !args.toc && !args.export_txt && args.export_chapter.is_none() && !args.merge_txt {
         display_basic_info(&epub)?;
         return Ok(());
     }
@@ -133,7 +253,7 @@ fn run(args: &Args) -> Result<()> {
     
     // 显示指定章节内容
     if let Some(index) = args.chapter {
-        display_chapter_content(&epub, index, &args.format, args.max_length)?;
+        display_chapter_content(&epub, index, &args.format, args.max_length, args.wrap)?;
     }
     
     // 显示封面信息
@@ -146,6 +266,11 @@ fn run(args: &Args) -> Result<()> {
         display_images(&epub, args.verbose)?;
     }
     
+    // 显示页码列表
+    if args.pages {
+        display_page_list(&epub)?;
+    }
+    
     // 列出所有文件
     if args.list {
         display_file_list(&epub, args.verbose)?;
@@ -153,7 +278,7 @@ fn run(args: &Args) -> Result<()> {
     
     // 显示目录树
     if args.toc {
-        display_toc_tree(&epub, args.verbose)?;
+        display_toc_tree(&epub, args)?;
     }
     
     // 导出所有章节为txt文件
@@ -179,18 +304,8 @@ fn display_basic_info(epub: &Epub) -> Result<()> {
     let info = epub.book_info()?;
     
     println!("\n📚 书籍信息:");
-    println!("  标题: {}", info.title);
-    
-    if !info.authors.is_empty() {
-        println!("  作者: {}", info.authors.join(", "));
-    }
-    
-    if let Some(language) = &info.language {
-        println!("  语言: {}", language);
-    }
-    
-    if let Some(publisher) = &info.publisher {
-        println!("  出版社: {}", publisher);
+    for line in info.to_string().lines() {
+        println!("  {}", line);
     }
     
     // 显示章节数量
@@ -229,6 +344,18 @@ fn display_book_info(epub: &Epub, verbose: bool) -> Result<()> {
         println!("  ISBN: {}", isbn);
     }
     
+    if let Some(date) = &info.published_date {
+        println!("  出版日期: {}", date);
+    }
+    
+    if !info.subjects.is_empty() {
+        println!("  主题: {}", info.subjects.join(", "));
+    }
+    
+    if let Some(generator) = epub.generator()? {
+        println!("  生成工具: {}", generator);
+    }
+    
     if let Some(description) = &info.description {
         if verbose {
             println!("  描述: {}", description);
@@ -252,6 +379,9 @@ fn display_book_info(epub: &Epub, verbose: bool) -> Result<()> {
             if epub.has_toc_tree()? {
                 println!("  ✅ 支持目录树结构");
             }
+        } else if epub.has_toc_tree()? {
+            println!("  ❌ 不包含NCX导航文件");
+            println!("  ✅ 支持目录树结构（来自nav导航文档）");
         } else {
             println!("  ❌ 不包含NCX导航文件");
             println!("  ❌ 不支持目录树结构");
@@ -325,11 +455,12 @@ fn display_chapters(epub: &Epub, verbose: bool) -> Result<()> {
     println!("\n📖 章节列表 (共{}章):", chapters.len());
     
     for (i, chapter) in chapters.iter().enumerate() {
+        let marker = if chapter.linear { "" } else { " [非线性]" };
         if verbose {
-            println!("  {}. {} (ID: {}, 路径: {})", 
-                i + 1, chapter.title, chapter.id, chapter.path);
+            println!("  {}. {}{} (ID: {}, 路径: {})", 
+                i + 1, chapter.title, marker, chapter.id, chapter.path);
         } else {
-            println!("  {}. {}", i + 1, chapter.title);
+            println!("  {}. {}{}", i + 1, chapter.title, marker);
         }
     }
     
@@ -337,7 +468,7 @@ fn display_chapters(epub: &Epub, verbose: bool) -> Result<()> {
 }
 
 /// 显示章节内容
-fn display_chapter_content(epub: &Epub, index: usize, format: &ContentFormat, max_length: usize) -> Result<()> {
+fn display_chapter_content(epub: &Epub, index: usize, format: &ContentFormat, max_length: usize, wrap: Option<usize>) -> Result<()> {
     let chapters = epub.chapter_list()?;
     
     if index == 0 || index > chapters.len() {
@@ -347,34 +478,41 @@ fn display_chapter_content(epub: &Epub, index: usize, format: &ContentFormat, ma
     }
     
     let chapter_info = &chapters[index - 1];
-    let chapter = epub.chapter(chapter_info)?;
+    let text = epub.chapter_text(chapter_info, true)?;
+    let char_count = text.chars().count();
     
-    println!("\n📄 章节 {}: {}", index, chapter.info.title);
-    println!("文件路径: {}", chapter.info.path);
-    println!("内容长度: {} 字符", chapter.content.len());
+    println!("\n📄 章节 {}: {}", index, chapter_info.title);
+    println!("文件路径: {}", chapter_info.path);
+    println!("内容长度: {} 字符", char_count);
     
     match format {
         ContentFormat::Summary => {
-            let content_preview = if chapter.content.len() > max_length && max_length > 0 {
-                format!("{}...", &chapter.content[..max_length])
+            let content_preview = if char_count > max_length && max_length > 0 {
+                format!("{}...", text.chars().take(max_length).collect::<String>())
             } else {
-                chapter.content.clone()
+                text
             };
             
-            // 简单的HTML标签移除
-            let text_content = strip_html_basic(&content_preview);
             println!("\n内容预览:");
-            println!("{}", text_content);
+            println!("{}", wrap_for_display(&content_preview, wrap));
         }
         ContentFormat::Full => {
             println!("\n完整内容:");
-            println!("{}", chapter.content);
+            println!("{}", wrap_for_display(&text, wrap));
         }
     }
     
     Ok(())
 }
 
+/// 按需对显示内容进行换行
+fn wrap_for_display(text: &str, wrap: Option<usize>) -> String {
+    match wrap {
+        Some(width) => wrap_text(text, width),
+        None => text.to_string(),
+    }
+}
+
 /// 显示封面信息
 fn display_cover_info(epub: &Epub) -> Result<()> {
     match epub.cover()? {
@@ -415,49 +553,50 @@ fn display_images(epub: &Epub, verbose: bool) -> Result<()> {
     Ok(())
 }
 
-/// 显示文件列表
-fn display_file_list(epub: &Epub, verbose: bool) -> Result<()> {
-    let files = epub.file_list()?;
+/// 显示页码列表
+fn display_page_list(epub: &Epub) -> Result<()> {
+    let Some(page_list) = epub.page_list()? else {
+        println!("\n❌ 没有找到页码列表");
+        return Ok(());
+    };
     
-    println!("\n📁 文件列表 (共{}个文件):", files.len());
+    println!("\n📄 页码列表 (共{}页):", page_list.page_targets.len());
     
+    for target in &page_list.page_targets {
+        if target.nav_label.text == target.value {
+            println!("  {} -> {}", target.value, target.content.src);
+        } else {
+            println!("  {} ({}) -> {}", target.value, target.nav_label.text, target.content.src);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 显示文件列表
+fn display_file_list(epub: &Epub, verbose: bool) -> Result<()> {
     if verbose {
+        let files = epub.file_list()?;
+        println!("\n📁 文件列表 (共{}个文件):", files.len());
         for (i, file) in files.iter().enumerate() {
             println!("  {}. {}", i + 1, file);
         }
     } else {
-        // 按类型分组显示
-        let mut html_files = Vec::new();
-        let mut css_files = Vec::new();
-        let mut image_files = Vec::new();
-        let mut other_files = Vec::new();
-        
-        for file in &files {
-            let lower = file.to_lowercase();
-            if lower.ends_with(".html") || lower.ends_with(".xhtml") || lower.ends_with(".htm") {
-                html_files.push(file);
-            } else if lower.ends_with(".css") {
-                css_files.push(file);
-            } else if lower.ends_with(".jpg") || lower.ends_with(".jpeg") || 
-                     lower.ends_with(".png") || lower.ends_with(".gif") || 
-                     lower.ends_with(".svg") || lower.ends_with(".webp") {
-                image_files.push(file);
-            } else {
-                other_files.push(file);
-            }
-        }
-        
-        if !html_files.is_empty() {
-            println!("  📄 HTML文件: {} 个", html_files.len());
-        }
-        if !css_files.is_empty() {
-            println!("  🎨 CSS文件: {} 个", css_files.len());
-        }
-        if !image_files.is_empty() {
-            println!("  🖼️  图片文件: {} 个", image_files.len());
-        }
-        if !other_files.is_empty() {
-            println!("  📦 其他文件: {} 个", other_files.len());
+        // 按清单中声明的媒体类型分组显示，不在清单中的文件（如mimetype、OPF）归入其他
+        let categories = epub.categorize_manifest()?;
+        let non_manifest = epub.non_manifest_files()?;
+        let groups = [
+            ("📄 HTML文件", categories.documents.len()),
+            ("🎨 CSS文件", categories.stylesheets.len()),
+            ("🖼️  图片文件", categories.images.len()),
+            ("🔤 字体文件", categories.fonts.len()),
+            ("🔊 音频文件", categories.audio.len()),
+            ("🎬 视频文件", categories.video.len()),
+            ("📦 其他文件", categories.other.len() + non_manifest.len()),
+        ];
+        println!("\n📁 文件列表 (共{}个文件):", groups.iter().map(|(_, count)| count).sum::<usize>());
+        for (label, count) in groups.into_iter().filter(|(_, count)| *count > 0) {
+            println!("  {}: {} 个", label, count);
         }
     }
     
@@ -465,19 +604,25 @@ fn display_file_list(epub: &Epub, verbose: bool) -> Result<()> {
 }
 
 /// 显示目录树
-fn display_toc_tree(epub: &Epub, verbose: bool) -> Result<()> {
+fn display_toc_tree(epub: &Epub, args: &Args) -> Result<()> {
+    let verbose = args.verbose;
     println!("\n🌳 目录树结构:");
     
     // 检查是否有目录树
     if !epub.has_toc_tree()? {
         println!("  ❌ 此EPUB文件不包含目录树信息");
-        println!("  💡 提示: EPUB文件需要包含NCX文件才能显示目录树");
+        println!("  💡 提示: EPUB文件需要包含NCX文件或nav导航文档才能显示目录树");
         return Ok(());
     }
     
     // 获取目录树
     match epub.toc_tree()? {
         Some(toc_tree) => {
+            let toc_tree = toc_tree
+                .with_style(args.toc_style.to_library_style())
+                .with_show_paths(!args.toc_no_paths)
+                .with_max_depth(args.toc_max_depth);
+            
             if verbose {
                 // 显示统计信息
                 let stats = toc_tree.get_statistics();
@@ -486,6 +631,7 @@ fn display_toc_tree(epub: &Epub, verbose: bool) -> Result<()> {
                 println!("    最大深度: {}", stats.max_depth);
                 println!("    叶子节点数: {}", stats.leaf_count);
                 println!("    根节点数: {}", stats.root_count);
+                println!("    导航概览: {}", epub.navigation_summary()?);
                 println!();
             }
             
@@ -524,7 +670,7 @@ fn export_all_chapters(epub: &Epub, args: &Args) -> Result<()> {
     // 检查是否有目录树
     if !epub.has_toc_tree()? {
         println!("❌ 此EPUB文件不包含目录树信息，无法导出章节");
-        println!("💡 提示: EPUB文件需要包含NCX文件才能导出章节");
+        println!("💡 提示: EPUB文件需要包含NCX文件或nav导航文档才能导出章节");
         return Ok(());
     }
     
@@ -539,29 +685,52 @@ fn export_all_chapters(epub: &Epub, args: &Args) -> Result<()> {
     
     let output_path = get_export_directory(epub, &args.export_dir)?;
     let output_dir = output_path.as_path();
-    let use_formatted_text = matches!(args.export_format, ExportFormat::Formatted);
+    let format = args.export_format.to_library_format();
     
     println!("📂 导出目录: {}", output_dir.display());
-    println!("📄 文本格式: {}", if use_formatted_text { "格式化文本" } else { "纯文本" });
+    println!("📄 文本格式: {}", format.label());
     println!("📁 创建子目录: {}", if args.create_subdirs { "是" } else { "否" });
+    println!("🔢 全局编号: {}", if args.global_numbering { "是" } else { "否" });
+    println!("⏭️ 跳过已存在文件: {}", if args.skip_existing { "是" } else { "否" });
     println!("📋 生成索引: {}", if args.with_index { "是" } else { "否" });
+    if args.mirror_paths {
+        println!("🗂️ 按原始路径导出: 是{}", if args.keep_html { "（保留HTML）" } else { "" });
+    }
     
-    let result = if args.with_index {
-        // 生成txt文件并创建索引
-        toc_tree.generate_all_txt_files_with_index(
-            Some(output_dir),
-            use_formatted_text,
-            args.create_subdirs,
-            Some("目录索引.txt"),
-        )?
-    } else {
-        // 只生成txt文件
-        let file_paths = toc_tree.generate_all_txt_files(
-            Some(output_dir),
-            use_formatted_text,
-            args.create_subdirs,
-        )?;
+    let result = if args.mirror_paths {
+        // 按原始路径导出，同一源文件只导出一次
+        let mirror_format = if args.keep_html { None } else { Some(format) };
+        let file_paths = toc_tree.generate_mirrored_files(Some(output_dir), mirror_format)?;
+        (file_paths, output_dir.join("unused"))
+    } else if args.dedupe_files {
+        // 平铺导出，同一源文件只导出一次
+        let file_paths = toc_tree.generate_deduplicated_txt_files(Some(output_dir), format)?;
         (file_paths, output_dir.join("unused"))
+    } else {
+        // 生成txt文件，按需创建索引
+        let mut options = ExportOptions::new(format)
+            .with_create_subdirs(args.create_subdirs)
+            .with_global_numbering(args.global_numbering)
+            .with_skip_existing(args.skip_existing);
+        if args.with_index {
+            options = options.with_index("目录索引.txt");
+        }
+        #[cfg(feature = "serde")]
+        if args.with_report {
+            options = options.with_report("export_report.json");
+        }
+        let summary = toc_tree.generate_all_txt_files_with_options(Some(output_dir), &options)?;
+        for skipped in summary.skipped() {
+            println!("⏭️ 已存在，跳过: {}", skipped.path.display());
+        }
+        for failed in summary.failed() {
+            println!("❌ 导出失败: {} ({})", failed.title, failed.error.as_deref().unwrap_or_default());
+        }
+        if let Some(report_path) = &summary.report_path {
+            println!("🧾 导出报告: {:?}", report_path);
+        }
+        let index_path = summary.index_path.clone().unwrap_or_else(|| output_dir.join("unused"));
+        (summary.paths(), index_path)
     };
     
     let (file_paths, index_path) = result;
@@ -569,7 +738,7 @@ fn export_all_chapters(epub: &Epub, args: &Args) -> Result<()> {
     println!("\n✅ 导出完成!");
     println!("📊 生成文件数: {}", file_paths.len());
     
-    if args.with_index && index_path.exists() {
+    if args.with_index && !args.dedupe_files && !args.mirror_paths && index_path.exists() {
         println!("📋 索引文件: {:?}", index_path);
     }
     
@@ -591,7 +760,7 @@ fn export_single_chapter(epub: &Epub, index: usize, args: &Args) -> Result<()> {
     // 检查是否有目录树
     if !epub.has_toc_tree()? {
         println!("❌ 此EPUB文件不包含目录树信息，无法导出章节");
-        println!("💡 提示: EPUB文件需要包含NCX文件才能导出章节");
+        println!("💡 提示: EPUB文件需要包含NCX文件或nav导航文档才能导出章节");
         return Ok(());
     }
     
@@ -619,14 +788,14 @@ fn export_single_chapter(epub: &Epub, index: usize, args: &Args) -> Result<()> {
     })?;
     let output_path = get_export_directory(epub, &args.export_dir)?;
     let output_dir = output_path.as_path();
-    let use_formatted_text = matches!(args.export_format, ExportFormat::Formatted);
+    let format = args.export_format.to_library_format();
     
     println!("📖 章节标题: {}", node.title);
     println!("📂 导出目录: {}", output_dir.display());
-    println!("📄 文本格式: {}", if use_formatted_text { "格式化文本" } else { "纯文本" });
+    println!("📄 文本格式: {}", format.label());
     
-    // 生成txt文件
-    let file_path = node.generate_txt_file(epub, Some(output_dir), use_formatted_text)?;
+    // 生成导出文件
+    let file_path = node.generate_txt_file(epub, Some(output_dir), format)?;
     
     println!("\n✅ 导出完成!");
     println!("📁 文件路径: {:?}", file_path);
@@ -641,7 +810,7 @@ fn merge_all_chapters(epub: &Epub, args: &Args) -> Result<()> {
     // 检查是否有目录树
     if !epub.has_toc_tree()? {
         println!("❌ 此EPUB文件不包含目录树信息，无法合并章节");
-        println!("💡 提示: EPUB文件需要包含NCX文件才能合并章节");
+        println!("💡 提示: EPUB文件需要包含NCX文件或nav导航文档才能合并章节");
         return Ok(());
     }
     
@@ -656,17 +825,42 @@ fn merge_all_chapters(epub: &Epub, args: &Args) -> Result<()> {
     
     let output_path = get_export_directory(epub, &args.export_dir)?;
     let output_dir = output_path.as_path();
-    let use_formatted_text = matches!(args.export_format, ExportFormat::Formatted);
+    let use_formatted_text = !matches!(args.export_format, ExportFormat::Plain);
+    // 只有合并为txt文件时才会去除页眉页脚
+    let strip_boilerplate = args.strip_boilerplate
+        && matches!(args.export_format, ExportFormat::Formatted | ExportFormat::Plain);
     
     println!("📂 导出目录: {}", output_dir.display());
-    println!("📄 文本格式: {}", if use_formatted_text { "格式化文本" } else { "纯文本" });
+    println!("📄 文本格式: {}", args.export_format.to_library_format().label());
+    println!("🧹 去除重复页眉页脚: {}", if strip_boilerplate { "是" } else { "否" });
+    
+    if args.per_part {
+        let file_paths = toc_tree.generate_merged_per_root(Some(output_dir), args.export_format.to_library_format())?;
+        println!("\n✅ 合并完成!");
+        println!("📊 生成文件数: {}", file_paths.len());
+        for path in &file_paths {
+            println!("  - {}", path.strip_prefix(output_dir).unwrap_or(path).display());
+        }
+        return Ok(());
+    }
     
-    // 生成合并的txt文件
-    let file_path = toc_tree.generate_merged_txt_file(
-        Some(output_dir),
-        use_formatted_text,
-        None, // 使用默认的书籍标题作为文件名
-    )?;
+    // 生成合并的文件（JSON格式时生成book.json）
+    let file_path = match args.export_format {
+        _ if args.markdown => toc_tree.generate_markdown_book(Some(output_dir), None)?,
+        ExportFormat::Markdown => toc_tree.generate_markdown_book(Some(output_dir), None)?,
+        #[cfg(feature = "serde")]
+        ExportFormat::Json => toc_tree.generate_json_file(Some(output_dir), true, None)?,
+        _ if strip_boilerplate => toc_tree.generate_merged_txt_file_without_boilerplate(
+            Some(output_dir),
+            use_formatted_text,
+            None, // 使用默认的书籍标题作为文件名
+        )?,
+        _ => toc_tree.generate_merged_txt_file(
+            Some(output_dir),
+            use_formatted_text,
+            None, // 使用默认的书籍标题作为文件名
+        )?,
+    };
     
     println!("\n✅ 合并完成!");
     println!("📁 文件路径: {:?}", file_path);
@@ -769,24 +963,3 @@ fn collect_node_paths_recursive(
         collect_node_paths_recursive(child, child_path, paths);
     }
 }
-
-/// 简单的HTML标签移除
-fn strip_html_basic(html: &str) -> String {
-    let mut result = String::new();
-    let mut in_tag = false;
-    let mut chars = html.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        match ch {
-            '<' => in_tag = true,
-            '>' => in_tag = false,
-            _ if !in_tag => {
-                result.push(ch);
-            }
-            _ => {} // 在标签内，忽略字符
-        }
-    }
-    
-    // 清理多余的空白字符
-    result.split_whitespace().collect::<Vec<&str>>().join(" ")
-}